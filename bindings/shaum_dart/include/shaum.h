@@ -0,0 +1,15 @@
+/* C interface of libshaum_dart. All strings are UTF-8; results are JSON
+ * envelopes ({"ok": ...} or {"error": {"code", "message"}}) that must be
+ * released with shaum_string_free. Panics never cross the boundary; they are
+ * reported as errors with the code "panic". */
+#ifndef SHAUM_H
+#define SHAUM_H
+
+#include <stdint.h>
+
+char *shaum_analyze(const char *date, const char *madhab, int64_t adjustment);
+char *shaum_prayer_times(const char *date, double lat, double lng, const char *method);
+char *shaum_query(const char *start, const char *end, const char *filter, uint32_t limit);
+void shaum_string_free(char *ptr);
+
+#endif /* SHAUM_H */
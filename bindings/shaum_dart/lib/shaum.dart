@@ -0,0 +1,134 @@
+/// Dart/Flutter bindings for shaum - Islamic fasting rules engine.
+///
+/// ```dart
+/// final shaum = Shaum.open();
+/// final analysis = shaum.analyze(DateTime(2026, 3, 1));
+/// print(analysis.primaryStatus); // Wajib
+/// ```
+library shaum;
+
+import 'dart:convert';
+import 'dart:ffi';
+import 'dart:io';
+
+import 'package:ffi/ffi.dart';
+
+typedef _AnalyzeC = Pointer<Utf8> Function(Pointer<Utf8>, Pointer<Utf8>, Int64);
+typedef _Analyze = Pointer<Utf8> Function(Pointer<Utf8>, Pointer<Utf8>, int);
+typedef _PrayerTimesC = Pointer<Utf8> Function(Pointer<Utf8>, Double, Double, Pointer<Utf8>);
+typedef _PrayerTimes = Pointer<Utf8> Function(Pointer<Utf8>, double, double, Pointer<Utf8>);
+typedef _QueryC = Pointer<Utf8> Function(Pointer<Utf8>, Pointer<Utf8>, Pointer<Utf8>, Uint32);
+typedef _Query = Pointer<Utf8> Function(Pointer<Utf8>, Pointer<Utf8>, Pointer<Utf8>, int);
+typedef _FreeC = Void Function(Pointer<Utf8>);
+typedef _Free = void Function(Pointer<Utf8>);
+
+/// Error raised by the native engine; [code] matches `ShaumError::code()`,
+/// or is `panic` when the engine hit an internal bug.
+class ShaumException implements Exception {
+  final String code;
+  final String message;
+
+  const ShaumException(this.code, this.message);
+
+  @override
+  String toString() => 'ShaumException($code): $message';
+}
+
+/// Fasting analysis of a single day.
+class FastingAnalysis {
+  final DateTime date;
+  final String primaryStatus;
+  final int hijriYear;
+  final int hijriMonth;
+  final int hijriDay;
+  final List<String> reasons;
+  final String explanation;
+
+  FastingAnalysis.fromJson(Map<String, dynamic> json)
+      : date = DateTime.parse(json['date'] as String),
+        primaryStatus = json['primaryStatus'] as String,
+        hijriYear = json['hijriYear'] as int,
+        hijriMonth = json['hijriMonth'] as int,
+        hijriDay = json['hijriDay'] as int,
+        reasons = List<String>.from(json['reasons'] as List),
+        explanation = json['explanation'] as String;
+}
+
+/// Imsak, Fajr, Maghrib and Isha in UTC.
+class PrayerTimes {
+  final DateTime imsak;
+  final DateTime fajr;
+  final DateTime maghrib;
+  final DateTime isha;
+
+  PrayerTimes.fromJson(Map<String, dynamic> json)
+      : imsak = DateTime.parse(json['imsak'] as String),
+        fajr = DateTime.parse(json['fajr'] as String),
+        maghrib = DateTime.parse(json['maghrib'] as String),
+        isha = DateTime.parse(json['isha'] as String);
+}
+
+/// Handle to the native library.
+class Shaum {
+  final _Analyze _analyze;
+  final _PrayerTimes _prayerTimes;
+  final _Query _query;
+  final _Free _free;
+
+  Shaum(DynamicLibrary lib)
+      : _analyze = lib.lookupFunction<_AnalyzeC, _Analyze>('shaum_analyze'),
+        _prayerTimes = lib.lookupFunction<_PrayerTimesC, _PrayerTimes>('shaum_prayer_times'),
+        _query = lib.lookupFunction<_QueryC, _Query>('shaum_query'),
+        _free = lib.lookupFunction<_FreeC, _Free>('shaum_string_free');
+
+  /// Opens the platform library (`libshaum_dart.so`, `.dylib`, `shaum_dart.dll`),
+  /// or the statically linked process on iOS.
+  factory Shaum.open([String? path]) {
+    if (path != null) return Shaum(DynamicLibrary.open(path));
+    if (Platform.isIOS) return Shaum(DynamicLibrary.process());
+    if (Platform.isMacOS) return Shaum(DynamicLibrary.open('libshaum_dart.dylib'));
+    if (Platform.isWindows) return Shaum(DynamicLibrary.open('shaum_dart.dll'));
+    return Shaum(DynamicLibrary.open('libshaum_dart.so'));
+  }
+
+  /// Analyzes [date]. [madhab] is `shafi`, `hanafi`, `maliki` or `hanbali`.
+  FastingAnalysis analyze(DateTime date, {String madhab = 'shafi', int adjustment = 0}) {
+    final json = _call((arena) => _analyze(_str(date, arena), madhab.toNativeUtf8(allocator: arena), adjustment));
+    return FastingAnalysis.fromJson(json as Map<String, dynamic>);
+  }
+
+  /// Prayer times for [date] at ([lat], [lng]) using a preset [method]
+  /// (`mabims`, `egyptian`, `mwl`, `isna`, `umm_al_qura`).
+  PrayerTimes prayerTimes(DateTime date, double lat, double lng, {String method = 'mabims'}) {
+    final json = _call((arena) => _prayerTimes(_str(date, arena), lat, lng, method.toNativeUtf8(allocator: arena)));
+    return PrayerTimes.fromJson(json as Map<String, dynamic>);
+  }
+
+  /// Days between [start] and [end] (inclusive) matching [filter]
+  /// (`all`, `wajib`, `sunnah`, `haram`, `makruh`).
+  List<FastingAnalysis> query(DateTime start, DateTime end, {String filter = 'all', int limit = 366}) {
+    final json = _call((arena) => _query(_str(start, arena), _str(end, arena), filter.toNativeUtf8(allocator: arena), limit));
+    return (json as List).map((e) => FastingAnalysis.fromJson(e as Map<String, dynamic>)).toList();
+  }
+
+  Object? _call(Pointer<Utf8> Function(Arena arena) f) {
+    final ptr = using(f);
+    if (ptr == nullptr) throw const ShaumException('analysis', 'native call returned null');
+    final Map<String, dynamic> envelope;
+    try {
+      envelope = jsonDecode(ptr.toDartString()) as Map<String, dynamic>;
+    } finally {
+      _free(ptr);
+    }
+    final error = envelope['error'];
+    if (error != null) throw ShaumException(error['code'] as String, error['message'] as String);
+    return envelope['ok'];
+  }
+
+  static Pointer<Utf8> _str(DateTime date, Arena arena) {
+    final iso = '${date.year.toString().padLeft(4, '0')}-'
+        '${date.month.toString().padLeft(2, '0')}-'
+        '${date.day.toString().padLeft(2, '0')}';
+    return iso.toNativeUtf8(allocator: arena);
+  }
+}
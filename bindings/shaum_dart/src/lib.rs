@@ -0,0 +1,257 @@
+//! C ABI for the Dart/Flutter package (`dart:ffi`).
+//!
+//! Every function takes NUL-terminated UTF-8 strings and returns a heap string
+//! holding a JSON envelope, either `{"ok": <value>}` or
+//! `{"error": {"code": "...", "message": "..."}}` with [`ShaumError::code`].
+//! A panic inside the engine is caught at the boundary and reported with the
+//! code `"panic"` instead of unwinding into Dart. Returned strings must be
+//! released with [`shaum_string_free`].
+//!
+//! Build with `--profile dart-release`: the workspace `release` profile sets
+//! `panic = "abort"`, under which a panic aborts the host app before it can
+//! be caught.
+
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use shaum_core::astronomy::prayer::calculate_prayer_times;
+use shaum_core::{check, FastingAnalysis, FastingQuery, GeoCoordinate, Madhab, PrayerParams, RuleContext, ShaumError};
+
+/// Dart-friendly representation of [`FastingAnalysis`].
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DartFastingAnalysis {
+    date: String,
+    primary_status: String,
+    hijri_year: usize,
+    hijri_month: usize,
+    hijri_day: usize,
+    reasons: Vec<String>,
+    explanation: String,
+}
+
+impl From<FastingAnalysis> for DartFastingAnalysis {
+    fn from(analysis: FastingAnalysis) -> Self {
+        Self {
+            date: analysis.date.date_naive().to_string(),
+            primary_status: format!("{:?}", analysis.primary_status),
+            hijri_year: analysis.hijri_year,
+            hijri_month: analysis.hijri_month,
+            hijri_day: analysis.hijri_day,
+            reasons: analysis.reasons().map(|r| r.to_string()).collect(),
+            explanation: analysis.explain(),
+        }
+    }
+}
+
+/// Prayer times as RFC 3339 strings (UTC).
+#[derive(Serialize)]
+struct DartPrayerTimes {
+    imsak: String,
+    fajr: String,
+    maghrib: String,
+    isha: String,
+}
+
+#[derive(Serialize)]
+struct DartError {
+    code: &'static str,
+    message: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Envelope<T> {
+    Ok(T),
+    Error(DartError),
+}
+
+/// Error code of a panic caught at the FFI boundary.
+const PANIC_CODE: &str = "panic";
+
+/// Runs an export body and encodes its outcome, catching panics so they never
+/// unwind across the C ABI.
+fn respond<T: Serialize>(body: impl FnOnce() -> Result<T, ShaumError>) -> *mut c_char {
+    let envelope = match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(value)) => Envelope::Ok(value),
+        Ok(Err(e)) => Envelope::Error(DartError { code: e.code(), message: e.to_string() }),
+        Err(payload) => {
+            let message = payload.downcast_ref::<&str>().map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            Envelope::Error(DartError { code: PANIC_CODE, message })
+        }
+    };
+    let json = catch_unwind(AssertUnwindSafe(|| serde_json::to_string(&envelope)))
+        .ok()
+        .and_then(Result::ok)
+        .unwrap_or_else(|| format!(r#"{{"error":{{"code":"{}","message":"failed to encode the result"}}}}"#, PANIC_CODE));
+    // serde_json escapes control characters, so the output never contains NUL.
+    CString::new(json).map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+/// Reads a borrowed C string argument.
+///
+/// # Safety
+/// `ptr` must be null or point to a NUL-terminated string valid for `'a`.
+unsafe fn arg<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, ShaumError> {
+    if ptr.is_null() {
+        return Err(ShaumError::ValidationError(format!("`{}` must not be null", name)));
+    }
+    // SAFETY: non-null and NUL-terminated per the caller contract.
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|e| ShaumError::ValidationError(format!("`{}` is not UTF-8: {}", name, e)))
+}
+
+fn parse_date(s: &str) -> Result<NaiveDate, ShaumError> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map_err(|e| ShaumError::ValidationError(format!("Invalid date '{}': {}", s, e)))
+}
+
+fn parse_madhab(s: &str) -> Result<Madhab, ShaumError> {
+    match s.to_ascii_lowercase().as_str() {
+        "" | "shafi" | "shafii" => Ok(Madhab::Shafi),
+        "hanafi" => Ok(Madhab::Hanafi),
+        "maliki" => Ok(Madhab::Maliki),
+        "hanbali" => Ok(Madhab::Hanbali),
+        _ => Err(ShaumError::invalid_config(format!("Unknown madhab: {}", s))),
+    }
+}
+
+/// Analyzes `date` (`YYYY-MM-DD`) for `madhab` (`shafi`, `hanafi`, `maliki`,
+/// `hanbali`; empty for the default) with a Hijri `adjustment` in days.
+///
+/// # Safety
+/// `date` and `madhab` must be null or valid NUL-terminated strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn shaum_analyze(date: *const c_char, madhab: *const c_char, adjustment: i64) -> *mut c_char {
+    respond(|| {
+        // SAFETY: forwarded caller contract.
+        let date = parse_date(unsafe { arg(date, "date") }?)?;
+        let madhab = parse_madhab(unsafe { arg(madhab, "madhab") }?)?;
+        let context = RuleContext::new().madhab(madhab).adjustment(adjustment);
+        check(date, &context).map(DartFastingAnalysis::from)
+    })
+}
+
+/// Imsak, Fajr, Maghrib and Isha for `date` at (`lat`, `lng`) using a preset
+/// `method` (see [`PrayerParams::by_name`]).
+///
+/// # Safety
+/// `date` and `method` must be null or valid NUL-terminated strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn shaum_prayer_times(date: *const c_char, lat: f64, lng: f64, method: *const c_char) -> *mut c_char {
+    respond(|| {
+        // SAFETY: forwarded caller contract.
+        let date = parse_date(unsafe { arg(date, "date") }?)?;
+        let method = unsafe { arg(method, "method") }?;
+        let params = PrayerParams::by_name(method)
+            .ok_or_else(|| ShaumError::invalid_config(format!("Unknown prayer method: {}", method)))?;
+        let times = calculate_prayer_times(date, GeoCoordinate::new(lat, lng)?, &params)?;
+        Ok(DartPrayerTimes {
+            imsak: times.imsak.to_rfc3339(),
+            fajr: times.fajr.to_rfc3339(),
+            maghrib: times.maghrib.to_rfc3339(),
+            isha: times.isha.to_rfc3339(),
+        })
+    })
+}
+
+/// Days from `start` to `end` (inclusive) whose status matches `filter`
+/// (`all`, `wajib`, `sunnah`, `haram`, `makruh`), at most `limit` results.
+///
+/// # Safety
+/// `start`, `end` and `filter` must be null or valid NUL-terminated strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn shaum_query(start: *const c_char, end: *const c_char, filter: *const c_char, limit: u32) -> *mut c_char {
+    respond(|| {
+        // SAFETY: forwarded caller contract.
+        let start = parse_date(unsafe { arg(start, "start") }?)?;
+        let end = parse_date(unsafe { arg(end, "end") }?)?;
+        let query = FastingQuery::starting_from(start).until(end);
+        let query = match unsafe { arg(filter, "filter") }?.to_ascii_lowercase().as_str() {
+            "" | "all" => query,
+            "wajib" => query.wajib(),
+            "sunnah" => query.sunnah(),
+            "haram" => query.haram(),
+            "makruh" => query.makruh(),
+            other => return Err(ShaumError::invalid_config(format!("Unknown filter: {}", other))),
+        };
+        query
+            .take(limit as usize)
+            .map(|r| r.map(DartFastingAnalysis::from))
+            .collect::<Result<Vec<_>, _>>()
+    })
+}
+
+/// Releases a string returned by this library. Null is ignored.
+///
+/// # Safety
+/// `ptr` must be null or a pointer returned by a `shaum_*` function that has
+/// not been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn shaum_string_free(ptr: *mut c_char) {
+    if !ptr.is_null() {
+        // Freeing cannot panic in practice; the guard keeps the ABI unwind-free.
+        // SAFETY: allocated by `CString::into_raw` in `respond`.
+        let _ = catch_unwind(|| drop(unsafe { CString::from_raw(ptr) }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Calls an export and decodes (then frees) its envelope.
+    fn call(ptr: *mut c_char) -> serde_json::Value {
+        assert!(!ptr.is_null());
+        // SAFETY: returned by an export, freed right after.
+        let json = unsafe { CStr::from_ptr(ptr) }.to_str().unwrap().to_owned();
+        unsafe { shaum_string_free(ptr) };
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn test_analyze_round_trip() {
+        let date = CString::new("2024-03-11").unwrap();
+        let madhab = CString::new("hanafi").unwrap();
+        let v = call(unsafe { shaum_analyze(date.as_ptr(), madhab.as_ptr(), 0) });
+        assert_eq!(v["ok"]["date"], "2024-03-11");
+        assert_eq!(v["ok"]["primaryStatus"], "Wajib");
+        assert_eq!((v["ok"]["hijriMonth"].as_u64(), v["ok"]["hijriDay"].as_u64()), (Some(9), Some(1)));
+
+        let query_end = CString::new("2024-04-30").unwrap();
+        let filter = CString::new("haram").unwrap();
+        let v = call(unsafe { shaum_query(date.as_ptr(), query_end.as_ptr(), filter.as_ptr(), 5) });
+        assert_eq!(v["ok"][0]["date"], "2024-04-10");
+    }
+
+    #[test]
+    fn test_errors_are_enveloped() {
+        let bad = CString::new("2024-13-01").unwrap();
+        let v = call(unsafe { shaum_analyze(bad.as_ptr(), std::ptr::null(), 0) });
+        assert_eq!(v["error"]["code"], "validation");
+
+        let date = CString::new("2024-03-11").unwrap();
+        let v = call(unsafe { shaum_analyze(date.as_ptr(), std::ptr::null(), 0) });
+        assert!(v["error"]["message"].as_str().unwrap().contains("`madhab` must not be null"));
+    }
+
+    #[test]
+    fn test_panic_becomes_error() {
+        let v = call(respond(|| -> Result<(), ShaumError> { panic!("engine bug") }));
+        assert_eq!(v["error"]["code"], PANIC_CODE);
+        assert_eq!(v["error"]["message"], "engine bug");
+    }
+
+    #[test]
+    fn test_dart_release_profile_unwinds() {
+        let manifest = include_str!("../../../Cargo.toml");
+        let profile = manifest.split("[profile.dart-release]").nth(1).expect("dart-release profile");
+        let profile = profile.split("\n[").next().unwrap();
+        assert!(profile.contains("panic = \"unwind\""), "{}", profile);
+    }
+}
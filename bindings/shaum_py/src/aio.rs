@@ -0,0 +1,176 @@
+//! `shaum.aio`: awaitable network-backed functions.
+//!
+//! Requests run on a shared background Tokio runtime; results are handed back
+//! to the calling asyncio loop with `call_soon_threadsafe`, so the event loop
+//! is never blocked. This is the part of `pyo3-async-runtimes` the module
+//! needs; that crate pins its own pyo3 minor version, and the one available
+//! when this was written targets an older pyo3 than these bindings.
+//!
+//! ```python
+//! import shaum
+//!
+//! async def main():
+//!     here = await shaum.aio.locate()
+//!     analysis = await shaum.aio.official_calendar("https://example.org/adjustment", "2026-03-01")
+//! ```
+
+use std::future::Future;
+use std::sync::OnceLock;
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use shaum_core::network::geo::{reverse_geocode, GeoProvider, IpApi, LocationInfo};
+use shaum_core::rules::RemoteMoonProvider;
+use shaum_core::{GeoCoordinate, MoonProvider, ShaumError};
+
+use crate::{errors, Context, FastingAnalysis};
+
+fn runtime() -> &'static tokio::runtime::Runtime {
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("shaum-aio")
+            .enable_all()
+            .build()
+            .expect("failed to start shaum.aio runtime")
+    })
+}
+
+/// Runs `fut` in the background and returns an `asyncio.Future` for its result.
+fn spawn<T, F, C>(py: Python<'_>, fut: F, convert: C) -> PyResult<Bound<'_, PyAny>>
+where
+    T: Send + 'static,
+    F: Future<Output = Result<T, ShaumError>> + Send + 'static,
+    C: FnOnce(Python<'_>, T) -> PyResult<Py<PyAny>> + Send + 'static,
+{
+    let event_loop = py.import("asyncio")?.call_method0("get_running_loop")?;
+    let py_future = event_loop.call_method0("create_future")?;
+    let (loop_ref, future_ref) = (event_loop.unbind(), py_future.clone().unbind());
+
+    runtime().spawn(async move {
+        let result = fut.await;
+        Python::attach(|py| {
+            let outcome = result
+                .map_err(errors::from_core)
+                .and_then(|value| convert(py, value));
+            let (value, failed) = match outcome {
+                Ok(v) => (v, false),
+                Err(e) => (e.into_value(py).into_any(), true),
+            };
+            let resolve = match wrap_pyfunction!(resolve, py) {
+                Ok(f) => f,
+                Err(e) => return e.write_unraisable(py, None),
+            };
+            if let Err(e) = loop_ref.call_method1(py, "call_soon_threadsafe", (resolve, future_ref, value, failed)) {
+                // The loop was closed before the request finished.
+                e.write_unraisable(py, None);
+            }
+        });
+    });
+    Ok(py_future)
+}
+
+/// Completes `future` unless it was cancelled meanwhile.
+#[pyfunction]
+fn resolve(future: Bound<'_, PyAny>, value: Bound<'_, PyAny>, failed: bool) -> PyResult<()> {
+    if future.call_method0("done")?.is_truthy()? {
+        return Ok(());
+    }
+    let method = if failed { "set_exception" } else { "set_result" };
+    future.call_method1(method, (value,))?;
+    Ok(())
+}
+
+fn location_dict(py: Python<'_>, info: LocationInfo) -> PyResult<Py<PyAny>> {
+    let d = PyDict::new(py);
+    d.set_item("lat", info.coords.lat)?;
+    d.set_item("lng", info.coords.lng)?;
+    d.set_item("city", info.city)?;
+    d.set_item("region", info.region)?;
+    d.set_item("country", info.country)?;
+    Ok(d.into_any().unbind())
+}
+
+/// Locates an IP address via ipapi.co, or the caller's public IP if omitted.
+///
+/// Returns:
+///     dict with `lat`, `lng`, `city`, `region`, `country`
+///
+/// Raises:
+///     shaum.errors.NetworkError: If the lookup fails
+#[pyfunction]
+#[pyo3(signature = (ip = None))]
+fn locate<'py>(py: Python<'py>, ip: Option<&str>) -> PyResult<Bound<'py, PyAny>> {
+    let ip: Option<std::net::IpAddr> = ip
+        .map(|s| s.parse().map_err(|e| errors::config(format!("Invalid IP address '{}': {}", s, e))))
+        .transpose()?;
+    spawn(py, async move {
+        let provider = IpApi::new();
+        match ip {
+            Some(ip) => provider.locate(ip).await,
+            None => provider.current().await,
+        }
+    }, location_dict)
+}
+
+/// Reverse-geocodes coordinates via OpenStreetMap Nominatim (1 request/second).
+///
+/// Returns:
+///     dict with `kelurahan`, `kecamatan`, `kabupaten`, `provinsi`, `country`, `display_name`
+#[pyfunction]
+#[pyo3(name = "reverse_geocode")]
+fn reverse_geocode_async(py: Python<'_>, lat: f64, lng: f64) -> PyResult<Bound<'_, PyAny>> {
+    let coords = GeoCoordinate::new(lat, lng).map_err(errors::from_core)?;
+    spawn(py, reverse_geocode(coords), |py, info| {
+        let d = PyDict::new(py);
+        d.set_item("kelurahan", info.kelurahan)?;
+        d.set_item("kecamatan", info.kecamatan)?;
+        d.set_item("kabupaten", info.kabupaten)?;
+        d.set_item("provinsi", info.provinsi)?;
+        d.set_item("country", info.country)?;
+        d.set_item("display_name", info.display_name)?;
+        Ok(d.into_any().unbind())
+    })
+}
+
+/// Analyzes a date using the adjustment announced by a sighting authority.
+///
+/// `endpoint` must return JSON `{"adjustment": <days>}`. The announced
+/// adjustment replaces `context.adjustment` rather than adding to it, since
+/// both are offsets from the same calculated calendar; other settings come
+/// from `context` (default: `Context.current()`).
+///
+/// Raises:
+///     shaum.errors.NetworkError: If the endpoint is unreachable
+#[pyfunction]
+#[pyo3(signature = (endpoint, date_str, context = None))]
+fn official_calendar<'py>(
+    py: Python<'py>,
+    endpoint: String,
+    date_str: &str,
+    context: Option<PyRef<'py, Context>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let date = crate::parse_date(date_str)?;
+    let ctx = Context::resolve(py, context)?;
+    spawn(py, async move {
+        let provider = RemoteMoonProvider::new(endpoint);
+        let adjustment = provider.get_adjustment(date, ctx.coords).await?;
+        // The announcement supersedes the context's own offset.
+        let rules = ctx.rule_context().adjustment(adjustment);
+        shaum_core::check(date, &rules).map(|analysis| (analysis, ctx.locale))
+    }, |py, (inner, locale)| {
+        Ok(Py::new(py, FastingAnalysis { inner, locale })?.into_any())
+    })
+}
+
+pub fn register(parent: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = parent.py();
+    let m = PyModule::new(py, "aio")?;
+    m.add_function(wrap_pyfunction!(locate, &m)?)?;
+    m.add_function(wrap_pyfunction!(official_calendar, &m)?)?;
+    m.add_function(wrap_pyfunction!(reverse_geocode_async, &m)?)?;
+    parent.add_submodule(&m)?;
+    py.import("sys")?.getattr("modules")?.set_item("shaum.aio", &m)?;
+    Ok(())
+}
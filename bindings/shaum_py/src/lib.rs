@@ -10,10 +10,318 @@
 //! print(f"Status: {analysis.status}")
 //! print(f"Hijri Date: {analysis.hijri_date}")
 //! print(analysis.explain())
+//!
+//! # Settings apply to every call inside the block
+//! with shaum.Context(madhab="hanafi", adjustment=1):
+//!     print(shaum.analyze("2026-03-01").status)
 //! ```
 
+use std::sync::Mutex;
+
+#[cfg(feature = "async")]
+mod aio;
+
 use pyo3::prelude::*;
-use shaum_core::{FastingAnalysis as CoreAnalysis, FastingStatus as CoreStatus};
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{PyDict, PyTuple};
+use shaum_core::{
+    FastingAnalysis as CoreAnalysis, FastingStatus as CoreStatus, FastingType, GeoCoordinate, Madhab, RuleContext,
+};
+use shaum_core::rules::{format, i18n::{EnglishLocalizer, Localizer}};
+
+/// Process-wide default set by `configure()`.
+static DEFAULT_CONTEXT: Mutex<Option<Context>> = Mutex::new(None);
+/// `contextvars.ContextVar` holding the tuple of contexts entered with `with`,
+/// innermost last. Each thread and asyncio task sees only its own blocks.
+static ACTIVE_CONTEXTS: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+fn lock<T>(m: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    m.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn active_contexts(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    ACTIVE_CONTEXTS
+        .get_or_try_init(py, || {
+            let kwargs = PyDict::new(py);
+            kwargs.set_item("default", PyTuple::empty(py))?;
+            let var = py.import("contextvars")?.getattr("ContextVar")?.call(("shaum_context",), Some(&kwargs))?;
+            Ok::<_, PyErr>(var.unbind())
+        })
+        .map(|var| var.bind(py))
+}
+
+/// Contexts entered in the calling thread or task, innermost last.
+fn entered_contexts(py: Python<'_>) -> PyResult<Bound<'_, PyTuple>> {
+    Ok(active_contexts(py)?.call_method0("get")?.extract()?)
+}
+
+/// Exception classes, importable as `shaum.errors`.
+///
+/// All derive from `shaum.errors.ShaumError`, itself a `ValueError` so code
+/// written against earlier versions keeps working. Instances carry a `code`
+/// attribute with `ShaumError::code()`.
+mod errors {
+    use pyo3::create_exception;
+    use pyo3::exceptions::PyValueError;
+    use pyo3::prelude::*;
+
+    create_exception!(errors, ShaumError, PyValueError, "Base class for shaum errors.");
+    create_exception!(errors, DateOutOfRangeError, ShaumError, "Date outside the supported range (1938-2076).");
+    create_exception!(errors, NetworkError, ShaumError, "Remote provider or network failure.");
+    create_exception!(errors, ConfigError, ShaumError, "Invalid configuration or input.");
+
+    /// Maps a core error to its Python exception.
+    pub fn from_core(e: shaum_core::ShaumError) -> PyErr {
+        use shaum_core::ShaumError as E;
+        let msg = e.to_string();
+        let err = match e {
+            E::DateOutOfRange { .. } => DateOutOfRangeError::new_err(msg),
+            E::NetworkError(_) | E::ProviderUnavailable { .. } => NetworkError::new_err(msg),
+            E::InvalidConfiguration { .. } | E::ValidationError(_) => ConfigError::new_err(msg),
+            _ => ShaumError::new_err(msg),
+        };
+        with_code(err, e.code())
+    }
+
+    /// `ConfigError` for invalid binding arguments.
+    pub fn config(msg: impl Into<String>) -> PyErr {
+        with_code(ConfigError::new_err(msg.into()), "invalid_configuration")
+    }
+
+    fn with_code(err: PyErr, code: &'static str) -> PyErr {
+        Python::attach(|py| {
+            let _ = err.value(py).setattr("code", code);
+        });
+        err
+    }
+
+    pub fn register(parent: &Bound<'_, PyModule>) -> PyResult<()> {
+        let py = parent.py();
+        let m = PyModule::new(py, "errors")?;
+        m.add("ShaumError", py.get_type::<ShaumError>())?;
+        m.add("DateOutOfRangeError", py.get_type::<DateOutOfRangeError>())?;
+        m.add("NetworkError", py.get_type::<NetworkError>())?;
+        m.add("ConfigError", py.get_type::<ConfigError>())?;
+        parent.add_submodule(&m)?;
+        // Make `import shaum.errors` work for a compiled submodule.
+        py.import("sys")?.getattr("modules")?.set_item("shaum.errors", &m)?;
+        Ok(())
+    }
+}
+
+fn parse_madhab(name: &str) -> PyResult<Madhab> {
+    match name.to_ascii_lowercase().as_str() {
+        "shafi" | "shafii" => Ok(Madhab::Shafi),
+        "hanafi" => Ok(Madhab::Hanafi),
+        "maliki" => Ok(Madhab::Maliki),
+        "hanbali" => Ok(Madhab::Hanbali),
+        _ => Err(errors::config(format!("Unknown madhab '{}'. Expected shafi, hanafi, maliki or hanbali", name))),
+    }
+}
+
+fn localizer(locale: &str) -> PyResult<&'static dyn Localizer> {
+    match locale {
+        "en" => Ok(&EnglishLocalizer),
+        _ => Err(errors::config(format!("Unsupported locale '{}'. Available: en", locale))),
+    }
+}
+
+/// Analysis settings, mirroring the Rust `RuleContext`.
+///
+/// Pass as `context=` to any function, or use as a context manager to apply it
+/// to every call inside the block. Blocks are tracked per thread and per
+/// asyncio task. An `adjustment` outside [-30, 30] raises `ConfigError`.
+/// Haram and Wajib rules cannot be listed in `disabled_rules`.
+///
+/// Example:
+///     >>> ctx = shaum.Context(madhab="hanafi", coords=(-6.2, 106.8))
+///     >>> shaum.analyze("2026-03-01", context=ctx)
+///     >>> with ctx:
+///     ...     shaum.analyze("2026-03-01")
+///     >>> shaum.Context(disabled_rules=["SaturdayExclusive"])
+#[pyclass(name = "Context")]
+#[derive(Clone, Debug)]
+pub struct Context {
+    madhab: Madhab,
+    adjustment: i64,
+    coords: Option<GeoCoordinate>,
+    locale: String,
+    disabled_rules: Vec<FastingType>,
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self { madhab: Madhab::default(), adjustment: 0, coords: None, locale: "en".into(), disabled_rules: Vec::new() }
+    }
+}
+
+impl Context {
+    /// Innermost `with` block, else the `configure()` default, else built-in defaults.
+    fn current(py: Python<'_>) -> PyResult<Context> {
+        if let Some(innermost) = entered_contexts(py)?.iter().last() {
+            return Ok(innermost.extract::<PyRef<'_, Context>>()?.clone());
+        }
+        Ok(lock(&DEFAULT_CONTEXT).clone().unwrap_or_default())
+    }
+
+    fn resolve(py: Python<'_>, explicit: Option<PyRef<'_, Context>>) -> PyResult<Context> {
+        match explicit {
+            Some(c) => Ok(c.clone()),
+            None => Context::current(py),
+        }
+    }
+
+    fn rule_context(&self) -> RuleContext {
+        // Validated by `disable_rules_by_name` when set.
+        let mut context = RuleContext::new().madhab(self.madhab).adjustment(self.adjustment);
+        context.disabled_rules = self.disabled_rules.clone();
+        context
+    }
+
+    fn update(
+        &mut self,
+        madhab: Option<&str>,
+        adjustment: Option<i64>,
+        coords: Option<(f64, f64)>,
+        locale: Option<&str>,
+        disabled_rules: Option<Vec<String>>,
+    ) -> PyResult<()> {
+        if let Some(m) = madhab {
+            self.madhab = parse_madhab(m)?;
+        }
+        if let Some(adj) = adjustment {
+            self.adjustment = RuleContext::new()
+                .adjustment(adj)
+                .strict(true)
+                .effective_adjustment()
+                .map_err(errors::from_core)?;
+        }
+        if let Some((lat, lng)) = coords {
+            self.coords = Some(GeoCoordinate::new(lat, lng).map_err(errors::from_core)?);
+        }
+        if let Some(l) = locale {
+            localizer(l)?;
+            self.locale = l.to_string();
+        }
+        if let Some(names) = disabled_rules {
+            self.disabled_rules = RuleContext::new()
+                .disable_rules_by_name(names)
+                .map_err(errors::from_core)?
+                .disabled_rules;
+        }
+        Ok(())
+    }
+}
+
+#[pymethods]
+impl Context {
+    #[new]
+    #[pyo3(signature = (madhab = "shafi", adjustment = 0, coords = None, locale = "en", disabled_rules = None))]
+    fn new(
+        madhab: &str,
+        adjustment: i64,
+        coords: Option<(f64, f64)>,
+        locale: &str,
+        disabled_rules: Option<Vec<String>>,
+    ) -> PyResult<Self> {
+        let mut ctx = Context::default();
+        ctx.update(Some(madhab), Some(adjustment), coords, Some(locale), disabled_rules)?;
+        Ok(ctx)
+    }
+
+    /// Settings in effect for calls without an explicit context.
+    #[staticmethod]
+    #[pyo3(name = "current")]
+    fn py_current(py: Python<'_>) -> PyResult<Context> {
+        Context::current(py)
+    }
+
+    /// School of jurisprudence ("shafi", "hanafi", "maliki", "hanbali").
+    #[getter]
+    fn madhab(&self) -> String {
+        format!("{:?}", self.madhab).to_lowercase()
+    }
+
+    /// Hijri day offset, within [-30, 30].
+    #[getter]
+    fn adjustment(&self) -> i64 {
+        self.adjustment
+    }
+
+    /// Observer (latitude, longitude), if set. Timestamps passed to `analyze`
+    /// switch to the next Hijri day at sunset there.
+    #[getter]
+    fn coords(&self) -> Option<(f64, f64)> {
+        self.coords.map(|c| (c.lat, c.lng))
+    }
+
+    /// Locale used for formatted output.
+    #[getter]
+    fn locale(&self) -> String {
+        self.locale.clone()
+    }
+
+    /// Built-in rules that are skipped, by type name (e.g. "SaturdayExclusive").
+    #[getter]
+    fn disabled_rules(&self) -> Vec<String> {
+        self.disabled_rules.iter().map(|t| t.to_string()).collect()
+    }
+
+    fn __enter__(slf: Bound<'_, Self>) -> PyResult<Bound<'_, Self>> {
+        let py = slf.py();
+        let mut entered: Vec<Bound<'_, PyAny>> = entered_contexts(py)?.iter().collect();
+        entered.push(slf.clone().into_any());
+        active_contexts(py)?.call_method1("set", (PyTuple::new(py, entered)?,))?;
+        Ok(slf)
+    }
+
+    #[pyo3(signature = (_exc_type = None, _exc_value = None, _traceback = None))]
+    fn __exit__(
+        &self,
+        py: Python<'_>,
+        _exc_type: Option<Bound<'_, PyAny>>,
+        _exc_value: Option<Bound<'_, PyAny>>,
+        _traceback: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<bool> {
+        let entered = entered_contexts(py)?;
+        let outer = entered.get_slice(0, entered.len().saturating_sub(1));
+        active_contexts(py)?.call_method1("set", (outer,))?;
+        Ok(false)
+    }
+
+    fn __repr__(&self) -> String {
+        let coords = match self.coords {
+            Some(c) => format!("({}, {})", c.lat, c.lng),
+            None => "None".to_string(),
+        };
+        format!(
+            "Context(madhab='{}', adjustment={}, coords={}, locale='{}', disabled_rules={:?})",
+            self.madhab(), self.adjustment, coords, self.locale, self.disabled_rules()
+        )
+    }
+}
+
+/// Sets process-wide defaults used when no context is passed or entered.
+///
+/// Only the given settings change. Returns the new default context.
+///
+/// Example:
+///     >>> shaum.configure(madhab="maliki", locale="en")
+#[pyfunction]
+#[pyo3(signature = (madhab = None, adjustment = None, coords = None, locale = None, disabled_rules = None))]
+fn configure(
+    madhab: Option<&str>,
+    adjustment: Option<i64>,
+    coords: Option<(f64, f64)>,
+    locale: Option<&str>,
+    disabled_rules: Option<Vec<String>>,
+) -> PyResult<Context> {
+    let mut default = lock(&DEFAULT_CONTEXT);
+    let mut ctx = default.clone().unwrap_or_default();
+    ctx.update(madhab, adjustment, coords, locale, disabled_rules)?;
+    *default = Some(ctx.clone());
+    Ok(ctx)
+}
 
 /// Fasting status according to Islamic jurisprudence.
 #[pyclass(name = "FastingStatus", eq)]
@@ -75,6 +383,7 @@ impl FastingStatus {
 #[pyclass(name = "FastingAnalysis")]
 pub struct FastingAnalysis {
     inner: CoreAnalysis,
+    locale: String,
 }
 
 #[pymethods]
@@ -120,6 +429,11 @@ impl FastingAnalysis {
         self.inner.explain()
     }
     
+    /// Returns a chat-ready Telegram MarkdownV2 message in the context's locale.
+    fn to_markdown(&self) -> PyResult<String> {
+        Ok(format::markdown(&self.inner, localizer(&self.locale)?))
+    }
+
     /// Returns True if this is a Ramadan day.
     fn is_ramadan(&self) -> bool {
         self.inner.is_ramadhan()
@@ -151,29 +465,58 @@ impl FastingAnalysis {
 /// Analyze a date and return its fasting status.
 ///
 /// Args:
-///     date_str: Date in YYYY-MM-DD format (Gregorian)
+///     date_str: Gregorian date (YYYY-MM-DD), or an RFC 3339 timestamp. With
+///         the context's `coords`, a timestamp at or after sunset falls on
+///         the next Hijri day; without them the day ends at UTC midnight.
+///     context: Settings to use; defaults to `Context.current()`
 ///
 /// Returns:
 ///     FastingAnalysis with status, Hijri date, and explanation
 ///
 /// Raises:
-///     ValueError: If date format is invalid or date is out of range
+///     shaum.errors.ConfigError: If the date format is invalid
+///     shaum.errors.DateOutOfRangeError: If the date is outside 1938-2076
 ///
 /// Example:
 ///     >>> analysis = shaum.analyze("2026-03-01")
 ///     >>> print(analysis.status)
 ///     FastingStatus.Wajib
 #[pyfunction]
-fn analyze(date_str: &str) -> PyResult<FastingAnalysis> {
-    let date = chrono::NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
-        .map_err(|e| pyo3::exceptions::PyValueError::new_err(
-            format!("Invalid date format '{}': {}. Expected YYYY-MM-DD", date_str, e)
-        ))?;
-    
-    let analysis = shaum_core::analyze_date(date)
-        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+#[pyo3(signature = (date_str, context = None))]
+fn analyze(py: Python<'_>, date_str: &str, context: Option<PyRef<'_, Context>>) -> PyResult<FastingAnalysis> {
+    let ctx = Context::resolve(py, context)?;
+    let analysis = match chrono::DateTime::parse_from_rfc3339(date_str) {
+        Ok(instant) => shaum_core::analyze(instant.to_utc(), &ctx.rule_context(), ctx.coords),
+        Err(_) => shaum_core::check(parse_date(date_str)?, &ctx.rule_context()),
+    }
+    .map_err(errors::from_core)?;
     
-    Ok(FastingAnalysis { inner: analysis })
+    Ok(FastingAnalysis { inner: analysis, locale: ctx.locale })
+}
+
+/// Every rule trace code (stable snake_case), for exhaustive mappings.
+///
+/// Returns:
+///     list[str], e.g. ["eid_al_fitr", "eid_al_adha", ...]
+#[pyfunction]
+fn trace_codes() -> Vec<&'static str> {
+    shaum_core::TraceCode::all().iter().map(|c| c.code()).collect()
+}
+
+/// Every built-in fasting type name, as found in `reasons`.
+///
+/// Returns:
+///     list[str], e.g. ["Haid", "Nifas", "EidAlFitr", ...]
+#[pyfunction]
+fn fasting_types() -> Vec<String> {
+    shaum_core::FastingType::builtin().iter().map(|t| t.to_string()).collect()
+}
+
+fn parse_date(date_str: &str) -> PyResult<chrono::NaiveDate> {
+    chrono::NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
+        .map_err(|e| errors::config(
+            format!("Invalid date format '{}': {}. Expected YYYY-MM-DD", date_str, e)
+        ))
 }
 
 /// Shaum - Islamic Fasting Rules Engine
@@ -190,6 +533,13 @@ fn analyze(date_str: &str) -> PyResult<FastingAnalysis> {
 fn shaum(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<FastingStatus>()?;
     m.add_class::<FastingAnalysis>()?;
+    m.add_class::<Context>()?;
     m.add_function(wrap_pyfunction!(analyze, m)?)?;
+    m.add_function(wrap_pyfunction!(configure, m)?)?;
+    m.add_function(wrap_pyfunction!(trace_codes, m)?)?;
+    m.add_function(wrap_pyfunction!(fasting_types, m)?)?;
+    errors::register(m)?;
+    #[cfg(feature = "async")]
+    aio::register(m)?;
     Ok(())
 }
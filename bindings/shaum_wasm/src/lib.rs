@@ -3,8 +3,10 @@
 //! Provides WebAssembly bindings for analyzing fasting status based on Islamic jurisprudence.
 
 use wasm_bindgen::prelude::*;
-use shaum_core::{analyze_date, FastingAnalysis};
-use serde::Serialize;
+use wasm_bindgen::JsCast;
+use shaum_core::{analyze_date, FastingAnalysis, GeoCoordinate, PrayerParams, RuleContext};
+use serde::{Deserialize, Serialize};
+use chrono::Datelike;
 
 #[wasm_bindgen(start)]
 pub fn init_panic_hook() {
@@ -31,6 +33,178 @@ pub fn analyze(date_str: &str) -> Result<JsValue, JsValue> {
         .map_err(|e| JsValue::from_str(&e.to_string()))
 }
 
+/// Like [`analyze`], with engine options.
+///
+/// # Example (JavaScript)
+/// ```js
+/// const result = analyzeWith("2026-03-07", { disabledRules: ["SaturdayExclusive"] });
+/// ```
+#[wasm_bindgen(js_name = analyzeWith)]
+pub fn analyze_with(date_str: &str, options: JsValue) -> Result<JsValue, JsValue> {
+    Shaum::new(date_str)?.with_options(options)?.analyze()
+}
+
+/// Prayer timetable for a whole Gregorian month in one call.
+///
+/// `method` is a preset name (`mabims`, `egyptian`, `mwl`, `isna`, `umm_al_qura`).
+/// Returns an object of parallel typed arrays, one entry per day: `day`,
+/// `hijriMonth`, `hijriDay` (`Uint8Array`) and `imsak`, `fajr`, `maghrib`, `isha`
+/// (`Float64Array`, Unix epoch milliseconds; `NaN` where a time is undefined).
+/// The array buffers can be transferred to a worker without copying.
+///
+/// # Example (JavaScript)
+/// ```js
+/// const t = prayerTimetable(2026, 2, -6.2088, 106.8456, "mabims");
+/// for (let i = 0; i < t.day.length; i++) {
+///   console.log(t.day[i], new Date(t.imsak[i]), new Date(t.maghrib[i]));
+/// }
+/// ```
+#[wasm_bindgen(js_name = prayerTimetable)]
+pub fn prayer_timetable(year: i32, month: u32, lat: f64, lng: f64, method: &str) -> Result<js_sys::Object, JsValue> {
+    let coords = GeoCoordinate::new(lat, lng).map_err(to_js)?;
+    let params = PrayerParams::by_name(method)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown prayer method: {}", method)))?;
+    let first = chrono::NaiveDate::from_ymd_opt(year, month, 1)
+        .ok_or_else(|| JsValue::from_str(&format!("Invalid month: {}-{}", year, month)))?;
+    let ctx = RuleContext::default();
+
+    let mut table = Timetable::default();
+    for date in first.iter_days().take_while(|d| d.month() == month) {
+        let hijri = ctx.hijri(date).map_err(to_js)?;
+        table.day.push(date.day() as u8);
+        table.hijri_month.push(hijri.month as u8);
+        table.hijri_day.push(hijri.day as u8);
+        let ms = |t: chrono::DateTime<chrono::Utc>| t.timestamp_millis() as f64;
+        match shaum_core::prayer_times(date, coords, params, &ctx) {
+            Ok(times) => {
+                table.imsak.push(ms(times.imsak));
+                table.fajr.push(ms(times.fajr));
+                table.maghrib.push(ms(times.maghrib));
+                table.isha.push(ms(times.isha));
+            }
+            Err(_) => {
+                for column in [&mut table.imsak, &mut table.fajr, &mut table.maghrib, &mut table.isha] {
+                    column.push(f64::NAN);
+                }
+            }
+        }
+    }
+    table.into_js()
+}
+
+/// Column-oriented month of prayer times, see [`prayer_timetable`].
+#[derive(Default)]
+struct Timetable {
+    day: Vec<u8>,
+    hijri_month: Vec<u8>,
+    hijri_day: Vec<u8>,
+    imsak: Vec<f64>,
+    fajr: Vec<f64>,
+    maghrib: Vec<f64>,
+    isha: Vec<f64>,
+}
+
+impl Timetable {
+    fn into_js(self) -> Result<js_sys::Object, JsValue> {
+        let obj = js_sys::Object::new();
+        let set = |key: &str, value: JsValue| js_sys::Reflect::set(&obj, &JsValue::from_str(key), &value).map(|_| ());
+        set("day", js_sys::Uint8Array::from(self.day.as_slice()).into())?;
+        set("hijriMonth", js_sys::Uint8Array::from(self.hijri_month.as_slice()).into())?;
+        set("hijriDay", js_sys::Uint8Array::from(self.hijri_day.as_slice()).into())?;
+        set("imsak", js_sys::Float64Array::from(self.imsak.as_slice()).into())?;
+        set("fajr", js_sys::Float64Array::from(self.fajr.as_slice()).into())?;
+        set("maghrib", js_sys::Float64Array::from(self.maghrib.as_slice()).into())?;
+        set("isha", js_sys::Float64Array::from(self.isha.as_slice()).into())?;
+        Ok(obj)
+    }
+}
+
+/// Streams an iCalendar (.ics) export of `start`..=`end` (YYYY-MM-DD) to
+/// `onChunk`, one Gregorian month per call, so multi-year exports never build
+/// one giant string. Chunks concatenate to a valid document.
+///
+/// # Example (JavaScript)
+/// ```js
+/// const parts = [];
+/// exportIcs("2026-01-01", "2030-12-31", chunk => parts.push(chunk));
+/// const blob = new Blob(parts, { type: "text/calendar" });
+/// ```
+#[wasm_bindgen(js_name = exportIcs)]
+pub fn export_ics(start: &str, end: &str, on_chunk: &js_sys::Function) -> Result<(), JsValue> {
+    let mut stream = IcsStream::new(start, end)?;
+    while let Some(chunk) = stream.next_chunk()? {
+        on_chunk.call1(&JsValue::NULL, &JsValue::from_str(&chunk))?;
+    }
+    Ok(())
+}
+
+/// Pull-based iCalendar export, one chunk per [`IcsStream::next_chunk`] call.
+///
+/// # Example (JavaScript)
+/// ```js
+/// async function* icsChunks(start, end) {
+///   const stream = new IcsStream(start, end);
+///   try {
+///     for (let chunk; (chunk = stream.nextChunk()) !== undefined;) {
+///       yield chunk;
+///       await new Promise(r => setTimeout(r)); // keep the page responsive
+///     }
+///   } finally {
+///     stream.free();
+///   }
+/// }
+/// const body = new ReadableStream({ async start(c) { for await (const s of icsChunks("2026-01-01", "2030-12-31")) c.enqueue(s); c.close(); } });
+/// ```
+#[wasm_bindgen]
+pub struct IcsStream {
+    inner: shaum_core::rules::export::ics::IcsExport<'static>,
+}
+
+#[wasm_bindgen]
+impl IcsStream {
+    #[wasm_bindgen(constructor)]
+    pub fn new(start: &str, end: &str) -> Result<IcsStream, JsValue> {
+        let parse = |s: &str| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .map_err(|e| JsValue::from_str(&format!("Invalid date format: {}", e)));
+        let inner = shaum_core::rules::export::ics::IcsExport::owned(
+            parse(start)?,
+            parse(end)?,
+            RuleContext::default(),
+            &shaum_core::rules::i18n::EnglishLocalizer,
+        )
+        .map_err(to_js)?;
+        Ok(IcsStream { inner })
+    }
+
+    /// Next chunk of the document, or `undefined` when complete.
+    #[wasm_bindgen(js_name = nextChunk)]
+    pub fn next_chunk(&mut self) -> Result<Option<String>, JsValue> {
+        self.inner.next().transpose().map_err(to_js)
+    }
+}
+
+/// Every rule trace code (stable snake_case), for exhaustive mappings.
+///
+/// # Example (JavaScript)
+/// ```js
+/// const labels = Object.fromEntries(traceCodes().map(c => [c, t(`trace.${c}`)]));
+/// ```
+#[wasm_bindgen(js_name = traceCodes)]
+pub fn trace_codes() -> Vec<String> {
+    shaum_core::TraceCode::all().iter().map(|c| c.code().to_string()).collect()
+}
+
+/// Every built-in fasting type name (e.g. "AyyamulBidh"), as found in `reasons`.
+///
+/// # Example (JavaScript)
+/// ```js
+/// const missing = fastingTypes().filter(t => !(t in translations));
+/// ```
+#[wasm_bindgen(js_name = fastingTypes)]
+pub fn fasting_types() -> Vec<String> {
+    shaum_core::FastingType::builtin().iter().map(|t| t.to_string()).collect()
+}
+
 /// Class-based API for Shaum analysis.
 ///
 /// # Example (JavaScript)
@@ -39,10 +213,28 @@ pub fn analyze(date_str: &str) -> Result<JsValue, JsValue> {
 /// const analysis = shaum.analyze();
 /// console.log(analysis.status);
 /// console.log(shaum.explain());
+///
+/// // Location-aware (Maghrib boundary, prayer times)
+/// const here = await Shaum.fromBrowserLocation("2026-03-01");
+/// console.log(here.prayerTimes().maghrib);
+///
+/// // Engine options
+/// const lenient = new Shaum("2026-03-07").withOptions({ disabledRules: ["SaturdayExclusive"] });
 /// ```
 #[wasm_bindgen]
 pub struct Shaum {
     date: chrono::NaiveDate,
+    coords: Option<GeoCoordinate>,
+    context: RuleContext,
+}
+
+/// Engine options accepted by [`Shaum::with_options`] and [`analyze_with`].
+#[derive(Deserialize, Default, tsify::Tsify)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WasmOptions {
+    /// Built-in rules to skip, by type name (e.g. `"SaturdayExclusive"`).
+    /// Haram and Wajib rules cannot be skipped.
+    pub disabled_rules: Vec<String>,
 }
 
 #[wasm_bindgen]
@@ -53,12 +245,95 @@ impl Shaum {
         console_error_panic_hook::set_once();
         let date = chrono::NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
             .map_err(|e| JsValue::from_str(&format!("Invalid date format: {}", e)))?;
-        Ok(Shaum { date })
+        Ok(Shaum { date, coords: None, context: RuleContext::default() })
+    }
+
+    /// Applies engine options (see `WasmOptions`). Unknown rule names are rejected.
+    #[wasm_bindgen(js_name = withOptions)]
+    pub fn with_options(mut self, options: JsValue) -> Result<Shaum, JsValue> {
+        let options: WasmOptions = if options.is_undefined() || options.is_null() {
+            WasmOptions::default()
+        } else {
+            serde_wasm_bindgen::from_value(options).map_err(|e| JsValue::from_str(&e.to_string()))?
+        };
+        self.context = RuleContext::default().disable_rules_by_name(options.disabled_rules).map_err(to_js)?;
+        Ok(self)
+    }
+
+    /// Creates a location-aware instance from explicit coordinates.
+    #[wasm_bindgen(js_name = withLocation)]
+    pub fn with_location(date_str: &str, lat: f64, lng: f64) -> Result<Shaum, JsValue> {
+        let mut shaum = Shaum::new(date_str)?;
+        shaum.coords = Some(GeoCoordinate::new(lat, lng).map_err(to_js)?);
+        Ok(shaum)
+    }
+
+    /// Creates a location-aware instance from a coordinate string: `"lat,lng"`,
+    /// degrees/minutes/seconds (`6°12'31"S 106°50'44"E`) or a `geo:` URI.
+    #[wasm_bindgen(js_name = withLocationString)]
+    pub fn with_location_string(date_str: &str, location: &str) -> Result<Shaum, JsValue> {
+        let mut shaum = Shaum::new(date_str)?;
+        shaum.coords = Some(location.parse::<GeoCoordinate>().map_err(to_js)?);
+        Ok(shaum)
+    }
+
+    /// Creates a location-aware instance using `navigator.geolocation`.
+    ///
+    /// Rejects if the user denies permission or geolocation is unavailable.
+    #[wasm_bindgen(js_name = fromBrowserLocation)]
+    pub async fn from_browser_location(date_str: String) -> Result<Shaum, JsValue> {
+        let mut shaum = Shaum::new(&date_str)?;
+        shaum.coords = Some(browser_position().await?);
+        Ok(shaum)
+    }
+
+    /// Latitude of a location-aware instance.
+    #[wasm_bindgen(getter)]
+    pub fn latitude(&self) -> Option<f64> {
+        self.coords.map(|c| c.lat)
+    }
+
+    /// Longitude of a location-aware instance.
+    #[wasm_bindgen(getter)]
+    pub fn longitude(&self) -> Option<f64> {
+        self.coords.map(|c| c.lng)
+    }
+
+    /// Analyzes the current moment. Location-aware instances roll over to
+    /// the next day at local Maghrib.
+    #[wasm_bindgen(js_name = analyzeNow)]
+    pub fn analyze_now(&self) -> Result<JsValue, JsValue> {
+        let now = chrono::DateTime::from_timestamp_millis(js_sys::Date::now() as i64)
+            .ok_or_else(|| JsValue::from_str("Invalid system clock"))?;
+        let ctx = &self.context;
+        let analysis = match self.coords {
+            Some(coords) => shaum_core::analyze_local(now, coords, ctx),
+            None => shaum_core::analyze(now, ctx, None),
+        }
+        .map_err(to_js)?;
+        serde_wasm_bindgen::to_value(&WasmFastingAnalysis::from(analysis))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Returns Imsak, Fajr and Maghrib (RFC 3339, UTC) for this date and location.
+    #[wasm_bindgen(js_name = prayerTimes)]
+    pub fn prayer_times(&self) -> Result<JsValue, JsValue> {
+        let coords = self.coords
+            .ok_or_else(|| JsValue::from_str("Prayer times require a location"))?;
+        let times = shaum_core::astronomy::prayer::calculate_prayer_times(self.date, coords, &PrayerParams::default())
+            .map_err(to_js)?;
+        let result = WasmPrayerTimes {
+            imsak: times.imsak.to_rfc3339(),
+            fajr: times.fajr.to_rfc3339(),
+            maghrib: times.maghrib.to_rfc3339(),
+        };
+        serde_wasm_bindgen::to_value(&result)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
     }
     
     /// Returns the fasting analysis for this date.
     pub fn analyze(&self) -> Result<JsValue, JsValue> {
-        let analysis = shaum_core::analyze_date(self.date)
+        let analysis = shaum_core::check(self.date, &self.context)
             .map_err(|e| JsValue::from_str(&e.to_string()))?;
         let result = WasmFastingAnalysis::from(analysis);
         serde_wasm_bindgen::to_value(&result)
@@ -67,19 +342,47 @@ impl Shaum {
     
     /// Returns a human-readable explanation of the fasting status.
     pub fn explain(&self) -> Result<String, JsValue> {
-        let analysis = shaum_core::analyze_date(self.date)
+        let analysis = shaum_core::check(self.date, &self.context)
             .map_err(|e| JsValue::from_str(&e.to_string()))?;
         Ok(analysis.explain())
     }
     
     /// Returns the Hijri date as a string (day-month-year).
     pub fn hijri_date(&self) -> Result<String, JsValue> {
-        let analysis = shaum_core::analyze_date(self.date)
+        let analysis = shaum_core::check(self.date, &self.context)
             .map_err(|e| JsValue::from_str(&e.to_string()))?;
         Ok(format!("{}-{}-{}", analysis.hijri_day, analysis.hijri_month, analysis.hijri_year))
     }
 }
 
+/// Prayer times as RFC 3339 strings.
+#[derive(Serialize, tsify::Tsify)]
+#[tsify(into_wasm_abi)]
+pub struct WasmPrayerTimes {
+    pub imsak: String,
+    pub fajr: String,
+    pub maghrib: String,
+}
+
+fn to_js(e: shaum_core::ShaumError) -> JsValue {
+    JsValue::from_str(&e.to_string())
+}
+
+/// Resolves the device position via `navigator.geolocation.getCurrentPosition`.
+async fn browser_position() -> Result<GeoCoordinate, JsValue> {
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("Browser geolocation requires a window"))?;
+    let geolocation = window.navigator().geolocation()?;
+
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        if let Err(e) = geolocation.get_current_position_with_error_callback(&resolve, Some(&reject)) {
+            let _ = reject.call1(&JsValue::NULL, &e);
+        }
+    });
+    let position: web_sys::Position = wasm_bindgen_futures::JsFuture::from(promise).await?.unchecked_into();
+    let coords = position.coords();
+    GeoCoordinate::new(coords.latitude(), coords.longitude()).map_err(to_js)
+}
+
 /// WASM-friendly representation of FastingAnalysis for TypeScript generation.
 #[derive(Serialize, tsify::Tsify)]
 #[tsify(into_wasm_abi)]
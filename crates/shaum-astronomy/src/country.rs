@@ -0,0 +1,121 @@
+//! Coarse country outlines, for picking a default prayer method from coordinates.
+
+use shaum_types::{GeoCoordinate, PrayerParams};
+
+/// Method for the country containing `coords`, using coarse outlines.
+///
+/// Points within a few kilometres of a border may resolve to a neighbour; use
+/// [`PrayerParams::for_country`] when the country is known.
+pub fn params_for_location(coords: GeoCoordinate) -> PrayerParams {
+    params_for_location_with(coords, &[])
+}
+
+/// Like [`params_for_location`], but `overrides` take precedence.
+pub fn params_for_location_with(coords: GeoCoordinate, overrides: &[(&str, PrayerParams)]) -> PrayerParams {
+    PrayerParams::for_country_with(country_at(coords).unwrap_or(""), overrides)
+}
+
+/// (code, outline as (lat, lng) vertices). Checked in order, enclaves first.
+///
+/// Outlines are coarse and follow the borders shared with Muslim-majority
+/// neighbours (Gulf states, Iraq, Jordan, Yemen, southern Thailand, Mexico,
+/// Ireland) closely enough that their cities do not resolve to the country
+/// next door. Coastlines are drawn offshore. Neighbours inside a coarse
+/// outline (Timor-Leste and the southern Philippines in Indonesia's) are
+/// listed before it.
+const COUNTRY_OUTLINES: &[(&str, &[(f64, f64)])] = &[
+    ("SG", &[(1.15, 103.6), (1.48, 103.6), (1.48, 104.1), (1.15, 104.1)]),
+    ("BN", &[(4.0, 114.0), (5.1, 114.0), (5.1, 115.4), (4.0, 115.4)]),
+    // Peninsular Malaysia, north edge along the Thai border.
+    ("MY", &[
+        (6.72, 100.12), (6.45, 100.5), (5.6, 101.1), (5.85, 101.65), (6.2, 102.1), (6.3, 102.5),
+        (2.5, 104.3), (1.25, 104.3), (1.2, 103.4), (3.0, 100.9), (5.5, 100.0), (6.5, 99.6),
+    ]),
+    // Sabah and Sarawak, south edge along Kalimantan.
+    ("MY", &[
+        (2.0, 109.5), (1.0, 110.5), (1.0, 112.0), (1.5, 113.6), (2.2, 114.9), (4.0, 115.5),
+        (4.15, 116.0), (4.15, 119.3), (7.4, 119.3), (7.4, 116.5), (5.0, 114.3),
+    ]),
+    // Timor-Leste, with Atauro but not Alor, Wetar or Kisar; then Oecusse.
+    ("TL", &[
+        (-8.93, 125.08), (-8.5, 125.25), (-8.15, 125.5), (-8.1, 125.75), (-8.3, 126.6), (-8.3, 127.5),
+        (-8.9, 127.5), (-9.3, 126.5), (-9.65, 125.3), (-9.55, 125.02), (-9.15, 125.1),
+    ]),
+    ("TL", &[(-9.1, 124.0), (-9.1, 124.5), (-9.35, 124.5), (-9.5, 124.3), (-9.45, 124.05)]),
+    // Sulu Archipelago and southern Mindanao, short of Miangas and the Sangihe Islands.
+    ("PH", &[
+        (4.4, 119.35), (6.2, 119.35), (6.2, 126.35), (5.8, 126.35), (5.1, 125.8), (5.1, 121.0), (4.4, 120.3),
+    ]),
+    ("ID", &[(-11.0, 95.0), (6.1, 95.0), (6.1, 141.1), (-11.0, 141.1)]),
+    ("EG", &[
+        (31.7, 25.0), (31.6, 32.0), (31.3, 34.22), (29.5, 34.9), (27.7, 34.3), (22.0, 36.9), (22.0, 25.0),
+    ]),
+    ("SA", &[
+        (29.4, 34.85), (29.2, 36.07), (29.5, 36.5), (30.0, 37.67), (31.5, 37.0), (32.15, 39.2),
+        (31.0, 41.4), (29.1, 44.7), (29.1, 46.55), (28.53, 48.42), (27.0, 49.8), (26.4, 50.25),
+        (25.6, 50.3), (24.7, 50.8), (24.25, 51.6), (22.7, 55.2), (22.0, 55.65), (19.0, 52.0),
+        (18.6, 50.5), (16.9, 47.5), (17.35, 46.0), (17.3, 44.0), (16.4, 42.75), (20.0, 39.9),
+        (21.5, 38.9), (24.0, 37.6), (26.5, 35.9), (28.0, 34.6),
+    ]),
+    // Great Britain, then Northern Ireland.
+    ("GB", &[
+        (49.8, -6.5), (50.5, -1.0), (51.0, 1.2), (51.5, 1.8), (53.0, 2.0), (56.0, -1.5), (58.0, -1.5),
+        (60.9, -0.7), (60.5, -2.0), (58.6, -7.0), (57.5, -7.8), (56.0, -6.6), (55.4, -5.9),
+        (54.0, -5.0), (53.3, -4.8), (52.0, -5.4), (51.6, -5.6),
+    ]),
+    ("GB", &[
+        (55.25, -6.9), (55.3, -6.0), (54.6, -5.4), (54.1, -6.3), (54.3, -7.0), (54.1, -7.6),
+        (54.4, -8.2), (54.85, -7.55), (55.05, -7.35),
+    ]),
+    // Contiguous US, along the Great Lakes and the Rio Grande.
+    ("US", &[
+        (49.0, -123.1), (49.0, -95.15), (48.0, -89.5), (46.5, -84.5), (45.3, -82.5), (42.3, -82.9),
+        (41.7, -82.5), (42.9, -79.0), (43.6, -79.2), (43.6, -76.5), (45.0, -74.7), (45.0, -71.5),
+        (47.4, -69.2), (45.2, -67.1), (44.8, -66.9), (41.0, -69.9), (35.2, -75.4), (30.5, -81.2),
+        (26.7, -79.9), (25.0, -80.2), (24.4, -81.9), (29.5, -85.5), (28.8, -89.5), (29.5, -94.0),
+        (25.96, -97.15), (26.4, -99.0), (27.5, -99.5), (29.4, -100.9), (29.0, -103.2), (29.8, -104.6),
+        (31.75, -106.45), (31.78, -108.2), (31.33, -108.2), (31.33, -111.07), (32.49, -114.8),
+        (32.72, -114.72), (32.53, -117.12), (32.5, -117.3), (34.4, -120.6), (40.4, -124.5),
+        (48.4, -124.8), (48.25, -123.3), (48.75, -123.0),
+    ]),
+    // Canada, east edge along Nares Strait, Baffin Bay and Davis Strait.
+    ("CA", &[
+        (41.6, -141.1), (83.2, -141.1), (83.2, -61.0), (82.0, -63.0), (80.5, -67.5), (79.0, -73.0),
+        (78.2, -73.7), (76.5, -74.5), (72.0, -64.0), (70.0, -61.0), (67.0, -57.5), (64.0, -58.0),
+        (62.0, -57.0), (60.0, -55.0), (52.0, -52.0), (41.6, -52.0),
+    ]),
+    ("US", &[(51.0, -170.0), (71.5, -170.0), (71.5, -129.9), (51.0, -129.9)]),
+];
+
+/// ISO 3166-1 alpha-2 code of the country containing `coords`, for the
+/// countries [`PrayerParams::for_country`] has a method for (and a few
+/// neighbours outlined to keep border cities apart).
+///
+/// ```rust
+/// use shaum_astronomy::country::country_at;
+/// use shaum_types::GeoCoordinate;
+///
+/// assert_eq!(country_at(GeoCoordinate::new(-6.1754, 106.8272).unwrap()), Some("ID"));
+/// assert_eq!(country_at(GeoCoordinate::new(35.6895, 139.6917).unwrap()), None);
+/// ```
+pub fn country_at(coords: GeoCoordinate) -> Option<&'static str> {
+    COUNTRY_OUTLINES
+        .iter()
+        .find(|(_, outline)| contains(outline, coords))
+        .map(|(code, _)| *code)
+}
+
+/// Even-odd ray casting along the parallel through `coords`.
+fn contains(outline: &[(f64, f64)], coords: GeoCoordinate) -> bool {
+    let (lat, lng) = (coords.lat, coords.lng);
+    let mut inside = false;
+    let mut prev = outline[outline.len() - 1];
+    for &vertex in outline {
+        let ((lat_a, lng_a), (lat_b, lng_b)) = (prev, vertex);
+        if (lat_a > lat) != (lat_b > lat) && lng < lng_a + (lat - lat_a) / (lat_b - lat_a) * (lng_b - lng_a) {
+            inside = !inside;
+        }
+        prev = vertex;
+    }
+    inside
+}
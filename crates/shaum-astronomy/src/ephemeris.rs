@@ -0,0 +1,105 @@
+//! Sun and Moon position snapshots.
+//!
+//! Bundles the VSOP87/ELP2000 and coordinate-conversion steps used internally by
+//! visibility and prayer calculations, for callers building custom criteria.
+
+use chrono::{DateTime, Utc};
+use shaum_types::GeoCoordinate;
+use super::{vsop87, elp2000, coords};
+use super::visibility::{datetime_to_jd, calculate_elongation};
+
+/// Kilometres per astronomical unit.
+const AU_KM: f64 = 149_597_870.7;
+
+/// Position of a body as seen by the observer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BodyPosition {
+    /// Geocentric ecliptic longitude (degrees).
+    pub ecliptic_longitude: f64,
+    /// Geocentric ecliptic latitude (degrees).
+    pub ecliptic_latitude: f64,
+    /// Right ascension (degrees). Topocentric for the Moon.
+    pub ra: f64,
+    /// Declination (degrees). Topocentric for the Moon.
+    pub dec: f64,
+    /// Azimuth (degrees from North, clockwise).
+    pub azimuth: f64,
+    /// Geometric altitude (degrees).
+    pub altitude: f64,
+    /// Altitude including atmospheric refraction (degrees).
+    pub apparent_altitude: f64,
+    /// Geocentric distance (km).
+    pub distance_km: f64,
+}
+
+/// Sun and Moon positions at one instant for one observer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ephemeris {
+    pub time: DateTime<Utc>,
+    pub julian_day: f64,
+    /// Local sidereal time (degrees).
+    pub local_sidereal_time: f64,
+    pub sun: BodyPosition,
+    pub moon: BodyPosition,
+    /// Geocentric Sun-Moon elongation (degrees).
+    pub elongation: f64,
+}
+
+/// Computes Sun and Moon positions for `datetime` as seen from `coords`.
+///
+/// The Moon's equatorial and horizontal coordinates are corrected for
+/// topocentric parallax using the observer's altitude.
+///
+/// # Example
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use shaum_types::GeoCoordinate;
+/// use shaum_astronomy::ephemeris;
+///
+/// let jakarta = GeoCoordinate::new(-6.2088, 106.8456).unwrap();
+/// let eph = ephemeris(Utc.with_ymd_and_hms(2024, 3, 10, 11, 0, 0).unwrap(), jakarta);
+/// println!("Moon altitude: {:.2}°", eph.moon.apparent_altitude);
+/// ```
+pub fn ephemeris(datetime: DateTime<Utc>, coords: GeoCoordinate) -> Ephemeris {
+    let jd = datetime_to_jd(datetime);
+    let obliquity = coords::mean_obliquity(jd);
+    let lst = coords::local_sidereal_time(jd, coords.lng);
+
+    let (sun_lon, sun_lat, sun_dist_au) = vsop87::calculate(jd);
+    let (sun_ra, sun_dec) = coords::ecliptic_to_equatorial(sun_lon, sun_lat, obliquity);
+    let (sun_az, sun_alt) = coords::equatorial_to_horizontal(sun_ra, sun_dec, lst, coords.lat);
+
+    let (moon_lon, moon_lat, moon_dist) = elp2000::calculate(jd);
+    let (moon_ra, moon_dec) = coords::ecliptic_to_equatorial(moon_lon, moon_lat, obliquity);
+    let (moon_ra, moon_dec) = coords::apply_parallax(
+        moon_ra, moon_dec, moon_dist, coords.lat, coords.altitude, lst
+    );
+    let (moon_az, moon_alt) = coords::equatorial_to_horizontal(moon_ra, moon_dec, lst, coords.lat);
+
+    Ephemeris {
+        time: datetime,
+        julian_day: jd,
+        local_sidereal_time: lst,
+        sun: BodyPosition {
+            ecliptic_longitude: sun_lon,
+            ecliptic_latitude: sun_lat,
+            ra: sun_ra,
+            dec: sun_dec,
+            azimuth: sun_az,
+            altitude: sun_alt,
+            apparent_altitude: sun_alt + coords::refraction_correction(sun_alt),
+            distance_km: sun_dist_au * AU_KM,
+        },
+        moon: BodyPosition {
+            ecliptic_longitude: moon_lon,
+            ecliptic_latitude: moon_lat,
+            ra: moon_ra,
+            dec: moon_dec,
+            azimuth: moon_az,
+            altitude: moon_alt,
+            apparent_altitude: moon_alt + coords::refraction_correction(moon_alt),
+            distance_km: moon_dist,
+        },
+        elongation: calculate_elongation(sun_lon, sun_lat, moon_lon, moon_lat),
+    }
+}
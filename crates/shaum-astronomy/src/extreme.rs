@@ -0,0 +1,283 @@
+//! Prayer times and Qibla that stay well-defined for arbitrary input.
+//!
+//! [`crate::prayer::calculate_prayer_times`] rejects latitudes beyond 66.5°
+//! and assumes every event happens. [`prayer_times_checked`] instead inspects
+//! the sun's daily altitude range and reports each time as
+//! [`PrayerTime::Defined`], [`PrayerTime::Adjusted`] by an [`ExtremePolicy`],
+//! or [`PrayerTime::Undefined`] with a reason and a suggested policy.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use shaum_types::{
+    ExtremePolicy, GeoCoordinate, IshaRule, PrayerParams, PrayerTime, Qibla, ShaumError, UndefinedReason,
+    KAABA, ORBIT_ALTITUDE_M,
+};
+
+use crate::prayer::{apply_ihtiyat_and_round, find_sun_altitude_time, solar_transit, sun_altitude};
+use crate::visibility::{estimate_sunset, horizon_altitude};
+
+/// Mean Earth radius in meters.
+const EARTH_RADIUS_M: f64 = 6_371_000.0;
+
+/// Prayer times where each entry may be undefined.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CheckedPrayerTimes {
+    pub imsak: PrayerTime,
+    pub fajr: PrayerTime,
+    pub maghrib: PrayerTime,
+    pub isha: PrayerTime,
+}
+
+type Event = Result<DateTime<Utc>, UndefinedReason>;
+
+/// Raw (unrounded) event times at one location.
+struct RawTimes {
+    fajr: Event,
+    maghrib: Event,
+    isha: Event,
+}
+
+/// Prayer times for any observer, including polar regions and orbit.
+///
+/// Defined times equal those of
+/// [`calculate_prayer_times_in_ramadhan`](crate::prayer::calculate_prayer_times_in_ramadhan).
+/// Undefined ones are substituted according to `policy`; anything the policy
+/// cannot resolve is returned as [`PrayerTime::Undefined`].
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_astronomy::extreme::prayer_times_checked;
+/// use shaum_types::{ExtremePolicy, GeoCoordinate, PrayerParams, PrayerTime, UndefinedReason};
+///
+/// // Tromsø at midsummer: the sun does not set.
+/// let tromso = GeoCoordinate::new(69.6492, 18.9553).unwrap();
+/// let date = NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
+/// let times = prayer_times_checked(date, tromso, &PrayerParams::default(), false, ExtremePolicy::Strict).unwrap();
+/// assert!(matches!(times.maghrib, PrayerTime::Undefined { reason: UndefinedReason::SunNeverSets, .. }));
+/// ```
+pub fn prayer_times_checked(
+    date: NaiveDate,
+    coords: GeoCoordinate,
+    params: &PrayerParams,
+    in_ramadhan: bool,
+    policy: ExtremePolicy,
+) -> Result<CheckedPrayerTimes, ShaumError> {
+    let raw = raw_times(date, coords, params, in_ramadhan)?;
+    let fajr = resolve(raw.fajr, policy, |at| Ok(raw_times(date, at, params, in_ramadhan)?.fajr), || {
+        let night = night_length(date.pred_opt()?, coords)?;
+        Some(find_sun_altitude_time(date, coords, horizon_altitude(coords.altitude), true).ok()? - night / 7)
+    }, coords)?;
+    let maghrib = resolve(raw.maghrib, policy, |at| Ok(raw_times(date, at, params, in_ramadhan)?.maghrib), || None, coords)?;
+    let isha = resolve(raw.isha, policy, |at| Ok(raw_times(date, at, params, in_ramadhan)?.isha), || {
+        let night = night_length(date, coords)?;
+        Some(raw.maghrib.ok()? - Duration::minutes(params.maghrib_offset_minutes) + night / 7)
+    }, coords)?;
+
+    let finish = |t| apply_ihtiyat_and_round(t, params.ihtiyat_minutes, params.rounding_granularity_seconds);
+    Ok(CheckedPrayerTimes {
+        imsak: fajr.map(|t| finish(t - Duration::minutes(params.imsak_buffer_minutes))),
+        fajr: fajr.map(finish),
+        maghrib: maghrib.map(finish),
+        isha: isha.map(finish),
+    })
+}
+
+/// Applies `policy` to an undefined event.
+fn resolve(
+    event: Event,
+    policy: ExtremePolicy,
+    at: impl Fn(GeoCoordinate) -> Result<Event, ShaumError>,
+    seventh_of_night: impl FnOnce() -> Option<DateTime<Utc>>,
+    coords: GeoCoordinate,
+) -> Result<PrayerTime, ShaumError> {
+    let reason = match event {
+        Ok(t) => return Ok(PrayerTime::Defined(t)),
+        Err(reason) => reason,
+    };
+    let substitute = match policy {
+        ExtremePolicy::Strict => None,
+        ExtremePolicy::NearestLatitude { max_lat } if reason != UndefinedReason::Orbit && coords.lat.abs() > max_lat => {
+            at(GeoCoordinate { lat: max_lat.copysign(coords.lat), ..coords })?.ok()
+        }
+        ExtremePolicy::NearestLatitude { .. } => None,
+        ExtremePolicy::SeventhOfNight if reason == UndefinedReason::TwilightNeverEnds => seventh_of_night(),
+        ExtremePolicy::SeventhOfNight => None,
+        ExtremePolicy::ReferenceLocation(place) => at(place)?.ok(),
+    };
+    Ok(match substitute {
+        Some(time) => PrayerTime::Adjusted { time, policy },
+        None => PrayerTime::undefined(reason),
+    })
+}
+
+fn raw_times(date: NaiveDate, coords: GeoCoordinate, params: &PrayerParams, in_ramadhan: bool) -> Result<RawTimes, ShaumError> {
+    if coords.altitude > ORBIT_ALTITUDE_M {
+        let orbit = Err(UndefinedReason::Orbit);
+        return Ok(RawTimes { fajr: orbit, maghrib: orbit, isha: orbit });
+    }
+
+    let (max_alt, min_alt) = altitude_range(date, coords)?;
+    let horizon = horizon_altitude(coords.altitude);
+    let maghrib = if max_alt <= horizon {
+        Err(UndefinedReason::SunNeverRises)
+    } else if min_alt >= horizon {
+        Err(UndefinedReason::SunNeverSets)
+    } else if coords.lat.abs() <= 66.5 {
+        Ok(estimate_sunset(date, coords)?)
+    } else {
+        Ok(find_sun_altitude_time(date, coords, horizon, false)?)
+    }
+    .map(|t| t + Duration::minutes(params.maghrib_offset_minutes));
+
+    let twilight = |angle: f64, morning: bool| -> Result<Event, ShaumError> {
+        Ok(if max_alt <= angle {
+            Err(UndefinedReason::SunNeverRises)
+        } else if min_alt >= angle {
+            Err(match maghrib {
+                Err(UndefinedReason::SunNeverSets) => UndefinedReason::SunNeverSets,
+                _ => UndefinedReason::TwilightNeverEnds,
+            })
+        } else {
+            Ok(find_sun_altitude_time(date, coords, angle, morning)?)
+        })
+    };
+
+    let fajr = twilight(params.fajr_angle, true)?;
+    let isha = match params.isha {
+        IshaRule::Angle(angle) => twilight(angle, false)?,
+        rule @ IshaRule::Interval { .. } => {
+            maghrib.map(|t| t + Duration::minutes(rule.interval_minutes(in_ramadhan).unwrap_or_default()))
+        }
+    };
+    Ok(RawTimes { fajr, maghrib, isha })
+}
+
+/// Highest and lowest sun altitude (degrees) over the local day.
+fn altitude_range(date: NaiveDate, coords: GeoCoordinate) -> Result<(f64, f64), ShaumError> {
+    let transit = solar_transit(date, coords)?;
+    let max = sun_altitude(transit, coords);
+    let min = sun_altitude(transit - Duration::hours(12), coords)
+        .min(sun_altitude(transit + Duration::hours(12), coords));
+    Ok((max, min))
+}
+
+/// Sunset of `date` to sunrise of the next day, if both happen.
+fn night_length(date: NaiveDate, coords: GeoCoordinate) -> Option<Duration> {
+    let horizon = horizon_altitude(coords.altitude);
+    let (max_alt, min_alt) = altitude_range(date, coords).ok()?;
+    if max_alt <= horizon || min_alt >= horizon {
+        return None;
+    }
+    let sunset = find_sun_altitude_time(date, coords, horizon, false).ok()?;
+    let sunrise = find_sun_altitude_time(date.succ_opt()?, coords, horizon, true).ok()?;
+    Some(sunrise - sunset)
+}
+
+/// Direction of the Kaaba from `coords`.
+///
+/// ```rust
+/// use shaum_astronomy::extreme::qibla;
+/// use shaum_types::{GeoCoordinate, Qibla};
+///
+/// let Qibla::Bearing(jakarta) = qibla(GeoCoordinate::new(-6.2088, 106.8456).unwrap()) else { panic!() };
+/// assert!((jakarta - 295.1).abs() < 0.5);
+/// ```
+pub fn qibla(coords: GeoCoordinate) -> Qibla {
+    if coords.altitude > ORBIT_ALTITUDE_M {
+        return Qibla::Undefined(UndefinedReason::Orbit);
+    }
+    if coords.lat.abs() >= 90.0 - 1e-6 {
+        return Qibla::Undefined(UndefinedReason::Pole);
+    }
+    let (phi1, phi2) = (coords.lat.to_radians(), KAABA.lat.to_radians());
+    let dlambda = (KAABA.lng - coords.lng).to_radians();
+
+    let central = (phi1.sin() * phi2.sin() + phi1.cos() * phi2.cos() * dlambda.cos()).clamp(-1.0, 1.0).acos();
+    if central * EARTH_RADIUS_M < 10.0 {
+        return Qibla::Undefined(UndefinedReason::AtKaaba);
+    }
+    if (std::f64::consts::PI - central) * EARTH_RADIUS_M < 10.0 {
+        return Qibla::Undefined(UndefinedReason::Antipode);
+    }
+
+    let y = dlambda.sin() * phi2.cos();
+    let x = phi1.cos() * phi2.sin() - phi1.sin() * phi2.cos() * dlambda.cos();
+    Qibla::Bearing(y.atan2(x).to_degrees().rem_euclid(360.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prayer::calculate_prayer_times;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate { NaiveDate::from_ymd_opt(y, m, d).unwrap() }
+
+    #[test]
+    fn test_defined_matches_calculate_prayer_times() {
+        let jakarta = GeoCoordinate::new(-6.2088, 106.8456).unwrap();
+        let params = PrayerParams::default();
+        let checked = prayer_times_checked(date(2024, 3, 20), jakarta, &params, false, ExtremePolicy::Strict).unwrap();
+        let plain = calculate_prayer_times(date(2024, 3, 20), jakarta, &params).unwrap();
+        assert_eq!(checked.fajr, PrayerTime::Defined(plain.fajr));
+        assert_eq!(checked.imsak, PrayerTime::Defined(plain.imsak));
+        assert_eq!(checked.maghrib, PrayerTime::Defined(plain.maghrib));
+        assert_eq!(checked.isha, PrayerTime::Defined(plain.isha));
+    }
+
+    #[test]
+    fn test_white_nights_seventh_of_night() {
+        // London in June: sunset happens but the sun never reaches -18°.
+        let london = GeoCoordinate::new(51.5074, -0.1278).unwrap();
+        let params = PrayerParams::default();
+        let strict = prayer_times_checked(date(2024, 6, 21), london, &params, false, ExtremePolicy::Strict).unwrap();
+        assert!(strict.maghrib.is_defined());
+        assert_eq!(strict.isha, PrayerTime::undefined(UndefinedReason::TwilightNeverEnds));
+        assert_eq!(strict.fajr.time(), None);
+
+        let seventh = prayer_times_checked(date(2024, 6, 21), london, &params, false, ExtremePolicy::SeventhOfNight).unwrap();
+        let (maghrib, isha) = (seventh.maghrib.time().unwrap(), seventh.isha.time().unwrap());
+        assert!(matches!(seventh.isha, PrayerTime::Adjusted { policy: ExtremePolicy::SeventhOfNight, .. }));
+        assert!(isha > maghrib && isha - maghrib < Duration::hours(2));
+        assert!(seventh.imsak.time().unwrap() < seventh.fajr.time().unwrap());
+    }
+
+    #[test]
+    fn test_polar_night_and_nearest_latitude() {
+        let svalbard = GeoCoordinate::new(78.2232, 15.6267).unwrap();
+        let params = PrayerParams::default();
+        let strict = prayer_times_checked(date(2024, 12, 21), svalbard, &params, false, ExtremePolicy::Strict).unwrap();
+        assert_eq!(strict.maghrib, PrayerTime::undefined(UndefinedReason::SunNeverRises));
+
+        let policy = ExtremePolicy::NearestLatitude { max_lat: 60.0 };
+        let nearest = prayer_times_checked(date(2024, 12, 21), svalbard, &params, false, policy).unwrap();
+        assert!(matches!(nearest.maghrib, PrayerTime::Adjusted { .. }));
+        assert!(nearest.fajr.time().is_some());
+    }
+
+    #[test]
+    fn test_orbit() {
+        let iss = GeoCoordinate::new(10.0, 20.0).unwrap().with_altitude(420_000.0);
+        let params = PrayerParams::default();
+        let strict = prayer_times_checked(date(2024, 3, 20), iss, &params, false, ExtremePolicy::Strict).unwrap();
+        assert!(matches!(
+            strict.maghrib,
+            PrayerTime::Undefined { reason: UndefinedReason::Orbit, suggested_policy: ExtremePolicy::ReferenceLocation(_) }
+        ));
+
+        let baikonur = GeoCoordinate::new(45.965, 63.305).unwrap();
+        let launch_site = prayer_times_checked(date(2024, 3, 20), iss, &params, false, ExtremePolicy::ReferenceLocation(baikonur)).unwrap();
+        let expected = calculate_prayer_times(date(2024, 3, 20), baikonur, &params).unwrap();
+        assert_eq!(launch_site.maghrib.time(), Some(expected.maghrib));
+        assert_eq!(qibla(iss), Qibla::Undefined(UndefinedReason::Orbit));
+    }
+
+    #[test]
+    fn test_qibla_degenerate() {
+        assert_eq!(qibla(KAABA), Qibla::Undefined(UndefinedReason::AtKaaba));
+        assert_eq!(qibla(GeoCoordinate::new(90.0, 0.0).unwrap()), Qibla::Undefined(UndefinedReason::Pole));
+        let antipode = GeoCoordinate::new(-KAABA.lat, KAABA.lng - 180.0).unwrap();
+        assert_eq!(qibla(antipode), Qibla::Undefined(UndefinedReason::Antipode));
+
+        let Qibla::Bearing(new_york) = qibla(GeoCoordinate::new(40.7128, -74.0060).unwrap()) else { panic!() };
+        assert!((new_york - 58.5).abs() < 0.5, "{}", new_york);
+    }
+}
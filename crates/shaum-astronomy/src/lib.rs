@@ -7,3 +7,14 @@ pub mod elp2000;
 pub mod coords;
 pub mod visibility;
 pub mod prayer;
+pub mod prayer_cache;
+pub mod route;
+pub mod extreme;
+pub mod ephemeris;
+pub mod lunar_calendar;
+pub mod country;
+
+pub use ephemeris::{ephemeris, Ephemeris, BodyPosition};
+pub use lunar_calendar::{AstronomicalCalendar, predict_month_starts, MonthStartPrediction, CrescentVisibility};
+pub use country::{country_at, params_for_location, params_for_location_with};
+pub use prayer_cache::{PrayerCache, DEFAULT_PRAYER_CACHE_CAPACITY};
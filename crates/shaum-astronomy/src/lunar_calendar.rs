@@ -0,0 +1,505 @@
+//! Hijri calendar driven by computed crescent visibility.
+//!
+//! Each month starts on the day after the first evening at which the crescent
+//! meets the configured [`VisibilityCriteria`]. The [`SightingZone`] decides
+//! whose evening counts: anywhere on Earth (global) or one observer (local).
+//! Months are kept to 29 or 30 days: an unseen crescent completes the month
+//! (istikmal), and a sighting before the 29th evening waits for it.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use shaum_calendar::{cache_id_of, HijriCalendar, HijriYmd, UmmAlQura};
+use shaum_types::{GeoCoordinate, PrayerParams, ShaumError, SightingZone, VisibilityCriteria};
+
+use crate::prayer::calculate_prayer_times;
+use crate::visibility::{calculate_visibility, datetime_to_jd, jd_to_datetime, MoonVisibilityReport};
+use crate::{elp2000, vsop87};
+
+/// Mean synodic month (days).
+const SYNODIC_MONTH: f64 = 29.530588853;
+/// Reference new moon: 2000-01-06 18:14 UT.
+const REF_NEW_MOON_JD: f64 = 2451550.26;
+/// Mean Moon-minus-Sun longitude rate (degrees/day).
+const ELONGATION_RATE: f64 = 360.0 / SYNODIC_MONTH;
+/// Evenings checked after conjunction before completing the month (istikmal).
+const MAX_EVENINGS: i64 = 3;
+/// Preceding months whose observed starts a month start is resolved from.
+const LENGTH_LOOKBACK: usize = 12;
+/// Longitudes west of this count as the Americas for the Unified Hijri Calendar.
+const AMERICAS_EAST_LNG: f64 = -30.0;
+/// Reference site for the Unified Hijri Calendar's New Zealand Fajr condition.
+const WELLINGTON: GeoCoordinate = GeoCoordinate { lat: -41.2865, lng: 174.7762, altitude: 0.0 };
+/// Reference site for predictions with a global sighting zone.
+const MAKKAH: GeoCoordinate = GeoCoordinate { lat: 21.4225, lng: 39.8262, altitude: 0.0 };
+/// Altitude/elongation margin (degrees) within which a prediction is flagged as uncertain.
+const CONFIDENCE_MARGIN_DEG: f64 = 1.0;
+
+/// Observer grid used for [`SightingZone::Global`]: every 15° of longitude
+/// between 40°S and 40°N, where the crescent is usually first seen.
+fn global_sites() -> impl Iterator<Item = GeoCoordinate> {
+    (-2..=2).flat_map(|lat| {
+        (-11..=12).map(move |lng| GeoCoordinate::new_unchecked(lat as f64 * 20.0, lng as f64 * 15.0))
+    })
+}
+
+/// Instant of the last geocentric conjunction at or before `t`.
+pub fn new_moon_before(t: DateTime<Utc>) -> Result<DateTime<Utc>, ShaumError> {
+    let jd = datetime_to_jd(t);
+    let lunation = ((jd - REF_NEW_MOON_JD) / SYNODIC_MONTH).floor();
+    let mut conjunction = refine_conjunction(REF_NEW_MOON_JD + lunation * SYNODIC_MONTH);
+    // The true conjunction can be up to ~14 hours off the mean one.
+    if conjunction > jd {
+        conjunction = refine_conjunction(conjunction - SYNODIC_MONTH);
+    } else if conjunction + SYNODIC_MONTH - 1.0 <= jd {
+        let next = refine_conjunction(conjunction + SYNODIC_MONTH);
+        if next <= jd {
+            conjunction = next;
+        }
+    }
+    jd_to_datetime(conjunction)
+}
+
+/// Newton iteration on the Moon-Sun longitude difference.
+fn refine_conjunction(mut jd: f64) -> f64 {
+    for _ in 0..6 {
+        let (sun_lon, _, _) = vsop87::calculate(jd);
+        let (moon_lon, _, _) = elp2000::calculate(jd);
+        let diff = (moon_lon - sun_lon + 540.0).rem_euclid(360.0) - 180.0;
+        if diff.abs() < 1e-5 {
+            break;
+        }
+        jd -= diff / ELONGATION_RATE;
+    }
+    jd
+}
+
+/// Astronomical Hijri calendar backend.
+///
+/// Month labels (year and month number) follow the Umm al-Qura month
+/// overlapping mid-month, so only month start days can differ from it.
+/// Clones share a cache of computed month starts.
+///
+/// ```rust,no_run
+/// use chrono::NaiveDate;
+/// use shaum_astronomy::lunar_calendar::AstronomicalCalendar;
+/// use shaum_calendar::HijriCalendar;
+/// use shaum_types::{GeoCoordinate, SightingZone, VisibilityCriteria};
+///
+/// let jakarta = GeoCoordinate::new(-6.2088, 106.8456).unwrap();
+/// let calendar = AstronomicalCalendar::new(VisibilityCriteria::mabims())
+///     .with_zone(SightingZone::Local(jakarta));
+/// let h = calendar.to_hijri(NaiveDate::from_ymd_opt(2025, 3, 1).unwrap()).unwrap();
+/// println!("{}-{}-{}", h.year, h.month, h.day);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AstronomicalCalendar {
+    criteria: VisibilityCriteria,
+    zone: SightingZone,
+    /// Unified Hijri Calendar (Istanbul 2016) constraints on global sightings.
+    unified: bool,
+    /// [`HijriCalendar::cache_id`] of this configuration.
+    id: u64,
+    starts: Arc<Mutex<MonthStarts>>,
+}
+
+/// Conjunction (unix minutes) -> first day of the following month.
+#[derive(Debug, Default)]
+struct MonthStarts {
+    /// From the sighting alone.
+    observed: HashMap<i64, NaiveDate>,
+    /// Kept 29 or 30 days after the previous month.
+    resolved: HashMap<i64, NaiveDate>,
+}
+
+impl Default for AstronomicalCalendar {
+    fn default() -> Self { Self::new(VisibilityCriteria::default()) }
+}
+
+impl AstronomicalCalendar {
+    /// Calendar using `criteria` with a global sighting zone.
+    pub fn new(criteria: VisibilityCriteria) -> Self {
+        Self::configured(criteria, SightingZone::default(), false)
+    }
+
+    fn configured(criteria: VisibilityCriteria, zone: SightingZone, unified: bool) -> Self {
+        // The criteria hold floats, so their debug form stands in for a hash.
+        let id = cache_id_of(("astronomical", format!("{:?} {:?}", criteria, zone), unified));
+        Self { criteria, zone, unified, id, starts: Arc::default() }
+    }
+
+    /// Turkish Diyanet method: the Unified Hijri Calendar adopted at the 2016
+    /// Istanbul congress. A month starts the day after an evening on which the
+    /// crescent reaches 5° altitude and 8° elongation anywhere on Earth before
+    /// 24:00 UTC; later sightings count only in the Americas, and only if
+    /// conjunction precedes Fajr in New Zealand.
+    pub fn diyanet() -> Self {
+        Self::configured(VisibilityCriteria::istanbul_1978(), SightingZone::default(), true)
+    }
+
+    /// Sets the sighting zone.
+    pub fn with_zone(self, zone: SightingZone) -> Self {
+        Self::configured(self.criteria, zone, self.unified)
+    }
+
+    pub fn criteria(&self) -> &VisibilityCriteria { &self.criteria }
+    pub fn zone(&self) -> SightingZone { self.zone }
+    pub fn is_unified(&self) -> bool { self.unified }
+
+    /// First day of the Hijri month containing `date`.
+    pub fn month_start(&self, date: NaiveDate) -> Result<NaiveDate, ShaumError> {
+        let end_of_day = date.and_hms_opt(23, 59, 59)
+            .ok_or_else(|| ShaumError::AstronomyError("Invalid date".into()))?
+            .and_utc();
+        let conjunction = new_moon_before(end_of_day)?;
+        let start = self.start_after(conjunction)?;
+        if start <= date {
+            return Ok(start);
+        }
+        self.start_after(new_moon_before(conjunction - Duration::days(1))?)
+    }
+
+    /// First day of the month begun by `conjunction`.
+    ///
+    /// Each observed start is clamped to 29 or 30 days after the previous
+    /// month, starting [`LENGTH_LOOKBACK`] months back so the result does not
+    /// depend on which months were asked for before.
+    fn start_after(&self, conjunction: DateTime<Utc>) -> Result<NaiveDate, ShaumError> {
+        let key = conjunction.timestamp() / 60;
+        if let Some(start) = self.lock().resolved.get(&key) {
+            return Ok(*start);
+        }
+
+        let mut conjunctions = vec![conjunction];
+        for _ in 0..LENGTH_LOOKBACK {
+            let last = conjunctions[conjunctions.len() - 1];
+            conjunctions.push(new_moon_before(last - Duration::days(1))?);
+        }
+        let oldest = conjunctions.pop().unwrap_or(conjunction);
+        let mut start = self.observed_start(oldest)?;
+        for c in conjunctions.into_iter().rev() {
+            start = self.observed_start(c)?.clamp(start + Duration::days(29), start + Duration::days(30));
+        }
+
+        self.lock().resolved.insert(key, start);
+        Ok(start)
+    }
+
+    /// Day after the first evening on which the crescent is sighted, or
+    /// [`MAX_EVENINGS`] days after conjunction without a sighting.
+    fn observed_start(&self, conjunction: DateTime<Utc>) -> Result<NaiveDate, ShaumError> {
+        let key = conjunction.timestamp() / 60;
+        if let Some(start) = self.lock().observed.get(&key) {
+            return Ok(*start);
+        }
+
+        let first_evening = match self.zone {
+            SightingZone::Global => conjunction.date_naive(),
+            SightingZone::Local(site) => local_date(conjunction, site),
+        };
+        let mut start = first_evening + Duration::days(MAX_EVENINGS);
+        for offset in 0..MAX_EVENINGS {
+            let evening = first_evening + Duration::days(offset);
+            if self.sighted(evening, conjunction)? {
+                start = evening + Duration::days(1);
+                break;
+            }
+        }
+
+        self.lock().observed.insert(key, start);
+        Ok(start)
+    }
+
+    /// Whether the crescent is visible in the zone on the evening of `date`.
+    fn sighted(&self, date: NaiveDate, conjunction: DateTime<Utc>) -> Result<bool, ShaumError> {
+        // Sunset is searched around local evening, so noon UTC picks the local date.
+        let noon = date.and_hms_opt(12, 0, 0)
+            .ok_or_else(|| ShaumError::AstronomyError("Invalid date".into()))?
+            .and_utc();
+        let deadline = noon + Duration::hours(12);
+        let sighting_at = |site: GeoCoordinate| -> Result<Option<DateTime<Utc>>, ShaumError> {
+            let report = calculate_visibility(noon, site, &self.criteria)?;
+            Ok((report.observation_time > conjunction && report.meets_mabims).then_some(report.observation_time))
+        };
+        match self.zone {
+            SightingZone::Local(site) => Ok(sighting_at(site)?.is_some()),
+            SightingZone::Global => {
+                let mut nz_fajr = None;
+                for site in global_sites() {
+                    let Some(observed) = sighting_at(site)? else { continue };
+                    if !self.unified || observed < deadline {
+                        return Ok(true);
+                    }
+                    if site.lng >= AMERICAS_EAST_LNG {
+                        continue;
+                    }
+                    let fajr = match nz_fajr {
+                        Some(fajr) => fajr,
+                        None => *nz_fajr.insert(new_zealand_fajr(date)?),
+                    };
+                    if unified_sighting_counts(observed, deadline, true, conjunction, fajr) {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MonthStarts> {
+        self.starts.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl HijriCalendar for AstronomicalCalendar {
+    fn to_hijri(&self, date: NaiveDate) -> Result<HijriYmd, ShaumError> {
+        let start = self.month_start(date)?;
+        let label = UmmAlQura.to_hijri(start + Duration::days(14))?;
+        let day = (date - start).num_days() as usize + 1;
+        Ok(HijriYmd::new(label.year, label.month, day))
+    }
+
+    fn cache_id(&self) -> Option<u64> {
+        Some(self.id)
+    }
+}
+
+/// Crescent visibility on the observation evening at the reference site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CrescentVisibility {
+    /// Sunset before the conjunction, or the moon sets first: no crescent exists.
+    Impossible,
+    /// A crescent exists but misses the criteria.
+    BelowCriteria,
+    /// The criteria are met.
+    Visible,
+}
+
+/// Predicted start of one Hijri month, as published in moonsighting bulletins.
+#[derive(Debug, Clone)]
+pub struct MonthStartPrediction {
+    /// Hijri month label (Umm al-Qura numbering).
+    pub hijri: HijriYmd,
+    /// Geocentric conjunction ending the previous month.
+    pub conjunction: DateTime<Utc>,
+    /// Predicted first day under the zone and criteria.
+    pub start: NaiveDate,
+    /// Site the visibility is reported for: the local site, or Makkah for a global zone.
+    pub reference: GeoCoordinate,
+    /// Evening of the first sunset after conjunction at the reference site.
+    pub evening: NaiveDate,
+    pub visibility: CrescentVisibility,
+    /// Metrics on that evening.
+    pub report: MoonVisibilityReport,
+    /// False when altitude or elongation is within 1° of its threshold, or
+    /// the crescent is visible somewhere in a global zone but not at the
+    /// reference site; sightings may go either way.
+    pub confident: bool,
+}
+
+/// Predicts the next `n` month starts on or after `from`.
+///
+/// ```rust,no_run
+/// use chrono::NaiveDate;
+/// use shaum_astronomy::lunar_calendar::predict_month_starts;
+/// use shaum_types::{GeoCoordinate, SightingZone, VisibilityCriteria};
+///
+/// let jakarta = GeoCoordinate::new(-6.2088, 106.8456).unwrap();
+/// let from = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+/// for p in predict_month_starts(from, 3, VisibilityCriteria::mabims(), SightingZone::Local(jakarta)).unwrap() {
+///     println!("{}-{}: {} ({:?}, alt {:.1}°)", p.hijri.year, p.hijri.month, p.start, p.visibility, p.report.moon_altitude);
+/// }
+/// ```
+///
+/// # Errors
+/// Astronomy errors (e.g. polar reference sites), or a conversion error for
+/// months outside the Umm al-Qura range.
+pub fn predict_month_starts(
+    from: NaiveDate,
+    n: usize,
+    criteria: VisibilityCriteria,
+    zone: SightingZone,
+) -> Result<Vec<MonthStartPrediction>, ShaumError> {
+    let calendar = AstronomicalCalendar::new(criteria).with_zone(zone);
+    let reference = match zone {
+        SightingZone::Local(site) => site,
+        SightingZone::Global => MAKKAH,
+    };
+    let midnight = from.and_hms_opt(0, 0, 0)
+        .ok_or_else(|| ShaumError::AstronomyError("Invalid date".into()))?
+        .and_utc();
+
+    let mut predictions = Vec::with_capacity(n);
+    let mut conjunction = new_moon_before(midnight)?;
+    while predictions.len() < n {
+        let start = calendar.start_after(conjunction)?;
+        if start >= from {
+            predictions.push(calendar.predict(conjunction, start, reference)?);
+        }
+        // Lunations last 29.3-29.8 days, so 30 days later is within the next one.
+        conjunction = new_moon_before(conjunction + Duration::days(30))?;
+    }
+    Ok(predictions)
+}
+
+impl AstronomicalCalendar {
+    fn predict(&self, conjunction: DateTime<Utc>, start: NaiveDate, reference: GeoCoordinate) -> Result<MonthStartPrediction, ShaumError> {
+        let evening_report = |evening: NaiveDate| -> Result<MoonVisibilityReport, ShaumError> {
+            let noon = evening.and_hms_opt(12, 0, 0)
+                .ok_or_else(|| ShaumError::AstronomyError("Invalid date".into()))?
+                .and_utc();
+            calculate_visibility(noon, reference, &self.criteria)
+        };
+        let mut evening = local_date(conjunction, reference);
+        let mut report = evening_report(evening)?;
+        if report.observation_time <= conjunction {
+            evening += Duration::days(1);
+            report = evening_report(evening)?;
+        }
+
+        let visibility = if report.lag_time_minutes <= 0.0 || report.moon_altitude <= 0.0 {
+            CrescentVisibility::Impossible
+        } else if report.meets_mabims {
+            CrescentVisibility::Visible
+        } else {
+            CrescentVisibility::BelowCriteria
+        };
+        let params = &self.criteria.params;
+        let near = (report.moon_altitude - params.min_altitude).abs() < CONFIDENCE_MARGIN_DEG
+            || (report.elongation - params.min_elongation).abs() < CONFIDENCE_MARGIN_DEG;
+        // In a global zone the start may follow a sighting elsewhere.
+        let elsewhere = start == evening + Duration::days(1) && !report.meets_mabims;
+
+        let label = self.to_hijri(start)?;
+        Ok(MonthStartPrediction {
+            hijri: HijriYmd::new(label.year, label.month, 1),
+            conjunction,
+            start,
+            reference,
+            evening,
+            visibility,
+            report,
+            confident: !near && !elsewhere,
+        })
+    }
+}
+
+/// Unified Hijri Calendar acceptance of one sighting: any sighting before the
+/// 24:00 UTC `deadline` counts; a later one counts only in the Americas, and
+/// only if conjunction preceded Fajr in New Zealand.
+fn unified_sighting_counts(
+    observed: DateTime<Utc>,
+    deadline: DateTime<Utc>,
+    in_americas: bool,
+    conjunction: DateTime<Utc>,
+    new_zealand_fajr: DateTime<Utc>,
+) -> bool {
+    observed < deadline || (in_americas && conjunction < new_zealand_fajr)
+}
+
+/// Fajr in New Zealand on the morning after the evening of `date`.
+fn new_zealand_fajr(date: NaiveDate) -> Result<DateTime<Utc>, ShaumError> {
+    let morning = date.succ_opt().ok_or_else(|| ShaumError::date_out_of_range(date))?;
+    Ok(calculate_prayer_times(morning, WELLINGTON, &PrayerParams::mwl())?.fajr)
+}
+
+/// Observer's mean solar date at `t`.
+fn local_date(t: DateTime<Utc>, site: GeoCoordinate) -> NaiveDate {
+    (t + Duration::seconds((site.lng * 240.0) as i64)).date_naive()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Timelike};
+
+    #[test]
+    fn test_new_moon_before() {
+        // New moon of 2024-03-10 09:00 UT.
+        let t = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let nm = new_moon_before(t).unwrap();
+        assert_eq!(nm.date_naive(), NaiveDate::from_ymd_opt(2024, 3, 10).unwrap());
+        assert!((8..=10).contains(&nm.hour()), "got {}", nm);
+
+        // Just before a conjunction returns the previous one.
+        let before = new_moon_before(nm - Duration::hours(1)).unwrap();
+        assert!((nm - before).num_days() >= 29);
+    }
+
+    #[test]
+    fn test_local_zone_month_start() {
+        // Ramadhan 1445: conjunction 2024-03-10 09:00 UT, too young at Jakarta
+        // sunset that evening under MABIMS, so fasting starts on the 12th.
+        let jakarta = GeoCoordinate::new(-6.2088, 106.8456).unwrap();
+        let calendar = AstronomicalCalendar::new(VisibilityCriteria::mabims())
+            .with_zone(SightingZone::Local(jakarta));
+        let h = calendar.to_hijri(NaiveDate::from_ymd_opt(2024, 3, 12).unwrap()).unwrap();
+        assert_eq!((h.year, h.month, h.day), (1445, 9, 1));
+    }
+
+    #[test]
+    fn test_months_last_29_or_30_days() {
+        // At 60°N a 3° crescent is seen on the first evening in some months and
+        // not at all in others, which left 28- and 32-day months unclamped.
+        let helsinki = GeoCoordinate::new(60.17, 24.94).unwrap();
+        let mut criteria = VisibilityCriteria::mabims();
+        criteria.params.min_altitude = 3.0;
+        criteria.params.min_elongation = 0.0;
+        let from = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let predictions = predict_month_starts(from, 25, criteria, SightingZone::Local(helsinki)).unwrap();
+        for pair in predictions.windows(2) {
+            let days = (pair[1].start - pair[0].start).num_days();
+            assert!((29..=30).contains(&days), "{} to {}: {} days", pair[0].start, pair[1].start, days);
+        }
+    }
+
+    #[test]
+    fn test_diyanet_month_starts() {
+        let calendar = AstronomicalCalendar::diyanet();
+        let day = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        // Diyanet: Eid al-Fitr 1444 on 21 April 2023, Ramadhan 1445 on 11 March 2024,
+        // Eid al-Fitr 1445 on 10 April 2024, Ramadhan 1446 on 1 March 2025.
+        for (date, month) in [(day(2023, 4, 21), 10), (day(2024, 3, 11), 9), (day(2024, 4, 10), 10), (day(2025, 3, 1), 9)] {
+            let h = calendar.to_hijri(date).unwrap();
+            assert_eq!((h.month, h.day), (month, 1), "{}", date);
+        }
+    }
+
+    #[test]
+    fn test_unified_americas_exception() {
+        let at = |d, h| Utc.with_ymd_and_hms(2024, 3, d, h, 0, 0).unwrap();
+        let deadline = at(11, 0);
+        let nz_fajr = at(10, 17);
+
+        // Before 24:00 UTC any sighting counts, without the New Zealand condition.
+        assert!(unified_sighting_counts(at(10, 20), deadline, false, at(10, 18), nz_fajr));
+        assert!(unified_sighting_counts(at(10, 23), deadline, true, at(10, 18), nz_fajr));
+
+        // Late Americas sighting: counts only if conjunction preceded Fajr in New Zealand.
+        assert!(unified_sighting_counts(at(11, 2), deadline, true, at(10, 9), nz_fajr));
+        assert!(!unified_sighting_counts(at(11, 2), deadline, true, at(10, 18), nz_fajr));
+
+        // Late sightings elsewhere never count.
+        assert!(!unified_sighting_counts(at(11, 2), deadline, false, at(10, 9), nz_fajr));
+    }
+
+    #[test]
+    fn test_predict_month_starts() {
+        let jakarta = GeoCoordinate::new(-6.2088, 106.8456).unwrap();
+        let from = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let predictions = predict_month_starts(from, 2, VisibilityCriteria::mabims(), SightingZone::Local(jakarta)).unwrap();
+        assert_eq!(predictions.len(), 2);
+
+        let ramadhan = &predictions[0];
+        assert_eq!((ramadhan.hijri.year, ramadhan.hijri.month), (1445, 9));
+        assert_eq!(ramadhan.conjunction.date_naive(), NaiveDate::from_ymd_opt(2024, 3, 10).unwrap());
+        assert_eq!(ramadhan.evening, NaiveDate::from_ymd_opt(2024, 3, 10).unwrap());
+        assert_eq!(ramadhan.start, NaiveDate::from_ymd_opt(2024, 3, 12).unwrap());
+        assert_ne!(ramadhan.visibility, CrescentVisibility::Visible);
+        assert_eq!(ramadhan.reference, jakarta);
+
+        assert_eq!(predictions[1].hijri.month, 10);
+        assert!(predictions[1].start > ramadhan.start + Duration::days(28));
+    }
+}
@@ -4,9 +4,9 @@
 //! Reuses the existing astronomy infrastructure (VSOP87, coordinate conversions).
 
 use chrono::{DateTime, Duration, NaiveDate, Utc, TimeZone, Datelike};
-use shaum_types::{GeoCoordinate, PrayerParams};
+use shaum_types::{GeoCoordinate, PrayerParams, IshaRule};
 use super::{vsop87, coords};
-use super::visibility::{datetime_to_jd, estimate_sunset};
+use super::visibility::{datetime_to_jd, estimate_sunset, horizon_altitude};
 
 /// Prayer times for a specific date and location.
 #[derive(Debug, Clone)]
@@ -17,6 +17,38 @@ pub struct PrayerTimes {
     pub fajr: DateTime<Utc>,
     /// Maghrib time (sunset, end of fasting).
     pub maghrib: DateTime<Utc>,
+    /// Isha time (angle- or interval-based, see [`IshaRule`]).
+    pub isha: DateTime<Utc>,
+    /// Recommended end of suhoor under [`PrayerParams::suhoor`]; never
+    /// after `suhoor_deadline`.
+    pub suhoor_recommended: DateTime<Utc>,
+    /// Last moment to eat: calculated dawn rounded down, without Ihtiyat.
+    pub suhoor_deadline: DateTime<Utc>,
+}
+
+/// Local mean midnight of `date` (longitude-based offset, 15° = 1 hour) expressed in UTC.
+fn local_midnight_utc(date: NaiveDate, coords: GeoCoordinate) -> Result<DateTime<Utc>, shaum_types::ShaumError> {
+    // For Yogyakarta (lng=110.36), offset ≈ +7.36 hours
+    // So local midnight = UTC - offset
+    let tz_offset_hours = coords.lng / 15.0;
+    let tz_offset_minutes = (tz_offset_hours * 60.0).round() as i64;
+
+    // Local 00:00 = UTC 00:00 - tz_offset
+    let base_utc_midnight = Utc.with_ymd_and_hms(date.year(), date.month(), date.day(), 0, 0, 0)
+        .single()
+        .ok_or_else(|| shaum_types::ShaumError::AstronomyError("Invalid date for prayer time calculation".to_string()))?;
+
+    Ok(base_utc_midnight - Duration::minutes(tz_offset_minutes))
+}
+
+/// Geometric altitude of the sun's centre (degrees) at `t` for `coords`.
+pub(crate) fn sun_altitude(t: DateTime<Utc>, coords: GeoCoordinate) -> f64 {
+    let jd = datetime_to_jd(t);
+    let (sun_lon, sun_lat, _) = vsop87::calculate(jd);
+    let obliquity = coords::mean_obliquity(jd);
+    let (sun_ra, sun_dec) = coords::ecliptic_to_equatorial(sun_lon, sun_lat, obliquity);
+    let lst = coords::local_sidereal_time(jd, coords.lng);
+    coords::equatorial_to_horizontal(sun_ra, sun_dec, lst, coords.lat).1
 }
 
 /// Finds the time when the sun reaches a specific altitude using binary search.
@@ -29,28 +61,13 @@ pub struct PrayerTimes {
 ///
 /// # Returns
 /// The UTC time when sun altitude crosses the target value.
-fn find_sun_altitude_time(
+pub(crate) fn find_sun_altitude_time(
     date: NaiveDate,
     coords: GeoCoordinate,
     target_altitude: f64,
     is_morning: bool,
 ) -> Result<DateTime<Utc>, shaum_types::ShaumError> {
-    use shaum_types::ShaumError;
-    
-    // Calculate timezone offset from longitude (approximate: 15° = 1 hour)
-    // For Yogyakarta (lng=110.36), offset ≈ +7.36 hours
-    // So local midnight = UTC - offset
-    let tz_offset_hours = coords.lng / 15.0;
-    let tz_offset_minutes = (tz_offset_hours * 60.0).round() as i64;
-    
-    // Base datetime at local midnight (converted to UTC)
-    // Local 00:00 = UTC 00:00 - tz_offset
-    let base_utc_midnight = Utc.with_ymd_and_hms(date.year(), date.month(), date.day(), 0, 0, 0)
-        .single()
-        .ok_or_else(|| ShaumError::AstronomyError("Invalid date for prayer time calculation".to_string()))?;
-    
-    // Shift to local midnight in UTC
-    let local_midnight_utc = base_utc_midnight - Duration::minutes(tz_offset_minutes);
+    let local_midnight_utc = local_midnight_utc(date, coords)?;
     
     let (mut low, mut high) = if is_morning {
         // Search from local midnight to local noon (in UTC)
@@ -64,13 +81,7 @@ fn find_sun_altitude_time(
     // Binary search with 20 iterations (~1 second precision)
     for _ in 0..20 {
         let mid = low + Duration::seconds((high - low).num_seconds() / 2);
-        let jd = datetime_to_jd(mid);
-        
-        let (sun_lon, sun_lat, _) = vsop87::calculate(jd);
-        let obliquity = coords::mean_obliquity(jd);
-        let (sun_ra, sun_dec) = coords::ecliptic_to_equatorial(sun_lon, sun_lat, obliquity);
-        let lst = coords::local_sidereal_time(jd, coords.lng);
-        let (_, sun_alt) = coords::equatorial_to_horizontal(sun_ra, sun_dec, lst, coords.lat);
+        let sun_alt = sun_altitude(mid, coords);
 
         if is_morning {
             // For morning: sun altitude increases, search for when it crosses from below
@@ -109,7 +120,7 @@ fn find_sun_altitude_time(
 /// # Example
 /// ```rust
 /// use chrono::NaiveDate;
-/// use shaum_types::{GeoCoordinate, PrayerParams};
+/// use shaum_types::{GeoCoordinate, PrayerParams, IshaRule};
 /// use shaum_astronomy::prayer::calculate_prayer_times;
 ///
 /// let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
@@ -120,12 +131,23 @@ fn find_sun_altitude_time(
 /// println!("Fajr: {}", times.fajr);
 /// println!("Maghrib: {}", times.maghrib);
 /// ```
-
-
 pub fn calculate_prayer_times(
     date: NaiveDate,
     coords: GeoCoordinate,
     params: &PrayerParams,
+) -> Result<PrayerTimes, shaum_types::ShaumError> {
+    calculate_prayer_times_in_ramadhan(date, coords, params, false)
+}
+
+/// Like [`calculate_prayer_times`], selecting the Ramadhan Isha interval when `in_ramadhan`.
+///
+/// The caller decides whether `date` falls in Ramadhan; the rules crate wraps this
+/// with its own Hijri detection.
+pub fn calculate_prayer_times_in_ramadhan(
+    date: NaiveDate,
+    coords: GeoCoordinate,
+    params: &PrayerParams,
+    in_ramadhan: bool,
 ) -> Result<PrayerTimes, shaum_types::ShaumError> {
     use shaum_types::ShaumError;
     
@@ -143,7 +165,16 @@ pub fn calculate_prayer_times(
     // Note: Use 0 altitude here, estimate_sunset handles horizon dip internally if using _with_altitude
     // But since estimate_sunset is hardcoded for -0.833, we use it directly.
     // Ideally update this to use altitude if available in coords (need z-coord support)
-    let maghrib_raw = estimate_sunset(date, coords)?;
+    let maghrib_raw = estimate_sunset(date, coords)? + Duration::minutes(params.maghrib_offset_minutes);
+
+    // Isha calculation (raw)
+    let isha_raw = match params.isha {
+        IshaRule::Angle(angle) => find_sun_altitude_time(date, coords, angle, false)?,
+        rule @ IshaRule::Interval { .. } => {
+            let minutes = rule.interval_minutes(in_ramadhan).unwrap_or_default();
+            maghrib_raw + Duration::minutes(minutes)
+        }
+    };
 
     // Apply Ihtiyat and Rounding
     let fajr = apply_ihtiyat_and_round(
@@ -169,11 +200,167 @@ pub fn calculate_prayer_times(
         params.rounding_granularity_seconds
     );
 
-    Ok(PrayerTimes { imsak, fajr, maghrib })
+    let isha = apply_ihtiyat_and_round(
+        isha_raw,
+        params.ihtiyat_minutes,
+        params.rounding_granularity_seconds
+    );
+
+    let suhoor_deadline = round_down(fajr_raw, params.rounding_granularity_seconds);
+    let margin = match params.suhoor.angle_margin() {
+        Some(margin) => {
+            let dawn = find_sun_altitude_time(date, coords, params.fajr_angle - margin, true)?;
+            Some(round_down(dawn, params.rounding_granularity_seconds))
+        }
+        None => None,
+    };
+    let suhoor_recommended = params.suhoor.uses_buffer().then_some(imsak)
+        .into_iter()
+        .chain(margin)
+        .fold(suhoor_deadline, DateTime::min);
+
+    Ok(PrayerTimes { imsak, fajr, maghrib, isha, suhoor_recommended, suhoor_deadline })
+}
+
+/// Rounds down to the previous multiple of `granularity_sec`, for deadlines
+/// that must not be late.
+fn round_down(dt: DateTime<Utc>, granularity_sec: i64) -> DateTime<Utc> {
+    if granularity_sec <= 1 {
+        return dt;
+    }
+    dt - Duration::seconds(dt.timestamp().rem_euclid(granularity_sec))
+}
+
+/// Minutes after sunrise during which prayer is discouraged (until the sun has risen a spear's length).
+pub const SUNRISE_FORBIDDEN_MINUTES: i64 = 15;
+/// Minutes before solar transit during which prayer is discouraged.
+pub const ZAWAL_FORBIDDEN_MINUTES: i64 = 5;
+/// Minutes before sunset during which prayer is discouraged (the sun yellowing).
+pub const SUNSET_FORBIDDEN_MINUTES: i64 = 15;
+
+/// Which of the three discouraged prayer times a window covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForbiddenKind {
+    /// While the sun is rising.
+    Sunrise,
+    /// While the sun is at its zenith.
+    Zawal,
+    /// While the sun is setting.
+    Sunset,
+}
+
+/// A time range in which voluntary prayer is discouraged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForbiddenWindow {
+    pub kind: ForbiddenKind,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl ForbiddenWindow {
+    /// Whether `t` falls inside the window (start inclusive, end exclusive).
+    pub fn contains(&self, t: DateTime<Utc>) -> bool {
+        self.start <= t && t < self.end
+    }
+}
+
+/// Calculates sunrise (apparent upper limb on the horizon, altitude-corrected).
+///
+/// # Errors
+/// Returns `ShaumError::AstronomyError` for polar regions (|lat| > 66.5°).
+pub fn sunrise(date: NaiveDate, coords: GeoCoordinate) -> Result<DateTime<Utc>, shaum_types::ShaumError> {
+    check_polar(coords)?;
+    find_sun_altitude_time(date, coords, horizon_altitude(coords.altitude), true)
+}
+
+/// Calculates solar transit (zawal): the moment the sun crosses the local meridian.
+///
+/// Dhuhr begins right after this instant.
+pub fn solar_transit(date: NaiveDate, coords: GeoCoordinate) -> Result<DateTime<Utc>, shaum_types::ShaumError> {
+    let midnight = local_midnight_utc(date, coords)?;
+    // The hour angle increases monotonically through zero around local noon.
+    let (mut low, mut high) = (midnight + Duration::hours(6), midnight + Duration::hours(18));
+
+    for _ in 0..20 {
+        let mid = low + Duration::seconds((high - low).num_seconds() / 2);
+        let jd = datetime_to_jd(mid);
+
+        let (sun_lon, sun_lat, _) = vsop87::calculate(jd);
+        let obliquity = coords::mean_obliquity(jd);
+        let (sun_ra, _) = coords::ecliptic_to_equatorial(sun_lon, sun_lat, obliquity);
+        let lst = coords::local_sidereal_time(jd, coords.lng);
+        let hour_angle = (lst - sun_ra + 540.0).rem_euclid(360.0) - 180.0;
+
+        if hour_angle < 0.0 {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    Ok(low + Duration::seconds((high - low).num_seconds() / 2))
+}
+
+/// Calculates the sunrise, zawal, and sunset windows in which voluntary prayer is discouraged.
+///
+/// Windows use [`SUNRISE_FORBIDDEN_MINUTES`], [`ZAWAL_FORBIDDEN_MINUTES`] and
+/// [`SUNSET_FORBIDDEN_MINUTES`]. Prohibitions tied to having prayed Fajr or Asr
+/// depend on the worshipper and are not included.
+///
+/// # Errors
+/// Returns `ShaumError::AstronomyError` for polar regions (|lat| > 66.5°).
+pub fn forbidden_windows(
+    date: NaiveDate,
+    coords: GeoCoordinate,
+) -> Result<[ForbiddenWindow; 3], shaum_types::ShaumError> {
+    let rise = sunrise(date, coords)?;
+    let transit = solar_transit(date, coords)?;
+    let set = estimate_sunset(date, coords)?;
+
+    Ok([
+        ForbiddenWindow {
+            kind: ForbiddenKind::Sunrise,
+            start: rise,
+            end: rise + Duration::minutes(SUNRISE_FORBIDDEN_MINUTES),
+        },
+        ForbiddenWindow {
+            kind: ForbiddenKind::Zawal,
+            start: transit - Duration::minutes(ZAWAL_FORBIDDEN_MINUTES),
+            end: transit,
+        },
+        ForbiddenWindow {
+            kind: ForbiddenKind::Sunset,
+            start: set - Duration::minutes(SUNSET_FORBIDDEN_MINUTES),
+            end: set,
+        },
+    ])
+}
+
+/// Calculates the Duha window: from the end of the sunrise forbidden window
+/// (~15 min after sunrise) to the start of the zawal forbidden window.
+///
+/// # Errors
+/// Returns `ShaumError::AstronomyError` for polar regions (|lat| > 66.5°).
+pub fn duha_window(
+    date: NaiveDate,
+    coords: GeoCoordinate,
+) -> Result<std::ops::Range<DateTime<Utc>>, shaum_types::ShaumError> {
+    let start = sunrise(date, coords)? + Duration::minutes(SUNRISE_FORBIDDEN_MINUTES);
+    let end = solar_transit(date, coords)? - Duration::minutes(ZAWAL_FORBIDDEN_MINUTES);
+    Ok(start..end)
+}
+
+fn check_polar(coords: GeoCoordinate) -> Result<(), shaum_types::ShaumError> {
+    if coords.lat.abs() > 66.5 {
+        return Err(shaum_types::ShaumError::AstronomyError(
+            format!("Polar region latitude {:.2}° not supported for prayer times", coords.lat)
+        ));
+    }
+    Ok(())
 }
 
 /// Helper to apply Ihtiyat and rounding
-fn apply_ihtiyat_and_round(
+pub(crate) fn apply_ihtiyat_and_round(
     dt: DateTime<Utc>, 
     ihtiyat_min: i64, 
     granularity_sec: i64
@@ -201,6 +388,7 @@ fn apply_ihtiyat_and_round(
 mod tests {
     use super::*;
     use chrono::Timelike;
+    use shaum_types::SuhoorPolicy;
 
     #[test]
     fn test_prayer_times_jakarta() {
@@ -230,6 +418,28 @@ mod tests {
         assert!(times.fajr < times.maghrib);
     }
 
+    #[test]
+    fn test_suhoor_policy() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        let jakarta = GeoCoordinate::new_unchecked(-6.2088, 106.8456);
+        let times = |policy| calculate_prayer_times(date, jakarta, &PrayerParams::default().with_suhoor(policy)).unwrap();
+
+        let buffer = times(SuhoorPolicy::Buffer);
+        assert_eq!(buffer.suhoor_recommended, buffer.imsak);
+        // The deadline is dawn itself: before Fajr with Ihtiyat, after Imsak.
+        assert!(buffer.imsak < buffer.suhoor_deadline && buffer.suhoor_deadline < buffer.fajr);
+        assert_eq!(buffer.suhoor_deadline.second(), 0);
+
+        // One degree of twilight is about four minutes near the equator.
+        let angle = times(SuhoorPolicy::AngleMargin(1.0));
+        let early = (angle.suhoor_deadline - angle.suhoor_recommended).num_minutes();
+        assert!((3..=6).contains(&early), "{} minutes", early);
+        let both = times(SuhoorPolicy::Both(1.0));
+        assert_eq!(both.suhoor_recommended, buffer.suhoor_recommended.min(angle.suhoor_recommended));
+        // A margin never moves the deadline.
+        assert_eq!(angle.suhoor_deadline, buffer.suhoor_deadline);
+    }
+
     #[test]
     fn test_imsak_buffer() {
         let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
@@ -246,6 +456,62 @@ mod tests {
         assert_eq!(diff, 5);
     }
 
+    #[test]
+    fn test_isha_interval_ramadhan() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        let mecca = GeoCoordinate::new_unchecked(21.4225, 39.8262);
+        let params = PrayerParams::umm_al_qura();
+
+        let normal = calculate_prayer_times(date, mecca, &params).unwrap();
+        let ramadhan = calculate_prayer_times_in_ramadhan(date, mecca, &params, true).unwrap();
+
+        assert_eq!((normal.isha - normal.maghrib).num_minutes(), 90);
+        assert_eq!((ramadhan.isha - ramadhan.maghrib).num_minutes(), 120);
+    }
+
+    #[test]
+    fn test_isha_angle_after_maghrib() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        let jakarta = GeoCoordinate::new_unchecked(-6.2088, 106.8456);
+
+        let times = calculate_prayer_times(date, jakarta, &PrayerParams::default()).unwrap();
+        let gap = (times.isha - times.maghrib).num_minutes();
+        assert!((60..=90).contains(&gap), "Isha gap {} min", gap);
+    }
+
+    #[test]
+    fn test_zawal_and_forbidden_windows() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        let jakarta = GeoCoordinate::new_unchecked(-6.2088, 106.8456);
+
+        let transit = solar_transit(date, jakarta).unwrap();
+        // Jakarta transit in mid-March is ~12:01 WIB = 05:01 UTC (equation of time ≈ -9 min).
+        assert_eq!(transit.hour(), 5);
+        assert!(transit.minute() <= 4, "transit {}", transit);
+
+        let windows = forbidden_windows(date, jakarta).unwrap();
+        let rise = sunrise(date, jakarta).unwrap();
+        let set = estimate_sunset(date, jakarta).unwrap();
+        assert!(rise < transit && transit < set);
+        assert_eq!(windows[0].start, rise);
+        assert_eq!(windows[1].end, transit);
+        assert_eq!(windows[2].end, set);
+        assert!(windows[1].contains(transit - Duration::minutes(1)));
+        assert!(!windows[1].contains(transit));
+    }
+
+    #[test]
+    fn test_duha_window_between_forbidden_windows() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        let jakarta = GeoCoordinate::new_unchecked(-6.2088, 106.8456);
+
+        let duha = duha_window(date, jakarta).unwrap();
+        let windows = forbidden_windows(date, jakarta).unwrap();
+        assert_eq!(duha.start, windows[0].end);
+        assert_eq!(duha.end, windows[1].start);
+        assert!((duha.end - duha.start).num_hours() >= 5);
+    }
+
     #[test]
     fn test_polar_region_returns_error() {
         let date = NaiveDate::from_ymd_opt(2024, 6, 15).unwrap();
@@ -0,0 +1,221 @@
+//! Shared cache for prayer time calculations.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use chrono::{Datelike, NaiveDate};
+use shaum_calendar::{cache_id_of, CacheMetrics};
+use shaum_types::{GeoCoordinate, PrayerParams, ShaumError};
+
+use crate::prayer::{calculate_prayer_times_in_ramadhan, PrayerTimes};
+
+/// Number of independently locked shards.
+const SHARDS: usize = 16;
+
+/// Coordinate grid: 0.01° (about 1.1 km of latitude).
+const GRID: f64 = 100.0;
+
+/// Calculations kept by [`PrayerCache::new`].
+pub const DEFAULT_PRAYER_CACHE_CAPACITY: usize = 4096;
+
+/// Identity of a calculation: date, grid cell, altitude in whole metres,
+/// parameters and Ramadhan flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Key {
+    date: NaiveDate,
+    lat: i32,
+    lng: i32,
+    altitude: i32,
+    /// [`cache_id_of`] the whole [`PrayerParams`], so fields added later
+    /// are part of the key without listing them here.
+    params: u64,
+    in_ramadhan: bool,
+}
+
+impl Key {
+    fn new(date: NaiveDate, coords: GeoCoordinate, params: &PrayerParams, in_ramadhan: bool) -> Self {
+        Self {
+            date,
+            lat: (coords.lat * GRID).round() as i32,
+            lng: (coords.lng * GRID).round() as i32,
+            altitude: coords.altitude.round() as i32,
+            params: cache_id_of(format!("{params:?}")),
+            in_ramadhan,
+        }
+    }
+}
+
+/// Sharded, `Arc`-shared cache of [`PrayerTimes`].
+///
+/// Coordinates are snapped to a 0.01° grid (about 1 km) and altitudes to
+/// whole metres: a miss calculates for the caller's exact coordinates, and
+/// later callers in the same cell get that answer, at most a few seconds
+/// from their own. Clones share storage and metrics. Each shard evicts in
+/// insertion order (FIFO) once full.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_astronomy::PrayerCache;
+/// use shaum_types::{GeoCoordinate, PrayerParams};
+///
+/// let cache = PrayerCache::new();
+/// let date = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+/// let a = GeoCoordinate::new(-6.2088, 106.8456).unwrap();
+/// let b = GeoCoordinate::new(-6.2091, 106.8461).unwrap(); // ~60 m away
+/// cache.get_or_calculate(date, a, &PrayerParams::default(), false).unwrap();
+/// cache.get_or_calculate(date, b, &PrayerParams::default(), false).unwrap();
+/// assert_eq!(cache.metrics().hits, 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PrayerCache {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    shards: [Mutex<Shard>; SHARDS],
+    shard_capacity: usize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Debug, Default)]
+struct Shard {
+    entries: HashMap<Key, PrayerTimes>,
+    /// Insertion order, for eviction.
+    order: VecDeque<Key>,
+}
+
+impl Default for PrayerCache {
+    fn default() -> Self { Self::with_capacity(DEFAULT_PRAYER_CACHE_CAPACITY) }
+}
+
+impl PrayerCache {
+    /// Cache holding up to [`DEFAULT_PRAYER_CACHE_CAPACITY`] calculations.
+    pub fn new() -> Self { Self::default() }
+
+    /// Cache holding about `capacity` calculations (at least one per shard).
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                shards: Default::default(),
+                shard_capacity: capacity.div_ceil(SHARDS).max(1),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Cached equivalent of [`calculate_prayer_times_in_ramadhan`]. Errors are not cached.
+    pub fn get_or_calculate(
+        &self,
+        date: NaiveDate,
+        coords: GeoCoordinate,
+        params: &PrayerParams,
+        in_ramadhan: bool,
+    ) -> Result<PrayerTimes, ShaumError> {
+        let key = Key::new(date, coords, params, in_ramadhan);
+        let shard = self.shard(&key);
+        if let Some(times) = lock(shard).entries.get(&key) {
+            self.inner.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(times.clone());
+        }
+        self.inner.misses.fetch_add(1, Ordering::Relaxed);
+        let times = calculate_prayer_times_in_ramadhan(date, coords, params, in_ramadhan)?;
+        let mut shard = lock(shard);
+        if shard.entries.insert(key, times.clone()).is_none() {
+            shard.order.push_back(key);
+            while shard.order.len() > self.inner.shard_capacity {
+                if let Some(oldest) = shard.order.pop_front() {
+                    shard.entries.remove(&oldest);
+                }
+            }
+        }
+        Ok(times)
+    }
+
+    /// Current counters.
+    pub fn metrics(&self) -> CacheMetrics {
+        CacheMetrics {
+            hits: self.inner.hits.load(Ordering::Relaxed),
+            misses: self.inner.misses.load(Ordering::Relaxed),
+            entries: self.inner.shards.iter().map(|s| lock(s).entries.len()).sum(),
+        }
+    }
+
+    /// Drops all entries and resets the counters.
+    pub fn clear(&self) {
+        for shard in &self.inner.shards {
+            let mut shard = lock(shard);
+            shard.entries.clear();
+            shard.order.clear();
+        }
+        self.inner.hits.store(0, Ordering::Relaxed);
+        self.inner.misses.store(0, Ordering::Relaxed);
+    }
+
+    fn shard(&self, key: &Key) -> &Mutex<Shard> {
+        let mix = key.date.num_days_from_ce() ^ key.lat ^ key.lng.rotate_left(8);
+        &self.inner.shards[mix.rem_euclid(SHARDS as i32) as usize]
+    }
+}
+
+fn lock<T>(m: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    m.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date() -> NaiveDate { NaiveDate::from_ymd_opt(2024, 3, 11).unwrap() }
+
+    #[test]
+    fn test_altitude_is_part_of_the_key() {
+        let cache = PrayerCache::new();
+        let coast = GeoCoordinate::new(-6.9175, 107.6191).unwrap();
+        let summit = coast.with_altitude(2000.0);
+        let low = cache.get_or_calculate(date(), coast, &PrayerParams::default(), false).unwrap();
+        let high = cache.get_or_calculate(date(), summit, &PrayerParams::default(), false).unwrap();
+        assert_eq!(cache.metrics().misses, 2);
+        let exact = calculate_prayer_times_in_ramadhan(date(), summit, &PrayerParams::default(), false).unwrap();
+        assert_eq!(high.maghrib, exact.maghrib);
+        assert_ne!(low.maghrib, high.maghrib);
+    }
+
+    #[test]
+    fn test_miss_uses_the_callers_coordinates() {
+        let cache = PrayerCache::new();
+        let coords = GeoCoordinate::new(-6.20884, 106.84567).unwrap();
+        let params = PrayerParams::default().with_rounding(1);
+        let cached = cache.get_or_calculate(date(), coords, &params, false).unwrap();
+        let exact = calculate_prayer_times_in_ramadhan(date(), coords, &params, false).unwrap();
+        assert_eq!((cached.fajr, cached.maghrib), (exact.fajr, exact.maghrib));
+    }
+
+    #[test]
+    fn test_params_are_keyed_whole() {
+        let cache = PrayerCache::new();
+        let coords = GeoCoordinate::new(-6.2088, 106.8456).unwrap();
+        cache.get_or_calculate(date(), coords, &PrayerParams::default(), false).unwrap();
+        cache.get_or_calculate(date(), coords, &PrayerParams::default(), false).unwrap();
+        cache.get_or_calculate(date(), coords, &PrayerParams::mwl(), false).unwrap();
+        let m = cache.metrics();
+        assert_eq!((m.hits, m.misses), (1, 2));
+    }
+
+    #[test]
+    fn test_capacity_evicts_oldest() {
+        let cache = PrayerCache::with_capacity(1);
+        let coords = GeoCoordinate::new(-6.2088, 106.8456).unwrap();
+        let first = date();
+        // Consecutive days spread over the shards; one entry per shard at most.
+        for day in first.iter_days().take(64) {
+            cache.get_or_calculate(day, coords, &PrayerParams::default(), false).unwrap();
+        }
+        assert!(cache.metrics().entries <= SHARDS);
+        cache.get_or_calculate(first, coords, &PrayerParams::default(), false).unwrap();
+        assert_eq!(cache.metrics().hits, 0);
+    }
+}
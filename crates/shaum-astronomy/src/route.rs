@@ -0,0 +1,265 @@
+//! Prayer times for moving observers.
+//!
+//! A [`Route`] is a list of timestamped positions (e.g. a flight path).
+//! Positions between waypoints follow the great circle, altitude is
+//! interpolated linearly, and each prayer time is the first moment the sun
+//! crosses the corresponding altitude *as seen from the moving position*.
+//! Because the crossing is searched directly, routes over polar regions work
+//! as long as the event actually happens during the trip.
+
+use chrono::{DateTime, Duration, Utc};
+use shaum_types::{GeoCoordinate, IshaRule, PrayerParams, ShaumError};
+
+use crate::prayer::{apply_ihtiyat_and_round, sun_altitude};
+use crate::visibility::horizon_altitude;
+
+/// Coarse scan step before bisection.
+const SCAN_STEP_SECONDS: i64 = 120;
+
+/// Closest two consecutive waypoints may come to antipodal (radians, about
+/// 6 m on the ground): beyond it the great circle between them is undefined.
+const ANTIPODAL_EPSILON: f64 = 1e-6;
+
+/// A timestamped position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Waypoint {
+    pub time: DateTime<Utc>,
+    pub coords: GeoCoordinate,
+}
+
+impl Waypoint {
+    pub fn new(time: DateTime<Utc>, coords: GeoCoordinate) -> Self { Self { time, coords } }
+}
+
+/// Prayer times reached during a route; `None` when the event does not occur
+/// between the first and last waypoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoutePrayerTimes {
+    /// Fajr minus the Imsak buffer (may precede the route start).
+    pub imsak: Option<DateTime<Utc>>,
+    pub fajr: Option<DateTime<Utc>>,
+    pub maghrib: Option<DateTime<Utc>>,
+    pub isha: Option<DateTime<Utc>>,
+}
+
+/// Path of a moving observer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Route {
+    waypoints: Vec<Waypoint>,
+}
+
+impl Route {
+    /// Creates a route from waypoints in chronological order.
+    ///
+    /// # Errors
+    /// `ValidationError` if `waypoints` is empty, timestamps are not strictly
+    /// increasing, or two consecutive waypoints are (nearly) antipodal, since
+    /// any great circle joins those; add a waypoint on the path flown.
+    pub fn new(waypoints: Vec<Waypoint>) -> Result<Self, ShaumError> {
+        if waypoints.is_empty() {
+            return Err(ShaumError::ValidationError("Route needs at least one waypoint".to_string()));
+        }
+        if let Some(w) = waypoints.windows(2).find(|w| w[1].time <= w[0].time) {
+            return Err(ShaumError::ValidationError(format!(
+                "Waypoint times must be strictly increasing ({} after {})", w[1].time, w[0].time
+            )));
+        }
+        let antipodal = |w: &&[Waypoint]| std::f64::consts::PI - central_angle(w[0].coords, w[1].coords) < ANTIPODAL_EPSILON;
+        if let Some(w) = waypoints.windows(2).find(antipodal) {
+            return Err(ShaumError::ValidationError(format!(
+                "Waypoints ({}, {}) and ({}, {}) are antipodal; add one in between to fix the path",
+                w[0].coords.lat, w[0].coords.lng, w[1].coords.lat, w[1].coords.lng
+            )));
+        }
+        Ok(Self { waypoints })
+    }
+
+    pub fn waypoints(&self) -> &[Waypoint] { &self.waypoints }
+    pub fn start(&self) -> DateTime<Utc> { self.waypoints[0].time }
+    pub fn end(&self) -> DateTime<Utc> { self.waypoints[self.waypoints.len() - 1].time }
+
+    /// Interpolated position at `t`, or `None` outside the route's time span.
+    pub fn position_at(&self, t: DateTime<Utc>) -> Option<GeoCoordinate> {
+        if t < self.start() || t > self.end() {
+            return None;
+        }
+        let i = self.waypoints.partition_point(|w| w.time <= t);
+        if i == self.waypoints.len() {
+            return Some(self.waypoints[i - 1].coords);
+        }
+        let (a, b) = (self.waypoints[i - 1], self.waypoints[i]);
+        let f = (t - a.time).num_milliseconds() as f64 / (b.time - a.time).num_milliseconds() as f64;
+        Some(slerp(a.coords, b.coords, f))
+    }
+
+    /// First time during the route that the sun crosses `altitude` (degrees),
+    /// rising or setting, optionally relative to the observer's horizon.
+    fn crossing(&self, altitude: f64, relative_to_horizon: bool, rising: bool) -> Option<DateTime<Utc>> {
+        let f = |t: DateTime<Utc>| {
+            let pos = self.position_at(t)?;
+            let target = if relative_to_horizon { horizon_altitude(pos.altitude) } else { altitude };
+            let diff = sun_altitude(t, pos) - target;
+            Some(if rising { diff } else { -diff })
+        };
+
+        let mut low = self.start();
+        let mut f_low = f(low)?;
+        while low < self.end() {
+            let high = (low + Duration::seconds(SCAN_STEP_SECONDS)).min(self.end());
+            let f_high = f(high)?;
+            if f_low < 0.0 && f_high >= 0.0 {
+                let (mut lo, mut hi) = (low, high);
+                for _ in 0..12 {
+                    let mid = lo + (hi - lo) / 2;
+                    if f(mid)? < 0.0 { lo = mid } else { hi = mid }
+                }
+                return Some(hi);
+            }
+            low = high;
+            f_low = f_high;
+        }
+        None
+    }
+
+    /// Imsak, Fajr, Maghrib and Isha as experienced along the route, with the
+    /// same Ihtiyat and rounding as [`crate::prayer::calculate_prayer_times`].
+    /// Interval-based Isha uses the Ramadhan interval when `in_ramadhan`.
+    ///
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use shaum_astronomy::route::{Route, Waypoint};
+    /// use shaum_types::{GeoCoordinate, PrayerParams};
+    ///
+    /// // Jakarta to Jeddah, departing 09:00 UTC: chasing the sun delays Maghrib.
+    /// let route = Route::new(vec![
+    ///     Waypoint::new(Utc.with_ymd_and_hms(2024, 3, 20, 9, 0, 0).unwrap(),
+    ///                   GeoCoordinate::new(-6.1256, 106.6559).unwrap().with_altitude(11_000.0)),
+    ///     Waypoint::new(Utc.with_ymd_and_hms(2024, 3, 20, 18, 30, 0).unwrap(),
+    ///                   GeoCoordinate::new(21.6796, 39.1565).unwrap().with_altitude(11_000.0)),
+    /// ]).unwrap();
+    /// let times = route.prayer_times(&PrayerParams::default(), true);
+    /// assert!(times.maghrib.unwrap() > Utc.with_ymd_and_hms(2024, 3, 20, 11, 30, 0).unwrap());
+    /// ```
+    pub fn prayer_times(&self, params: &PrayerParams, in_ramadhan: bool) -> RoutePrayerTimes {
+        let fajr_raw = self.crossing(params.fajr_angle, false, true);
+        let maghrib_raw = self.crossing(0.0, true, false)
+            .map(|t| t + Duration::minutes(params.maghrib_offset_minutes));
+        let isha_raw = match params.isha {
+            IshaRule::Angle(angle) => self.crossing(angle, false, false),
+            rule @ IshaRule::Interval { .. } => maghrib_raw
+                .map(|t| t + Duration::minutes(rule.interval_minutes(in_ramadhan).unwrap_or_default())),
+        };
+
+        let finish = |t: DateTime<Utc>| {
+            apply_ihtiyat_and_round(t, params.ihtiyat_minutes, params.rounding_granularity_seconds)
+        };
+        RoutePrayerTimes {
+            imsak: fajr_raw.map(|t| finish(t - Duration::minutes(params.imsak_buffer_minutes))),
+            fajr: fajr_raw.map(finish),
+            maghrib: maghrib_raw.map(finish),
+            isha: isha_raw.map(finish),
+        }
+    }
+}
+
+/// Unit vector of `c` on the sphere.
+fn to_vec(c: GeoCoordinate) -> [f64; 3] {
+    let (lat, lng) = (c.lat.to_radians(), c.lng.to_radians());
+    [lat.cos() * lng.cos(), lat.cos() * lng.sin(), lat.sin()]
+}
+
+/// Angle between `a` and `b` seen from the Earth's centre (radians, 0..=π).
+fn central_angle(a: GeoCoordinate, b: GeoCoordinate) -> f64 {
+    let (va, vb) = (to_vec(a), to_vec(b));
+    (va[0] * vb[0] + va[1] * vb[1] + va[2] * vb[2]).clamp(-1.0, 1.0).acos()
+}
+
+/// Great-circle interpolation between `a` and `b` (fraction `f` in 0..=1).
+/// [`Route::new`] rejects antipodal pairs, for which the path is undefined.
+fn slerp(a: GeoCoordinate, b: GeoCoordinate, f: f64) -> GeoCoordinate {
+    let (va, vb) = (to_vec(a), to_vec(b));
+    let omega = central_angle(a, b);
+    let (wa, wb) = if omega < 1e-9 {
+        (1.0 - f, f)
+    } else {
+        (((1.0 - f) * omega).sin() / omega.sin(), (f * omega).sin() / omega.sin())
+    };
+    let v = [0, 1, 2].map(|i| wa * va[i] + wb * vb[i]);
+    let lat = v[2].atan2((v[0] * v[0] + v[1] * v[1]).sqrt()).to_degrees();
+    let lng = v[1].atan2(v[0]).to_degrees();
+    GeoCoordinate {
+        lat,
+        lng,
+        altitude: a.altitude + (b.altitude - a.altitude) * f,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone};
+    use crate::prayer::calculate_prayer_times;
+
+    #[test]
+    fn test_stationary_route_matches_prayer_times() {
+        let jakarta = GeoCoordinate::new(-6.2088, 106.8456).unwrap();
+        let route = Route::new(vec![
+            Waypoint::new(Utc.with_ymd_and_hms(2024, 3, 19, 18, 0, 0).unwrap(), jakarta),
+            Waypoint::new(Utc.with_ymd_and_hms(2024, 3, 20, 17, 0, 0).unwrap(), jakarta),
+        ]).unwrap();
+        let params = PrayerParams::default();
+        let on_route = route.prayer_times(&params, false);
+        let fixed = calculate_prayer_times(NaiveDate::from_ymd_opt(2024, 3, 20).unwrap(), jakarta, &params).unwrap();
+
+        for (a, b) in [(on_route.fajr, fixed.fajr), (on_route.maghrib, fixed.maghrib), (on_route.isha, fixed.isha)] {
+            assert!((a.unwrap() - b).num_minutes().abs() <= 1, "{:?} vs {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_slerp_midpoint() {
+        let a = GeoCoordinate::new(0.0, 0.0).unwrap();
+        let b = GeoCoordinate::new(0.0, 90.0).unwrap();
+        let mid = slerp(a, b, 0.5);
+        assert!(mid.lat.abs() < 1e-9 && (mid.lng - 45.0).abs() < 1e-9);
+
+        // Across the antimeridian the short way round.
+        let c = slerp(GeoCoordinate::new(0.0, 170.0).unwrap(), GeoCoordinate::new(0.0, -170.0).unwrap(), 0.5);
+        assert!((c.lng.abs() - 180.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_route_validation() {
+        let t = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let p = GeoCoordinate::new(0.0, 0.0).unwrap();
+        assert!(Route::new(vec![]).is_err());
+        assert!(Route::new(vec![Waypoint::new(t, p), Waypoint::new(t, p)]).is_err());
+        let single = Route::new(vec![Waypoint::new(t, p)]).unwrap();
+        assert_eq!(single.position_at(t), Some(p));
+        assert_eq!(single.position_at(t + Duration::seconds(1)), None);
+    }
+
+    #[test]
+    fn test_antipodal_waypoints_are_rejected() {
+        let t = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let leg = |a: (f64, f64), b: (f64, f64)| Route::new(vec![
+            Waypoint::new(t, GeoCoordinate::new(a.0, a.1).unwrap()),
+            Waypoint::new(t + Duration::hours(20), GeoCoordinate::new(b.0, b.1).unwrap()),
+        ]);
+        for (a, b) in [((0.0, 0.0), (0.0, 180.0)), ((21.4, 39.8), (-21.4, -140.2)), ((90.0, 0.0), (-90.0, 0.0))] {
+            let err = leg(a, b).unwrap_err();
+            assert!(err.to_string().contains("antipodal"), "{err}");
+        }
+
+        // Nearly antipodal is still well defined, and an intermediate waypoint fixes the path.
+        let near = leg((0.0, 0.0), (0.0, 179.0)).unwrap();
+        let mid = near.position_at(t + Duration::hours(10)).unwrap();
+        assert!(mid.lat.is_finite() && (mid.lng - 89.5).abs() < 1e-6);
+        let via = Route::new(vec![
+            Waypoint::new(t, GeoCoordinate::new(0.0, 0.0).unwrap()),
+            Waypoint::new(t + Duration::hours(10), GeoCoordinate::new(90.0, 0.0).unwrap()),
+            Waypoint::new(t + Duration::hours(20), GeoCoordinate::new(0.0, 180.0).unwrap()),
+        ]).unwrap();
+        assert!(via.position_at(t + Duration::hours(15)).unwrap().lat > 0.0);
+    }
+}
@@ -5,10 +5,17 @@
 //!
 //! Reference: Various Islamic astronomy sources, MABIMS criteria documentation.
 
-use crate::{vsop87, elp2000, coords};
-use shaum_types::{GeoCoordinate, VisibilityCriteria};
+use crate::{vsop87, coords};
+use crate::ephemeris::ephemeris;
+use shaum_types::{GeoCoordinate, VisibilityCriteria, EvaluationTime};
 use chrono::{DateTime, Utc, Duration, Datelike, Timelike, TimeZone};
 
+/// Mean lunar radius (km).
+const MOON_RADIUS_KM: f64 = 1737.4;
+
+/// Hours either side of sunset searched for moonset.
+pub const MOONSET_WINDOW_HOURS: i64 = 4;
+
 
 
 /// Report containing all visibility metrics for hilal observation.
@@ -23,10 +30,16 @@ pub struct MoonVisibilityReport {
     /// Hours since last astronomical new moon (conjunction).
     pub moon_age_hours: f64,
     /// Minutes between sunset and moonset (positive = moon sets after sun).
+    /// Clamped to ±[`MOONSET_WINDOW_HOURS`] when `moonset` is `None`.
     pub lag_time_minutes: f64,
-    /// Whether MABIMS criteria are satisfied.
+    /// Moonset, or `None` when the Moon does not set within
+    /// [`MOONSET_WINDOW_HOURS`] of sunset.
+    pub moonset: Option<DateTime<Utc>>,
+    /// Width of the illuminated crescent (arc minutes).
+    pub crescent_width: f64,
+    /// Whether the configured criteria are satisfied.
     pub meets_mabims: bool,
-    /// Observation datetime (sunset time).
+    /// Instant the metrics were evaluated at (see `VisibilityCriteria::evaluation_time`).
     pub observation_time: DateTime<Utc>,
 }
 
@@ -131,15 +144,7 @@ pub fn estimate_sunset(
     let offset_minutes = (offset_hours * 60.0).round() as i64;
     let mut dt = base_dt + Duration::minutes(offset_minutes);
     
-    // Calculate target sunset altitude with corrections:
-    // - Standard refraction: 34 arcminutes = 0.567°
-    // - Sun semi-diameter: 16 arcminutes = 0.267°
-    // - Horizon dip: 2.076 * sqrt(altitude_m) arcminutes
-    let horizon_dip_arcmin = 2.076 * altitude_m.max(0.0).sqrt();
-    let horizon_dip_deg = horizon_dip_arcmin / 60.0;
-    
-    // Target altitude = -(refraction + semi_diameter + horizon_dip)
-    let target_alt = -(0.567 + 0.267 + horizon_dip_deg);
+    let target_alt = horizon_altitude(altitude_m);
     
     // Iterative refinement (simple Newton-Raphson-like)
     for _ in 0..8 {  // Increased iterations for better precision
@@ -161,12 +166,25 @@ pub fn estimate_sunset(
             break;
         }
         
-        dt = dt + Duration::seconds((time_correction_minutes * 60.0) as i64);
+        dt += Duration::seconds((time_correction_minutes * 60.0) as i64);
     }
     
     Ok(dt)
 }
 
+/// Apparent sun altitude (degrees) at sunrise/sunset for an observer `altitude_m` above sea level.
+pub(crate) fn horizon_altitude(altitude_m: f64) -> f64 {
+    // Corrections:
+    // - Standard refraction: 34 arcminutes = 0.567°
+    // - Sun semi-diameter: 16 arcminutes = 0.267°
+    // - Horizon dip: 2.076 * sqrt(altitude_m) arcminutes
+    let horizon_dip_arcmin = 2.076 * altitude_m.max(0.0).sqrt();
+    let horizon_dip_deg = horizon_dip_arcmin / 60.0;
+
+    // Target altitude = -(refraction + semi_diameter + horizon_dip)
+    -(0.567 + 0.267 + horizon_dip_deg)
+}
+
 /// Calculates the approximate time of the last new moon (conjunction) before the given date.
 ///
 /// Uses a simplified algorithm based on the Metonic cycle.
@@ -186,7 +204,7 @@ fn approximate_last_new_moon(dt: DateTime<Utc>) -> Result<DateTime<Utc>, shaum_t
 }
 
 /// Calculates the elongation (angular separation) between Sun and Moon.
-fn calculate_elongation(sun_lon: f64, sun_lat: f64, moon_lon: f64, moon_lat: f64) -> f64 {
+pub(crate) fn calculate_elongation(sun_lon: f64, sun_lat: f64, moon_lon: f64, moon_lat: f64) -> f64 {
     // Spherical law of cosines for angular distance
     let sun_lon_rad = sun_lon.to_radians();
     let sun_lat_rad = sun_lat.to_radians();
@@ -199,17 +217,50 @@ fn calculate_elongation(sun_lon: f64, sun_lat: f64, moon_lon: f64, moon_lat: f64
     cos_elong.acos().to_degrees()
 }
 
+/// Finds moonset within [`MOONSET_WINDOW_HOURS`] of `sunset`, using the same
+/// horizon as sunset.
+///
+/// Around conjunction the Moon's altitude decreases monotonically through this
+/// window. Returns `None` when the Moon is already down at the start of the
+/// window or still up at its end, e.g. several days from conjunction.
+pub fn estimate_moonset(
+    sunset: DateTime<Utc>,
+    coords: GeoCoordinate,
+) -> Option<DateTime<Utc>> {
+    let target_alt = horizon_altitude(coords.altitude);
+    let above = |t: DateTime<Utc>| ephemeris(t, coords).moon.altitude > target_alt;
+
+    let window = Duration::hours(MOONSET_WINDOW_HOURS);
+    let (mut low, mut high) = (sunset - window, sunset + window);
+    if !above(low) || above(high) {
+        return None;
+    }
+    for _ in 0..20 {
+        let mid = low + Duration::seconds((high - low).num_seconds() / 2);
+        if above(mid) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Some(low + Duration::seconds((high - low).num_seconds() / 2))
+}
+
 /// Calculates the visibility report for the Moon at a specific datetime and location.
 ///
-/// This is the main entry point for hilal visibility determination.
+/// This is the main entry point for hilal visibility determination. Positions are
+/// evaluated at `criteria.evaluation_time` on the date of `datetime`:
+/// sunset (MABIMS, Istanbul 1978), moonset, or the Yallop/Odeh best time.
 ///
 /// # Arguments
 /// * `datetime` - Observation datetime in UTC
 /// * `coords` - Observer's geographic coordinates
-/// * `criteria` - Visibility criteria thresholds (altitude, elongation)
+/// * `criteria` - Visibility criteria thresholds ([`shaum_types::CriterionParams`]) and evaluation time
 ///
 /// # Errors
-/// Returns `ShaumError::AstronomyError` for polar regions or invalid calculations.
+/// Returns `ShaumError::AstronomyError` for polar regions or invalid calculations,
+/// and when evaluating at moonset or the best time while the Moon does not set
+/// within [`MOONSET_WINDOW_HOURS`] of sunset.
 pub fn calculate_visibility(
     datetime: DateTime<Utc>,
     coords: GeoCoordinate,
@@ -217,59 +268,59 @@ pub fn calculate_visibility(
 ) -> Result<MoonVisibilityReport, shaum_types::ShaumError> {
     let date = datetime.date_naive();
     
-    // 1. Find sunset time for observation
+    // 1. Find sunset and moonset
     let sunset = estimate_sunset(date, coords)?;
-    let jd = datetime_to_jd(sunset);
-    
-    // 2. Calculate Sun's position
-    let (sun_lon, sun_lat, _sun_dist) = vsop87::calculate(jd);
-    let obliquity = coords::mean_obliquity(jd);
-    let (sun_ra, sun_dec) = coords::ecliptic_to_equatorial(sun_lon, sun_lat, obliquity);
-    let lst = coords::local_sidereal_time(jd, coords.lng);
-    let (_, sun_alt) = coords::equatorial_to_horizontal(sun_ra, sun_dec, lst, coords.lat);
-    
-    // 3. Calculate Moon's geocentric position
-    let (moon_lon, moon_lat, moon_dist) = elp2000::calculate(jd);
-    let (moon_ra, moon_dec) = coords::ecliptic_to_equatorial(moon_lon, moon_lat, obliquity);
-    
-    // 4. Apply topocentric parallax correction
-    // Note: GeoCoordinate doesn't have elevation field currently, assume sea level
-    let elevation_m = 0.0;
-    let (moon_ra_topo, moon_dec_topo) = coords::apply_parallax(
-        moon_ra, moon_dec, moon_dist, coords.lat, elevation_m, lst
-    );
-    
-    // 5. Convert to horizontal coordinates
-    let (_, moon_alt_apparent) = coords::equatorial_to_horizontal(
-        moon_ra_topo, moon_dec_topo, lst, coords.lat
-    );
-    
-    // 6. Apply atmospheric refraction
-    let refraction = coords::refraction_correction(moon_alt_apparent);
-    let moon_alt = moon_alt_apparent + refraction;
-    
-    // 7. Calculate elongation
-    let elongation = calculate_elongation(sun_lon, sun_lat, moon_lon, moon_lat);
-    
-    // 8. Calculate moon age
-    let last_new_moon = approximate_last_new_moon(sunset)?;
-    let moon_age_hours = (sunset - last_new_moon).num_seconds() as f64 / 3600.0;
+    let moonset = estimate_moonset(sunset, coords);
+    let lag_time_minutes = match moonset {
+        Some(moonset) => (moonset - sunset).num_seconds() as f64 / 60.0,
+        // Outside the window the bound still orders correctly against lag thresholds.
+        None if ephemeris(sunset + Duration::hours(MOONSET_WINDOW_HOURS), coords).moon.altitude
+            > horizon_altitude(coords.altitude) => {
+            (MOONSET_WINDOW_HOURS * 60) as f64
+        }
+        None => -(MOONSET_WINDOW_HOURS * 60) as f64,
+    };
+
+    // 2. Pick the evaluation instant
+    let observation_time = match (criteria.evaluation_time, moonset) {
+        (EvaluationTime::Sunset, _) => sunset,
+        (EvaluationTime::BestTime, _) if lag_time_minutes <= 0.0 => sunset,
+        (EvaluationTime::Moonset, Some(moonset)) => moonset,
+        (EvaluationTime::BestTime, Some(_)) => {
+            sunset + Duration::seconds((lag_time_minutes * 60.0 * 4.0 / 9.0) as i64)
+        }
+        (_, None) => {
+            return Err(shaum_types::ShaumError::AstronomyError(format!(
+                "Moonset on {} is more than {} hours from sunset", date, MOONSET_WINDOW_HOURS
+            )));
+        }
+    };
+
+    // 3. Sun and Moon positions (Moon topocentric, refraction-corrected)
+    let eph = ephemeris(observation_time, coords);
+    let moon_alt = eph.moon.apparent_altitude;
+    let elongation = eph.elongation;
     
-    // 9. Estimate lag time (simplified: difference in set times based on position)
-    // For now, approximate using the difference in altitudes and typical motion
-    let alt_diff = moon_alt - sun_alt;
-    let lag_time_minutes = alt_diff * 4.0; // Rough estimate: 4 min per degree
+    // 4. Calculate moon age
+    let last_new_moon = approximate_last_new_moon(observation_time)?;
+    let moon_age_hours = (observation_time - last_new_moon).num_seconds() as f64 / 3600.0;
     
-    // 10. Check criteria
-    let meets_mabims = moon_alt >= criteria.min_altitude && elongation >= criteria.min_elongation;
+    // 5. Crescent width: semi-diameter times (1 - cos elongation)
+    let semi_diameter = (MOON_RADIUS_KM / eph.moon.distance_km).asin().to_degrees() * 60.0;
+    let crescent_width = semi_diameter * (1.0 - elongation.to_radians().cos());
+
+    // 6. Check criteria
+    let meets_mabims = criteria.params.is_met(moon_alt, elongation, lag_time_minutes, crescent_width);
     
     Ok(MoonVisibilityReport {
         moon_altitude: moon_alt,
-        sun_altitude: sun_alt,
+        sun_altitude: eph.sun.altitude,
         elongation,
         moon_age_hours,
         lag_time_minutes,
+        moonset,
+        crescent_width,
         meets_mabims,
-        observation_time: sunset,
+        observation_time,
     })
 }
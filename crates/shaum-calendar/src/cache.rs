@@ -0,0 +1,159 @@
+//! Thread-safe conversion cache shared across threads and tasks.
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::{HijriCalendar, HijriYmd, ShaumError};
+
+/// Number of independently locked shards.
+const SHARDS: usize = 16;
+
+/// Dates kept by [`HijriCache::new`].
+pub const DEFAULT_CACHE_CAPACITY: usize = 4096;
+
+/// Hit/miss counters of a [`HijriCache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+    /// Dates currently cached.
+    pub entries: usize,
+}
+
+impl CacheMetrics {
+    /// Fraction of lookups served from the cache (0.0 when unused).
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 { 0.0 } else { self.hits as f64 / total as f64 }
+    }
+}
+
+/// Sharded, `Arc`-shared cache of Gregorian to Hijri conversions.
+///
+/// Clones share storage and metrics, so one cache can serve every task of an
+/// async executor regardless of which thread polls it. Entries are keyed by
+/// the already-adjusted Gregorian date and the calendar's
+/// [`cache_id`](HijriCalendar::cache_id), so contexts share entries whenever
+/// their backends are configured alike, even as separate instances.
+/// Calendars without an id are converted on every lookup. Each shard evicts
+/// in insertion order (FIFO): when full, the earliest cached dates go first,
+/// however recently they were read.
+#[derive(Debug, Clone)]
+pub struct HijriCache {
+    inner: Arc<Inner>,
+}
+
+/// Calendar cache id and date.
+type Key = (u64, NaiveDate);
+
+#[derive(Debug)]
+struct Inner {
+    shards: [Mutex<Shard>; SHARDS],
+    shard_capacity: usize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Debug, Default)]
+struct Shard {
+    entries: HashMap<Key, HijriYmd>,
+    /// Insertion order, for eviction.
+    order: VecDeque<Key>,
+}
+
+impl Default for HijriCache {
+    fn default() -> Self { Self::with_capacity(DEFAULT_CACHE_CAPACITY) }
+}
+
+impl HijriCache {
+    /// Cache holding up to [`DEFAULT_CACHE_CAPACITY`] dates.
+    pub fn new() -> Self { Self::default() }
+
+    /// Cache holding about `capacity` dates (at least one per shard).
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                shards: Default::default(),
+                shard_capacity: capacity.div_ceil(SHARDS).max(1),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Returns the cached conversion of `date` in `calendar`, computing it on a
+    /// miss. Errors are not cached, nor are calendars without a
+    /// [`cache_id`](HijriCalendar::cache_id).
+    pub fn get_or_convert(&self, date: NaiveDate, calendar: &Arc<dyn HijriCalendar>) -> Result<HijriYmd, ShaumError> {
+        let Some(id) = calendar.cache_id() else {
+            self.inner.misses.fetch_add(1, Ordering::Relaxed);
+            return calendar.to_hijri(date);
+        };
+        let key = (id, date);
+        let shard = self.shard(date);
+        if let Some(h) = lock(shard).entries.get(&key) {
+            self.inner.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(*h);
+        }
+        self.inner.misses.fetch_add(1, Ordering::Relaxed);
+        let h = calendar.to_hijri(date)?;
+        let mut shard = lock(shard);
+        if shard.entries.insert(key, h).is_none() {
+            shard.order.push_back(key);
+            while shard.order.len() > self.inner.shard_capacity {
+                if let Some(oldest) = shard.order.pop_front() {
+                    shard.entries.remove(&oldest);
+                }
+            }
+        }
+        Ok(h)
+    }
+
+    /// Current counters.
+    pub fn metrics(&self) -> CacheMetrics {
+        CacheMetrics {
+            hits: self.inner.hits.load(Ordering::Relaxed),
+            misses: self.inner.misses.load(Ordering::Relaxed),
+            entries: self.inner.shards.iter().map(|s| lock(s).entries.len()).sum(),
+        }
+    }
+
+    /// Drops all entries and resets the counters.
+    pub fn clear(&self) {
+        for shard in &self.inner.shards {
+            let mut shard = lock(shard);
+            shard.entries.clear();
+            shard.order.clear();
+        }
+        self.inner.hits.store(0, Ordering::Relaxed);
+        self.inner.misses.store(0, Ordering::Relaxed);
+    }
+
+    fn shard(&self, date: NaiveDate) -> &Mutex<Shard> {
+        &self.inner.shards[date.num_days_from_ce().rem_euclid(SHARDS as i32) as usize]
+    }
+}
+
+/// [`HijriCalendar::cache_id`] derived from `identity`, typically a method
+/// name and the configuration that changes its conversions.
+///
+/// ```rust
+/// use shaum_calendar::{cache_id_of, HijriCalendar, Tabular};
+///
+/// assert_eq!(Tabular::new().cache_id(), Tabular::new().cache_id());
+/// assert_ne!(Tabular::new().cache_id(), Tabular::fatimid().cache_id());
+/// assert_ne!(cache_id_of("umm_al_qura"), cache_id_of("tabular"));
+/// ```
+pub fn cache_id_of(identity: impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    identity.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn lock<T>(m: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    m.lock().unwrap_or_else(|e| e.into_inner())
+}
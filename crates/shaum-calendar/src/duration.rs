@@ -0,0 +1,203 @@
+//! Spans of Hijri months and days.
+//!
+//! A Hijri month is 29 or 30 days depending on the calendar, so month counts
+//! (kaffarah, iddah) cannot be expressed as a fixed number of days. A
+//! [`HijriDuration`] keeps months and days apart and resolves them against a
+//! [`HijriCalendar`] only when added to a date.
+
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::{month_start_in, HijriCalendar, HijriYmd, ShaumError};
+
+/// Mean synodic month in days, used for approximate Gregorian spans.
+const MEAN_MONTH_DAYS: f64 = 29.530_588_853;
+
+/// A span of Hijri months plus days. Either part may be negative.
+///
+/// ```rust
+/// use shaum_calendar::{HijriDuration, HijriYmd, UmmAlQura};
+///
+/// // Two consecutive months of kaffarah starting 1 Rajab 1445.
+/// let start = HijriYmd::new(1445, 7, 1);
+/// let end = start.checked_add(HijriDuration::months(2), &UmmAlQura).unwrap();
+/// assert_eq!(end, HijriYmd::new(1445, 9, 1));
+///
+/// // Four months and ten days.
+/// let iddah = HijriDuration::new(4, 10);
+/// assert_eq!(iddah.to_string(), "4 months 10 days");
+/// assert_eq!(iddah.approx_gregorian().num_days(), 128);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct HijriDuration {
+    pub months: i64,
+    pub days: i64,
+}
+
+impl HijriDuration {
+    pub const ZERO: Self = Self::new(0, 0);
+
+    pub const fn new(months: i64, days: i64) -> Self {
+        Self { months, days }
+    }
+
+    pub const fn months(months: i64) -> Self {
+        Self::new(months, 0)
+    }
+
+    pub const fn days(days: i64) -> Self {
+        Self::new(0, days)
+    }
+
+    /// Approximate Gregorian length, counting each month as a mean synodic
+    /// month (29.53 days). Use [`HijriYmd::checked_add`] for exact dates.
+    pub fn approx_gregorian(&self) -> Duration {
+        Duration::days((self.months as f64 * MEAN_MONTH_DAYS).round() as i64 + self.days)
+    }
+
+    /// Whole months then remaining days from `from` to `to` in `calendar`,
+    /// so `from.checked_add(result)` is `to`. Negative if `to` is earlier.
+    ///
+    /// ```rust
+    /// use shaum_calendar::{HijriDuration, HijriYmd, UmmAlQura};
+    ///
+    /// let from = HijriYmd::new(1445, 1, 20);
+    /// let to = HijriYmd::new(1445, 5, 29);
+    /// assert_eq!(HijriDuration::between(from, to, &UmmAlQura).unwrap(), HijriDuration::new(4, 9));
+    /// ```
+    ///
+    /// # Errors
+    /// `ValidationError` if either date does not exist in `calendar`.
+    pub fn between(from: HijriYmd, to: HijriYmd, calendar: &dyn HijriCalendar) -> Result<Self, ShaumError> {
+        let (start, end) = (from.to_gregorian(calendar)?, to.to_gregorian(calendar)?);
+        // Step back one month if the label difference overshoots `to`.
+        let step = if end < start { -1 } else { 1 };
+        let mut months = month_index(to) - month_index(from);
+        let mut anchor = from.add_months(months, calendar)?.to_gregorian(calendar)?;
+        if (anchor - end).num_days() * step > 0 {
+            months -= step;
+            anchor = from.add_months(months, calendar)?.to_gregorian(calendar)?;
+        }
+        Ok(Self::new(months, (end - anchor).num_days()))
+    }
+}
+
+impl fmt::Display for HijriDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = |n: i64, name: &str| format!("{} {}{}", n, name, if n.abs() == 1 { "" } else { "s" });
+        match (self.months, self.days) {
+            (0, days) => f.write_str(&unit(days, "day")),
+            (months, 0) => f.write_str(&unit(months, "month")),
+            (months, days) => write!(f, "{} {}", unit(months, "month"), unit(days, "day")),
+        }
+    }
+}
+
+impl Add for HijriDuration {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.months + rhs.months, self.days + rhs.days)
+    }
+}
+
+impl Sub for HijriDuration {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl Neg for HijriDuration {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.months, -self.days)
+    }
+}
+
+impl Mul<i64> for HijriDuration {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self {
+        Self::new(self.months * rhs, self.days * rhs)
+    }
+}
+
+/// Months since 1 Muharram 1 AH.
+fn month_index(date: HijriYmd) -> i64 {
+    date.year as i64 * 12 + date.month as i64 - 1
+}
+
+impl HijriYmd {
+    /// Same day `months` later (or earlier) in `calendar`, clamped to the
+    /// last day of a shorter month: 30 Muharram plus one month is 29 Safar
+    /// when Safar has 29 days.
+    ///
+    /// # Errors
+    /// `ValidationError` before 1 AH, or any error locating the month.
+    pub fn add_months(&self, months: i64, calendar: &dyn HijriCalendar) -> Result<HijriYmd, ShaumError> {
+        let index = month_index(*self) + months;
+        if index < 12 {
+            return Err(ShaumError::ValidationError(format!(
+                "{}-{}-{} plus {} months is before 1 AH", self.year, self.month, self.day, months
+            )));
+        }
+        let (year, month) = ((index / 12) as usize, (index % 12) as usize + 1);
+        let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+        let length = (month_start_in(calendar, next_year, next_month)? - month_start_in(calendar, year, month)?).num_days();
+        Ok(HijriYmd::new(year, month, self.day.min(length as usize)))
+    }
+
+    /// Adds `duration` in `calendar`: months first (see [`Self::add_months`]),
+    /// then days.
+    ///
+    /// # Errors
+    /// `ValidationError` if this date does not exist in `calendar`, or any
+    /// error converting the result.
+    pub fn checked_add(&self, duration: HijriDuration, calendar: &dyn HijriCalendar) -> Result<HijriYmd, ShaumError> {
+        self.to_gregorian(calendar)?;
+        let shifted = self.add_months(duration.months, calendar)?.to_gregorian(calendar)?;
+        let date = shifted.checked_add_signed(Duration::days(duration.days))
+            .ok_or_else(|| ShaumError::date_out_of_range(shifted))?;
+        calendar.to_hijri(date)
+    }
+
+    /// Subtracts `duration`; see [`Self::checked_add`].
+    pub fn checked_sub(&self, duration: HijriDuration, calendar: &dyn HijriCalendar) -> Result<HijriYmd, ShaumError> {
+        self.checked_add(-duration, calendar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tabular, UmmAlQura};
+
+    #[test]
+    fn test_hijri_duration_arithmetic() {
+        // Ramadhan 1444 had 29 days in Umm al-Qura: the day is clamped.
+        let last_shaban = HijriYmd::new(1444, 8, 30);
+        assert_eq!(last_shaban.add_months(1, &UmmAlQura).unwrap(), HijriYmd::new(1444, 9, 29));
+        // Months roll over the year, in both directions.
+        let d = HijriYmd::new(1445, 11, 15);
+        assert_eq!(d.checked_add(HijriDuration::new(3, 20), &UmmAlQura).unwrap().month, 3);
+        assert_eq!(d.checked_sub(HijriDuration::months(11), &UmmAlQura).unwrap(), HijriYmd::new(1444, 12, 15));
+        for calendar in [&UmmAlQura as &dyn HijriCalendar, &Tabular::new()] {
+            for span in [HijriDuration::new(4, 10), HijriDuration::days(-45), HijriDuration::new(-2, 3)] {
+                let end = d.checked_add(span, calendar).unwrap();
+                let back = HijriDuration::between(d, end, calendar).unwrap();
+                assert_eq!(d.checked_add(back, calendar).unwrap(), end);
+            }
+        }
+        assert!(HijriYmd::new(1444, 9, 30).checked_add(HijriDuration::ZERO, &UmmAlQura).is_err());
+        assert!(HijriYmd::new(1, 2, 1).add_months(-2, &Tabular::new()).is_err());
+        assert_eq!(HijriDuration::new(1, 2) * 3 - HijriDuration::days(6), HijriDuration::months(3));
+        assert_eq!(HijriDuration::months(12).approx_gregorian().num_days(), 354);
+        assert_eq!(HijriDuration::days(1).to_string(), "1 day");
+    }
+}
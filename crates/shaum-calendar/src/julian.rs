@@ -0,0 +1,63 @@
+//! Julian Day conversions.
+//!
+//! Julian Days count days from noon UT on 1 January 4713 BCE (proleptic
+//! Julian), so a civil date starts at `.5`: 1 January 2000 is JD 2451544.5.
+
+use chrono::{Duration, NaiveDate};
+
+use crate::ShaumError;
+
+/// Julian Day of 1970-01-01 00:00 UT.
+const UNIX_EPOCH_JD: f64 = 2_440_587.5;
+
+fn unix_epoch() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1970, 1, 1).expect("valid epoch")
+}
+
+/// Julian Day at 00:00 UT of `date` (proleptic Gregorian).
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_calendar::to_julian_day;
+///
+/// assert_eq!(to_julian_day(NaiveDate::from_ymd_opt(2000, 1, 1).unwrap()), 2_451_544.5);
+/// ```
+pub fn to_julian_day(date: NaiveDate) -> f64 {
+    UNIX_EPOCH_JD + (date - unix_epoch()).num_days() as f64
+}
+
+/// Civil (UT) date containing Julian Day `jd`; fractions are truncated to the
+/// date, so both 2451544.5 and 2451545.4 give 1 January 2000.
+///
+/// # Errors
+/// `ValidationError` for a non-finite value or one outside chrono's date range.
+pub fn from_julian_day(jd: f64) -> Result<NaiveDate, ShaumError> {
+    let invalid = || ShaumError::ValidationError(format!("Julian Day {} is out of range", jd));
+    let days = (jd - UNIX_EPOCH_JD).floor();
+    if !days.is_finite() || days.abs() > i32::MAX as f64 {
+        return Err(invalid());
+    }
+    unix_epoch().checked_add_signed(Duration::days(days as i64)).ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate { NaiveDate::from_ymd_opt(y, m, d).unwrap() }
+
+    #[test]
+    fn test_julian_day_round_trip() {
+        // Meeus, Example 7.a: 1957 October 4.81 = JD 2436116.31.
+        assert_eq!(to_julian_day(date(1957, 10, 4)), 2_436_115.5);
+        assert_eq!(from_julian_day(2_436_116.31).unwrap(), date(1957, 10, 4));
+        // Gregorian reform: 15 October 1582.
+        assert_eq!(to_julian_day(date(1582, 10, 15)), 2_299_160.5);
+        for d in [date(-4712, 1, 1), date(622, 7, 16), date(2024, 3, 11), date(2100, 2, 28)] {
+            assert_eq!(from_julian_day(to_julian_day(d)).unwrap(), d);
+            assert_eq!(from_julian_day(to_julian_day(d) + 0.999).unwrap(), d);
+        }
+        assert!(from_julian_day(f64::NAN).is_err());
+        assert!(from_julian_day(1e300).is_err());
+    }
+}
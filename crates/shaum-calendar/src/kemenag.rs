@@ -0,0 +1,80 @@
+//! Indonesian Ministry of Religious Affairs (Kemenag) calendar.
+//!
+//! Month starts decided at the sidang isbat override Umm al-Qura for the
+//! months they cover; every other month follows Umm al-Qura. The bundled
+//! table holds Ramadhan, Shawwal and Dhu al-Hijjah for 1440-1446 AH; newer
+//! decisions can be added with [`TableCalendar::with_month_start`].
+
+use chrono::NaiveDate;
+
+use crate::{TableCalendar, UmmAlQura};
+
+/// Published 1 Ramadhan, 1 Shawwal and 1 Dhu al-Hijjah (year, month, y, m, d).
+const ISBAT_DECISIONS: [(usize, usize, i32, u32, u32); 21] = [
+    (1440, 9, 2019, 5, 6), (1440, 10, 2019, 6, 5), (1440, 12, 2019, 8, 2),
+    (1441, 9, 2020, 4, 24), (1441, 10, 2020, 5, 24), (1441, 12, 2020, 7, 22),
+    (1442, 9, 2021, 4, 13), (1442, 10, 2021, 5, 13), (1442, 12, 2021, 7, 11),
+    (1443, 9, 2022, 4, 3), (1443, 10, 2022, 5, 2), (1443, 12, 2022, 7, 1),
+    (1444, 9, 2023, 3, 23), (1444, 10, 2023, 4, 22), (1444, 12, 2023, 6, 20),
+    (1445, 9, 2024, 3, 12), (1445, 10, 2024, 4, 10), (1445, 12, 2024, 6, 8),
+    (1446, 9, 2025, 3, 1), (1446, 10, 2025, 3, 31), (1446, 12, 2025, 5, 28),
+];
+
+impl TableCalendar {
+    /// Kemenag taqwim: the bundled isbat decisions over [`UmmAlQura`].
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use shaum_calendar::{HijriCalendar, HijriYmd, TableCalendar, UmmAlQura};
+    ///
+    /// // Umm al-Qura starts Ramadhan 1445 on 11 March 2024; Indonesia on the 12th.
+    /// let date = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+    /// assert_eq!(UmmAlQura.to_hijri(date).unwrap(), HijriYmd::new(1445, 9, 1));
+    /// assert_eq!(TableCalendar::kemenag().to_hijri(date).unwrap(), HijriYmd::new(1445, 8, 30));
+    /// ```
+    pub fn kemenag() -> Self {
+        ISBAT_DECISIONS.iter().fold(Self::new(UmmAlQura), |calendar, &(hy, hm, y, m, d)| {
+            let start = NaiveDate::from_ymd_opt(y, m, d).expect("isbat dates are valid");
+            calendar.with_month_start(hy, hm, start).expect("isbat decisions are consistent")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HijriCalendar, HijriYmd};
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate { NaiveDate::from_ymd_opt(y, m, d).unwrap() }
+
+    #[test]
+    fn test_official_dates() {
+        let kemenag = TableCalendar::kemenag();
+        assert_eq!(kemenag.len(), ISBAT_DECISIONS.len());
+        for &(hy, hm, y, m, d) in &ISBAT_DECISIONS {
+            assert_eq!(kemenag.to_hijri(date(y, m, d)).unwrap(), HijriYmd::new(hy, hm, 1));
+        }
+        // Eid al-Adha 1443: 9 July 2022 in Saudi Arabia, 10 July in Indonesia.
+        assert_eq!(kemenag.to_hijri(date(2022, 7, 10)).unwrap(), HijriYmd::new(1443, 12, 10));
+        // 30 Ramadhan 1444 before the later Shawwal.
+        assert_eq!(kemenag.to_hijri(date(2023, 4, 21)).unwrap(), HijriYmd::new(1444, 9, 30));
+    }
+
+    #[test]
+    fn test_matches_umm_al_qura_outside_table() {
+        let kemenag = TableCalendar::kemenag();
+        let d = date(2010, 5, 1);
+        assert_eq!(kemenag.to_hijri(d).unwrap(), UmmAlQura.to_hijri(d).unwrap());
+    }
+
+    #[test]
+    fn test_with_month_start() {
+        let kemenag = TableCalendar::kemenag().with_month_start(1450, 9, date(2029, 1, 16)).unwrap();
+        assert_eq!(kemenag.table_month_start(1450, 9), Some(date(2029, 1, 16)));
+        assert!(TableCalendar::kemenag().with_month_start(1450, 9, date(2029, 3, 1)).is_err());
+        assert!(TableCalendar::kemenag().with_month_start(1450, 13, date(2029, 1, 16)).is_err());
+        // A Shawwal 1445 on 9 April would leave Ramadhan 28 days.
+        assert!(TableCalendar::kemenag().with_month_start(1445, 10, date(2024, 4, 9)).is_err());
+        assert_ne!(kemenag.cache_id(), TableCalendar::kemenag().cache_id());
+    }
+}
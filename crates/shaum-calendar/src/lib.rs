@@ -1,20 +1,53 @@
 //! Hijri calendar conversion for Shaum.
 //!
-//! Provides Gregorian to Hijri date conversion with caching.
+//! Provides Gregorian to Hijri date conversion with caching, plus the
+//! [`HijriCalendar`] trait for plugging in other calendar backends
+//! ([`UmmAlQura`] tables, the arithmetic [`Tabular`] calendar, the
+//! announced month starts in a [`TableCalendar`], such as the Indonesian
+//! [`TableCalendar::kemenag`] taqwim).
 
-use chrono::{Duration, Datelike, NaiveDate};
+use chrono::{Duration, Datelike, NaiveDate, Weekday};
 use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::sync::Arc;
 
 pub use shaum_types::ShaumError;
 
+mod cache;
+pub use cache::{cache_id_of, CacheMetrics, HijriCache, DEFAULT_CACHE_CAPACITY};
+
+mod table;
+
+pub mod tabular;
+pub use tabular::Tabular;
+
+pub mod kemenag;
+
+pub mod table_calendar;
+pub use table_calendar::TableCalendar;
+
+pub mod julian;
+pub use julian::{from_julian_day, to_julian_day};
+
+pub mod duration;
+pub use duration::HijriDuration;
+
 /// Minimum Gregorian year for Hijri conversion.
 pub const HIJRI_MIN_YEAR: i32 = 1938;
 /// Maximum Gregorian year for Hijri conversion.
 pub const HIJRI_MAX_YEAR: i32 = 2076;
 
-// Thread-local cache: (gregorian, adjustment) -> (hijri_year, month, day)
+/// Cache entry: (gregorian, adjustment, hijri_year, month, day).
+type CacheEntry = (NaiveDate, i64, usize, usize, usize);
+
+/// Entries kept by the per-thread cache of [`to_hijri`].
+const HIJRI_CACHE_CAPACITY: usize = 64;
+
+// Thread-local LRU cache, most recently used first:
+// (gregorian, adjustment) -> (hijri_year, month, day)
 thread_local! {
-    static HIJRI_CACHE: RefCell<Option<(NaiveDate, i64, usize, usize, usize)>> = const { RefCell::new(None) };
+    static HIJRI_CACHE: RefCell<VecDeque<CacheEntry>> = const { RefCell::new(VecDeque::new()) };
 }
 
 /// Converts Gregorian to Hijri with adjustment.
@@ -22,16 +55,18 @@ thread_local! {
 /// # Arguments
 /// * `date` - Gregorian date
 /// * `adjustment` - Day offset for moon sighting (positive = Hijri ahead)
+#[deprecated(
+    since = "0.9.0",
+    note = "always uses Umm al-Qura through a per-thread cache; use `to_hijri_with` with an explicit `HijriCalendar`"
+)]
 pub fn to_hijri(date: NaiveDate, adjustment: i64) -> Result<HijriDate, ShaumError> {
-    // Check cache
+    // Check cache, moving a hit to the front
     let cached = HIJRI_CACHE.with(|cache| {
-        cache.borrow().as_ref().and_then(|(d, adj, y, m, day)| {
-            if *d == date && *adj == adjustment {
-                Some((*y, *m, *day))
-            } else {
-                None
-            }
-        })
+        let mut cache = cache.borrow_mut();
+        let i = cache.iter().position(|(d, adj, ..)| *d == date && *adj == adjustment)?;
+        let entry = cache.remove(i)?;
+        cache.push_front(entry);
+        Some((entry.2, entry.3, entry.4))
     });
     
     if let Some((y, m, d)) = cached {
@@ -39,11 +74,11 @@ pub fn to_hijri(date: NaiveDate, adjustment: i64) -> Result<HijriDate, ShaumErro
             .map_err(|e| ShaumError::HijriConversionError(e.to_string()));
     }
     
-    let adjusted_date = date + Duration::days(adjustment);
+    let adjusted_date = adjusted_date(date, adjustment)?;
     
     // Check bounds
     let year = adjusted_date.year();
-    if year < HIJRI_MIN_YEAR || year > HIJRI_MAX_YEAR {
+    if !(HIJRI_MIN_YEAR..=HIJRI_MAX_YEAR).contains(&year) {
        return Err(ShaumError::date_out_of_range(adjusted_date));
     }
 
@@ -53,14 +88,546 @@ pub fn to_hijri(date: NaiveDate, adjustment: i64) -> Result<HijriDate, ShaumErro
         adjusted_date.day() as usize
     ).map_err(|e| ShaumError::HijriConversionError(e.to_string()))?;
     
-    // Update cache
+    // Update cache, evicting the least recently used entry
     HIJRI_CACHE.with(|cache| {
-        *cache.borrow_mut() = Some((date, adjustment, hijri.year(), hijri.month(), hijri.day()));
+        let mut cache = cache.borrow_mut();
+        cache.truncate(HIJRI_CACHE_CAPACITY - 1);
+        cache.push_front((date, adjustment, hijri.year(), hijri.month(), hijri.day()));
     });
     
     Ok(hijri)
 }
 
+/// Calendar-agnostic Hijri date, for backends that cannot produce a [`HijriDate`]
+/// (which is tied to the Umm al-Qura tables).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+pub struct HijriYmd {
+    pub year: usize,
+    pub month: usize,
+    pub day: usize,
+}
+
+impl HijriYmd {
+    pub const fn new(year: usize, month: usize, day: usize) -> Self {
+        Self { year, month, day }
+    }
+
+    // Accessors mirroring `HijriDate`, so callers can switch types freely.
+    #[inline] pub const fn year(&self) -> usize { self.year }
+    #[inline] pub const fn month(&self) -> usize { self.month }
+    #[inline] pub const fn day(&self) -> usize { self.day }
+
+    /// Day of the week of this date in `calendar`.
+    ///
+    /// ```rust
+    /// use chrono::Weekday;
+    /// use shaum_calendar::{HijriYmd, UmmAlQura};
+    ///
+    /// // 1 Ramadhan 1445 = Monday 11 March 2024.
+    /// let day = HijriYmd::new(1445, 9, 1).weekday(&UmmAlQura).unwrap();
+    /// assert_eq!(day.weekday, Weekday::Mon);
+    /// assert_eq!(day.name, "Yawm al-Ithnayn");
+    /// assert_eq!(day.arabic, "الإثنين");
+    /// ```
+    ///
+    /// # Errors
+    /// `ValidationError` if the date does not exist in `calendar`, or any
+    /// error locating its month.
+    pub fn weekday(&self, calendar: &dyn HijriCalendar) -> Result<HijriWeekday, ShaumError> {
+        Ok(self.to_gregorian(calendar)?.weekday().into())
+    }
+
+    /// Gregorian date of this day in `calendar`.
+    ///
+    /// # Errors
+    /// `ValidationError` if the date does not exist in `calendar`, or any
+    /// error locating its month.
+    pub fn to_gregorian(&self, calendar: &dyn HijriCalendar) -> Result<NaiveDate, ShaumError> {
+        let start = month_start_in(calendar, self.year, self.month)?;
+        let date = start + Duration::days(self.day as i64 - 1);
+        if self.day == 0 || calendar.to_hijri(date)? != *self {
+            return Err(ShaumError::ValidationError(format!(
+                "{}-{}-{} does not exist in {:?}", self.year, self.month, self.day, calendar
+            )));
+        }
+        Ok(date)
+    }
+}
+
+/// Day of the week with its Arabic name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HijriWeekday {
+    pub weekday: Weekday,
+    /// Transliterated name, e.g. `"Yawm al-Jumu'ah"`.
+    pub name: &'static str,
+    /// Name in Arabic script, e.g. `"الجمعة"`.
+    pub arabic: &'static str,
+}
+
+impl From<Weekday> for HijriWeekday {
+    fn from(weekday: Weekday) -> Self {
+        let (name, arabic) = match weekday {
+            Weekday::Sun => ("Yawm al-Ahad", "الأحد"),
+            Weekday::Mon => ("Yawm al-Ithnayn", "الإثنين"),
+            Weekday::Tue => ("Yawm ath-Thulatha'", "الثلاثاء"),
+            Weekday::Wed => ("Yawm al-Arbi'a'", "الأربعاء"),
+            Weekday::Thu => ("Yawm al-Khamis", "الخميس"),
+            Weekday::Fri => ("Yawm al-Jumu'ah", "الجمعة"),
+            Weekday::Sat => ("Yawm as-Sabt", "السبت"),
+        };
+        Self { weekday, name, arabic }
+    }
+}
+
+impl std::fmt::Display for HijriWeekday {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+impl HijriYmd {
+    /// Formats with strftime-like `pattern` and English month names.
+    ///
+    /// Supported: `%d` / `%-d` (day, padded / unpadded), `%m` / `%-m` (month),
+    /// `%Y` (year), `%B` (month name) and `%%`. Other text is copied as is.
+    ///
+    /// ```rust
+    /// use shaum_calendar::HijriYmd;
+    ///
+    /// let date = HijriYmd::new(1445, 9, 1);
+    /// assert_eq!(date.format("%d %B %Y AH"), "01 Ramadhan 1445 AH");
+    /// assert_eq!(date.format("%-d/%-m/%Y"), "1/9/1445");
+    /// assert_eq!(date.format_arabic("%-d %B %Y هـ"), "١ رمضان ١٤٤٥ هـ");
+    /// ```
+    pub fn format(&self, pattern: &str) -> String {
+        self.format_with(pattern, get_hijri_month_name, |s| s)
+    }
+
+    /// Like [`Self::format`] with Arabic month names and Eastern Arabic digits.
+    pub fn format_arabic(&self, pattern: &str) -> String {
+        self.format_with(pattern, get_hijri_month_name_arabic, |s| {
+            s.chars().map(|c| c.to_digit(10).and_then(|d| char::from_u32(0x0660 + d)).unwrap_or(c)).collect()
+        })
+    }
+
+    fn format_with(&self, pattern: &str, month_name: fn(usize) -> &'static str, digits: impl Fn(String) -> String) -> String {
+        let mut out = String::with_capacity(pattern.len() + 16);
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            let unpadded = chars.next_if_eq(&'-').is_some();
+            let field = match chars.next() {
+                Some('d') if unpadded => digits(self.day.to_string()),
+                Some('d') => digits(format!("{:02}", self.day)),
+                Some('m') if unpadded => digits(self.month.to_string()),
+                Some('m') => digits(format!("{:02}", self.month)),
+                Some('Y') => digits(self.year.to_string()),
+                Some('B') => month_name(self.month).to_string(),
+                Some('%') => "%".to_string(),
+                Some(other) => format!("%{}{}", if unpadded { "-" } else { "" }, other),
+                None => format!("%{}", if unpadded { "-" } else { "" }),
+            };
+            out.push_str(&field);
+        }
+        out
+    }
+}
+
+impl HijriYmd {
+    /// Parses `"1445-09-01"` (also with `/` or `.`) or `"1 Ramadhan 1445"`
+    /// (optionally followed by `AH`), resolving month names with `month`.
+    ///
+    /// `month` receives the name lowercased with everything but letters and
+    /// digits removed (`"Rabi' al-Awwal"` becomes `"rabialawwal"`), so callers
+    /// can match localized names. Only the range of each field is validated.
+    pub fn parse_with(input: &str, month: impl Fn(&str) -> Option<usize>) -> Result<Self, ShaumError> {
+        let input = input.trim().trim_end_matches(|c: char| !c.is_alphanumeric());
+        let invalid = |why: &str| ShaumError::ValidationError(format!("Invalid Hijri date '{}': {}", input, why));
+        let number = |s: &str| s.parse::<usize>().map_err(|_| invalid("expected a number"));
+
+        let numeric: Vec<&str> = input.split(['-', '/', '.']).collect();
+        let date = if let [y, m, d] = numeric[..] && numeric.iter().all(|p| !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit())) {
+            Self::new(number(y)?, number(m)?, number(d)?)
+        } else {
+            let mut words: Vec<&str> = input.split_whitespace().collect();
+            if words.last().is_some_and(|w| w.eq_ignore_ascii_case("AH") || w.eq_ignore_ascii_case("H")) {
+                words.pop();
+            }
+            let [day, name @ .., year] = &words[..] else { return Err(invalid("expected day, month name and year")) };
+            if name.is_empty() {
+                return Err(invalid("missing month name"));
+            }
+            let key = normalize_month_name(&name.join(" "));
+            let m = month(&key).ok_or_else(|| invalid("unknown month name"))?;
+            Self::new(number(year)?, m, number(day)?)
+        };
+
+        if date.year == 0 || !(1..=12).contains(&date.month) || !(1..=30).contains(&date.day) {
+            return Err(invalid("field out of range"));
+        }
+        Ok(date)
+    }
+}
+
+/// Parses numeric or English/transliterated dates; see [`HijriYmd::parse_with`]
+/// and [`hijri_month_from_name`].
+///
+/// ```rust
+/// use shaum_calendar::HijriYmd;
+///
+/// assert_eq!("1445-09-01".parse::<HijriYmd>().unwrap(), HijriYmd::new(1445, 9, 1));
+/// assert_eq!("10 Muharram 1447".parse::<HijriYmd>().unwrap(), HijriYmd::new(1447, 1, 10));
+/// assert_eq!("1 Rabi' al-Awwal 1446 AH".parse::<HijriYmd>().unwrap(), HijriYmd::new(1446, 3, 1));
+/// ```
+impl FromStr for HijriYmd {
+    type Err = ShaumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, hijri_month_from_name)
+    }
+}
+
+/// Lowercase letters and digits only, the form passed to [`HijriYmd::parse_with`].
+fn normalize_month_name(name: &str) -> String {
+    name.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// Common transliterations of each month, normalized.
+const MONTH_ALIASES: [&[&str]; 12] = [
+    &["muharram", "muharam"],
+    &["safar", "shafar"],
+    &["rabialawwal", "rabiulawal", "rabiulawwal", "rabii", "rabi1"],
+    &["rabialthani", "rabiulakhir", "rabialakhir", "rabiuthani", "rabiussani", "rabiii", "rabi2"],
+    &["jumadaalula", "jumadalula", "jumadilawal", "jumadaalawwal", "jumadai", "jumada1"],
+    &["jumadaalakhirah", "jumadaalakhira", "jumadilakhir", "jumadaalthani", "jumadaii", "jumada2"],
+    &["rajab"],
+    &["shaban", "syaban", "shaaban"],
+    &["ramadhan", "ramadan", "ramazan"],
+    &["shawwal", "shawal", "syawal", "sawwal"],
+    &["dhualqidah", "dhualqadah", "dhulqidah", "dhulqadah", "zulkaidah", "dzulqaidah"],
+    &["dhualhijjah", "dhulhijjah", "zulhijjah", "dzulhijjah"],
+];
+
+/// Month number (1-12) of an English or Indonesian/Malay transliterated month
+/// name, in the normalized form (see [`HijriYmd::parse_with`]) or as written.
+///
+/// Matching is tolerant: doubled letters and a final `h` are ignored
+/// (`"Dhu'l-Hijja"`), and a single-letter typo is accepted when only one
+/// month is that close.
+///
+/// ```rust
+/// use shaum_calendar::hijri_month_from_name;
+///
+/// assert_eq!(hijri_month_from_name("Dhul-Hijjah"), Some(12));
+/// assert_eq!(hijri_month_from_name("Dhu'l-Hijja"), Some(12));
+/// assert_eq!(hijri_month_from_name("Ramadhn"), Some(9));
+/// assert_eq!(hijri_month_from_name("Smarch"), None);
+/// ```
+pub fn hijri_month_from_name(name: &str) -> Option<usize> {
+    let key = normalize_month_name(name);
+    if let Some(i) = MONTH_ALIASES.iter().position(|aliases| aliases.contains(&key.as_str())) {
+        return Some(i + 1);
+    }
+
+    let key = fold_month_name(&key);
+    let matches = |accept: &dyn Fn(&str) -> bool| -> Vec<usize> {
+        MONTH_ALIASES.iter().enumerate()
+            .filter(|(_, aliases)| aliases.iter().any(|a| accept(&fold_month_name(a))))
+            .map(|(i, _)| i + 1)
+            .collect()
+    };
+    if let [month] = matches(&|alias| alias == key)[..] {
+        return Some(month);
+    }
+    // Typos only for names long enough not to collide by accident.
+    if key.len() < 5 || key.bytes().any(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    match matches(&|alias| within_one_edit(alias, &key))[..] {
+        [month] => Some(month),
+        _ => None,
+    }
+}
+
+/// Collapses doubled letters and drops a final `h` (`"dhulhijjah"` → `"dhulhija"`).
+fn fold_month_name(key: &str) -> String {
+    let mut folded: String = key.chars().fold(String::with_capacity(key.len()), |mut acc, c| {
+        if !acc.ends_with(c) {
+            acc.push(c);
+        }
+        acc
+    });
+    if folded.len() > 3 && folded.ends_with('h') {
+        folded.pop();
+    }
+    folded
+}
+
+/// Whether `a` and `b` differ by at most one insertion, deletion or substitution.
+fn within_one_edit(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if long.len() - short.len() > 1 {
+        return false;
+    }
+    let prefix = short.iter().zip(long).take_while(|(x, y)| x == y).count();
+    if short.len() == long.len() {
+        short[prefix..].iter().skip(1).eq(long[prefix..].iter().skip(1))
+    } else {
+        short[prefix..] == long[prefix + 1..]
+    }
+}
+
+impl From<&HijriDate> for HijriYmd {
+    fn from(h: &HijriDate) -> Self {
+        Self::new(h.year(), h.month(), h.day())
+    }
+}
+
+impl From<HijriDate> for HijriYmd {
+    fn from(h: HijriDate) -> Self {
+        Self::from(&h)
+    }
+}
+
+/// A Gregorian to Hijri conversion backend.
+///
+/// Implementations must be deterministic and hold any caches themselves, so
+/// several calendars can be used side by side in one process.
+pub trait HijriCalendar: std::fmt::Debug + Send + Sync {
+    /// Converts a Gregorian date to a Hijri date.
+    fn to_hijri(&self, date: NaiveDate) -> Result<HijriYmd, ShaumError>;
+
+    /// Gregorian date of 1 Muharram of `year` AH.
+    ///
+    /// The default searches around the tabular estimate with [`Self::to_hijri`];
+    /// arithmetic backends should override it.
+    fn year_start(&self, year: usize) -> Result<NaiveDate, ShaumError> {
+        let estimate = Tabular::new().to_gregorian(HijriYmd::new(year.max(1), 1, 1))?;
+        (-YEAR_START_WINDOW..=YEAR_START_WINDOW)
+            .map(|offset| estimate + Duration::days(offset))
+            .find(|d| self.to_hijri(*d).is_ok_and(|h| h.year >= year))
+            .filter(|d| self.to_hijri(*d).is_ok_and(|h| h == HijriYmd::new(year, 1, 1)))
+            .ok_or_else(|| ShaumError::HijriConversionError(format!("Cannot locate the start of {} AH", year)))
+    }
+
+    /// Number of days in `year` AH (354 or 355 for lunar calendars).
+    fn days_in_year(&self, year: usize) -> Result<u32, ShaumError> {
+        let days = (self.year_start(year + 1)? - self.year_start(year)?).num_days();
+        Ok(days as u32)
+    }
+
+    /// Stable identity of the conversions this calendar performs, e.g. from
+    /// [`cache_id_of`] over its method name and configuration. Calendars with
+    /// equal ids must convert every date alike, and share [`HijriCache`]
+    /// entries. The default, `None`, keeps the calendar out of the cache.
+    fn cache_id(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// Days searched on either side of the tabular estimate by [`HijriCalendar::year_start`].
+const YEAR_START_WINDOW: i64 = 15;
+
+/// Days searched on either side of the tabular estimate by [`month_start_in`].
+const MONTH_START_WINDOW: i64 = 5;
+
+/// First day of `month` `year` AH in `calendar`.
+pub(crate) fn month_start_in(calendar: &dyn HijriCalendar, year: usize, month: usize) -> Result<NaiveDate, ShaumError> {
+    let estimate = Tabular::new().to_gregorian(HijriYmd::new(year, month, 1))?;
+    (-MONTH_START_WINDOW..=MONTH_START_WINDOW)
+        .map(|offset| estimate + Duration::days(offset))
+        .find(|d| calendar.to_hijri(*d).is_ok_and(|h| h == HijriYmd::new(year, month, 1)))
+        .ok_or_else(|| ShaumError::HijriConversionError(format!(
+            "Cannot locate the start of {}-{} in {:?}", year, month, calendar
+        )))
+}
+
+/// Places `date` in `base`'s month or a neighbour, given each month's first
+/// day. For calendars whose month starts differ from `base`'s backend by a
+/// day or two.
+pub(crate) fn place_in_month(
+    date: NaiveDate,
+    base: HijriYmd,
+    month_start: impl Fn(usize, usize) -> Result<NaiveDate, ShaumError>,
+) -> Result<HijriYmd, ShaumError> {
+    let (y, m) = (base.year, base.month);
+    let next = if m == 12 { (y + 1, 1) } else { (y, m + 1) };
+    let prev = if m == 1 { (y - 1, 12) } else { (y, m - 1) };
+    for (year, month) in [next, (y, m), prev] {
+        let start = month_start(year, month)?;
+        if start <= date {
+            return Ok(HijriYmd::new(year, month, (date - start).num_days() as usize + 1));
+        }
+    }
+    Err(ShaumError::HijriConversionError(format!("Cannot place {} around {}-{}", date, y, m)))
+}
+
+/// Umm al-Qura tables (the `hijri_date` crate), converted through a month
+/// table built once per process; each conversion is constant time.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct UmmAlQura;
+
+impl HijriCalendar for UmmAlQura {
+    fn to_hijri(&self, date: NaiveDate) -> Result<HijriYmd, ShaumError> {
+        if !(HIJRI_MIN_YEAR..=HIJRI_MAX_YEAR).contains(&date.year()) {
+            return Err(ShaumError::date_out_of_range(date));
+        }
+        table::lookup(date).ok_or_else(|| {
+            ShaumError::HijriConversionError(format!("{} is not covered by the Umm al-Qura table", date))
+        })
+    }
+
+    fn cache_id(&self) -> Option<u64> {
+        Some(cache_id_of("umm_al_qura"))
+    }
+}
+
+/// Converts Gregorian to Hijri with `calendar`, shifting by `adjustment` days
+/// (positive = Hijri ahead).
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_calendar::{to_hijri_with, UmmAlQura};
+///
+/// let h = to_hijri_with(NaiveDate::from_ymd_opt(2024, 3, 11).unwrap(), 0, &UmmAlQura).unwrap();
+/// assert_eq!((h.year, h.month, h.day), (1445, 9, 1));
+/// ```
+pub fn to_hijri_with(
+    date: NaiveDate,
+    adjustment: i64,
+    calendar: &dyn HijriCalendar,
+) -> Result<HijriYmd, ShaumError> {
+    calendar.to_hijri(adjusted_date(date, adjustment)?)
+}
+
+/// `date` shifted by `adjustment` days.
+///
+/// # Errors
+/// `AdjustmentOutOfRange` if the result is not a representable date.
+pub fn adjusted_date(date: NaiveDate, adjustment: i64) -> Result<NaiveDate, ShaumError> {
+    Duration::try_days(adjustment)
+        .and_then(|shift| date.checked_add_signed(shift))
+        .ok_or_else(|| ShaumError::adjustment_out_of_range(date, adjustment))
+}
+
+/// Calendar backends built into this crate: the table and arithmetic ones.
+///
+/// Astronomical backends, the Diyanet calendar among them, live in
+/// `shaum-astronomy` (`AstronomicalCalendar`), which builds on this crate, so
+/// they are not listed here and take no part in [`compare_methods`] or
+/// [`calendar_by_name`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CalendarMethod {
+    UmmAlQura,
+    Tabular,
+    Kemenag,
+    /// Fatimid (Misri) tabular calendar, see [`Tabular::fatimid`].
+    Fatimid,
+}
+
+impl CalendarMethod {
+    /// Every backend in this enum; see the type docs for what it leaves out.
+    pub const ALL: [CalendarMethod; 4] = [Self::UmmAlQura, Self::Tabular, Self::Kemenag, Self::Fatimid];
+
+    /// Stable snake_case name, as accepted by [`calendar_by_name`].
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::UmmAlQura => "umm_al_qura",
+            Self::Tabular => "tabular",
+            Self::Kemenag => "kemenag",
+            Self::Fatimid => "fatimid",
+        }
+    }
+
+    /// A new instance of the backend.
+    pub fn calendar(&self) -> Arc<dyn HijriCalendar> {
+        match self {
+            Self::UmmAlQura => Arc::new(UmmAlQura),
+            Self::Tabular => Arc::new(Tabular::new()),
+            Self::Kemenag => Arc::new(TableCalendar::kemenag()),
+            Self::Fatimid => Arc::new(Tabular::fatimid()),
+        }
+    }
+}
+
+impl FromStr for CalendarMethod {
+    type Err = ShaumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('-', "_").as_str() {
+            "umm_al_qura" | "ummalqura" => Ok(Self::UmmAlQura),
+            "tabular" => Ok(Self::Tabular),
+            "kemenag" => Ok(Self::Kemenag),
+            "fatimid" | "misri" | "bohra" => Ok(Self::Fatimid),
+            "diyanet" | "astronomical" => Err(ShaumError::invalid_config(format!(
+                "'{}' is an astronomical calendar; use shaum-astronomy's AstronomicalCalendar", s
+            ))),
+            _ => Err(ShaumError::invalid_config(format!("Unknown calendar method '{}'", s))),
+        }
+    }
+}
+
+/// Calendar backend by name (case-insensitive): `umm_al_qura`, `tabular`,
+/// `kemenag` or `fatimid` (also `misri`, `bohra`), for selecting a backend
+/// from configuration. Astronomical backends such as `diyanet` are not known
+/// here; see [`CalendarMethod`].
+pub fn calendar_by_name(name: &str) -> Option<Arc<dyn HijriCalendar>> {
+    name.parse::<CalendarMethod>().ok().map(|m| m.calendar())
+}
+
+/// Hijri date of `date` under each [`CalendarMethod`], skipping backends that
+/// cannot convert it (e.g. outside the Umm al-Qura range). Astronomical
+/// backends are not compared; convert with them directly.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_calendar::{compare_methods, CalendarMethod, HijriYmd};
+///
+/// // Indonesia started Ramadhan 1445 a day after Saudi Arabia.
+/// let dates = compare_methods(NaiveDate::from_ymd_opt(2024, 3, 11).unwrap());
+/// assert!(dates.contains(&(CalendarMethod::UmmAlQura, HijriYmd::new(1445, 9, 1))));
+/// assert!(dates.contains(&(CalendarMethod::Kemenag, HijriYmd::new(1445, 8, 30))));
+/// ```
+pub fn compare_methods(date: NaiveDate) -> Vec<(CalendarMethod, HijriYmd)> {
+    CalendarMethod::ALL
+        .iter()
+        .filter_map(|m| Some((*m, m.calendar().to_hijri(date).ok()?)))
+        .collect()
+}
+
+/// Whether `year` AH has 355 days in `calendar`.
+///
+/// ```rust
+/// use shaum_calendar::{is_hijri_leap_year, Tabular, UmmAlQura};
+///
+/// assert!(is_hijri_leap_year(1445, &Tabular::new()).unwrap());
+/// assert!(!is_hijri_leap_year(1446, &Tabular::new()).unwrap());
+/// assert!(is_hijri_leap_year(1445, &UmmAlQura).is_ok());
+/// ```
+pub fn is_hijri_leap_year(year: usize, calendar: &dyn HijriCalendar) -> Result<bool, ShaumError> {
+    Ok(calendar.days_in_year(year)? > 354)
+}
+
+/// Number of days in `year` AH in `calendar`.
+pub fn days_in_hijri_year(year: usize, calendar: &dyn HijriCalendar) -> Result<u32, ShaumError> {
+    calendar.days_in_year(year)
+}
+
+/// Returns the Hijri month name in Arabic script.
+pub fn get_hijri_month_name_arabic(month: usize) -> &'static str {
+    match month {
+        1 => "محرم", 2 => "صفر", 3 => "ربيع الأول", 4 => "ربيع الآخر",
+        5 => "جمادى الأولى", 6 => "جمادى الآخرة", 7 => "رجب", 8 => "شعبان",
+        9 => "رمضان", 10 => "شوال", 11 => "ذو القعدة", 12 => "ذو الحجة",
+        _ => "غير معروف",
+    }
+}
+
 /// Returns Hijri month name.
 pub fn get_hijri_month_name(month: usize) -> &'static str {
     match month {
@@ -76,6 +643,7 @@ pub use hijri_date;
 pub use hijri_date::HijriDate;
 
 #[cfg(test)]
+#[allow(deprecated)]
 mod tests {
     use super::*;
 
@@ -89,6 +657,27 @@ mod tests {
         assert_eq!(h1.year(), h2.year());
     }
     
+    #[test]
+    fn test_cache_keeps_alternating_dates() {
+        let a = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+        let b = NaiveDate::from_ymd_opt(2024, 4, 10).unwrap();
+        HIJRI_CACHE.with(|c| c.borrow_mut().clear());
+        for _ in 0..3 {
+            to_hijri(a, 0).unwrap();
+            to_hijri(b, 0).unwrap();
+        }
+        HIJRI_CACHE.with(|c| assert_eq!(c.borrow().len(), 2));
+
+        for i in 0..(HIJRI_CACHE_CAPACITY as i64 + 10) {
+            to_hijri(a + Duration::days(i), 1).unwrap();
+        }
+        HIJRI_CACHE.with(|c| {
+            let cache = c.borrow();
+            assert_eq!(cache.len(), HIJRI_CACHE_CAPACITY);
+            assert!(cache.iter().all(|(_, adj, ..)| *adj == 1));
+        });
+    }
+
     #[test]
     fn test_out_of_range() {
         let old_date = NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
@@ -97,4 +686,132 @@ mod tests {
         let future_date = NaiveDate::from_ymd_opt(2100, 1, 1).unwrap();
         assert!(to_hijri(future_date, 0).is_err());
     }
+
+    #[test]
+    fn test_to_hijri_with_matches_legacy() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+        for adj in -1..=1 {
+            let legacy = HijriYmd::from(to_hijri(date, adj).unwrap());
+            assert_eq!(to_hijri_with(date, adj, &UmmAlQura).unwrap(), legacy);
+        }
+        let old_date = NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
+        assert!(to_hijri_with(old_date, 0, &UmmAlQura).is_err());
+        for (date, adj) in [(NaiveDate::MAX, 1), (NaiveDate::MIN, -1), (date, i64::MAX)] {
+            assert!(matches!(
+                to_hijri_with(date, adj, &UmmAlQura),
+                Err(ShaumError::AdjustmentOutOfRange { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_shared_cache_across_threads() {
+        let cache = HijriCache::new();
+        let calendar: std::sync::Arc<dyn HijriCalendar> = std::sync::Arc::new(UmmAlQura);
+        let date = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+        let expected = UmmAlQura.to_hijri(date).unwrap();
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let cache = cache.clone();
+            let calendar = calendar.clone();
+            std::thread::spawn(move || cache.get_or_convert(date, &calendar).unwrap())
+        }).collect();
+        for h in handles {
+            assert_eq!(h.join().unwrap(), expected);
+        }
+
+        let m = cache.metrics();
+        assert_eq!(m.hits + m.misses, 4);
+        assert!(m.misses >= 1);
+        assert_eq!(m.entries, 1);
+    }
+
+    #[test]
+    fn test_shared_cache_keys_by_calendar_and_is_bounded() {
+        use std::sync::Arc;
+
+        let cache = HijriCache::with_capacity(32);
+        let umm: Arc<dyn HijriCalendar> = Arc::new(UmmAlQura);
+        let tabular: Arc<dyn HijriCalendar> = Arc::new(Tabular::default());
+        let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+
+        assert_eq!(cache.get_or_convert(date, &umm).unwrap(), UmmAlQura.to_hijri(date).unwrap());
+        assert_eq!(cache.get_or_convert(date, &tabular).unwrap(), Tabular::default().to_hijri(date).unwrap());
+        assert_eq!(cache.metrics().misses, 2);
+
+        for i in 0..200 {
+            cache.get_or_convert(date + Duration::days(i), &umm).unwrap();
+        }
+        assert!(cache.metrics().entries <= 32);
+        // The newest dates survive eviction.
+        let hits = cache.metrics().hits;
+        cache.get_or_convert(date + Duration::days(199), &umm).unwrap();
+        assert_eq!(cache.metrics().hits, hits + 1);
+
+        // Another instance of the same backend shares the entries.
+        let other: Arc<dyn HijriCalendar> = Arc::new(UmmAlQura);
+        cache.get_or_convert(date + Duration::days(199), &other).unwrap();
+        assert_eq!(cache.metrics().hits, hits + 2);
+
+        // Differently configured tabular calendars do not.
+        let fatimid: Arc<dyn HijriCalendar> = Arc::new(Tabular::fatimid());
+        let misses = cache.metrics().misses;
+        assert_eq!(cache.get_or_convert(date, &fatimid).unwrap(), Tabular::fatimid().to_hijri(date).unwrap());
+        assert_eq!(cache.metrics().misses, misses + 1);
+    }
+
+    #[test]
+    fn test_shared_cache_skips_calendars_without_id() {
+        #[derive(Debug)]
+        struct Anonymous;
+        impl HijriCalendar for Anonymous {
+            fn to_hijri(&self, date: NaiveDate) -> Result<HijriYmd, ShaumError> { UmmAlQura.to_hijri(date) }
+        }
+
+        let cache = HijriCache::new();
+        let calendar: std::sync::Arc<dyn HijriCalendar> = std::sync::Arc::new(Anonymous);
+        let date = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+        for _ in 0..2 {
+            assert_eq!(cache.get_or_convert(date, &calendar).unwrap(), UmmAlQura.to_hijri(date).unwrap());
+        }
+        assert_eq!((cache.metrics().misses, cache.metrics().entries), (2, 0));
+    }
+
+    #[test]
+    fn test_days_in_year_matches_day_count() {
+        for year in [1400, 1445, 1446, 1490] {
+            let start = UmmAlQura.year_start(year).unwrap();
+            assert_eq!(UmmAlQura.to_hijri(start).unwrap(), HijriYmd::new(year, 1, 1));
+            assert_eq!(UmmAlQura.to_hijri(start - Duration::days(1)).unwrap().year, year - 1);
+
+            let days = days_in_hijri_year(year, &UmmAlQura).unwrap();
+            assert!(days == 354 || days == 355);
+            assert_eq!(is_hijri_leap_year(year, &UmmAlQura).unwrap(), days == 355);
+        }
+        assert!(UmmAlQura.year_start(1600).is_err());
+    }
+
+    #[test]
+    fn test_format_patterns() {
+        let date = HijriYmd::new(1446, 12, 10);
+        assert_eq!(date.format("%Y-%m-%d"), "1446-12-10");
+        assert_eq!(date.format("%B"), "Dhu al-Hijjah");
+        assert_eq!(date.format("100%% %q %"), "100% %q %");
+        assert_eq!(date.format_arabic("%d %B"), "١٠ ذو الحجة");
+        assert_eq!(HijriYmd::new(1446, 1, 5).format_arabic("%m/%d"), "٠١/٠٥");
+    }
+
+    #[test]
+    fn test_hijri_weekday() {
+        // 1 Shawwal 1445 = Wednesday 10 April 2024.
+        let eid = HijriYmd::new(1445, 10, 1);
+        let day = eid.weekday(&UmmAlQura).unwrap();
+        assert_eq!((day.weekday, day.arabic), (Weekday::Wed, "الأربعاء"));
+        let tabular = Tabular::new();
+        let start = HijriYmd::new(1400, 1, 1);
+        assert_eq!(start.weekday(&tabular).unwrap().weekday, tabular.to_gregorian(start).unwrap().weekday());
+        assert_eq!(HijriWeekday::from(Weekday::Fri).to_string(), "Yawm al-Jumu'ah");
+        // Ramadhan 1444 had 29 days in Umm al-Qura.
+        assert!(HijriYmd::new(1444, 9, 30).weekday(&UmmAlQura).is_err());
+    }
 }
@@ -0,0 +1,93 @@
+//! Precomputed Umm al-Qura month table.
+//!
+//! Built lazily on first use from the `hijri_date` tables: one entry per Hijri
+//! month holding its first day, so a conversion is an index estimate, at most
+//! a couple of adjustments and a subtraction.
+
+use std::sync::OnceLock;
+
+use chrono::{Datelike, Duration, NaiveDate};
+use hijri_date::HijriDate;
+
+use crate::{HijriYmd, HIJRI_MAX_YEAR, HIJRI_MIN_YEAR};
+
+/// Mean length of a lunar month, for the initial index estimate.
+const MEAN_MONTH_DAYS: f64 = 29.530_588_853;
+
+struct MonthTable {
+    /// Hijri (year, month) of the first entry.
+    first: (usize, usize),
+    /// First day of each month (days from CE), plus the day after the last month.
+    starts: Vec<i32>,
+}
+
+impl MonthTable {
+    fn build() -> Option<Self> {
+        let first_day = NaiveDate::from_ymd_opt(HIJRI_MIN_YEAR, 1, 1)?;
+        let last_day = NaiveDate::from_ymd_opt(HIJRI_MAX_YEAR, 12, 31)?;
+        let h = from_gr(first_day)?;
+        let first = (h.year(), h.month());
+        let mut start = first_day - Duration::days(h.day() as i64 - 1);
+        let mut starts = vec![start.num_days_from_ce()];
+        let mut len = h.month_len();
+        loop {
+            start += Duration::days(len as i64);
+            starts.push(start.num_days_from_ce());
+            if start > last_day {
+                break;
+            }
+            len = from_gr(start)?.month_len();
+        }
+        Some(Self { first, starts })
+    }
+
+    fn lookup(&self, date: NaiveDate) -> Option<HijriYmd> {
+        let day = date.num_days_from_ce();
+        let last = self.starts.len() - 2;
+        let estimate = ((day - self.starts[0]) as f64 / MEAN_MONTH_DAYS) as isize;
+        let mut i = estimate.clamp(0, last as isize) as usize;
+        while i > 0 && self.starts[i] > day {
+            i -= 1;
+        }
+        while i < last && self.starts[i + 1] <= day {
+            i += 1;
+        }
+        if day < self.starts[i] || day >= self.starts[i + 1] {
+            return None;
+        }
+        let months = (self.first.0 - 1) * 12 + (self.first.1 - 1) + i;
+        Some(HijriYmd::new(months / 12 + 1, months % 12 + 1, (day - self.starts[i]) as usize + 1))
+    }
+}
+
+fn from_gr(date: NaiveDate) -> Option<HijriDate> {
+    HijriDate::from_gr(date.year() as usize, date.month() as usize, date.day() as usize).ok()
+}
+
+/// Umm al-Qura date of `date` from the precomputed table, or `None` outside
+/// [`HIJRI_MIN_YEAR`]..=[`HIJRI_MAX_YEAR`].
+pub(crate) fn lookup(date: NaiveDate) -> Option<HijriYmd> {
+    static TABLE: OnceLock<Option<MonthTable>> = OnceLock::new();
+    if !(HIJRI_MIN_YEAR..=HIJRI_MAX_YEAR).contains(&date.year()) {
+        return None;
+    }
+    TABLE.get_or_init(MonthTable::build).as_ref()?.lookup(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_hijri_date_crate() {
+        let mut date = NaiveDate::from_ymd_opt(HIJRI_MIN_YEAR, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(HIJRI_MAX_YEAR, 12, 31).unwrap();
+        while date <= end {
+            let expected = HijriYmd::from(from_gr(date).unwrap());
+            assert_eq!(lookup(date), Some(expected), "{}", date);
+            date += Duration::days(5);
+        }
+        assert_eq!(lookup(end), Some(HijriYmd::from(from_gr(end).unwrap())));
+        assert_eq!(lookup(end + Duration::days(1)), None);
+    }
+}
@@ -0,0 +1,234 @@
+//! Calendar backed by a user-supplied table of month starts.
+//!
+//! For communities that follow their own announcements (a mosque's printed
+//! calendar for the year, a national isbat): the table is the source of truth
+//! for the months it lists and every other month comes from a fallback
+//! backend, [`UmmAlQura`] by default.
+//!
+//! Tables load from CSV (`year,month,start` per line, month as a number or a
+//! name, `#` comments and a header line allowed) or from a JSON array of
+//! `{"year": .., "month": .., "start": "YYYY-MM-DD"}` objects.
+
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::{cache_id_of, hijri_month_from_name, month_start_in, place_in_month, HijriCalendar, HijriYmd, ShaumError, UmmAlQura};
+
+/// Largest distance, in days, between a table start and the fallback's start.
+const MAX_DRIFT_DAYS: i64 = 2;
+
+/// One row of a month-start table.
+#[derive(Debug, Deserialize)]
+struct MonthStart {
+    year: usize,
+    month: usize,
+    start: NaiveDate,
+}
+
+/// Announced month starts on top of a fallback calendar.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_calendar::{HijriCalendar, HijriYmd, TableCalendar};
+///
+/// let csv = "year,month,start\n1445,9,2024-03-12\n1445,10,2024-04-10\n";
+/// let calendar = TableCalendar::from_csv(csv.as_bytes()).unwrap();
+/// let date = NaiveDate::from_ymd_opt(2024, 3, 12).unwrap();
+/// assert_eq!(calendar.to_hijri(date).unwrap(), HijriYmd::new(1445, 9, 1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TableCalendar {
+    starts: BTreeMap<(usize, usize), NaiveDate>,
+    fallback: Arc<dyn HijriCalendar>,
+    /// [`HijriCalendar::cache_id`], refreshed whenever the table changes.
+    id: Option<u64>,
+}
+
+impl Default for TableCalendar {
+    fn default() -> Self { Self::new(UmmAlQura) }
+}
+
+impl TableCalendar {
+    /// Empty table over `fallback`.
+    pub fn new(fallback: impl HijriCalendar + 'static) -> Self {
+        let mut calendar = Self { starts: BTreeMap::new(), fallback: Arc::new(fallback), id: None };
+        calendar.refresh_id();
+        calendar
+    }
+
+    /// Loads a CSV table over [`UmmAlQura`].
+    pub fn from_csv(reader: impl Read) -> Result<Self, ShaumError> {
+        Self::default().load_csv(reader)
+    }
+
+    /// Loads a JSON table over [`UmmAlQura`].
+    pub fn from_json(reader: impl Read) -> Result<Self, ShaumError> {
+        Self::default().load_json(reader)
+    }
+
+    /// Adds the rows of a CSV table.
+    ///
+    /// # Errors
+    /// `InvalidConfiguration` if the reader fails; `ValidationError` naming the
+    /// line for a malformed or inconsistent row.
+    pub fn load_csv(mut self, mut reader: impl Read) -> Result<Self, ShaumError> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)
+            .map_err(|e| ShaumError::invalid_config(format!("Cannot read calendar table: {}", e)))?;
+
+        let mut first_row = true;
+        for (index, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            // Optional header line.
+            if std::mem::take(&mut first_row) && line.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                continue;
+            }
+            let invalid = |why: &str| ShaumError::ValidationError(format!("Line {}: {} in '{}'", index + 1, why, line));
+            let fields: Vec<&str> = line.split([',', ';']).map(str::trim).collect();
+            let [year, month, start] = fields[..] else {
+                return Err(invalid("expected year,month,start"));
+            };
+            let year = year.parse().map_err(|_| invalid("invalid year"))?;
+            let month = month.parse().ok()
+                .or_else(|| hijri_month_from_name(month))
+                .ok_or_else(|| invalid("invalid month"))?;
+            let start = NaiveDate::parse_from_str(start, "%Y-%m-%d").map_err(|_| invalid("invalid date"))?;
+            self = self.with_month_start(year, month, start)
+                .map_err(|e| invalid(&e.to_string()))?;
+        }
+        Ok(self)
+    }
+
+    /// Adds the entries of a JSON table.
+    ///
+    /// # Errors
+    /// `InvalidConfiguration` for unreadable or malformed JSON; `ValidationError`
+    /// for an inconsistent entry.
+    pub fn load_json(mut self, reader: impl Read) -> Result<Self, ShaumError> {
+        let rows: Vec<MonthStart> = serde_json::from_reader(reader)
+            .map_err(|e| ShaumError::invalid_config(format!("Invalid calendar table: {}", e)))?;
+        for row in rows {
+            self = self.with_month_start(row.year, row.month, row.start)?;
+        }
+        Ok(self)
+    }
+
+    /// Records the first day of `month` `year` AH.
+    ///
+    /// # Errors
+    /// `ValidationError` if `month` is not 1-12, `date` is more than two days
+    /// from the fallback's start, or an adjacent month in the table would not
+    /// have 29 or 30 days.
+    pub fn with_month_start(mut self, year: usize, month: usize, date: NaiveDate) -> Result<Self, ShaumError> {
+        if year == 0 || !(1..=12).contains(&month) {
+            return Err(ShaumError::ValidationError(format!("Invalid Hijri month: {}-{}", year, month)));
+        }
+        let reference = month_start_in(&*self.fallback, year, month)?;
+        if (date - reference).num_days().abs() > MAX_DRIFT_DAYS {
+            return Err(ShaumError::ValidationError(format!(
+                "{} is too far from the fallback start of {}-{} ({})", date, year, month, reference
+            )));
+        }
+        let prev = if month == 1 { (year - 1, 12) } else { (year, month - 1) };
+        let next = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+        let lengths = [
+            self.starts.get(&prev).map(|p| (date - *p).num_days()),
+            self.starts.get(&next).map(|n| (*n - date).num_days()),
+        ];
+        if lengths.into_iter().flatten().any(|len| !(29..=30).contains(&len)) {
+            return Err(ShaumError::ValidationError(format!(
+                "{} for {}-{} gives an adjacent month other than 29 or 30 days", date, year, month
+            )));
+        }
+        self.starts.insert((year, month), date);
+        self.refresh_id();
+        Ok(self)
+    }
+
+    /// First day of `month` `year` AH, if listed in the table.
+    pub fn table_month_start(&self, year: usize, month: usize) -> Option<NaiveDate> {
+        self.starts.get(&(year, month)).copied()
+    }
+
+    /// Number of months listed in the table.
+    pub fn len(&self) -> usize { self.starts.len() }
+
+    /// Whether the table lists no months (every date uses the fallback).
+    pub fn is_empty(&self) -> bool { self.starts.is_empty() }
+
+    /// Identifies the table together with its fallback; none without a fallback id.
+    fn refresh_id(&mut self) {
+        self.id = self.fallback.cache_id().map(|f| cache_id_of(("table", f, &self.starts)));
+    }
+}
+
+impl HijriCalendar for TableCalendar {
+    fn to_hijri(&self, date: NaiveDate) -> Result<HijriYmd, ShaumError> {
+        let base = self.fallback.to_hijri(date)?;
+        if self.starts.is_empty() {
+            return Ok(base);
+        }
+        place_in_month(date, base, |year, month| match self.table_month_start(year, month) {
+            Some(start) => Ok(start),
+            None => month_start_in(&*self.fallback, year, month),
+        })
+    }
+
+    fn cache_id(&self) -> Option<u64> {
+        self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Tabular;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate { NaiveDate::from_ymd_opt(y, m, d).unwrap() }
+
+    #[test]
+    fn test_csv_overrides_and_falls_back() {
+        let csv = "# Masjid calendar 1445\nyear,month,start\n1445,Ramadhan,2024-03-12\n1445;10;2024-04-10\n";
+        let calendar = TableCalendar::from_csv(csv.as_bytes()).unwrap();
+        assert_eq!(calendar.len(), 2);
+        // Sha'ban gets 30 days, Ramadhan 29.
+        assert_eq!(calendar.to_hijri(date(2024, 3, 11)).unwrap(), HijriYmd::new(1445, 8, 30));
+        assert_eq!(calendar.to_hijri(date(2024, 4, 9)).unwrap(), HijriYmd::new(1445, 9, 29));
+        assert_eq!(calendar.to_hijri(date(2024, 4, 10)).unwrap(), HijriYmd::new(1445, 10, 1));
+        // Outside the table.
+        let d = date(2020, 1, 1);
+        assert_eq!(calendar.to_hijri(d).unwrap(), UmmAlQura.to_hijri(d).unwrap());
+    }
+
+    #[test]
+    fn test_json_and_custom_fallback() {
+        let json = r#"[{"year": 1445, "month": 9, "start": "2024-03-11"}]"#;
+        let calendar = TableCalendar::new(Tabular::new()).load_json(json.as_bytes()).unwrap();
+        assert_eq!(calendar.to_hijri(date(2024, 3, 11)).unwrap(), HijriYmd::new(1445, 9, 1));
+        let d = date(2020, 1, 1);
+        assert_eq!(calendar.to_hijri(d).unwrap(), Tabular::new().to_hijri(d).unwrap());
+
+        // Equal tables share cache entries; the fallback and every row count.
+        let again = TableCalendar::new(Tabular::new()).load_json(json.as_bytes()).unwrap();
+        assert_eq!(calendar.cache_id(), again.cache_id());
+        assert_ne!(calendar.cache_id(), TableCalendar::new(Tabular::new()).cache_id());
+        assert_ne!(TableCalendar::default().cache_id(), TableCalendar::new(Tabular::new()).cache_id());
+    }
+
+    #[test]
+    fn test_rejects_inconsistent_rows() {
+        assert!(TableCalendar::from_csv("1445,9,2024-03-20\n".as_bytes()).is_err());
+        assert!(TableCalendar::from_csv("1445,13,2024-03-12\n".as_bytes()).is_err());
+        assert!(TableCalendar::from_csv("1445,9\n".as_bytes()).is_err());
+        // 28-day Ramadhan.
+        assert!(TableCalendar::from_csv("1445,9,2024-03-12\n1445,10,2024-04-09\n".as_bytes()).is_err());
+        assert!(TableCalendar::from_json("{}".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+//! Arithmetic (tabular) Islamic calendar.
+//!
+//! Months alternate 30/29 days and Dhu al-Hijjah gains a day in the 11 leap
+//! years of each 30-year cycle. Deterministic and valid for any date from
+//! 1 Muharram 1 AH onwards; it does not follow any sighting authority.
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::{cache_id_of, HijriCalendar, HijriYmd, ShaumError};
+
+/// Days in a 30-year cycle (19 × 354 + 11 × 355).
+const CYCLE_DAYS: i64 = 10_631;
+
+/// Julian Day Number of 1 January 1 CE (proleptic Gregorian), minus one.
+const JDN_CE_OFFSET: i64 = 1_721_425;
+
+/// Tabular calendar with a configurable leap-year pattern and epoch.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_calendar::{to_hijri_with, HijriYmd, Tabular};
+///
+/// let h = to_hijri_with(NaiveDate::from_ymd_opt(1979, 11, 21).unwrap(), 0, &Tabular::new()).unwrap();
+/// assert_eq!(h, HijriYmd::new(1400, 1, 1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tabular {
+    leap_years: [u8; 11],
+    epoch_jdn: i64,
+}
+
+impl Default for Tabular {
+    fn default() -> Self { Self::new() }
+}
+
+impl Tabular {
+    /// The most common pattern (leap years 2, 5, 7, 10, 13, 16, 18, 21, 24, 26, 29)
+    /// with the civil epoch, Friday 16 July 622 (Julian).
+    pub const fn new() -> Self {
+        Self {
+            leap_years: [2, 5, 7, 10, 13, 16, 18, 21, 24, 26, 29],
+            epoch_jdn: 1_948_440,
+        }
+    }
+
+    /// Fatimid (Misri) calendar used by the Dawoodi Bohra community: leap years
+    /// 2, 5, 8, 10, 13, 16, 19, 21, 24, 27, 29 with the astronomical epoch.
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use shaum_calendar::{HijriYmd, Tabular};
+    ///
+    /// // Bohra Ramadhan 1445 began on Sunday 10 March 2024.
+    /// let start = Tabular::fatimid().to_gregorian(HijriYmd::new(1445, 9, 1)).unwrap();
+    /// assert_eq!(start, NaiveDate::from_ymd_opt(2024, 3, 10).unwrap());
+    /// ```
+    pub const fn fatimid() -> Self {
+        Self { leap_years: [2, 5, 8, 10, 13, 16, 19, 21, 24, 27, 29], ..Self::new() }.astronomical()
+    }
+
+    /// Uses a different set of leap years within the 30-year cycle.
+    ///
+    /// # Errors
+    /// `InvalidConfiguration` unless the 11 years are distinct and each in
+    /// 1..=30, as the fixed 10,631-day cycle assumes.
+    ///
+    /// ```rust
+    /// use shaum_calendar::Tabular;
+    ///
+    /// assert!(Tabular::new().with_leap_years([2, 5, 8, 10, 13, 16, 19, 21, 24, 27, 29]).is_ok());
+    /// assert!(Tabular::new().with_leap_years([2, 2, 8, 10, 13, 16, 19, 21, 24, 27, 29]).is_err());
+    /// assert!(Tabular::new().with_leap_years([0, 5, 8, 10, 13, 16, 19, 21, 24, 27, 31]).is_err());
+    /// ```
+    pub fn with_leap_years(mut self, leap_years: [u8; 11]) -> Result<Self, ShaumError> {
+        let in_cycle = leap_years.iter().all(|y| (1..=30).contains(y));
+        let distinct = leap_years.iter().enumerate().all(|(i, y)| !leap_years[..i].contains(y));
+        if !in_cycle || !distinct {
+            return Err(ShaumError::invalid_config(format!(
+                "Tabular leap years must be 11 distinct years in 1..=30, got {:?}", leap_years
+            )));
+        }
+        self.leap_years = leap_years;
+        Ok(self)
+    }
+
+    /// Uses the astronomical epoch, Thursday 15 July 622 (Julian), one day earlier.
+    pub const fn astronomical(mut self) -> Self {
+        self.epoch_jdn = 1_948_439;
+        self
+    }
+
+    /// Whether `year` AH has 355 days. Year 0 does not exist and is not leap.
+    pub fn is_leap_year(&self, year: usize) -> bool {
+        let Some(before) = year.checked_sub(1) else { return false };
+        self.leap_years.contains(&((before % 30 + 1) as u8))
+    }
+
+    /// Days in `month` of `year` AH.
+    pub fn days_in_month(&self, year: usize, month: usize) -> u32 {
+        match month {
+            12 if self.is_leap_year(year) => 30,
+            m if m % 2 == 1 => 30,
+            _ => 29,
+        }
+    }
+
+    /// Gregorian date of a Hijri date in this calendar.
+    pub fn to_gregorian(&self, date: HijriYmd) -> Result<NaiveDate, ShaumError> {
+        if date.year == 0 || !(1..=12).contains(&date.month)
+            || date.day == 0 || date.day as u32 > self.days_in_month(date.year, date.month)
+        {
+            return Err(ShaumError::ValidationError(format!(
+                "Invalid Hijri date {}-{}-{}", date.year, date.month, date.day
+            )));
+        }
+        let years = i64::try_from(date.year - 1).map_err(|_| self.out_of_range())?;
+        let mut days = (1..=years % 30).map(|y| self.year_length(y as usize)).sum::<i64>();
+        days += (1..date.month).map(|m| i64::from(self.days_in_month(date.year, m))).sum::<i64>();
+        days += date.day as i64 - 1;
+
+        (years / 30).checked_mul(CYCLE_DAYS)
+            .and_then(|cycles| cycles.checked_add(days + self.epoch_jdn - JDN_CE_OFFSET))
+            .and_then(|days| i32::try_from(days).ok())
+            .and_then(NaiveDate::from_num_days_from_ce_opt)
+            .ok_or_else(|| self.out_of_range())
+    }
+
+    /// `DateOutOfRange` for a Hijri date past the last date chrono represents.
+    fn out_of_range(&self) -> ShaumError {
+        let epoch = i32::try_from(self.epoch_jdn - JDN_CE_OFFSET).ok().and_then(NaiveDate::from_num_days_from_ce_opt);
+        ShaumError::DateOutOfRange { date: NaiveDate::MAX, min: epoch.unwrap_or(NaiveDate::MIN), max: NaiveDate::MAX }
+    }
+
+    fn year_length(&self, year: usize) -> i64 {
+        if self.is_leap_year(year) { 355 } else { 354 }
+    }
+}
+
+impl HijriCalendar for Tabular {
+    fn year_start(&self, year: usize) -> Result<NaiveDate, ShaumError> {
+        self.to_gregorian(HijriYmd::new(year, 1, 1))
+    }
+
+    fn days_in_year(&self, year: usize) -> Result<u32, ShaumError> {
+        if year == 0 {
+            return Err(ShaumError::ValidationError("Hijri year must be at least 1".to_string()));
+        }
+        Ok(self.year_length(year) as u32)
+    }
+
+    fn to_hijri(&self, date: NaiveDate) -> Result<HijriYmd, ShaumError> {
+        let mut days = i64::from(date.num_days_from_ce()) + JDN_CE_OFFSET - self.epoch_jdn;
+        if days < 0 {
+            return Err(ShaumError::HijriConversionError(format!(
+                "{} is before the Hijri epoch",
+                date
+            )));
+        }
+
+        let mut year = (days / CYCLE_DAYS * 30) as usize + 1;
+        days %= CYCLE_DAYS;
+        while days >= self.year_length(year) {
+            days -= self.year_length(year);
+            year += 1;
+        }
+        let mut month = 1;
+        while days >= i64::from(self.days_in_month(year, month)) {
+            days -= i64::from(self.days_in_month(year, month));
+            month += 1;
+        }
+        Ok(HijriYmd::new(year, month, days as usize + 1))
+    }
+
+    fn cache_id(&self) -> Option<u64> {
+        Some(cache_id_of(("tabular", self.leap_years, self.epoch_jdn)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_round_trip() {
+        let tabular = Tabular::new();
+        let mut date = NaiveDate::from_ymd_opt(622, 7, 19).unwrap();
+        assert_eq!(tabular.to_hijri(date).unwrap(), HijriYmd::new(1, 1, 1));
+        while date.year() < 2200 {
+            let h = tabular.to_hijri(date).unwrap();
+            assert_eq!(tabular.to_gregorian(h).unwrap(), date);
+            date += Duration::days(97);
+        }
+        assert!(tabular.to_hijri(NaiveDate::from_ymd_opt(622, 7, 18).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_cycle_length() {
+        let tabular = Tabular::new();
+        let total: i64 = (1..=30).map(|y| tabular.year_length(y)).sum();
+        assert_eq!(total, CYCLE_DAYS);
+        assert_eq!(tabular.days_in_month(1445, 12), 30);
+        assert_eq!(tabular.days_in_month(1446, 12), 29);
+        // Year 0 has no cycle position.
+        assert!(!tabular.is_leap_year(0));
+        assert_eq!(tabular.days_in_month(0, 12), 29);
+        assert!(tabular.days_in_year(0).is_err());
+    }
+
+    #[test]
+    fn test_far_years_are_out_of_range() {
+        let tabular = Tabular::new();
+        for year in [1_000_000_000, usize::MAX / 30, usize::MAX / 2, usize::MAX] {
+            let err = tabular.to_gregorian(HijriYmd::new(year, 1, 1)).unwrap_err();
+            assert!(matches!(err, ShaumError::DateOutOfRange { .. }), "{year}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_astronomical_epoch() {
+        let civil = Tabular::new().to_gregorian(HijriYmd::new(1445, 9, 1)).unwrap();
+        let astro = Tabular::new().astronomical().to_gregorian(HijriYmd::new(1445, 9, 1)).unwrap();
+        assert_eq!(civil - astro, Duration::days(1));
+    }
+}
@@ -0,0 +1,18 @@
+//! Embeds the calendar table named by `SHAUM_CALENDAR_TABLE`, if set (see
+//! `cargo xtask dist-cli`); other builds get an empty placeholder.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=SHAUM_CALENDAR_TABLE");
+    let out = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is set by cargo")).join("calendar.bin");
+    match env::var("SHAUM_CALENDAR_TABLE") {
+        Ok(path) if !path.is_empty() => {
+            println!("cargo:rerun-if-changed={}", path);
+            fs::copy(&path, &out).unwrap_or_else(|e| panic!("Cannot embed calendar table {}: {}", path, e));
+        }
+        _ => fs::write(&out, []).expect("write empty calendar table"),
+    }
+}
@@ -0,0 +1,162 @@
+//! Locale bundles embedded in the binary.
+//!
+//! Each bundle is a JSON file under `locales/` with every name the engine can
+//! produce plus the interface labels, so a translation is complete or fails
+//! to load. [`Bundle`] implements [`Localizer`], and names a bundle lacks
+//! (custom fasting types) fall back to the engine's own.
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Context, Result};
+use chrono::Weekday;
+use serde::Deserialize;
+use shaum_core::rules::i18n::{EnglishLocalizer, Localizer};
+use shaum_core::{FastingAnalysis, FastingStatus, FastingType};
+
+/// Language tag and JSON source of every built-in bundle, English first.
+pub const BUNDLES: [(&str, &str); 2] = [
+    ("en", include_str!("../locales/en.json")),
+    ("id", include_str!("../locales/id.json")),
+];
+
+/// Interface labels.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Labels {
+    pub status: String,
+    pub reasons: String,
+    pub traces: String,
+    pub prayer_times: String,
+    pub no_location: String,
+    pub imsak: String,
+    pub fajr: String,
+    pub maghrib: String,
+    pub isha: String,
+    /// Key bindings, for the TUI footer.
+    #[cfg_attr(not(feature = "tui"), allow(dead_code))]
+    pub help: String,
+}
+
+/// One translation.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Bundle {
+    pub language: String,
+    /// Name of the language in itself, for menus.
+    pub name: String,
+    hijri_months: [String; 12],
+    gregorian_months: [String; 12],
+    /// Monday first.
+    weekdays: [String; 7],
+    /// By `FastingStatus` variant name.
+    statuses: HashMap<String, String>,
+    /// By `FastingType` name.
+    types: HashMap<String, String>,
+    /// `{day}`, `{month}` and `{year}` are replaced by the Hijri date.
+    description: String,
+    pub ui: Labels,
+}
+
+impl Bundle {
+    /// Parses a bundle, checking that it names every status.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let bundle: Self = serde_json::from_str(json).context("Invalid locale bundle")?;
+        for status in STATUSES {
+            if !bundle.statuses.contains_key(&format!("{:?}", status)) {
+                bail!("Locale bundle '{}' has no name for {:?}", bundle.language, status);
+            }
+        }
+        Ok(bundle)
+    }
+
+    /// Built-in bundle for a BCP 47 tag such as `id` or `en-GB`, matched on
+    /// the primary language.
+    pub fn builtin(locale: &str) -> Result<Self> {
+        let language = locale.split(['-', '_']).next().unwrap_or_default();
+        let Some((_, json)) = BUNDLES.iter().find(|(tag, _)| tag.eq_ignore_ascii_case(language)) else {
+            let known: Vec<&str> = BUNDLES.iter().map(|(tag, _)| *tag).collect();
+            bail!("No locale '{}' (available: {})", locale, known.join(", "));
+        };
+        Self::from_json(json)
+    }
+
+    /// Every built-in bundle, in [`BUNDLES`] order.
+    pub fn all() -> Result<Vec<Self>> {
+        BUNDLES.iter().map(|(_, json)| Self::from_json(json)).collect()
+    }
+}
+
+const STATUSES: [FastingStatus; 6] = [
+    FastingStatus::Mubah, FastingStatus::Makruh, FastingStatus::Sunnah,
+    FastingStatus::SunnahMuakkadah, FastingStatus::Wajib, FastingStatus::Haram,
+];
+
+impl Localizer for Bundle {
+    fn month_name(&self, month: usize) -> String {
+        match self.hijri_months.get(month.wrapping_sub(1)) {
+            Some(name) => name.clone(),
+            None => EnglishLocalizer.month_name(month),
+        }
+    }
+
+    fn status_name(&self, status: FastingStatus) -> String {
+        self.statuses[&format!("{:?}", status)].clone()
+    }
+
+    fn type_name(&self, f_type: FastingType) -> String {
+        self.types.get(f_type.0.as_ref()).cloned().unwrap_or_else(|| f_type.to_string())
+    }
+
+    fn format_description(&self, analysis: &FastingAnalysis) -> String {
+        self.description
+            .replace("{day}", &analysis.hijri_day.to_string())
+            .replace("{month}", &self.month_name(analysis.hijri_month))
+            .replace("{year}", &analysis.hijri_year.to_string())
+    }
+
+    fn gregorian_month_name(&self, month: u32) -> String {
+        match self.gregorian_months.get(month.wrapping_sub(1) as usize) {
+            Some(name) => name.clone(),
+            None => EnglishLocalizer.gregorian_month_name(month),
+        }
+    }
+
+    fn weekday_name(&self, weekday: Weekday) -> String {
+        self.weekdays[weekday.num_days_from_monday() as usize].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use shaum_core::{check, RuleContext};
+
+    #[test]
+    fn test_bundles_are_complete() {
+        for bundle in Bundle::all().unwrap() {
+            for t in FastingType::STANDARD {
+                assert!(bundle.types.contains_key(t.0.as_ref()), "{} lacks {}", bundle.language, t);
+            }
+        }
+    }
+
+    #[test]
+    fn test_english_matches_engine() {
+        let en = Bundle::builtin("en-GB").unwrap();
+        let analysis = check(NaiveDate::from_ymd_opt(2024, 3, 15).unwrap(), &RuleContext::default()).unwrap();
+        assert_eq!(en.format_description(&analysis), EnglishLocalizer.format_description(&analysis));
+        for month in 1..=12 {
+            assert_eq!(en.month_name(month), EnglishLocalizer.month_name(month));
+        }
+        assert_eq!(en.status_name(FastingStatus::Wajib), FastingStatus::Wajib.to_string());
+    }
+
+    #[test]
+    fn test_lookup_and_fallbacks() {
+        let id = Bundle::builtin("id_ID").unwrap();
+        assert_eq!(id.month_name(9), "Ramadan");
+        assert_eq!(id.weekday_name(Weekday::Fri), "Jumat");
+        assert_eq!(id.type_name(FastingType::custom("Birthday")), "Birthday");
+        assert!(Bundle::builtin("xx").is_err());
+        assert!(Bundle::from_json(r#"{"language": "en"}"#).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+//! `shaum` - fasting status and Hijri calendar from the terminal.
+//!
+//! # Usage
+//! ```sh
+//! shaum check 2024-03-15                 # Status, reasons and traces of a day
+//! shaum check "9 Dhul-Hijjah 1447"       # Hijri input, in any bundled language
+//! shaum tui 2024-03 --locale id          # Month calendar (feature `tui`)
+//! shaum precompute 2024..2030 cal.bin    # Day table to embed (see `table`)
+//! shaum serve --precompute 2024..2030    # JSON per stdin line (feature `serve`)
+//! ```
+
+mod locale;
+mod report;
+#[cfg(feature = "serve")]
+mod serve;
+mod table;
+#[cfg(feature = "tui")]
+mod tui;
+
+use std::env;
+use std::fs;
+
+use anyhow::{bail, Context, Result};
+use chrono::{FixedOffset, NaiveDate};
+use shaum_core::rules::i18n::parse_hijri;
+use shaum_core::{CalendarMethod, GeoCoordinate};
+
+use locale::Bundle;
+use report::{DayReport, Options};
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(e) = run(&args) {
+        eprintln!("error: {:#}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(args: &[String]) -> Result<()> {
+    let Some(command) = args.first() else {
+        print_usage();
+        return Ok(());
+    };
+    if command == "serve" {
+        return serve(&args[1..]);
+    }
+    let (positional, options) = parse_options(&args[1..])?;
+
+    match command.as_str() {
+        "check" => {
+            let report = match positional.first() {
+                Some(input) => DayReport::new(parse_date(input, &options)?, &options)?,
+                None => {
+                    let (date, analysis) = options.today()?;
+                    DayReport::with_analysis(date, &analysis, &options)?
+                }
+            };
+            println!("{}", report.to_text(&options));
+        }
+        "tui" => tui(positional.first().map(String::as_str), options)?,
+        "precompute" => {
+            let [years, output] = &positional[..] else {
+                bail!("Usage: shaum precompute <FROM..TO> <FILE>");
+            };
+            let years = table::parse_years(years)?;
+            let bytes = table::precompute_calendar(years, options.calendar)?;
+            let written = table::DayTable::parse(bytes.as_slice())?;
+            let range = written.range();
+            println!("{} {} to {}: {} bytes in {}", written.label(), range.start(), range.end(), bytes.len(), output);
+            fs::write(output, &bytes).with_context(|| format!("Cannot write {}", output))?;
+        }
+        "-h" | "--help" | "help" => print_usage(),
+        cmd => {
+            print_usage();
+            bail!("Unknown command: {}", cmd);
+        }
+    }
+    Ok(())
+}
+
+fn print_usage() {
+    println!(r#"
+USAGE:
+    shaum <COMMAND> [ARGS] [OPTIONS]
+
+COMMANDS:
+    check [DATE]    Fasting status of DATE (YYYY-MM-DD or a Hijri date such
+                    as "9 Dhul-Hijjah 1447"; default: today)
+    tui [YYYY-MM]   Interactive month calendar (built with --features tui)
+    precompute <FROM..TO> <FILE>
+                    Write the analyses of years FROM through TO as a day table
+    serve --precompute <FROM..TO> [--http <ADDR>] [--workers <N>] [--table <FILE>]
+                    Precompute FROM through TO into FILE, map it and answer
+                    one date per stdin line, or GET /<DATE> over HTTP, with
+                    JSON (built with --features serve)
+
+OPTIONS:
+    --locale <TAG>          Language of the output: {}
+    --calendar <NAME>       {} (default: {})
+    --lat <DEG> --lon <DEG> Location for prayer times
+    --utc-offset <+HH:MM>   Offset prayer times are shown in (default: local)
+"#, languages(), calendars(), CalendarMethod::UmmAlQura.name());
+}
+
+/// "en (English), id (Bahasa Indonesia)".
+fn languages() -> String {
+    let bundles = Bundle::all().unwrap_or_default();
+    bundles.iter().map(|b| format!("{} ({})", b.language, b.name)).collect::<Vec<_>>().join(", ")
+}
+
+/// "umm_al_qura, tabular, ...".
+fn calendars() -> String {
+    CalendarMethod::ALL.iter().map(CalendarMethod::name).collect::<Vec<_>>().join(", ")
+}
+
+/// Splits `args` into positional arguments and the shared [`Options`].
+fn parse_options(args: &[String]) -> Result<(Vec<String>, Options)> {
+    let mut positional = Vec::new();
+    let mut locale = "en".to_string();
+    let mut calendar = CalendarMethod::UmmAlQura;
+    let (mut lat, mut lon) = (None, None);
+    let mut utc_offset = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            positional.push(arg.clone());
+            continue;
+        }
+        let value = args.next().with_context(|| format!("{} needs a value", arg))?;
+        match arg.as_str() {
+            "--locale" => locale = value.clone(),
+            "--calendar" => calendar = value.parse()?,
+            "--lat" => lat = Some(value.parse::<f64>().with_context(|| format!("Invalid latitude '{}'", value))?),
+            "--lon" => lon = Some(value.parse::<f64>().with_context(|| format!("Invalid longitude '{}'", value))?),
+            "--utc-offset" => {
+                utc_offset = Some(value.parse::<FixedOffset>().with_context(|| format!("Invalid UTC offset '{}'", value))?);
+            }
+            _ => bail!("Unknown option: {}", arg),
+        }
+    }
+
+    let mut options = Options::new(Bundle::builtin(&locale)?, calendar);
+    options.coords = match (lat, lon) {
+        (Some(lat), Some(lon)) => Some(GeoCoordinate::new(lat, lon)?),
+        (None, None) => None,
+        _ => bail!("--lat and --lon go together"),
+    };
+    options.utc_offset = utc_offset.unwrap_or(options.utc_offset);
+    Ok((positional, options))
+}
+
+/// Gregorian `YYYY-MM-DD`, or a Hijri date in the selected language or English.
+fn parse_date(input: &str, options: &Options) -> Result<NaiveDate> {
+    if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        return Ok(date);
+    }
+    let hijri = parse_hijri(input, &options.locale)
+        .with_context(|| format!("'{}' is neither YYYY-MM-DD nor a Hijri date", input))?;
+    Ok(hijri.to_gregorian(&*options.context.calendar)?)
+}
+
+#[cfg(feature = "tui")]
+fn tui(month: Option<&str>, options: Options) -> Result<()> {
+    let start = match month {
+        Some(month) => NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d")
+            .with_context(|| format!("Invalid month '{}', expected YYYY-MM", month))?,
+        None => chrono::Local::now().date_naive(),
+    };
+    tui::run(start, options)
+}
+
+#[cfg(not(feature = "tui"))]
+fn tui(_month: Option<&str>, _options: Options) -> Result<()> {
+    bail!("This build has no TUI; rebuild with `cargo install shaum-cli --features tui`")
+}
+
+#[cfg(feature = "serve")]
+fn serve(args: &[String]) -> Result<()> {
+    let (serve_args, rest) = serve::ServeArgs::extract(args)?;
+    let (positional, options) = parse_options(&rest)?;
+    if let Some(arg) = positional.first() {
+        bail!("Unexpected argument: {}", arg);
+    }
+    serve::run(&serve_args, options)
+}
+
+#[cfg(not(feature = "serve"))]
+fn serve(_args: &[String]) -> Result<()> {
+    bail!("This build has no server; rebuild with `cargo install shaum-cli --features serve`")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> { list.iter().map(|s| s.to_string()).collect() }
+
+    #[test]
+    fn test_parse_options() {
+        let (positional, options) = parse_options(&args(&[
+            "2024-03", "--locale", "id", "--lat", "-6.2", "--lon", "106.8", "--utc-offset", "+07:00", "--calendar", "kemenag",
+        ])).unwrap();
+        assert_eq!(positional, ["2024-03"]);
+        assert_eq!(options.locale.language, "id");
+        assert_eq!(options.calendar, CalendarMethod::Kemenag);
+        assert_eq!(options.coords.unwrap().lng, 106.8);
+        assert_eq!(options.utc_offset.local_minus_utc(), 7 * 3600);
+
+        assert!(parse_options(&args(&["--lat", "1"])).is_err());
+        assert!(parse_options(&args(&["--calendar", "nope"])).is_err());
+        assert!(parse_options(&args(&["--locale"])).is_err());
+        assert!(parse_options(&args(&["--verbose", "1"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_date() {
+        let (_, options) = parse_options(&args(&["--locale", "id"])).unwrap();
+        let expected = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+        assert_eq!(parse_date("2024-03-11", &options).unwrap(), expected);
+        assert_eq!(parse_date("1 Ramadan 1445", &options).unwrap(), expected);
+        assert_eq!(parse_date("1 Ramadhan 1445", &options).unwrap(), expected);
+        assert!(parse_date("someday", &options).is_err());
+    }
+}
@@ -0,0 +1,227 @@
+//! What the CLI shows for one day, shared by `check` and the TUI detail pane.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveDate, Offset, Utc};
+use shaum_core::rules::i18n::Localizer;
+use shaum_core::{analyze_local, check, prayer_times, CalendarMethod, FastingAnalysis, FastingStatus, GeoCoordinate, LocalDay, RuleContext};
+use shaum_core::astronomy::params_for_location;
+use shaum_core::astronomy::prayer::PrayerTimes;
+use shaum_core::types::TracePayload;
+
+use crate::locale::Bundle;
+use crate::table::{self, DayTable};
+
+/// Settings every command shares.
+pub struct Options {
+    pub locale: Bundle,
+    /// Backend of `context`, which [`Options::table`] must match.
+    pub calendar: CalendarMethod,
+    pub context: RuleContext,
+    pub coords: Option<GeoCoordinate>,
+    /// Offset prayer times are shown in, and where "today" is taken without coordinates.
+    pub utc_offset: FixedOffset,
+    /// Precomputed analyses under `context`, consulted before the engine.
+    pub table: Option<DayTable<&'static [u8]>>,
+}
+
+impl Options {
+    /// Default rules on `calendar`, with the embedded table when it matches.
+    pub fn new(locale: Bundle, calendar: CalendarMethod) -> Self {
+        Self {
+            locale,
+            calendar,
+            context: RuleContext { calendar: calendar.calendar(), ..RuleContext::default() },
+            coords: None,
+            utc_offset: Local::now().offset().fix(),
+            table: table::embedded_calendar(calendar),
+        }
+    }
+
+    /// Analysis of `date`, from the table when it covers the day.
+    pub fn analysis(&self, date: NaiveDate) -> Result<FastingAnalysis> {
+        if let Some(table) = &self.table
+            && let Some(analysis) = table::analysis(table, date)?
+        {
+            return Ok(analysis);
+        }
+        Ok(check(date, &self.context)?)
+    }
+
+    /// Today and its analysis, as [`shaum_core::analyze_today`] sees it: the
+    /// date at `utc_offset`, or with coordinates the local date there, which
+    /// turns to the next day at Maghrib.
+    pub fn today(&self) -> Result<(NaiveDate, FastingAnalysis)> {
+        self.today_at(Utc::now())
+    }
+
+    fn today_at(&self, now: DateTime<Utc>) -> Result<(NaiveDate, FastingAnalysis)> {
+        let local = match self.coords {
+            Some(coords) => LocalDay::Coords(coords),
+            None => LocalDay::Offset(self.utc_offset),
+        };
+        let analysis = analyze_local(now, local, &self.context)?;
+        let civil = local.civil_date(now);
+        let date = if analysis.traces().any(|t| t.payload == TracePayload::PostMaghribOffset) {
+            civil.succ_opt().context("Today is out of range")?
+        } else {
+            civil
+        };
+        Ok((date, analysis))
+    }
+
+    /// Prayer times of `date`, `None` without coordinates.
+    pub fn prayer_times(&self, date: NaiveDate) -> Result<Option<PrayerTimes>> {
+        let Some(coords) = self.coords else {
+            return Ok(None);
+        };
+        Ok(Some(prayer_times(date, coords, params_for_location(coords), &self.context)?))
+    }
+}
+
+/// A day's analysis, localized.
+#[derive(Debug, Clone)]
+pub struct DayReport {
+    /// E.g. "Friday, 15 March 2024".
+    pub heading: String,
+    /// The localizer's Hijri description.
+    pub description: String,
+    #[cfg_attr(not(feature = "tui"), allow(dead_code))]
+    pub status: FastingStatus,
+    pub status_name: String,
+    pub reasons: Vec<String>,
+    pub traces: Vec<String>,
+    /// Labelled `HH:MM` times, `None` without coordinates.
+    pub prayer_times: Option<Vec<(String, String)>>,
+}
+
+impl DayReport {
+    pub fn new(date: NaiveDate, options: &Options) -> Result<Self> {
+        Self::with_analysis(date, &options.analysis(date)?, options)
+    }
+
+    /// Report of `date` from an analysis already made, e.g. by [`Options::today`].
+    pub fn with_analysis(date: NaiveDate, analysis: &FastingAnalysis, options: &Options) -> Result<Self> {
+        let locale = &options.locale;
+        let reasons = analysis.reason_statuses()
+            .map(|(t, status)| format!("{} · {}", locale.type_name(t), locale.status_name(status)))
+            .collect();
+        let traces = analysis.traces()
+            .map(|t| match &t.payload {
+                TracePayload::None => t.code.code().to_string(),
+                payload => format!("{}: {}", t.code.code(), payload),
+            })
+            .collect();
+        let local = |t: DateTime<Utc>| t.with_timezone(&options.utc_offset).format("%H:%M").to_string();
+        let prayer_times = options.prayer_times(date)?.map(|times| vec![
+            (locale.ui.imsak.clone(), local(times.imsak)),
+            (locale.ui.fajr.clone(), local(times.fajr)),
+            (locale.ui.maghrib.clone(), local(times.maghrib)),
+            (locale.ui.isha.clone(), local(times.isha)),
+        ]);
+        Ok(Self {
+            heading: format!(
+                "{}, {} {} {}",
+                locale.weekday_name(date.weekday()), date.day(), locale.gregorian_month_name(date.month()), date.year()
+            ),
+            description: locale.format_description(analysis),
+            status: analysis.primary_status,
+            status_name: locale.status_name(analysis.primary_status),
+            reasons,
+            traces,
+            prayer_times,
+        })
+    }
+
+    /// Plain-text rendering for the terminal.
+    pub fn to_text(&self, options: &Options) -> String {
+        let ui = &options.locale.ui;
+        let mut lines = vec![
+            self.heading.clone(),
+            self.description.clone(),
+            format!("{}: {}", ui.status, self.status_name),
+        ];
+        if !self.reasons.is_empty() {
+            lines.push(format!("{}: {}", ui.reasons, self.reasons.join(", ")));
+        }
+        if !self.traces.is_empty() {
+            lines.push(format!("{}: {}", ui.traces, self.traces.join("; ")));
+        }
+        match &self.prayer_times {
+            Some(times) => {
+                let times: Vec<String> = times.iter().map(|(label, time)| format!("{} {}", label, time)).collect();
+                lines.push(format!("{}: {}", ui.prayer_times, times.join("  ")));
+            }
+            None => lines.push(format!("{}: {}", ui.prayer_times, ui.no_location)),
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn options(locale: &str, coords: Option<GeoCoordinate>) -> Options {
+        Options {
+            coords,
+            utc_offset: FixedOffset::east_opt(7 * 3600).unwrap(),
+            ..Options::new(Bundle::builtin(locale).unwrap(), CalendarMethod::UmmAlQura)
+        }
+    }
+
+    #[test]
+    fn test_report_is_localized() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        let report = DayReport::new(date, &options("id", None)).unwrap();
+        assert_eq!(report.heading, "Jumat, 15 Maret 2024");
+        assert_eq!(report.status, FastingStatus::Wajib);
+        assert!(report.reasons.iter().any(|r| r.starts_with("Ramadan")));
+        assert!(report.prayer_times.is_none());
+    }
+
+    #[test]
+    fn test_today_turns_at_maghrib() {
+        let jakarta = GeoCoordinate::new(-6.2, 106.8).unwrap();
+        let at_jakarta = options("en", Some(jakarta));
+        // 15 March 2024 in Jakarta: 11:00 and 19:00 local (UTC+7), either side of Maghrib.
+        let noon = Utc.with_ymd_and_hms(2024, 3, 15, 4, 0, 0).unwrap();
+        let evening = Utc.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap();
+        let (date, analysis) = at_jakarta.today_at(noon).unwrap();
+        assert_eq!(date, NaiveDate::from_ymd_opt(2024, 3, 15).unwrap());
+        assert_eq!(analysis.hijri_day, check(date, &at_jakarta.context).unwrap().hijri_day);
+        let (date, analysis) = at_jakarta.today_at(evening).unwrap();
+        assert_eq!(date, NaiveDate::from_ymd_opt(2024, 3, 16).unwrap());
+        assert_eq!(analysis.hijri_day, check(date, &at_jakarta.context).unwrap().hijri_day);
+
+        // Without coordinates the day follows the offset: 23:30 UTC is already the 16th at UTC+7.
+        let late = Utc.with_ymd_and_hms(2024, 3, 15, 23, 30, 0).unwrap();
+        let (date, _) = options("en", None).today_at(late).unwrap();
+        assert_eq!(date, NaiveDate::from_ymd_opt(2024, 3, 16).unwrap());
+    }
+
+    #[test]
+    fn test_prayer_times_in_offset() {
+        let jakarta = GeoCoordinate::new(-6.2, 106.8).unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        let options = options("en", Some(jakarta));
+        let report = DayReport::new(date, &options).unwrap();
+        let times = report.prayer_times.as_ref().unwrap();
+        assert_eq!(times[2].0, "Maghrib");
+        assert!(times[2].1.starts_with("18:"), "{:?}", times);
+        assert!(report.to_text(&options).contains("Prayer times: Imsak 04:"));
+    }
+
+    #[test]
+    fn test_table_is_consulted() {
+        let bytes = table::precompute_calendar(2024..=2024, CalendarMethod::Kemenag).unwrap();
+        let mut options = options("en", None);
+        options.table = Some(DayTable::parse(&*Vec::leak(bytes)).unwrap());
+        // The table says Kemenag, the context Umm al-Qura: in range the table wins.
+        let date = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+        assert_eq!(options.analysis(date).unwrap().hijri_month, 8);
+        assert_eq!(check(date, &options.context).unwrap().hijri_month, 9);
+        let outside = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        assert_eq!(options.analysis(outside).unwrap().to_canonical_string(), check(outside, &options.context).unwrap().to_canonical_string());
+    }
+}
@@ -0,0 +1,346 @@
+//! `shaum serve`: answers from a memory-mapped table of precomputed days.
+//!
+//! Each record of the [`DayTable`] is the finished JSON response for one day
+//! (analysis, localized status and prayer times), so a query is a date parse
+//! and a slice. Days outside the table are computed on the spot. The table is
+//! kept in a file and reused while its label (calendar, rule set, language,
+//! location, offset) and range still match.
+
+use std::borrow::Cow;
+use std::fs::{self, File};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{self, BufRead, Write};
+use std::num::NonZeroUsize;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use anyhow::{bail, ensure, Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use memmap2::Mmap;
+use percent_encoding::percent_decode_str;
+use serde_json::json;
+use shaum_core::rules::i18n::Localizer;
+use shaum_core::rules::RULESET_VERSION;
+use tiny_http::{Header, Method, Response, Server};
+
+use crate::report::Options;
+use crate::table::{self, DayTable};
+
+/// Flags only `serve` takes, split off before the shared ones.
+#[derive(Debug)]
+pub struct ServeArgs {
+    pub years: RangeInclusive<i32>,
+    /// Listen address; stdin is served without one.
+    pub http: Option<String>,
+    pub workers: usize,
+    /// Table file; by default one per set of options in the user's cache directory.
+    pub table: Option<PathBuf>,
+}
+
+impl ServeArgs {
+    /// Takes `--precompute`, `--http`, `--workers` and `--table` out of
+    /// `args`, returning the rest for `parse_options`.
+    pub fn extract(args: &[String]) -> Result<(Self, Vec<String>)> {
+        let mut years = None;
+        let mut http = None;
+        let mut workers = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        let mut table = None;
+        let mut rest = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let flag = matches!(arg.as_str(), "--precompute" | "--http" | "--workers" | "--table");
+            if !flag {
+                rest.push(arg.clone());
+                continue;
+            }
+            let value = args.next().with_context(|| format!("{} needs a value", arg))?;
+            match arg.as_str() {
+                "--precompute" => years = Some(table::parse_years(value)?),
+                "--http" => http = Some(value.clone()),
+                "--workers" => {
+                    workers = value.parse().with_context(|| format!("Invalid worker count '{}'", value))?;
+                    ensure!(workers > 0, "--workers must be at least 1");
+                }
+                _ => table = Some(PathBuf::from(value)),
+            }
+        }
+        let years = years.context("Usage: shaum serve --precompute <FROM..TO> [--http <ADDR>]")?;
+        Ok((Self { years, http, workers, table }, rest))
+    }
+}
+
+/// Label of the serve table for `options`; a stored table is reused only
+/// when it matches.
+fn label(options: &Options) -> String {
+    let coords = options.coords.map_or("-".to_string(), |c| format!("{},{}", c.lat, c.lng));
+    format!(
+        "serve/{}/ruleset-{}/{}/{}/{}",
+        options.calendar.name(), RULESET_VERSION, options.locale.language, coords, options.utc_offset
+    )
+}
+
+/// `shaum/serve-<hash>.bin` in the user's cache directory, named after the
+/// label and years so differently configured servers keep separate tables.
+fn default_table(options: &Options, years: &RangeInclusive<i32>) -> Result<PathBuf> {
+    let dir = dirs::cache_dir().context("No cache directory for the serve table; pass --table")?.join("shaum");
+    fs::create_dir_all(&dir).with_context(|| format!("Cannot create {}", dir.display()))?;
+    let mut hasher = DefaultHasher::new();
+    (label(options), years).hash(&mut hasher);
+    Ok(dir.join(format!("serve-{:016x}.bin", hasher.finish())))
+}
+
+/// Response body for `date`.
+pub fn record(date: NaiveDate, options: &Options) -> Result<Vec<u8>> {
+    let analysis = options.analysis(date)?;
+    let local = |t: DateTime<Utc>| t.with_timezone(&options.utc_offset).to_rfc3339();
+    let prayer_times = options.prayer_times(date)?.map(|t| json!({
+        "imsak": local(t.imsak),
+        "fajr": local(t.fajr),
+        "maghrib": local(t.maghrib),
+        "isha": local(t.isha),
+    }));
+    Ok(serde_json::to_vec(&json!({
+        "date": date,
+        "status": options.locale.status_name(analysis.primary_status),
+        "description": options.locale.format_description(&analysis),
+        "analysis": analysis,
+        "prayer_times": prayer_times,
+    }))?)
+}
+
+/// Records of every day of `years`, computed on `workers` threads.
+pub fn precompute(years: RangeInclusive<i32>, options: &Options, workers: usize) -> Result<Vec<u8>> {
+    let first = NaiveDate::from_ymd_opt(*years.start(), 1, 1).context("Invalid first year")?;
+    let last = NaiveDate::from_ymd_opt(*years.end(), 12, 31).context("Invalid last year")?;
+    ensure!(first <= last, "Empty year range {}..{}", years.start(), years.end());
+    let days: Vec<NaiveDate> = first.iter_days().take_while(|d| *d <= last).collect();
+    let chunk = days.len().div_ceil(workers.max(1));
+
+    let chunks = thread::scope(|scope| {
+        let handles: Vec<_> = days.chunks(chunk)
+            .map(|dates| scope.spawn(move || dates.iter().map(|&d| record(d, options)).collect::<Result<Vec<_>>>()))
+            .collect();
+        handles.into_iter()
+            .map(|h| h.join().unwrap_or_else(|_| bail!("Precompute worker panicked")))
+            .collect::<Result<Vec<_>>>()
+    })?;
+    table::write(first, &label(options), chunks.into_iter().flatten())
+}
+
+/// Precomputed days, memory-mapped, plus the options for days outside them.
+pub struct Service {
+    options: Options,
+    table: DayTable<Mmap>,
+    path: PathBuf,
+}
+
+impl Service {
+    /// Maps the table at `args.table`, first (re)writing it unless it already
+    /// covers `args.years` under `options`.
+    pub fn open(args: &ServeArgs, options: Options) -> Result<Self> {
+        let first = NaiveDate::from_ymd_opt(*args.years.start(), 1, 1).context("Invalid first year")?;
+        let last = NaiveDate::from_ymd_opt(*args.years.end(), 12, 31).context("Invalid last year")?;
+        let path = match &args.table {
+            Some(path) => path.clone(),
+            None => default_table(&options, &args.years)?,
+        };
+        let current = map(&path).ok().filter(|t| t.label() == label(&options) && t.range() == (first..=last));
+        let table = match current {
+            Some(table) => table,
+            None => {
+                let bytes = precompute(args.years.clone(), &options, args.workers)?;
+                fs::write(&path, bytes).with_context(|| format!("Cannot write {}", path.display()))?;
+                map(&path)?
+            }
+        };
+        Ok(Self { options, table, path })
+    }
+
+    /// File the table is mapped from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// First and last precomputed day.
+    pub fn range(&self) -> RangeInclusive<NaiveDate> {
+        self.table.range()
+    }
+
+    /// Response for a date query (see `parse_date`; empty means today).
+    pub fn answer(&self, query: &str) -> Result<Cow<'_, [u8]>> {
+        let date = match query.trim() {
+            "" | "today" => Utc::now().with_timezone(&self.options.utc_offset).date_naive(),
+            input => crate::parse_date(input, &self.options)?,
+        };
+        match self.table.get(date) {
+            Some(record) => Ok(Cow::Borrowed(record)),
+            None => Ok(Cow::Owned(record(date, &self.options)?)),
+        }
+    }
+
+    /// Status and body for an HTTP request: `GET /<date>`.
+    pub fn route(&self, method: &Method, url: &str) -> (u16, Cow<'_, [u8]>) {
+        if *method != Method::Get {
+            return (405, error("Only GET is supported"));
+        }
+        let path = url.split('?').next().unwrap_or_default().trim_start_matches('/');
+        let Ok(query) = percent_decode_str(path).decode_utf8() else {
+            return (400, error("Request path is not valid UTF-8"));
+        };
+        match self.answer(&query) {
+            Ok(body) => (200, body),
+            Err(e) => (400, error(&format!("{:#}", e))),
+        }
+    }
+
+    /// Answers one query per line of `input` with one line of JSON.
+    pub fn serve_lines(&self, input: impl BufRead, mut output: impl Write) -> Result<()> {
+        for line in input.lines() {
+            let line = line?;
+            let body = self.answer(&line).unwrap_or_else(|e| error(&format!("{:#}", e)));
+            output.write_all(&body)?;
+            output.write_all(b"\n")?;
+            output.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Serves HTTP on `addr` with `workers` threads until the process ends.
+    pub fn serve_http(&self, addr: &str, workers: usize) -> Result<()> {
+        let server = Server::http(addr).map_err(|e| anyhow::anyhow!("Cannot listen on {}: {}", addr, e))?;
+        let json = Header::from_bytes("Content-Type", "application/json").expect("static header is valid");
+        eprintln!("Listening on http://{}", server.server_addr());
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| {
+                    while let Ok(request) = server.recv() {
+                        let (status, body) = self.route(request.method(), request.url());
+                        let response = Response::from_data(body.into_owned())
+                            .with_status_code(status)
+                            .with_header(json.clone());
+                        // The client may have gone; nothing to do about it here.
+                        let _ = request.respond(response);
+                    }
+                });
+            }
+        });
+        Ok(())
+    }
+}
+
+fn map(path: &Path) -> Result<DayTable<Mmap>> {
+    let file = File::open(path).with_context(|| format!("Cannot open {}", path.display()))?;
+    // SAFETY: the table file is ours and only replaced before mapping; a
+    // concurrent writer would at worst make `parse` or a lookup fail.
+    let bytes = unsafe { Mmap::map(&file) }.with_context(|| format!("Cannot map {}", path.display()))?;
+    DayTable::parse(bytes)
+}
+
+fn error(message: &str) -> Cow<'static, [u8]> {
+    Cow::Owned(json!({ "error": message }).to_string().into_bytes())
+}
+
+/// `shaum serve`: stdin without `--http`, HTTP with it.
+pub fn run(args: &ServeArgs, options: Options) -> Result<()> {
+    let service = Service::open(args, options)?;
+    let range = service.range();
+    eprintln!("Serving {} to {} from {}", range.start(), range.end(), service.path().display());
+    match &args.http {
+        Some(addr) => service.serve_http(addr, args.workers),
+        None => service.serve_lines(io::stdin().lock(), io::stdout().lock()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locale::Bundle;
+    use chrono::FixedOffset;
+    use shaum_core::{CalendarMethod, GeoCoordinate};
+
+    fn args(list: &[&str]) -> Vec<String> { list.iter().map(|s| s.to_string()).collect() }
+
+    fn options() -> Options {
+        Options {
+            coords: Some(GeoCoordinate::new(-6.2, 106.8).unwrap()),
+            utc_offset: FixedOffset::east_opt(7 * 3600).unwrap(),
+            ..Options::new(Bundle::builtin("id").unwrap(), CalendarMethod::UmmAlQura)
+        }
+    }
+
+    fn service(name: &str, options: Options) -> (ServeArgs, Service) {
+        let table = std::env::temp_dir().join(format!("shaum-serve-test-{}-{}.bin", name, std::process::id()));
+        let _ = fs::remove_file(&table);
+        let serve = ServeArgs { years: 2024..=2024, http: None, workers: 3, table: Some(table) };
+        let service = Service::open(&serve, options).unwrap();
+        (serve, service)
+    }
+
+    #[test]
+    fn test_extract_args() {
+        let (serve, rest) = ServeArgs::extract(&args(&[
+            "--precompute", "2024..2030", "--locale", "id", "--http", "0.0.0.0:8080", "--workers", "2",
+        ])).unwrap();
+        assert_eq!(serve.years, 2024..=2030);
+        assert_eq!(serve.http.as_deref(), Some("0.0.0.0:8080"));
+        assert_eq!(serve.workers, 2);
+        assert_eq!(rest, ["--locale", "id"]);
+        assert!(ServeArgs::extract(&args(&["--http", "127.0.0.1:1"])).is_err());
+        assert!(ServeArgs::extract(&args(&["--precompute", "2024", "--workers", "0"])).is_err());
+    }
+
+    #[test]
+    fn test_answers_match_live_records() {
+        let (_, service) = service("answers", options());
+        assert_eq!(service.range(), NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()..=NaiveDate::from_ymd_opt(2024, 12, 31).unwrap());
+        let date = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+        let body = service.answer("2024-03-11").unwrap();
+        assert!(matches!(body, Cow::Borrowed(_)));
+        assert_eq!(*body, *record(date, &options()).unwrap());
+        assert_eq!(*service.answer("1 Ramadhan 1445").unwrap(), *body);
+
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["status"], "Wajib");
+        assert!(json["prayer_times"]["maghrib"].as_str().unwrap().starts_with("2024-03-11T18:"));
+
+        // Outside the table the same record is computed live.
+        let outside = service.answer("2025-03-01").unwrap();
+        assert!(matches!(outside, Cow::Owned(_)));
+        assert_eq!(*outside, *record(NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(), &options()).unwrap());
+        fs::remove_file(service.path()).unwrap();
+    }
+
+    #[test]
+    fn test_table_is_reused_only_when_it_matches() {
+        let (serve, first) = service("reuse", Options { coords: None, ..options() });
+        let modified = fs::metadata(first.path()).unwrap().modified().unwrap();
+        Service::open(&serve, Options { coords: None, ..options() }).unwrap();
+        assert_eq!(fs::metadata(first.path()).unwrap().modified().unwrap(), modified);
+
+        let english = Options { locale: Bundle::builtin("en").unwrap(), coords: None, ..options() };
+        let service = Service::open(&serve, english).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&service.answer("2024-03-11").unwrap()).unwrap();
+        assert_eq!(json["status"], "Wajib (Obligatory)");
+        fs::remove_file(service.path()).unwrap();
+    }
+
+    #[test]
+    fn test_lines_and_routes() {
+        let (_, service) = service("routes", Options { coords: None, ..options() });
+        let mut out = Vec::new();
+        service.serve_lines(&b"2024-04-10\nsomeday\n"[..], &mut out).unwrap();
+        let lines: Vec<serde_json::Value> = out.split(|&b| b == b'\n').filter(|l| !l.is_empty())
+            .map(|l| serde_json::from_slice(l).unwrap())
+            .collect();
+        assert_eq!(lines[0]["status"], "Haram (Dilarang)");
+        assert!(lines[1]["error"].as_str().unwrap().contains("someday"));
+
+        assert_eq!(service.route(&Method::Get, "/2024-04-10?x=1").0, 200);
+        assert_eq!(service.route(&Method::Get, "/9%20Dhul-Hijjah%201445").0, 200);
+        assert_eq!(service.route(&Method::Get, "/2024-04-%FF").0, 400);
+        assert_eq!(service.route(&Method::Get, "/nope").0, 400);
+        assert_eq!(service.route(&Method::Post, "/2024-04-10").0, 405);
+        fs::remove_file(service.path()).unwrap();
+    }
+}
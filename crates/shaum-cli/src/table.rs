@@ -0,0 +1,225 @@
+//! Day-indexed tables of precomputed records.
+//!
+//! A table holds one opaque record per consecutive day plus a label saying
+//! what was computed, with an offset index so a record is found without
+//! reading the others; the same bytes work from `include_bytes!` or a file.
+//!
+//! Layout (integers little-endian):
+//!
+//! ```text
+//! "SHDT" version:u8  label_len:u16 label  first_day:i32 (days from CE)  days:u32
+//! offsets:(days + 1) × u32, relative to the first record   records
+//! ```
+//!
+//! The calendar table built by `shaum precompute` (and embedded by
+//! `cargo xtask dist-cli`) stores [`binary`] batches of one analysis each,
+//! labelled with the calendar and rule set they were computed under.
+
+use std::ops::{Range, RangeInclusive};
+
+use anyhow::{bail, ensure, Context, Result};
+use chrono::{Datelike, NaiveDate};
+use shaum_core::binary;
+use shaum_core::rules::RULESET_VERSION;
+use shaum_core::{check, CalendarMethod, FastingAnalysis, RuleContext};
+
+/// Version of the table layout; bumped whenever it changes.
+pub const TABLE_VERSION: u8 = 1;
+
+const MAGIC: &[u8; 4] = b"SHDT";
+
+/// Calendar table compiled into the binary; empty unless the build set
+/// `SHAUM_CALENDAR_TABLE` (see `build.rs`).
+static EMBEDDED: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/calendar.bin"));
+
+/// A parsed table over borrowed or owned bytes.
+#[derive(Debug)]
+pub struct DayTable<B> {
+    bytes: B,
+    label: Range<usize>,
+    first: NaiveDate,
+    days: usize,
+    /// Start of the offset index.
+    index: usize,
+}
+
+impl<B: AsRef<[u8]>> DayTable<B> {
+    /// Checks the header and index of `bytes`.
+    pub fn parse(bytes: B) -> Result<Self> {
+        let data = bytes.as_ref();
+        let mut rest = data;
+        ensure!(take(&mut rest, 4)? == MAGIC, "Not a shaum day table");
+        let version = take(&mut rest, 1)?[0];
+        ensure!(version == TABLE_VERSION, "Unsupported day table version {} (expected {})", version, TABLE_VERSION);
+        let label_len = u16::from_le_bytes(take(&mut rest, 2)?.try_into()?) as usize;
+        let label_start = data.len() - rest.len();
+        std::str::from_utf8(take(&mut rest, label_len)?).context("Day table label is not UTF-8")?;
+        let first = i32::from_le_bytes(take(&mut rest, 4)?.try_into()?);
+        let first = NaiveDate::from_num_days_from_ce_opt(first).context("Invalid first day in day table")?;
+        let days = u32::from_le_bytes(take(&mut rest, 4)?.try_into()?) as usize;
+        let index = data.len() - rest.len();
+        take(&mut rest, (days + 1) * 4)?;
+        let records = rest.len();
+
+        let table = Self { label: label_start..label_start + label_len, first, days, index, bytes };
+        let indexed = table.offset(days);
+        ensure!(indexed == records, "Day table records are {} bytes, index says {}", records, indexed);
+        Ok(table)
+    }
+
+    /// What the records hold, as given to [`write`].
+    pub fn label(&self) -> &str {
+        std::str::from_utf8(&self.bytes.as_ref()[self.label.clone()]).unwrap_or_default()
+    }
+
+    /// First and last day covered.
+    pub fn range(&self) -> RangeInclusive<NaiveDate> {
+        self.first..=self.first + chrono::Duration::days(self.days as i64 - 1)
+    }
+
+    /// Record of `date`, or `None` outside [`DayTable::range`].
+    pub fn get(&self, date: NaiveDate) -> Option<&[u8]> {
+        let day = usize::try_from((date - self.first).num_days()).ok().filter(|&d| d < self.days)?;
+        let start = self.records_start();
+        self.bytes.as_ref().get(start + self.offset(day)..start + self.offset(day + 1))
+    }
+
+    fn offset(&self, i: usize) -> usize {
+        let at = self.index + i * 4;
+        let raw: [u8; 4] = self.bytes.as_ref()[at..at + 4].try_into().expect("index checked in parse");
+        u32::from_le_bytes(raw) as usize
+    }
+
+    fn records_start(&self) -> usize {
+        self.index + (self.days + 1) * 4
+    }
+}
+
+/// Splits the first `n` bytes off `rest`.
+fn take<'a>(rest: &mut &'a [u8], n: usize) -> Result<&'a [u8]> {
+    ensure!(rest.len() >= n, "Truncated day table");
+    let (head, tail) = rest.split_at(n);
+    *rest = tail;
+    Ok(head)
+}
+
+/// Serializes `records`, one per day from `first`, as a table labelled `label`.
+pub fn write(first: NaiveDate, label: &str, records: impl IntoIterator<Item = Vec<u8>>) -> Result<Vec<u8>> {
+    let mut offsets = vec![0u32];
+    let mut body = Vec::new();
+    for record in records {
+        body.extend_from_slice(&record);
+        offsets.push(u32::try_from(body.len()).context("Day table larger than 4 GiB")?);
+    }
+    let label_len = u16::try_from(label.len()).context("Day table label too long")?;
+
+    let mut bytes = Vec::with_capacity(32 + label.len() + offsets.len() * 4 + body.len());
+    bytes.extend_from_slice(MAGIC);
+    bytes.push(TABLE_VERSION);
+    bytes.extend_from_slice(&label_len.to_le_bytes());
+    bytes.extend_from_slice(label.as_bytes());
+    bytes.extend_from_slice(&first.num_days_from_ce().to_le_bytes());
+    bytes.extend_from_slice(&(offsets.len() as u32 - 1).to_le_bytes());
+    for offset in offsets {
+        bytes.extend_from_slice(&offset.to_le_bytes());
+    }
+    bytes.extend_from_slice(&body);
+    Ok(bytes)
+}
+
+/// Label of a calendar table computed under `method` with the default rules.
+pub fn calendar_label(method: CalendarMethod) -> String {
+    format!("analysis/{}/ruleset-{}", method.name(), RULESET_VERSION)
+}
+
+/// Analyses of every day of Gregorian `years` under `method`.
+pub fn precompute_calendar(years: RangeInclusive<i32>, method: CalendarMethod) -> Result<Vec<u8>> {
+    let first = NaiveDate::from_ymd_opt(*years.start(), 1, 1).context("Invalid first year")?;
+    let last = NaiveDate::from_ymd_opt(*years.end(), 12, 31).context("Invalid last year")?;
+    ensure!(first <= last, "Empty year range {}..{}", years.start(), years.end());
+    let context = RuleContext { calendar: method.calendar(), ..RuleContext::default() };
+    let records = first.iter_days().take_while(|d| *d <= last)
+        .map(|date| Ok(binary::encode(&[check(date, &context)?])?))
+        .collect::<Result<Vec<_>>>()?;
+    write(first, &calendar_label(method), records)
+}
+
+/// The embedded calendar table, if this build has one computed under `method`.
+pub fn embedded_calendar(method: CalendarMethod) -> Option<DayTable<&'static [u8]>> {
+    if EMBEDDED.is_empty() {
+        return None;
+    }
+    let table = DayTable::parse(EMBEDDED).ok()?;
+    (table.label() == calendar_label(method)).then_some(table)
+}
+
+/// Analysis of `date` from a calendar table, `None` outside its range.
+pub fn analysis<B: AsRef<[u8]>>(table: &DayTable<B>, date: NaiveDate) -> Result<Option<FastingAnalysis>> {
+    let Some(record) = table.get(date) else {
+        return Ok(None);
+    };
+    match binary::decode(record)?.pop() {
+        Some(analysis) => Ok(Some(analysis)),
+        None => bail!("Empty record for {} in day table", date),
+    }
+}
+
+/// Parses `2024..2030` (both years included; `2024..=2030` also accepted) or a single year.
+pub fn parse_years(input: &str) -> Result<RangeInclusive<i32>> {
+    let invalid = || format!("Invalid year range '{}', expected e.g. 2024..2030", input);
+    let (from, to) = match input.split_once("..") {
+        Some((from, to)) => (from, to.strip_prefix('=').unwrap_or(to)),
+        None => (input, input),
+    };
+    let from: i32 = from.trim().parse().with_context(invalid)?;
+    let to: i32 = to.trim().parse().with_context(invalid)?;
+    ensure!(from <= to, "{}", invalid());
+    Ok(from..=to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate { NaiveDate::from_ymd_opt(y, m, d).unwrap() }
+
+    #[test]
+    fn test_round_trip() {
+        let records = vec![b"a".to_vec(), Vec::new(), b"ccc".to_vec()];
+        let bytes = write(date(2024, 2, 28), "test", records).unwrap();
+        let table = DayTable::parse(bytes.as_slice()).unwrap();
+        assert_eq!(table.label(), "test");
+        assert_eq!(table.range(), date(2024, 2, 28)..=date(2024, 3, 1));
+        assert_eq!(table.get(date(2024, 2, 28)), Some(&b"a"[..]));
+        assert_eq!(table.get(date(2024, 2, 29)), Some(&b""[..]));
+        assert_eq!(table.get(date(2024, 3, 1)), Some(&b"ccc"[..]));
+        assert_eq!(table.get(date(2024, 3, 2)), None);
+        assert_eq!(table.get(date(2024, 2, 27)), None);
+
+        assert!(DayTable::parse(&bytes[..bytes.len() - 1]).is_err());
+        assert!(DayTable::parse(&b"SHMB"[..]).is_err());
+    }
+
+    #[test]
+    fn test_calendar_table_matches_engine() {
+        let bytes = precompute_calendar(2024..=2024, CalendarMethod::Kemenag).unwrap();
+        let table = DayTable::parse(bytes).unwrap();
+        assert_eq!(table.label(), calendar_label(CalendarMethod::Kemenag));
+        assert_eq!(table.range(), date(2024, 1, 1)..=date(2024, 12, 31));
+        let context = RuleContext { calendar: CalendarMethod::Kemenag.calendar(), ..RuleContext::default() };
+        for day in [date(2024, 3, 11), date(2024, 4, 10), date(2024, 12, 31)] {
+            let stored = analysis(&table, day).unwrap().unwrap();
+            assert_eq!(stored.to_canonical_string(), check(day, &context).unwrap().to_canonical_string());
+        }
+        assert!(analysis(&table, date(2025, 1, 1)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_parse_years() {
+        assert_eq!(parse_years("2024..2030").unwrap(), 2024..=2030);
+        assert_eq!(parse_years("2024..=2030").unwrap(), 2024..=2030);
+        assert_eq!(parse_years("2025").unwrap(), 2025..=2025);
+        assert!(parse_years("2030..2024").is_err());
+        assert!(parse_years("soon").is_err());
+    }
+}
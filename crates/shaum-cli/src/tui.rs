@@ -0,0 +1,340 @@
+//! `shaum tui`: a month calendar with a detail pane for the selected day.
+//!
+//! The grid is a [`MonthView`] anchored on the Gregorian or the Hijri month of
+//! the selection; every label goes through the active locale [`Bundle`], which
+//! can be switched while running.
+
+use anyhow::Result;
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Row, Table, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use shaum_core::rules::i18n::Localizer;
+use shaum_core::{FastingStatus, MonthAnchor, MonthView};
+
+use crate::locale::Bundle;
+use crate::report::{DayReport, Options};
+
+/// What a key does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    /// Moves the selection by this many days.
+    Move(i64),
+    NextMonth,
+    PrevMonth,
+    /// Switches the grid between Gregorian and Hijri months.
+    ToggleCalendar,
+    Today,
+    NextLocale,
+    Quit,
+}
+
+impl Action {
+    fn from_key(code: KeyCode) -> Option<Self> {
+        Some(match code {
+            KeyCode::Left => Self::Move(-1),
+            KeyCode::Right => Self::Move(1),
+            KeyCode::Up => Self::Move(-7),
+            KeyCode::Down => Self::Move(7),
+            KeyCode::Char('n') | KeyCode::PageDown => Self::NextMonth,
+            KeyCode::Char('p') | KeyCode::PageUp => Self::PrevMonth,
+            KeyCode::Char('m') => Self::ToggleCalendar,
+            KeyCode::Char('t') => Self::Today,
+            KeyCode::Char('l') => Self::NextLocale,
+            KeyCode::Char('q') | KeyCode::Esc => Self::Quit,
+            _ => return None,
+        })
+    }
+}
+
+struct App {
+    options: Options,
+    locales: Vec<Bundle>,
+    view: MonthView,
+    selected: NaiveDate,
+    hijri: bool,
+    report: DayReport,
+    /// Last failed action, shown in the footer until the next one.
+    error: Option<String>,
+    quit: bool,
+}
+
+impl App {
+    fn new(selected: NaiveDate, options: Options) -> Result<Self> {
+        let view = month_of(selected, false, &options)?;
+        let report = DayReport::new(selected, &options)?;
+        Ok(Self { options, locales: Bundle::all()?, view, selected, hijri: false, report, error: None, quit: false })
+    }
+
+    fn apply(&mut self, action: Action) {
+        self.error = self.try_apply(action).err().map(|e| e.to_string());
+    }
+
+    fn try_apply(&mut self, action: Action) -> Result<()> {
+        match action {
+            Action::Move(days) => self.select(self.selected + Duration::days(days))?,
+            Action::NextMonth => self.show(self.view.next()?)?,
+            Action::PrevMonth => self.show(self.view.prev()?)?,
+            Action::ToggleCalendar => {
+                self.hijri = !self.hijri;
+                if let Err(e) = self.select(self.selected) {
+                    self.hijri = !self.hijri;
+                    return Err(e);
+                }
+            }
+            Action::Today => self.select(Local::now().date_naive())?,
+            Action::NextLocale => {
+                let current = self.locales.iter().position(|b| b.language == self.options.locale.language);
+                let next = current.map_or(0, |i| (i + 1) % self.locales.len());
+                self.options.locale = self.locales[next].clone();
+                self.report = DayReport::new(self.selected, &self.options)?;
+            }
+            Action::Quit => self.quit = true,
+        }
+        Ok(())
+    }
+
+    /// Selects `date`, moving to its month when it is outside the view.
+    fn select(&mut self, date: NaiveDate) -> Result<()> {
+        let report = DayReport::new(date, &self.options)?;
+        if !(self.view.first..=self.view.last).contains(&date) || self.hijri != is_hijri(&self.view) {
+            self.view = month_of(date, self.hijri, &self.options)?;
+        }
+        self.selected = date;
+        self.report = report;
+        Ok(())
+    }
+
+    /// Shows `view` with its first day selected.
+    fn show(&mut self, view: MonthView) -> Result<()> {
+        self.report = DayReport::new(view.first, &self.options)?;
+        self.selected = view.first;
+        self.view = view;
+        Ok(())
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.render(frame))?;
+            if let Event::Key(key) = event::read()?
+                && let Some(action) = Action::from_key(key.code).filter(|_| key.kind == KeyEventKind::Press)
+            {
+                self.apply(action);
+            }
+        }
+        Ok(())
+    }
+
+    fn render(&self, frame: &mut Frame) {
+        let [main, footer] = Layout::vertical([Constraint::Min(10), Constraint::Length(1)]).areas(frame.area());
+        let [grid, detail] = Layout::horizontal([Constraint::Length(43), Constraint::Min(24)]).areas(main);
+
+        frame.render_widget(self.grid(), grid);
+        frame.render_widget(self.detail(), detail);
+        let footer_line = match &self.error {
+            Some(error) => Line::from(error.as_str()).fg(Color::Red),
+            None => Line::from(self.options.locale.ui.help.as_str()).dim(),
+        };
+        frame.render_widget(footer_line, footer);
+    }
+
+    fn grid(&self) -> Table<'_> {
+        let locale = &self.options.locale;
+        let weekdays = std::iter::successors(Some(self.view.week_start), |w| Some(w.succ())).take(7);
+        let header = Row::new(weekdays.map(|w| locale.weekday_name(w).chars().take(3).collect::<String>())).bold();
+        let rows = self.view.weeks.iter().map(|week| {
+            Row::new(week.days.iter().map(|day| {
+                let (primary, secondary) = if self.hijri {
+                    (day.analysis.hijri_day, day.date.day() as usize)
+                } else {
+                    (day.date.day() as usize, day.analysis.hijri_day)
+                };
+                let mut style = if day.in_month { status_style(day.analysis.primary_status) } else { Style::new().dim() };
+                if day.date == self.selected {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                Line::from(vec![
+                    Span::raw(format!("{:>2}", primary)),
+                    Span::raw(format!("{:>3}", secondary)).dim(),
+                ])
+                .style(style)
+            }))
+        });
+        Table::new(rows, [Constraint::Length(5); 7])
+            .header(header)
+            .block(Block::bordered().title(self.title()))
+    }
+
+    /// The anchored month with the other calendar's span, e.g.
+    /// "March 2024 · Sha'ban-Ramadhan 1445".
+    fn title(&self) -> String {
+        let locale = &self.options.locale;
+        let mut days = self.view.days_in_month();
+        let (Some(first), Some(last)) = (days.next(), days.last()) else {
+            return String::new();
+        };
+        let (first, last) = (&first.analysis, &last.analysis);
+        if self.hijri {
+            let span = span(
+                (locale.gregorian_month_name(self.view.first.month()), self.view.first.year()),
+                (locale.gregorian_month_name(self.view.last.month()), self.view.last.year()),
+            );
+            format!(" {} {} · {} ", locale.month_name(first.hijri_month), first.hijri_year, span)
+        } else {
+            let span = span(
+                (locale.month_name(first.hijri_month), first.hijri_year as i32),
+                (locale.month_name(last.hijri_month), last.hijri_year as i32),
+            );
+            format!(" {} {} · {} ", locale.gregorian_month_name(self.view.first.month()), self.view.first.year(), span)
+        }
+    }
+
+    fn detail(&self) -> Paragraph<'_> {
+        let report = &self.report;
+        let ui = &self.options.locale.ui;
+        let mut lines = vec![
+            Line::from(report.heading.as_str()).bold(),
+            Line::from(report.description.as_str()),
+            Line::default(),
+            Line::from(vec![Span::raw(format!("{}: ", ui.status)), Span::styled(report.status_name.as_str(), status_style(report.status))]),
+        ];
+        if !report.reasons.is_empty() {
+            lines.push(Line::default());
+            lines.push(Line::from(ui.reasons.as_str()).bold());
+            lines.extend(report.reasons.iter().map(|r| Line::from(format!("  • {}", r))));
+        }
+        if !report.traces.is_empty() {
+            lines.push(Line::default());
+            lines.push(Line::from(ui.traces.as_str()).bold());
+            lines.extend(report.traces.iter().map(|t| Line::from(format!("  {}", t)).dim()));
+        }
+        lines.push(Line::default());
+        lines.push(Line::from(ui.prayer_times.as_str()).bold());
+        match &report.prayer_times {
+            Some(times) => lines.extend(times.iter().map(|(label, time)| Line::from(format!("  {:<10}{}", label, time)))),
+            None => lines.push(Line::from(format!("  {}", ui.no_location)).dim()),
+        }
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Block::bordered().title(format!(" {} ", self.options.locale.name)))
+    }
+}
+
+/// View of the Gregorian or Hijri month containing `date`.
+fn month_of(date: NaiveDate, hijri: bool, options: &Options) -> Result<MonthView> {
+    let builder = if hijri {
+        let h = options.context.hijri(date)?;
+        MonthView::hijri_builder(h.year, h.month)
+    } else {
+        MonthView::builder(date.year(), date.month())
+    };
+    Ok(builder.with_context(options.context.clone()).build()?)
+}
+
+fn is_hijri(view: &MonthView) -> bool {
+    matches!(view.anchor, MonthAnchor::Hijri { .. })
+}
+
+/// "A-B year", "A year-B year" or just "A year" when both ends match.
+fn span(first: (String, i32), last: (String, i32)) -> String {
+    if first == last {
+        format!("{} {}", first.0, first.1)
+    } else if first.1 == last.1 {
+        format!("{}-{} {}", first.0, last.0, last.1)
+    } else {
+        format!("{} {}-{} {}", first.0, first.1, last.0, last.1)
+    }
+}
+
+fn status_style(status: FastingStatus) -> Style {
+    match status {
+        FastingStatus::Haram => Style::new().fg(Color::Red).bold(),
+        FastingStatus::Wajib => Style::new().fg(Color::Green).bold(),
+        FastingStatus::SunnahMuakkadah => Style::new().fg(Color::Cyan).bold(),
+        FastingStatus::Sunnah => Style::new().fg(Color::Cyan),
+        FastingStatus::Makruh => Style::new().fg(Color::Yellow),
+        FastingStatus::Mubah => Style::new(),
+    }
+}
+
+/// Runs the calendar on the terminal until the user quits.
+pub fn run(start: NaiveDate, options: Options) -> Result<()> {
+    let mut app = App::new(start, options)?;
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use shaum_core::CalendarMethod;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate { NaiveDate::from_ymd_opt(y, m, d).unwrap() }
+
+    fn app(selected: NaiveDate) -> App {
+        App::new(selected, Options::new(Bundle::builtin("en").unwrap(), CalendarMethod::UmmAlQura)).unwrap()
+    }
+
+    fn screen(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect()
+    }
+
+    #[test]
+    fn test_navigation_follows_selection() {
+        let mut app = app(date(2024, 3, 31));
+        app.apply(Action::Move(1));
+        assert_eq!(app.selected, date(2024, 4, 1));
+        assert_eq!(app.view.anchor, MonthAnchor::Gregorian { year: 2024, month: 4 });
+        app.apply(Action::PrevMonth);
+        assert_eq!(app.selected, date(2024, 3, 1));
+        app.apply(Action::Move(7));
+        assert_eq!(app.view.anchor, MonthAnchor::Gregorian { year: 2024, month: 3 });
+    }
+
+    #[test]
+    fn test_hijri_months() {
+        let mut app = app(date(2024, 3, 15));
+        app.apply(Action::ToggleCalendar);
+        assert_eq!(app.view.anchor, MonthAnchor::Hijri { year: 1445, month: 9 });
+        app.apply(Action::NextMonth);
+        assert_eq!(app.selected, date(2024, 4, 10));
+        assert!(screen(&app).contains("Shawwal 1445 · April-May 2024"));
+    }
+
+    #[test]
+    fn test_locale_switching() {
+        let mut app = app(date(2024, 3, 15));
+        let english = screen(&app);
+        assert!(english.contains("March 2024 · Sha'ban-Ramadhan 1445"));
+        assert!(english.contains("Friday, 15 March 2024"));
+        assert!(english.contains("Wajib (Obligatory)"));
+
+        app.apply(Action::NextLocale);
+        let indonesian = screen(&app);
+        assert!(indonesian.contains("Maret 2024 · Syakban-Ramadan 1445"));
+        assert!(indonesian.contains("Jumat, 15 Maret 2024"));
+        assert!(indonesian.contains("Sen"));
+
+        app.apply(Action::NextLocale);
+        assert_eq!(app.options.locale.language, "en");
+    }
+
+    #[test]
+    fn test_errors_stay_in_footer() {
+        let mut app = app(date(2024, 3, 15));
+        app.apply(Action::Move(-365 * 1000));
+        assert!(app.error.is_some());
+        assert_eq!(app.selected, date(2024, 3, 15));
+        app.apply(Action::Quit);
+        assert!(app.quit && app.error.is_none());
+    }
+}
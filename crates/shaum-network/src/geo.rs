@@ -8,6 +8,14 @@ use shaum_types::ShaumError;
 use shaum_types::GeoCoordinate;
 #[cfg(feature = "async")]
 use serde::Deserialize;
+#[cfg(feature = "async")]
+use std::sync::Arc;
+#[cfg(feature = "async")]
+use crate::http::{get_json, HttpClient};
+#[cfg(all(feature = "async", not(target_arch = "wasm32")))]
+use crate::http::ReqwestClient;
+#[cfg(all(feature = "async", target_arch = "wasm32"))]
+use crate::http::default_client;
 
 /// Location information with coordinates and place name.
 #[derive(Debug, Clone)]
@@ -42,6 +50,24 @@ impl LocationInfo {
     }
 }
 
+/// Resolves an IP address to a location.
+#[cfg(feature = "async")]
+pub trait GeoProvider: std::fmt::Debug + Send + Sync {
+    fn locate(
+        &self,
+        ip: std::net::IpAddr,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<LocationInfo, ShaumError>> + Send + '_>>;
+}
+
+/// Resolves coordinates to a detailed address.
+#[cfg(feature = "async")]
+pub trait ReverseGeocoder: std::fmt::Debug + Send + Sync {
+    fn reverse(
+        &self,
+        coords: GeoCoordinate,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<DetailedLocationInfo, ShaumError>> + Send + '_>>;
+}
+
 // =============================================================================
 // Local MaxMind Database Lookup (privacy-preserving, offline)
 // =============================================================================
@@ -121,6 +147,82 @@ impl LocalGeoProvider {
 
 
 
+// =============================================================================
+// ipapi.co IP Geolocation (HTTP)
+// =============================================================================
+
+/// [`GeoProvider`] backed by the ipapi.co HTTP API.
+///
+/// Unlike the MaxMind-backed `LocalGeoProvider`, this sends the IP address to a third party.
+#[cfg(feature = "async")]
+#[derive(Debug, Clone)]
+pub struct IpApi {
+    client: Arc<dyn HttpClient>,
+}
+
+#[cfg(feature = "async")]
+#[derive(Debug, Deserialize)]
+struct IpApiResponse {
+    #[serde(default)]
+    error: bool,
+    reason: Option<String>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    city: Option<String>,
+    region: Option<String>,
+    country_name: Option<String>,
+}
+
+#[cfg(feature = "async")]
+impl IpApi {
+    /// Uses the platform default HTTP client.
+    pub fn new() -> Self {
+        Self { client: crate::http::default_client() }
+    }
+
+    /// Uses a custom HTTP client.
+    pub fn with_client(client: impl HttpClient + 'static) -> Self {
+        Self { client: Arc::new(client) }
+    }
+
+    /// Locates the caller's own public IP address.
+    pub async fn current(&self) -> Result<LocationInfo, ShaumError> {
+        self.fetch("https://ipapi.co/json/").await
+    }
+
+    async fn fetch(&self, url: &str) -> Result<LocationInfo, ShaumError> {
+        let data: IpApiResponse = get_json(&*self.client, url).await?;
+        if data.error {
+            return Err(ShaumError::NetworkError(format!(
+                "ipapi.co lookup failed: {}", data.reason.unwrap_or_default()
+            )));
+        }
+        let (lat, lng) = data.latitude.zip(data.longitude)
+            .ok_or_else(|| ShaumError::NetworkError("ipapi.co returned no coordinates".into()))?;
+        Ok(LocationInfo {
+            coords: GeoCoordinate::new(lat, lng)?,
+            city: data.city,
+            region: data.region,
+            country: data.country_name,
+        })
+    }
+}
+
+#[cfg(feature = "async")]
+impl Default for IpApi {
+    fn default() -> Self { Self::new() }
+}
+
+#[cfg(feature = "async")]
+impl GeoProvider for IpApi {
+    fn locate(
+        &self,
+        ip: std::net::IpAddr,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<LocationInfo, ShaumError>> + Send + '_>> {
+        Box::pin(async move { self.fetch(&format!("https://ipapi.co/{}/json/", ip)).await })
+    }
+}
+
 // =============================================================================
 // Nominatim Reverse Geocoding (OpenStreetMap - detailed address lookup)
 // =============================================================================
@@ -196,10 +298,6 @@ struct NominatimAddress {
     province: Option<String>,
     
     country: Option<String>,
-    
-    // Fallback fields
-    #[serde(rename = "ISO3166-2-lvl4")]
-    iso_province: Option<String>,
 }
 
 /// Performs reverse geocoding using OpenStreetMap Nominatim API.
@@ -211,10 +309,10 @@ struct NominatimAddress {
 ///
 /// # Example
 /// ```rust,no_run
-/// use shaum_core::network::geo::reverse_geocode;
-/// use shaum_core::types::GeoCoordinate;
+/// use shaum_network::geo::reverse_geocode;
+/// use shaum_types::GeoCoordinate;
 ///
-/// #[tokio::main]
+/// #[tokio::main(flavor = "current_thread")]
 /// async fn main() {
 ///     let coords = GeoCoordinate::new(-7.8195, 110.3610).unwrap();
 ///     let info = reverse_geocode(coords).await.unwrap();
@@ -226,27 +324,23 @@ struct NominatimAddress {
 /// ```
 #[cfg(feature = "async")]
 pub async fn reverse_geocode(coords: GeoCoordinate) -> Result<DetailedLocationInfo, ShaumError> {
-    let client = reqwest::Client::builder()
-        .user_agent("shaum-lib/0.6.0 (Islamic prayer times library)")
-        .build()
-        .map_err(|e| ShaumError::NetworkError(format!("Failed to create HTTP client: {}", e)))?;
-    
+    Nominatim::new()?.reverse(coords).await
+}
+
+#[cfg(feature = "async")]
+const NOMINATIM_USER_AGENT: &str = "shaum-lib/0.6.0 (Islamic prayer times library)";
+
+#[cfg(feature = "async")]
+async fn nominatim_lookup(client: &dyn HttpClient, coords: GeoCoordinate) -> Result<DetailedLocationInfo, ShaumError> {
     let url = format!(
         "https://nominatim.openstreetmap.org/reverse?lat={}&lon={}&format=json&addressdetails=1&accept-language=id",
         coords.lat, coords.lng
     );
     
-    let response = client
-        .get(&url)
-        .send()
+    let data: NominatimResponse = get_json(client, &url)
         .await
         .map_err(|e| ShaumError::NetworkError(format!("Nominatim request failed: {}", e)))?;
     
-    let data: NominatimResponse = response
-        .json()
-        .await
-        .map_err(|e| ShaumError::NetworkError(format!("Failed to parse Nominatim response: {}", e)))?;
-    
     let addr = &data.address;
     
     // Extract kelurahan (village level)
@@ -274,6 +368,40 @@ pub async fn reverse_geocode(coords: GeoCoordinate) -> Result<DetailedLocationIn
     })
 }
 
+/// [`ReverseGeocoder`] backed by OpenStreetMap Nominatim. See [`reverse_geocode`].
+#[cfg(feature = "async")]
+#[derive(Debug, Clone)]
+pub struct Nominatim {
+    client: Arc<dyn HttpClient>,
+}
+
+#[cfg(feature = "async")]
+impl Nominatim {
+    /// Uses the platform default HTTP client (`fetch` on wasm32).
+    pub fn new() -> Result<Self, ShaumError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        let client: Arc<dyn HttpClient> = Arc::new(ReqwestClient::with_user_agent(NOMINATIM_USER_AGENT)?);
+        // Browsers do not allow overriding the User-Agent.
+        #[cfg(target_arch = "wasm32")]
+        let client = default_client();
+        Ok(Self { client })
+    }
+
+    /// Uses a custom HTTP client.
+    pub fn with_client(client: impl HttpClient + 'static) -> Self {
+        Self { client: Arc::new(client) }
+    }
+}
+
+#[cfg(feature = "async")]
+impl ReverseGeocoder for Nominatim {
+    fn reverse(
+        &self,
+        coords: GeoCoordinate,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<DetailedLocationInfo, ShaumError>> + Send + '_>> {
+        Box::pin(nominatim_lookup(&*self.client, coords))
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -305,8 +433,16 @@ mod tests {
     #[tokio::test]
     #[ignore]
     async fn test_get_location_info_http() {
-        #[allow(deprecated)]
-        let result = get_location_info_from_ip().await;
+        let result = IpApi::new().current().await;
+        assert!(result.is_ok());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    #[ignore]
+    async fn test_nominatim_reverse_http() {
+        let coords = GeoCoordinate::new(-7.8195, 110.3610).unwrap();
+        let result = reverse_geocode(coords).await;
         assert!(result.is_ok());
     }
 }
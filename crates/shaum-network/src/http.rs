@@ -0,0 +1,139 @@
+//! Minimal HTTP abstraction shared by network-backed providers.
+//!
+//! Native targets use `reqwest`; `wasm32` uses the browser `fetch` API so the
+//! same providers work inside the WASM bindings.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use shaum_types::ShaumError;
+
+/// Future returned by [`HttpClient::get`], resolving to the response body.
+pub type HttpFuture<'a> = Pin<Box<dyn Future<Output = Result<String, ShaumError>> + Send + 'a>>;
+
+/// Performs GET requests. Non-2xx responses must be reported as errors.
+pub trait HttpClient: std::fmt::Debug + Send + Sync {
+    fn get(&self, url: &str) -> HttpFuture<'_>;
+}
+
+/// Platform default client: [`ReqwestClient`] natively, [`FetchClient`] on `wasm32`.
+pub fn default_client() -> Arc<dyn HttpClient> {
+    #[cfg(not(target_arch = "wasm32"))]
+    { Arc::new(ReqwestClient::new()) }
+    #[cfg(target_arch = "wasm32")]
+    { Arc::new(FetchClient) }
+}
+
+/// GETs `url` and decodes the body as JSON.
+pub async fn get_json<T: DeserializeOwned>(client: &dyn HttpClient, url: &str) -> Result<T, ShaumError> {
+    let body = client.get(url).await?;
+    serde_json::from_str(&body)
+        .map_err(|e| ShaumError::NetworkError(format!("Invalid JSON from {}: {}", url, e)))
+}
+
+// =============================================================================
+// Native (reqwest)
+// =============================================================================
+
+/// `reqwest`-backed client.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestClient {
+    client: reqwest::Client,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ReqwestClient {
+    pub fn new() -> Self { Self::default() }
+
+    /// Client sending the given `User-Agent` header.
+    pub fn with_user_agent(user_agent: &str) -> Result<Self, ShaumError> {
+        let client = reqwest::Client::builder()
+            .user_agent(user_agent)
+            .build()
+            .map_err(|e| ShaumError::NetworkError(format!("Failed to create HTTP client: {}", e)))?;
+        Ok(Self { client })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl HttpClient for ReqwestClient {
+    fn get(&self, url: &str) -> HttpFuture<'_> {
+        let request = self.client.get(url);
+        Box::pin(async move {
+            let resp = request
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(|e| ShaumError::NetworkError(e.to_string()))?;
+            resp.text().await.map_err(|e| ShaumError::NetworkError(e.to_string()))
+        })
+    }
+}
+
+// =============================================================================
+// Browser (fetch)
+// =============================================================================
+
+/// `fetch`-backed client for browsers and web workers.
+#[cfg(target_arch = "wasm32")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FetchClient;
+
+#[cfg(target_arch = "wasm32")]
+impl HttpClient for FetchClient {
+    fn get(&self, url: &str) -> HttpFuture<'_> {
+        let url = url.to_owned();
+        Box::pin(AssertSend(async move { fetch_text(&url).await }))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn fetch_text(url: &str) -> Result<String, ShaumError> {
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+
+    let js_err = |e: JsValue| ShaumError::NetworkError(format!("fetch failed: {:?}", e));
+
+    let global = js_sys::global();
+    let promise = if let Some(window) = global.dyn_ref::<web_sys::Window>() {
+        window.fetch_with_str(url)
+    } else if let Some(worker) = global.dyn_ref::<web_sys::WorkerGlobalScope>() {
+        worker.fetch_with_str(url)
+    } else {
+        return Err(ShaumError::NetworkError("fetch is not available in this environment".into()));
+    };
+
+    let resp: web_sys::Response = JsFuture::from(promise)
+        .await
+        .map_err(js_err)?
+        .dyn_into()
+        .map_err(js_err)?;
+    if !resp.ok() {
+        return Err(ShaumError::NetworkError(format!("HTTP {} from {}", resp.status(), url)));
+    }
+
+    let text = JsFuture::from(resp.text().map_err(js_err)?).await.map_err(js_err)?;
+    text.as_string()
+        .ok_or_else(|| ShaumError::NetworkError(format!("Non-text response from {}", url)))
+}
+
+/// JS futures are `!Send`, but `wasm32` is single-threaded so they never cross threads.
+#[cfg(target_arch = "wasm32")]
+pub(crate) struct AssertSend<F>(pub(crate) F);
+
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+// SAFETY: wasm32 without atomics has a single thread.
+unsafe impl<F> Send for AssertSend<F> {}
+
+#[cfg(target_arch = "wasm32")]
+impl<F: Future> Future for AssertSend<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<F::Output> {
+        // SAFETY: structural pinning of the only field.
+        unsafe { self.map_unchecked_mut(|s| &mut s.0) }.poll(cx)
+    }
+}
@@ -1,3 +1,9 @@
 //! Network features for Shaum - IP geolocation and remote APIs.
 
 pub mod geo;
+#[cfg(feature = "async")]
+pub mod http;
+#[cfg(feature = "async")]
+pub mod time;
+#[cfg(feature = "async")]
+pub mod mock;
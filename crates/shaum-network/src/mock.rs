@@ -0,0 +1,151 @@
+//! Programmable mock providers for tests.
+//!
+//! Each mock replays a script of responses in order, then repeats a fallback
+//! response. Latency can be injected per call. Mocks are cheap to clone and
+//! clones share the same script and call counter.
+//!
+//! ```rust,no_run
+//! use shaum_network::mock::MockMoonProvider;
+//! use shaum_types::ShaumError;
+//!
+//! let moon = MockMoonProvider::always(Ok(0))
+//!     .then(Ok(1))
+//!     .then(Err(ShaumError::NetworkError("timeout".into())));
+//! ```
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use shaum_types::{GeoCoordinate, ShaumError};
+
+use crate::geo::{DetailedLocationInfo, GeoProvider, LocationInfo, ReverseGeocoder};
+
+/// Mock moon provider returning scripted adjustments.
+///
+/// `shaum-rules` implements its `MoonProvider` trait for this type.
+pub type MockMoonProvider = Mock<i64>;
+/// Mock [`GeoProvider`] returning scripted locations.
+pub type MockGeoProvider = Mock<LocationInfo>;
+/// Mock [`ReverseGeocoder`] returning scripted addresses.
+pub type MockGeocoder = Mock<DetailedLocationInfo>;
+
+/// Scripted mock provider. See module docs.
+#[derive(Debug, Clone)]
+pub struct Mock<T> {
+    inner: Arc<Mutex<Script<T>>>,
+}
+
+#[derive(Debug)]
+struct Script<T> {
+    queue: VecDeque<Result<T, ShaumError>>,
+    fallback: Result<T, ShaumError>,
+    latency: Duration,
+    calls: usize,
+}
+
+impl<T: Clone> Mock<T> {
+    /// Mock that returns `response` on every call.
+    pub fn always(response: Result<T, ShaumError>) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Script {
+                queue: VecDeque::new(),
+                fallback: response,
+                latency: Duration::ZERO,
+                calls: 0,
+            })),
+        }
+    }
+
+    /// Mock that fails every call with a `NetworkError`.
+    pub fn failing(reason: impl Into<String>) -> Self {
+        Self::always(Err(ShaumError::NetworkError(reason.into())))
+    }
+
+    /// Queues a one-shot response, served before the fallback.
+    pub fn then(self, response: Result<T, ShaumError>) -> Self {
+        self.lock().queue.push_back(response);
+        self
+    }
+
+    /// Delays every response by `latency`.
+    pub fn with_latency(self, latency: Duration) -> Self {
+        self.lock().latency = latency;
+        self
+    }
+
+    /// Number of calls served so far.
+    pub fn calls(&self) -> usize {
+        self.lock().calls
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Script<T>> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Serves the next scripted response, counting the call and applying the latency.
+    ///
+    /// Provider traits implement their request method with this.
+    pub fn respond(&self) -> Pin<Box<dyn Future<Output = Result<T, ShaumError>> + Send + '_>>
+    where
+        T: Send + 'static,
+    {
+        let (response, latency) = {
+            let mut script = self.lock();
+            script.calls += 1;
+            let response = script.queue.pop_front().unwrap_or_else(|| script.fallback.clone());
+            (response, script.latency)
+        };
+        Box::pin(async move {
+            if !latency.is_zero() {
+                crate::time::sleep(latency).await;
+            }
+            response
+        })
+    }
+}
+
+impl GeoProvider for Mock<LocationInfo> {
+    fn locate(
+        &self,
+        _ip: std::net::IpAddr,
+    ) -> Pin<Box<dyn Future<Output = Result<LocationInfo, ShaumError>> + Send + '_>> {
+        self.respond()
+    }
+}
+
+impl ReverseGeocoder for Mock<DetailedLocationInfo> {
+    fn reverse(
+        &self,
+        _coords: GeoCoordinate,
+    ) -> Pin<Box<dyn Future<Output = Result<DetailedLocationInfo, ShaumError>> + Send + '_>> {
+        self.respond()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mock_moon_provider_script() {
+        let moon = MockMoonProvider::always(Ok(0))
+            .then(Ok(1))
+            .then(Err(ShaumError::NetworkError("down".into())));
+
+        assert_eq!(moon.respond().await.unwrap(), 1);
+        assert!(moon.respond().await.is_err());
+        assert_eq!(moon.respond().await.unwrap(), 0);
+        assert_eq!(moon.calls(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_mock_latency() {
+        let geo = MockGeoProvider::failing("offline").with_latency(Duration::from_millis(20));
+        let start = std::time::Instant::now();
+        assert!(geo.locate("8.8.8.8".parse().unwrap()).await.is_err());
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+}
@@ -0,0 +1,81 @@
+//! Runtime-agnostic timer shared by the mocks and paced bulk requests.
+//!
+//! Native targets wake the task from a helper thread; `wasm32` uses
+//! `setTimeout`, so no async runtime needs to be linked.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+/// Future returned by [`sleep`].
+pub type Sleep = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Resolves once `duration` has elapsed.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn sleep(duration: Duration) -> Sleep {
+    Box::pin(Delay { duration, state: None })
+}
+
+/// Elapsed flag plus the waker to notify.
+#[cfg(not(target_arch = "wasm32"))]
+type TimerState = std::sync::Arc<std::sync::Mutex<(bool, Option<std::task::Waker>)>>;
+
+/// Helper-thread sleep: the thread wakes the task once the deadline passes.
+#[cfg(not(target_arch = "wasm32"))]
+struct Delay {
+    duration: Duration,
+    state: Option<TimerState>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<()> {
+        let duration = self.duration;
+        let state = self.state.get_or_insert_with(|| {
+            let state = TimerState::default();
+            let timer = state.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(duration);
+                let mut guard = timer.lock().unwrap_or_else(|e| e.into_inner());
+                guard.0 = true;
+                if let Some(waker) = guard.1.take() {
+                    waker.wake();
+                }
+            });
+            state
+        });
+        let mut guard = state.lock().unwrap_or_else(|e| e.into_inner());
+        if guard.0 {
+            std::task::Poll::Ready(())
+        } else {
+            guard.1 = Some(cx.waker().clone());
+            std::task::Poll::Pending
+        }
+    }
+}
+
+/// Resolves once `duration` has elapsed.
+#[cfg(target_arch = "wasm32")]
+pub fn sleep(duration: Duration) -> Sleep {
+    use wasm_bindgen::{JsCast, JsValue};
+
+    let millis = i32::try_from(duration.as_millis()).unwrap_or(i32::MAX);
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        let global = js_sys::global();
+        let scheduled = if let Some(window) = global.dyn_ref::<web_sys::Window>() {
+            window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, millis).is_ok()
+        } else if let Some(worker) = global.dyn_ref::<web_sys::WorkerGlobalScope>() {
+            worker.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, millis).is_ok()
+        } else {
+            false
+        };
+        if !scheduled {
+            let _ = resolve.call0(&JsValue::NULL);
+        }
+    });
+    Box::pin(crate::http::AssertSend(async move {
+        let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+    }))
+}
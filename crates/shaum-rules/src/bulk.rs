@@ -0,0 +1,322 @@
+//! Bulk construction of a year's calendar from an announcement provider.
+//!
+//! [`BulkCalendarBuilder`] asks a [`MoonProvider`] about every month of a
+//! Hijri year, one request per month probed mid-month, and assembles the
+//! answers into a [`TableCalendar`]. Each answer is an adjustment (positive =
+//! Hijri ahead), so the month's start moves by
+//! [`adjustment_to_month_offset`] of it. Requests run concurrently up to
+//! [`with_concurrency`](BulkCalendarBuilder::with_concurrency) and are spaced
+//! to honour [`with_rate_limit`](BulkCalendarBuilder::with_rate_limit).
+//! A month whose request fails, or whose announced start the table rejects,
+//! keeps the fallback calendar's start and is listed in
+//! [`BulkCalendar::failures`].
+//!
+//! The orchestrator needs no async runtime: waits use a timer thread
+//! natively and `setTimeout` on `wasm32`.
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use shaum_rules::bulk::BulkCalendarBuilder;
+//! use shaum_rules::{RemoteMoonProvider, RuleContext};
+//!
+//! # async fn run() -> Result<(), shaum_types::ShaumError> {
+//! let provider = RemoteMoonProvider::new("https://example.org/adjustment");
+//! let bulk = BulkCalendarBuilder::new(&provider, 1446)
+//!     .with_concurrency(3)
+//!     .with_rate_limit(5, Duration::from_secs(1))
+//!     .build()
+//!     .await?;
+//! for failure in bulk.failures() {
+//!     eprintln!("{}-{}: {:?}", failure.year, failure.month, failure.result);
+//! }
+//! let ctx = RuleContext::new().with_calendar(bulk.calendar);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Poll;
+use std::time::Duration;
+
+use chrono::NaiveDate;
+use shaum_calendar::{HijriCalendar, HijriYmd, TableCalendar, UmmAlQura};
+use shaum_network::time::sleep;
+use shaum_types::{GeoCoordinate, ShaumError};
+
+use crate::rules::{adjustment_to_month_offset, MoonProvider};
+
+/// Requests in flight at once unless configured otherwise.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Day of the month, counted from its calculated start, sent to the provider.
+const PROBE_DAY_OFFSET: i64 = 14;
+
+type Pending<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Outcome of one month's request.
+#[derive(Debug, Clone)]
+pub struct MonthFetch {
+    pub year: usize,
+    pub month: usize,
+    /// Announced first day of the month, or why the month kept the fallback.
+    pub result: Result<NaiveDate, ShaumError>,
+}
+
+impl MonthFetch {
+    pub fn is_ok(&self) -> bool { self.result.is_ok() }
+}
+
+/// A year's calendar assembled from announcements.
+#[derive(Debug, Clone)]
+pub struct BulkCalendar {
+    /// Announced starts over the fallback calendar.
+    pub calendar: TableCalendar,
+    /// One entry per requested month, in month order.
+    pub months: Vec<MonthFetch>,
+}
+
+impl BulkCalendar {
+    /// Months that kept the fallback's start.
+    pub fn failures(&self) -> impl Iterator<Item = &MonthFetch> {
+        self.months.iter().filter(|m| !m.is_ok())
+    }
+
+    /// Whether every requested month was announced and accepted.
+    pub fn is_complete(&self) -> bool { self.months.iter().all(MonthFetch::is_ok) }
+}
+
+/// Builder for a [`BulkCalendar`]. See the module docs.
+#[derive(Debug)]
+pub struct BulkCalendarBuilder<'a> {
+    provider: &'a dyn MoonProvider,
+    year: usize,
+    months: Vec<usize>,
+    coords: Option<GeoCoordinate>,
+    concurrency: usize,
+    rate_limit: Option<(u32, Duration)>,
+    table: TableCalendar,
+}
+
+impl<'a> BulkCalendarBuilder<'a> {
+    /// Every month of `year` AH from `provider`, over [`UmmAlQura`].
+    pub fn new(provider: &'a dyn MoonProvider, year: usize) -> Self {
+        Self {
+            provider,
+            year,
+            months: (1..=12).collect(),
+            coords: None,
+            concurrency: DEFAULT_CONCURRENCY,
+            rate_limit: None,
+            table: TableCalendar::new(UmmAlQura),
+        }
+    }
+
+    /// Only these months (1-12) of the year.
+    pub fn with_months(mut self, months: impl IntoIterator<Item = usize>) -> Self {
+        self.months = months.into_iter().collect();
+        self.months.sort_unstable();
+        self.months.dedup();
+        self
+    }
+
+    /// Location passed to every request.
+    pub fn with_coords(mut self, coords: GeoCoordinate) -> Self {
+        self.coords = Some(coords);
+        self
+    }
+
+    /// Largest number of requests in flight at once (at least 1).
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// At most `requests` request starts per `per`, spaced evenly.
+    pub fn with_rate_limit(mut self, requests: u32, per: Duration) -> Self {
+        self.rate_limit = Some((requests.max(1), per));
+        self
+    }
+
+    /// Calendar for the months not announced, and the reference each
+    /// announcement is checked against.
+    pub fn with_fallback(mut self, fallback: impl HijriCalendar + 'static) -> Self {
+        self.table = TableCalendar::new(fallback);
+        self
+    }
+
+    /// Fetches every month and assembles the calendar.
+    ///
+    /// # Errors
+    /// `ValidationError` for a month outside 1-12, or any error computing a
+    /// month's fallback start. Failed requests are reported per month instead.
+    pub async fn build(self) -> Result<BulkCalendar, ShaumError> {
+        let starts = self.months.iter()
+            .map(|&month| {
+                if !(1..=12).contains(&month) {
+                    return Err(ShaumError::ValidationError(format!("Invalid Hijri month: {}", month)));
+                }
+                HijriYmd::new(self.year, month, 1).to_gregorian(&self.table)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let provider = self.provider;
+        let coords = self.coords;
+        let requests: Vec<Pending<'a, Result<i64, ShaumError>>> = starts.iter()
+            .map(|start| provider.get_adjustment(*start + chrono::Duration::days(PROBE_DAY_OFFSET), coords))
+            .collect();
+        let interval = self.rate_limit.map(|(requests, per)| per / requests);
+        let adjustments = run_paced(requests, self.concurrency, interval).await;
+
+        let mut table = self.table;
+        let mut months = Vec::with_capacity(self.months.len());
+        for ((month, reference), adjustment) in self.months.into_iter().zip(starts).zip(adjustments) {
+            let adjustment = adjustment.unwrap_or_else(|| {
+                Err(ShaumError::MoonProviderError("request dropped before completing".into()))
+            });
+            let result = adjustment.and_then(|adj| {
+                let start = reference + chrono::Duration::days(adjustment_to_month_offset(adj));
+                table = table.clone().with_month_start(self.year, month, start)?;
+                Ok(start)
+            });
+            months.push(MonthFetch { year: self.year, month, result });
+        }
+        Ok(BulkCalendar { calendar: table, months })
+    }
+}
+
+/// Drives `requests` with at most `concurrency` in flight and at least
+/// `interval` between consecutive starts; results come back in input order,
+/// `None` for a request that never completed.
+async fn run_paced<'a, T: Send + 'a>(
+    requests: Vec<Pending<'a, T>>,
+    concurrency: usize,
+    interval: Option<Duration>,
+) -> Vec<Option<T>> {
+    let mut results: Vec<Option<T>> = std::iter::repeat_with(|| None).take(requests.len()).collect();
+    let mut queue = requests.into_iter().enumerate();
+    let mut next = queue.next();
+    let mut in_flight: Vec<(usize, Pending<'a, T>)> = Vec::with_capacity(concurrency);
+    let mut pace: Option<Pending<'static, ()>> = None;
+
+    std::future::poll_fn(|cx| loop {
+        in_flight.retain_mut(|(index, request)| match request.as_mut().poll(cx) {
+            Poll::Ready(value) => {
+                results[*index] = Some(value);
+                false
+            }
+            Poll::Pending => true,
+        });
+        if pace.as_mut().is_some_and(|p| p.as_mut().poll(cx).is_ready()) {
+            pace = None;
+        }
+        if pace.is_none() && in_flight.len() < concurrency && let Some(request) = next.take() {
+            in_flight.push(request);
+            next = queue.next();
+            if next.is_some() && let Some(interval) = interval {
+                pace = Some(sleep(interval));
+            }
+            continue;
+        }
+        return if in_flight.is_empty() && next.is_none() { Poll::Ready(()) } else { Poll::Pending };
+    })
+    .await;
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Wake, Waker};
+    use std::time::Instant;
+
+    use crate::rules::{FixedAdjustment, MonthSightings};
+
+    /// Wakes the blocked test thread.
+    struct Unpark(std::thread::Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) { self.0.unpark(); }
+    }
+
+    /// Minimal executor: polls `future` on this thread until it completes.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let waker = Waker::from(Arc::new(Unpark(std::thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+            std::thread::park();
+        }
+    }
+
+    #[test]
+    fn test_run_paced_limits_concurrency_and_keeps_order() {
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let requests: Vec<Pending<'static, usize>> = (0..6usize)
+            .map(|i| {
+                let (active, peak) = (active.clone(), peak.clone());
+                Box::pin(async move {
+                    let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    // Later requests finish first.
+                    sleep(Duration::from_millis(30 - 5 * i as u64)).await;
+                    active.fetch_sub(1, Ordering::SeqCst);
+                    i
+                }) as Pending<'static, usize>
+            })
+            .collect();
+
+        let results = block_on(run_paced(requests, 2, None));
+        assert_eq!(results, (0..6).map(Some).collect::<Vec<_>>());
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        assert_eq!(active.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_run_paced_spaces_starts() {
+        let starts = Arc::new(Mutex::new(Vec::new()));
+        let requests: Vec<Pending<'static, ()>> = (0..4)
+            .map(|_| {
+                let starts = starts.clone();
+                Box::pin(async move { starts.lock().unwrap().push(Instant::now()) }) as Pending<'static, ()>
+            })
+            .collect();
+
+        let interval = Duration::from_millis(15);
+        block_on(run_paced(requests, 4, Some(interval)));
+        let starts = starts.lock().unwrap();
+        assert_eq!(starts.len(), 4);
+        assert!(starts.windows(2).all(|w| w[1] - w[0] >= interval), "{:?}", *starts);
+    }
+
+    #[test]
+    fn test_build_without_runtime() {
+        // Ramadhan 1445 announced a day late; Shawwal beyond the table's two days.
+        let provider = MonthSightings::new().with_month(1445, 9, 1).with_month(1445, 10, 3);
+        let bulk = block_on(
+            BulkCalendarBuilder::new(&provider, 1445)
+                .with_months([8, 9, 10])
+                .with_concurrency(1)
+                .with_rate_limit(3, Duration::from_millis(30))
+                .build(),
+        )
+        .unwrap();
+        assert_eq!(bulk.calendar.table_month_start(1445, 9), NaiveDate::from_ymd_opt(2024, 3, 12));
+        assert_eq!(bulk.failures().map(|m| m.month).collect::<Vec<_>>(), vec![10]);
+    }
+
+    #[test]
+    fn test_build_moves_starts_against_the_adjustment() {
+        // Hijri one day ahead: Ramadhan 1445 starts a day before Umm al-Qura's 11 March.
+        let provider = FixedAdjustment(1);
+        let bulk = block_on(BulkCalendarBuilder::new(&provider, 1445).with_months([9]).build()).unwrap();
+        assert_eq!(bulk.calendar.table_month_start(1445, 9), NaiveDate::from_ymd_opt(2024, 3, 10));
+    }
+}
@@ -0,0 +1,159 @@
+//! Fitting the engine to locally observed month starts.
+//!
+//! Communities often start months a day before or after the calculated
+//! calendar. Given a few dates the user knows ("our mosque started Ramadhan
+//! on 12 March"), [`calibrate`] finds the [`RuleContext::adjustment`] that
+//! fits them best, adds per-month [`MonthSightings`] corrections for what the
+//! adjustment alone cannot explain, and reports the remaining residuals.
+
+use std::collections::BTreeSet;
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use shaum_calendar::HijriYmd;
+use shaum_types::ShaumError;
+
+use crate::rules::{MonthSightings, RuleContext};
+
+/// A Hijri date the user observed locally on `date`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Observation {
+    pub date: NaiveDate,
+    pub hijri: HijriYmd,
+}
+
+impl Observation {
+    pub fn new(date: NaiveDate, hijri: HijriYmd) -> Self { Self { date, hijri } }
+
+    /// `month` `year` AH started locally on `date`.
+    pub fn month_start(date: NaiveDate, year: usize, month: usize) -> Self {
+        Self::new(date, HijriYmd::new(year, month, 1))
+    }
+
+    /// Local first day of the observed month.
+    fn observed_start(&self) -> NaiveDate {
+        self.date - Duration::days(self.hijri.day as i64 - 1)
+    }
+}
+
+/// How far the fitted context is from one observation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Residual {
+    pub observation: Observation,
+    /// Days the local month started after the fitted context's start
+    /// (negative: before). Zero when the context reproduces the observation.
+    pub days: i64,
+}
+
+/// Result of [`calibrate`].
+#[derive(Debug, Clone)]
+pub struct Calibration {
+    /// Best-fitting [`RuleContext::adjustment`].
+    pub adjustment: i64,
+    /// Per-month corrections on top of `adjustment`; empty when the
+    /// adjustment alone fits every observation.
+    pub sightings: MonthSightings,
+    /// One per observation, in input order, under [`Self::context`].
+    pub residuals: Vec<Residual>,
+    /// The input context with `adjustment` and, if needed, `sightings` as
+    /// its moon provider.
+    pub context: RuleContext,
+}
+
+impl Calibration {
+    /// Every observation is reproduced exactly.
+    pub fn is_exact(&self) -> bool { self.residuals.iter().all(|r| r.days == 0) }
+
+    /// Largest residual in days, ignoring sign.
+    pub fn max_residual(&self) -> i64 {
+        self.residuals.iter().map(|r| r.days.abs()).max().unwrap_or(0)
+    }
+}
+
+/// Fits `context` to `observations`.
+///
+/// The adjustment minimizing the total residual is chosen first (the one
+/// closest to zero on ties); observations it leaves unexplained become
+/// [`MonthSightings`] entries, which replace any moon provider on `context`.
+/// Residuals that remain (conflicting observations, or corrections beyond the
+/// ±2 days a sighting may move a month) are reported rather than hidden.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_rules::calibrate::{calibrate, Observation};
+/// use shaum_rules::RuleContext;
+///
+/// // Ramadhan 1445 and Shawwal 1445 each began a day after Umm al-Qura.
+/// let observations = [
+///     Observation::month_start(NaiveDate::from_ymd_opt(2024, 3, 12).unwrap(), 1445, 9),
+///     Observation::month_start(NaiveDate::from_ymd_opt(2024, 4, 11).unwrap(), 1445, 10),
+/// ];
+/// let fit = calibrate(RuleContext::default(), &observations).unwrap();
+/// assert_eq!(fit.adjustment, -1);
+/// assert!(fit.is_exact());
+/// let eid = fit.context.hijri(NaiveDate::from_ymd_opt(2024, 4, 11).unwrap()).unwrap();
+/// assert_eq!((eid.month, eid.day), (10, 1));
+/// ```
+///
+/// # Errors
+/// `ValidationError` for no observations or an impossible Hijri date, or any
+/// conversion error.
+pub fn calibrate(mut context: RuleContext, observations: &[Observation]) -> Result<Calibration, ShaumError> {
+    if observations.is_empty() {
+        return Err(ShaumError::ValidationError("At least one observation is required".to_string()));
+    }
+    if let Some(o) = observations.iter().find(|o| !(1..=12).contains(&o.hijri.month) || !(1..=30).contains(&o.hijri.day)) {
+        return Err(ShaumError::ValidationError(format!(
+            "Invalid observed Hijri date {}-{}-{}", o.hijri.year, o.hijri.month, o.hijri.day
+        )));
+    }
+
+    // Candidates: the adjustment undoing each residual, and its neighbours
+    // (month lengths make the effect of an adjustment uneven).
+    let base = residuals(&context, observations)?;
+    let mut candidates: Vec<i64> = base.iter()
+        .flat_map(|r| [-r.days - 1, -r.days, -r.days + 1])
+        .chain([0])
+        .map(|a| (context.adjustment + a).clamp(-30, 30))
+        .collect();
+    candidates.sort_by_key(|a| (a.abs(), *a));
+    candidates.dedup();
+
+    // Trials reuse the caller's context in place: cloning it would drop the
+    // custom rules and sunset provider.
+    let original = context.adjustment;
+    let mut best: Option<(i64, i64)> = None;
+    for adjustment in candidates {
+        context.adjustment = adjustment;
+        let cost: i64 = residuals(&context, observations)?.iter().map(|r| r.days.abs()).sum();
+        if best.is_none_or(|(_, c)| cost < c) {
+            best = Some((adjustment, cost));
+        }
+    }
+    let adjustment = best.map_or(original, |(a, _)| a);
+    context.adjustment = adjustment;
+
+    // The first observation of each month decides its correction.
+    let mut sightings = MonthSightings::new();
+    let mut seen = BTreeSet::new();
+    for r in residuals(&context, observations)? {
+        let (year, month) = (r.observation.hijri.year, r.observation.hijri.month);
+        if seen.insert((year, month)) && r.days != 0 {
+            sightings = sightings.with_month(year, month, r.days);
+        }
+    }
+    if !sightings.is_empty() {
+        context = context.with_moon_provider(sightings.clone());
+    }
+    let residuals = residuals(&context, observations)?;
+    Ok(Calibration { adjustment, sightings, residuals, context })
+}
+
+fn residuals(context: &RuleContext, observations: &[Observation]) -> Result<Vec<Residual>, ShaumError> {
+    observations.iter()
+        .map(|&observation| {
+            let predicted = context.hijri_month_start(observation.hijri.year, observation.hijri.month)?;
+            Ok(Residual { observation, days: (observation.observed_start() - predicted).num_days() })
+        })
+        .collect()
+}
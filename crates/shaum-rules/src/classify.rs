@@ -0,0 +1,66 @@
+//! Const-evaluable day classification.
+//!
+//! A reduced form of [`crate::analyze`] that needs no calendar, context or
+//! allocation: callers supply the Hijri month/day and weekday. Custom rules,
+//! Maghrib boundaries and traces are out of scope. The result matches `analyze`
+//! with the default context: Shafi'i, with no habit exempting late Sha'ban.
+//! The day conditions are the built-in rules' own (see [`crate::registry`]).
+
+use chrono::Weekday;
+use shaum_types::FastingStatus;
+use crate::registry::{
+    is_arafah, is_ashura, is_day_of_doubt, is_dhul_hijjah_ten, is_eid_al_adha, is_eid_al_fitr, is_late_shaban,
+    is_ramadhan, is_shawwal_fast, is_tashriq, is_tasua, is_white_day,
+};
+
+/// Fasting status of a day from its Hijri month/day and weekday.
+///
+/// ```rust
+/// use chrono::Weekday;
+/// use shaum_rules::classify;
+/// use shaum_types::FastingStatus;
+///
+/// // Compile-time lookup, e.g. for firmware tables.
+/// const EID: FastingStatus = classify(10, 1, Weekday::Wed);
+/// assert_eq!(EID, FastingStatus::Haram);
+/// ```
+pub const fn classify(hijri_month: usize, hijri_day: usize, weekday: Weekday) -> FastingStatus {
+    let (m, d) = (hijri_month, hijri_day);
+
+    // Haram
+    if is_eid_al_fitr(m, d) || is_eid_al_adha(m, d) || is_tashriq(m, d) {
+        return FastingStatus::Haram;
+    }
+
+    // Wajib
+    if is_ramadhan(m) {
+        return FastingStatus::Wajib;
+    }
+
+    // Makruh: second half of Sha'ban, even on a Monday, Thursday or white day
+    if is_late_shaban(m, d) || is_day_of_doubt(m, d) {
+        return FastingStatus::Makruh;
+    }
+
+    // Sunnah Muakkadah
+    if is_arafah(m, d) || is_ashura(m, d) {
+        return FastingStatus::SunnahMuakkadah;
+    }
+
+    // Sunnah
+    if is_tasua(m, d)
+        || is_white_day(d)
+        || matches!(weekday, Weekday::Mon | Weekday::Thu)
+        || is_shawwal_fast(m, d)
+        || is_dhul_hijjah_ten(m, d)
+    {
+        return FastingStatus::Sunnah;
+    }
+
+    // Makruh: Friday or Saturday singled out
+    if matches!(weekday, Weekday::Fri | Weekday::Sat) {
+        return FastingStatus::Makruh;
+    }
+
+    FastingStatus::Mubah
+}
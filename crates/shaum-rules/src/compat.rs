@@ -0,0 +1,119 @@
+//! Bulk comparison of two engine configurations.
+//!
+//! Runs a baseline and a candidate [`RuleContext`] (e.g. the Umm al-Qura and
+//! Tabular backends, or an old and new adjustment) over a date range and
+//! reports every day where they disagree. The report serializes to JSON so it
+//! can gate an upgrade in CI: a one-day Eid shift shows up as a
+//! [`Severity::Critical`] divergence.
+
+use chrono::NaiveDate;
+use serde::{Serialize, Deserialize};
+use shaum_types::{FastingAnalysis, FastingStatus, ShaumError};
+
+use crate::rules::{check, RuleContext};
+
+/// How serious a divergence is for users.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Same status, different Hijri date or reasons.
+    Minor,
+    /// Different status, neither side Haram nor Wajib.
+    Major,
+    /// Different status where one side is Haram or Wajib (e.g. a shifted Eid).
+    Critical,
+}
+
+/// One day on which the two configurations disagree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Divergence {
+    pub date: NaiveDate,
+    pub severity: Severity,
+    pub baseline: FastingAnalysis,
+    pub candidate: FastingAnalysis,
+}
+
+/// Result of [`compare`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompatReport {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub days_compared: usize,
+    pub divergences: Vec<Divergence>,
+}
+
+impl CompatReport {
+    /// No divergence of any severity.
+    pub fn is_identical(&self) -> bool { self.divergences.is_empty() }
+
+    /// Divergences at or above `severity`.
+    pub fn at_least(&self, severity: Severity) -> impl Iterator<Item = &Divergence> {
+        self.divergences.iter().filter(move |d| d.severity >= severity)
+    }
+
+    /// Highest severity found, if any.
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.divergences.iter().map(|d| d.severity).max()
+    }
+}
+
+/// Compares `baseline` and `candidate` on every day from `start` to `end` inclusive.
+///
+/// # Errors
+/// `ValidationError` if `end` is before `start`; otherwise the first analysis error.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_calendar::Tabular;
+/// use shaum_rules::compat::{compare, Severity};
+/// use shaum_rules::RuleContext;
+///
+/// let report = compare(
+///     NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+///     NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+///     &RuleContext::new(),
+///     &RuleContext::new().with_calendar(Tabular::new()),
+/// ).unwrap();
+/// assert_eq!(report.days_compared, 366);
+/// for d in report.at_least(Severity::Critical) {
+///     println!("{}: {:?} vs {:?}", d.date, d.baseline.primary_status, d.candidate.primary_status);
+/// }
+/// ```
+pub fn compare(
+    start: NaiveDate,
+    end: NaiveDate,
+    baseline: &RuleContext,
+    candidate: &RuleContext,
+) -> Result<CompatReport, ShaumError> {
+    if end < start {
+        return Err(ShaumError::ValidationError(format!("Range end {} is before start {}", end, start)));
+    }
+
+    let mut divergences = Vec::new();
+    let mut days_compared = 0;
+    for date in start.iter_days().take_while(|d| *d <= end) {
+        days_compared += 1;
+        let a = check(date, baseline)?;
+        let b = check(date, candidate)?;
+        if let Some(severity) = severity(&a, &b) {
+            divergences.push(Divergence { date, severity, baseline: a, candidate: b });
+        }
+    }
+    Ok(CompatReport { start, end, days_compared, divergences })
+}
+
+fn severity(a: &FastingAnalysis, b: &FastingAnalysis) -> Option<Severity> {
+    if a.primary_status != b.primary_status {
+        let critical = |s| matches!(s, FastingStatus::Haram | FastingStatus::Wajib);
+        return Some(if critical(a.primary_status) || critical(b.primary_status) {
+            Severity::Critical
+        } else {
+            Severity::Major
+        });
+    }
+    let same_hijri = (a.hijri_year, a.hijri_month, a.hijri_day) == (b.hijri_year, b.hijri_month, b.hijri_day);
+    if !same_hijri || !a.reasons().eq(b.reasons()) {
+        return Some(Severity::Minor);
+    }
+    None
+}
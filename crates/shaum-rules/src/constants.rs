@@ -1,6 +1,7 @@
 //! Constants for Hijri months and days.
 
 pub const MONTH_MUHARRAM: usize = 1;
+pub const MONTH_SHABAN: usize = 8;
 pub const MONTH_RAMADHAN: usize = 9;
 pub const MONTH_SHAWWAL: usize = 10;
 pub const MONTH_DHUL_HIJJAH: usize = 12;
@@ -8,3 +9,18 @@ pub const MONTH_DHUL_HIJJAH: usize = 12;
 pub const DAY_ARAFAH: usize = 9;
 pub const DAY_ASHURA: usize = 10;
 pub const DAY_TASUA: usize = 9;
+/// 15 Sha'ban (Nisf Sha'ban).
+pub const DAY_NISF_SHABAN: usize = 15;
+/// First day of the second half of Sha'ban.
+pub const DAY_LATE_SHABAN: usize = 16;
+/// Days before Ramadhan that must not be singled out for voluntary fasting.
+pub const PRE_RAMADHAN_DAYS: i64 = 2;
+/// 30 Sha'ban, the day of doubt.
+pub const DAY_OF_DOUBT: usize = 30;
+/// Shortest journey permitting the traveler's concession: four burud,
+/// taken at its lower common estimate in kilometres.
+pub const SAFAR_MIN_KM: f64 = 81.0;
+/// Voluntary fasts in Shawwal following Ramadhan.
+pub const SHAWWAL_FASTS: usize = 6;
+/// Spare permissible days below which a qada deadline is reported as tight.
+pub const QADA_TIGHT_MARGIN: usize = 14;
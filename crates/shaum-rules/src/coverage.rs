@@ -0,0 +1,92 @@
+//! Introspection of the built-in rule set.
+//!
+//! Lists every rule the engine evaluates, with the madhabs it applies to, the
+//! statuses it can emit and its trace code, and reports which of them fired
+//! for a given analysis ("rules applied / not applicable" panels).
+
+use std::sync::LazyLock;
+
+use serde::Serialize;
+use shaum_types::{FastingAnalysis, FastingStatus, FastingType, Madhab, TraceCode};
+
+use crate::registry::builtin_registry;
+
+/// Static description of one built-in rule.
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleInfo {
+    pub trace_code: TraceCode,
+    /// Reason added to the analysis when the rule fires.
+    pub fasting_type: FastingType,
+    pub madhabs: &'static [Madhab],
+    /// Statuses the rule can produce on its own.
+    pub statuses: &'static [FastingStatus],
+    /// One-line English description of the condition.
+    pub condition: &'static str,
+}
+
+impl RuleInfo {
+    /// Whether this rule fired in `analysis`.
+    pub fn fired_in(&self, analysis: &FastingAnalysis) -> bool {
+        analysis.traces().any(|t| t.code == self.trace_code)
+    }
+
+    /// Whether this rule is evaluated under `madhab`.
+    pub fn applies_to(&self, madhab: Madhab) -> bool {
+        self.madhabs.contains(&madhab)
+    }
+}
+
+pub(crate) const fn rule(
+    trace_code: TraceCode,
+    fasting_type: FastingType,
+    status: &'static [FastingStatus],
+    condition: &'static str,
+) -> RuleInfo {
+    RuleInfo { trace_code, fasting_type, madhabs: &Madhab::ALL, statuses: status, condition }
+}
+
+pub(crate) const fn madhab_rule(
+    madhabs: &'static [Madhab],
+    trace_code: TraceCode,
+    fasting_type: FastingType,
+    status: &'static [FastingStatus],
+    condition: &'static str,
+) -> RuleInfo {
+    RuleInfo { trace_code, fasting_type, madhabs, statuses: status, condition }
+}
+
+/// Built-in rules in evaluation order, as reported by
+/// [`RuleRegistry::builtin`](crate::registry::RuleRegistry::builtin).
+pub static BUILTIN_RULES: LazyLock<Vec<RuleInfo>> =
+    LazyLock::new(|| builtin_registry().reports().cloned().collect());
+
+/// Whether a built-in rule fired.
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleCoverage {
+    pub rule: &'static RuleInfo,
+    pub fired: bool,
+}
+
+/// Every built-in rule applicable under `madhab`, marked with whether it fired in `analysis`.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_rules::{check, RuleContext};
+/// use shaum_rules::coverage::rule_coverage;
+/// use shaum_types::{Madhab, TraceCode};
+///
+/// let analysis = check(NaiveDate::from_ymd_opt(2024, 3, 11).unwrap(), &RuleContext::default()).unwrap();
+/// let fired: Vec<TraceCode> = rule_coverage(&analysis, Madhab::Shafi)
+///     .into_iter()
+///     .filter(|c| c.fired)
+///     .map(|c| c.rule.trace_code)
+///     .collect();
+/// assert_eq!(fired, [TraceCode::Ramadhan, TraceCode::Monday]);
+/// ```
+pub fn rule_coverage(analysis: &FastingAnalysis, madhab: Madhab) -> Vec<RuleCoverage> {
+    BUILTIN_RULES
+        .iter()
+        .filter(|rule| rule.applies_to(madhab))
+        .map(|rule| RuleCoverage { rule, fired: rule.fired_in(analysis) })
+        .collect()
+}
@@ -1,10 +1,26 @@
-use chrono::{NaiveDate, Datelike};
+use chrono::NaiveDate;
+use shaum_types::{DaudStrategy, ShaumError};
 use crate::RuleContext;
-use shaum_types::FastingStatus;
 
 /// Iterator for Daud fasting days.
+///
+/// Yields `Ok(date)` for each fasting day. If a day cannot be checked (for
+/// example it is outside the calendar's range) the error is yielded once and
+/// the iterator ends, so a schedule never stops short silently.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_rules::{DaudIterator, RuleContext};
+///
+/// let ctx = RuleContext::default();
+/// let start = NaiveDate::from_ymd_opt(2076, 11, 1).unwrap();
+/// let days: Vec<_> = DaudIterator::new(start, &ctx).collect();
+/// // Umm al-Qura ends in 2076: the last item says why the schedule stopped.
+/// assert!(days.last().unwrap().is_err());
+/// assert!(days[..days.len() - 1].iter().all(Result::is_ok));
+/// ```
 pub struct DaudIterator<'a> {
-    current: NaiveDate,
+    current: Option<NaiveDate>,
     context: &'a RuleContext,
     is_fasting_turn: bool,
 }
@@ -12,7 +28,7 @@ pub struct DaudIterator<'a> {
 impl<'a> DaudIterator<'a> {
     pub fn new(start: NaiveDate, context: &'a RuleContext) -> Self {
         Self {
-            current: start,
+            current: Some(start),
             context,
             is_fasting_turn: true, // Start with fasting unless configured otherwise
         }
@@ -20,21 +36,27 @@ impl<'a> DaudIterator<'a> {
 }
 
 impl Iterator for DaudIterator<'_> {
-    type Item = NaiveDate;
+    type Item = Result<NaiveDate, ShaumError>;
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
-            // Safety break 
-            if self.current.year() > 2100 { return None; }
-
-            let date = self.current;
-            self.current = self.current.succ_opt()?;
+            let date = self.current?;
+            let Some(next) = date.succ_opt() else {
+                self.current = None;
+                return Some(Err(ShaumError::date_out_of_range(date)));
+            };
+            self.current = Some(next);
 
             // Check if Haram
-            let analysis = crate::check(date, self.context).ok()?;
-            use shaum_types::DaudStrategy;
-            
-            if analysis.primary_status == FastingStatus::Haram {
+            let haram = match crate::rules::is_haram_day(date, self.context) {
+                Ok(haram) => haram,
+                Err(e) => {
+                    self.current = None;
+                    return Some(Err(e));
+                }
+            };
+
+            if haram {
                 // Formatting Note: Haram means we MUST NOT fast.
                 if self.is_fasting_turn {
                     // It was our turn to fast.
@@ -59,7 +81,7 @@ impl Iterator for DaudIterator<'_> {
 
             if self.is_fasting_turn {
                 self.is_fasting_turn = false;
-                return Some(date);
+                return Some(Ok(date));
             } else {
                 self.is_fasting_turn = true;
                 continue;
@@ -68,14 +90,20 @@ impl Iterator for DaudIterator<'_> {
     }
 }
 
+impl std::iter::FusedIterator for DaudIterator<'_> {}
+
 /// Generates a list of Daud fasting days between start and end (inclusive).
+///
+/// # Errors
+/// The first error from [`DaudIterator`] before `end`, e.g. a range that
+/// runs past the calendar's coverage.
 pub fn generate_daud_schedule(
     start: NaiveDate,
     end: NaiveDate,
     context: &RuleContext
-) -> Vec<NaiveDate> {
+) -> Result<Vec<NaiveDate>, ShaumError> {
     DaudIterator::new(start, context)
-        .take_while(|d| *d <= end)
+        .take_while(|d| d.as_ref().map_or(true, |d| *d <= end))
         .collect()
 }
 
@@ -83,7 +111,7 @@ pub fn generate_daud_schedule(
 pub struct DaudScheduleBuilder {
     start: NaiveDate,
     end: Option<NaiveDate>,
-    postpone_on_haram: bool,
+    strategy: Option<DaudStrategy>,
     context: RuleContext,
 }
 
@@ -92,7 +120,7 @@ impl DaudScheduleBuilder {
         Self {
             start,
             end: None,
-            postpone_on_haram: false,
+            strategy: None,
             context: RuleContext::default(),
         }
     }
@@ -102,13 +130,15 @@ impl DaudScheduleBuilder {
         self
     }
 
+    /// Uses [`DaudStrategy::Postpone`], overriding the context's strategy.
     pub fn postpone_on_haram(mut self) -> Self {
-        self.postpone_on_haram = true;
+        self.strategy = Some(DaudStrategy::Postpone);
         self
     }
 
+    /// Uses [`DaudStrategy::Skip`], overriding the context's strategy.
     pub fn skip_haram_days(mut self) -> Self {
-        self.postpone_on_haram = false;
+        self.strategy = Some(DaudStrategy::Skip);
         self
     }
 
@@ -117,18 +147,19 @@ impl DaudScheduleBuilder {
         self
     }
 
-    pub fn build(self) -> Vec<Result<NaiveDate, shaum_types::ShaumError>> {
-        let mut results = Vec::new();
-        // Use DaudIterator logic
-        let iter = DaudIterator::new(self.start, &self.context);
-        
-        let end = self.end.unwrap_or_else(|| self.start.checked_add_signed(chrono::Duration::days(365)).unwrap());
-        
-        // TODO: Implement postpone logic properly if needed.
-        // For now, simple wrapper to satisfy API.
-        for date in iter.take_while(|d| *d <= end) {
-             results.push(Ok(date));
+    /// Fasting days up to the end date (default: a year after the start).
+    /// Items follow [`DaudIterator`]: if the schedule stops early, the last
+    /// item is the error explaining why.
+    pub fn build(mut self) -> Vec<Result<NaiveDate, ShaumError>> {
+        if let Some(strategy) = self.strategy {
+            self.context.daud_strategy = strategy;
         }
-        results
+        let end = self.end
+            .or_else(|| self.start.checked_add_signed(chrono::Duration::days(365)))
+            .unwrap_or(NaiveDate::MAX);
+
+        DaudIterator::new(self.start, &self.context)
+            .take_while(|d| d.as_ref().map_or(true, |d| *d <= end))
+            .collect()
     }
 }
@@ -0,0 +1,167 @@
+//! iCalendar (RFC 5545) export of fasting days.
+//!
+//! One all-day event per day whose status is not Mubah. [`IcsExport`] yields
+//! the document in chunks (header, one Gregorian month of events at a time,
+//! footer) so multi-year calendars can be streamed to a file, a response body
+//! or JavaScript without holding the whole text in memory.
+
+use std::borrow::Cow;
+use std::fmt::Write;
+
+use chrono::{Datelike, Months, NaiveDate};
+use shaum_types::{FastingAnalysis, FastingStatus, ShaumError};
+
+use crate::i18n::Localizer;
+use crate::rules::{check, RuleContext};
+
+const HEADER: &str = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//shaum//fasting calendar//EN\r\nCALSCALE:GREGORIAN\r\n";
+const FOOTER: &str = "END:VCALENDAR\r\n";
+
+/// Maximum line length in octets before folding.
+const LINE_LIMIT: usize = 75;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Header,
+    Events,
+    Footer,
+    Done,
+}
+
+/// Chunked iCalendar export of `start..=end`.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_rules::export::ics::IcsExport;
+/// use shaum_rules::{i18n::EnglishLocalizer, RuleContext};
+///
+/// let start = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+/// let end = NaiveDate::from_ymd_opt(2024, 4, 30).unwrap();
+/// let context = RuleContext::default();
+/// let chunks: Vec<String> = IcsExport::new(start, end, &context, &EnglishLocalizer)
+///     .unwrap()
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(chunks.len(), 4); // header, March, April, footer
+/// assert!(chunks[1].contains("SUMMARY:Wajib (Obligatory): Ramadhan"));
+/// ```
+pub struct IcsExport<'a> {
+    context: Cow<'a, RuleContext>,
+    localizer: &'a dyn Localizer,
+    next: NaiveDate,
+    end: NaiveDate,
+    stage: Stage,
+}
+
+impl<'a> IcsExport<'a> {
+    /// # Errors
+    /// `ValidationError` if `end` is before `start`.
+    pub fn new(start: NaiveDate, end: NaiveDate, context: &'a RuleContext, localizer: &'a dyn Localizer) -> Result<Self, ShaumError> {
+        Self::with_context(start, end, Cow::Borrowed(context), localizer)
+    }
+
+    /// Like [`Self::new`], owning the context (for exports that outlive it,
+    /// e.g. a stream handed to another language).
+    pub fn owned(start: NaiveDate, end: NaiveDate, context: RuleContext, localizer: &'a dyn Localizer) -> Result<Self, ShaumError> {
+        Self::with_context(start, end, Cow::Owned(context), localizer)
+    }
+
+    fn with_context(start: NaiveDate, end: NaiveDate, context: Cow<'a, RuleContext>, localizer: &'a dyn Localizer) -> Result<Self, ShaumError> {
+        if end < start {
+            return Err(ShaumError::ValidationError(format!("Range end {} is before start {}", end, start)));
+        }
+        Ok(Self { context, localizer, next: start, end, stage: Stage::Header })
+    }
+
+    /// Events for the month containing `self.next`, up to `self.end`.
+    fn month_chunk(&mut self) -> Result<String, ShaumError> {
+        let first_of_next = self.next.with_day(1).and_then(|d| d.checked_add_months(Months::new(1)));
+        let last = first_of_next.and_then(|d| d.pred_opt()).map_or(self.end, |d| d.min(self.end));
+
+        let mut out = String::new();
+        for date in self.next.iter_days().take_while(|d| *d <= last) {
+            let analysis = check(date, &self.context)?;
+            if analysis.primary_status != FastingStatus::Mubah {
+                self.write_event(&mut out, date, &analysis);
+            }
+        }
+        match last.succ_opt().filter(|d| *d <= self.end) {
+            Some(next) => self.next = next,
+            None => self.stage = Stage::Footer,
+        }
+        Ok(out)
+    }
+
+    fn write_event(&self, out: &mut String, date: NaiveDate, analysis: &FastingAnalysis) {
+        let reasons: Vec<String> = analysis.reasons().map(|t| self.localizer.type_name(t.clone())).collect();
+        let mut summary = self.localizer.status_name(analysis.primary_status);
+        if !reasons.is_empty() {
+            summary = format!("{}: {}", summary, reasons.join(", "));
+        }
+        let day = date.format("%Y%m%d");
+        let next_day = date.succ_opt().unwrap_or(date).format("%Y%m%d");
+        out.push_str("BEGIN:VEVENT\r\n");
+        let _ = write!(out, "UID:{}@shaum\r\nDTSTAMP:{}T000000Z\r\n", day, day);
+        let _ = write!(out, "DTSTART;VALUE=DATE:{}\r\nDTEND;VALUE=DATE:{}\r\n", day, next_day);
+        push_folded(out, &format!("SUMMARY:{}", escape(&summary)));
+        push_folded(out, &format!("CATEGORIES:{}", escape(analysis.primary_status.label())));
+        out.push_str("TRANSP:TRANSPARENT\r\nEND:VEVENT\r\n");
+    }
+}
+
+impl Iterator for IcsExport<'_> {
+    type Item = Result<String, ShaumError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.stage {
+            Stage::Header => {
+                self.stage = Stage::Events;
+                Some(Ok(HEADER.to_string()))
+            }
+            Stage::Events => {
+                let chunk = self.month_chunk();
+                if chunk.is_err() {
+                    self.stage = Stage::Done;
+                }
+                Some(chunk)
+            }
+            Stage::Footer => {
+                self.stage = Stage::Done;
+                Some(Ok(FOOTER.to_string()))
+            }
+            Stage::Done => None,
+        }
+    }
+}
+
+/// Whole iCalendar document for `start..=end`. Prefer [`IcsExport`] for long ranges.
+pub fn to_ics(start: NaiveDate, end: NaiveDate, context: &RuleContext, localizer: &dyn Localizer) -> Result<String, ShaumError> {
+    IcsExport::new(start, end, context, localizer)?.collect()
+}
+
+/// Escapes TEXT values (RFC 5545 §3.3.11).
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => { out.push('\\'); out.push(c); }
+            '\n' => out.push_str("\\n"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Appends `line` with CRLF, folded at [`LINE_LIMIT`] octets on char boundaries.
+fn push_folded(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > LINE_LIMIT {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
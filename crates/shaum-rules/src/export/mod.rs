@@ -0,0 +1,4 @@
+//! Exporters rendering fasting analyses into shareable formats.
+
+pub mod ics;
+pub mod svg;
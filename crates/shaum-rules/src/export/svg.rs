@@ -0,0 +1,134 @@
+//! Year-at-a-glance SVG heatmap.
+//!
+//! One cell per day, arranged in week columns (Monday at the top), coloured by
+//! [`FastingStatus`], with month, weekday and legend labels from a [`Localizer`].
+
+use std::fmt::Write;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use shaum_types::{FastingStatus, ShaumError};
+
+use crate::i18n::Localizer;
+use crate::rules::{check, RuleContext};
+
+const CELL: i64 = 12;
+const GAP: i64 = 2;
+const STEP: i64 = CELL + GAP;
+const LEFT: i64 = 34;
+const TOP: i64 = 40;
+
+/// Legend order, most to least restrictive.
+const LEGEND: [FastingStatus; 6] = [
+    FastingStatus::Haram,
+    FastingStatus::Wajib,
+    FastingStatus::SunnahMuakkadah,
+    FastingStatus::Sunnah,
+    FastingStatus::Makruh,
+    FastingStatus::Mubah,
+];
+
+/// Fill colour for a status.
+pub fn status_color(status: FastingStatus) -> &'static str {
+    match status {
+        FastingStatus::Haram => "#d73a49",
+        FastingStatus::Wajib => "#1a7f37",
+        FastingStatus::SunnahMuakkadah => "#2da44e",
+        FastingStatus::Sunnah => "#8cd9a0",
+        FastingStatus::Makruh => "#f0b849",
+        FastingStatus::Mubah => "#ebedf0",
+    }
+}
+
+/// Renders Gregorian `year` as a standalone SVG document.
+///
+/// ```rust
+/// use shaum_rules::{export::svg, i18n::EnglishLocalizer, RuleContext};
+///
+/// let svg = svg::year_heatmap(2025, &RuleContext::default(), &EnglishLocalizer).unwrap();
+/// assert!(svg.starts_with("<svg"));
+/// ```
+pub fn year_heatmap(year: i32, context: &RuleContext, localizer: &dyn Localizer) -> Result<String, ShaumError> {
+    let invalid = || ShaumError::ValidationError(format!("Invalid year {}", year));
+    let first = NaiveDate::from_ymd_opt(year, 1, 1).ok_or_else(invalid)?;
+    let last = NaiveDate::from_ymd_opt(year, 12, 31).ok_or_else(invalid)?;
+    let lead = i64::from(first.weekday().num_days_from_monday());
+    let weeks = (lead + (last - first).num_days()) / 7 + 1;
+
+    // Approximate label advance: ~6px per glyph at 10px font.
+    let legend: Vec<(FastingStatus, String)> = LEGEND.iter()
+        .map(|&status| (status, escape(&localizer.status_name(status))))
+        .collect();
+    let legend_width: i64 = legend.iter().map(|(_, label)| STEP + 6 * label.chars().count() as i64 + 12).sum();
+
+    let width = (LEFT + weeks * STEP + GAP).max(LEFT + legend_width);
+    let height = TOP + 7 * STEP + 44;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif" font-size="10">"#,
+        w = width, h = height,
+    );
+    let _ = writeln!(out, r#"<text x="{}" y="14" font-size="13" font-weight="bold">{}</text>"#, LEFT, year);
+
+    // Weekday labels (Mon, Wed, Fri)
+    for (row, weekday) in [(0, Weekday::Mon), (2, Weekday::Wed), (4, Weekday::Fri)] {
+        let _ = writeln!(
+            out,
+            r#"<text x="0" y="{}">{}</text>"#,
+            TOP + row * STEP + CELL - 2,
+            escape(&abbrev(&localizer.weekday_name(weekday))),
+        );
+    }
+
+    let mut date = first;
+    while date <= last {
+        let offset = lead + (date - first).num_days();
+        let (col, row) = (offset / 7, offset % 7);
+        let (x, y) = (LEFT + col * STEP, TOP + row * STEP);
+
+        if date.day() == 1 {
+            let _ = writeln!(
+                out,
+                r#"<text x="{}" y="{}">{}</text>"#,
+                x, TOP - 6, escape(&abbrev(&localizer.gregorian_month_name(date.month()))),
+            );
+        }
+
+        let analysis = check(date, context)?;
+        let _ = writeln!(
+            out,
+            r#"<rect x="{}" y="{}" width="{c}" height="{c}" rx="2" fill="{}"><title>{} · {} · {}</title></rect>"#,
+            x, y, status_color(analysis.primary_status),
+            date,
+            escape(&localizer.format_description(&analysis)),
+            escape(&localizer.status_name(analysis.primary_status)),
+            c = CELL,
+        );
+        date += Duration::days(1);
+    }
+
+    // Legend
+    let legend_y = TOP + 7 * STEP + 16;
+    let mut x = LEFT;
+    for (status, label) in legend {
+        let _ = writeln!(
+            out,
+            r#"<rect x="{}" y="{}" width="{c}" height="{c}" rx="2" fill="{}"/><text x="{}" y="{}">{}</text>"#,
+            x, legend_y, status_color(status), x + STEP, legend_y + CELL - 2, label, c = CELL,
+        );
+        x += STEP + 6 * label.chars().count() as i64 + 12;
+    }
+
+    out.push_str("</svg>\n");
+    Ok(out)
+}
+
+/// First three characters of a label.
+fn abbrev(label: &str) -> String {
+    label.chars().take(3).collect()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -0,0 +1,106 @@
+//! Message-ready formatting for chat bots (Telegram, Discord, Slack).
+
+use shaum_types::{FastingAnalysis, FastingStatus};
+
+use crate::i18n::Localizer;
+
+/// Emoji marker for a status.
+pub fn status_emoji(status: FastingStatus) -> &'static str {
+    match status {
+        FastingStatus::Haram => "🚫",
+        FastingStatus::Wajib => "🌙",
+        FastingStatus::SunnahMuakkadah => "⭐",
+        FastingStatus::Sunnah => "✨",
+        FastingStatus::Makruh => "⚠️",
+        FastingStatus::Mubah => "⚪",
+    }
+}
+
+/// Multi-line Markdown message: date header, bold status and a bullet per reason.
+///
+/// Valid Telegram MarkdownV2 (`parse_mode=MarkdownV2`): the status is wrapped
+/// in single `*` and every reserved character in the text, including the
+/// date's hyphens, is backslash-escaped. CommonMark renderers (Discord,
+/// Matrix) accept the escapes too but show the status in italics.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_rules::{check, format, i18n::EnglishLocalizer, RuleContext};
+///
+/// let analysis = check(NaiveDate::from_ymd_opt(2024, 3, 11).unwrap(), &RuleContext::default()).unwrap();
+/// let msg = format::markdown(&analysis, &EnglishLocalizer);
+/// assert!(msg.contains("Ramadhan"));
+/// ```
+pub fn markdown(analysis: &FastingAnalysis, localizer: &dyn Localizer) -> String {
+    let mut out = format!(
+        "{} *{}*\n📅 {} · {} {} {} AH\n",
+        status_emoji(analysis.primary_status),
+        escape(&localizer.status_name(analysis.primary_status)),
+        escape(&analysis.date.date_naive().to_string()),
+        analysis.hijri_day,
+        escape(&localizer.month_name(analysis.hijri_month)),
+        analysis.hijri_year,
+    );
+    for reason in analysis.reasons() {
+        out.push_str("• ");
+        out.push_str(&escape(&localizer.type_name(reason.clone())));
+        out.push('\n');
+    }
+    out
+}
+
+/// Single-line summary, e.g. `🌙 Wajib · 1 Ramadhan 1445 · Ramadhan, Monday`.
+pub fn compact(analysis: &FastingAnalysis) -> String {
+    let mut out = format!(
+        "{} {} · {} {} {}",
+        status_emoji(analysis.primary_status),
+        analysis.primary_status.label(),
+        analysis.hijri_day,
+        shaum_calendar::get_hijri_month_name(analysis.hijri_month),
+        analysis.hijri_year,
+    );
+    let reasons: Vec<String> = analysis.reasons().map(|r| r.to_string()).collect();
+    if !reasons.is_empty() {
+        out.push_str(" · ");
+        out.push_str(&reasons.join(", "));
+    }
+    out
+}
+
+/// Single-line per-reason breakdown, e.g.
+/// `DayOfDoubt (Makruh) + Monday (Sunnah) → net: Makruh`.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_rules::{check, format, RuleContext};
+///
+/// // 2024-01-04: Thursday, 22 Jumada al-Akhirah 1445.
+/// let analysis = check(NaiveDate::from_ymd_opt(2024, 1, 4).unwrap(), &RuleContext::default()).unwrap();
+/// assert_eq!(format::breakdown(&analysis), "Thursday (Sunnah) → net: Sunnah");
+/// ```
+pub fn breakdown(analysis: &FastingAnalysis) -> String {
+    let parts: Vec<String> = analysis.reason_statuses()
+        .map(|(reason, status)| format!("{} ({})", reason, status.label()))
+        .collect();
+    let net = analysis.primary_status.label();
+    if parts.is_empty() {
+        format!("net: {}", net)
+    } else {
+        format!("{} → net: {}", parts.join(" + "), net)
+    }
+}
+
+/// Backslash-escapes the characters MarkdownV2 reserves outside entities.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '_' | '*' | '[' | ']' | '(' | ')' | '~' | '`' | '>' | '#' | '+' | '-' | '=' | '|' | '{' | '}' | '.' | '!'
+        ) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
@@ -0,0 +1,64 @@
+//! Day-by-day iteration over a Hijri month.
+
+use chrono::NaiveDate;
+use shaum_calendar::HijriYmd;
+use shaum_types::ShaumError;
+
+use crate::RuleContext;
+
+/// Yields `(HijriYmd, NaiveDate)` for every day of one Hijri month, using the
+/// context's calendar and adjustment.
+///
+/// A day that fails to convert is yielded as an `Err`, after which the
+/// iterator stops, so a broken calendar never looks like a short month.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_rules::RuleContext;
+///
+/// let ctx = RuleContext::new();
+/// let ramadhan: Vec<_> = ctx.hijri_month_days(1445, 9).unwrap().collect::<Result<_, _>>().unwrap();
+/// assert_eq!(ramadhan.len(), 30);
+/// assert_eq!(ramadhan[0].1, NaiveDate::from_ymd_opt(2024, 3, 11).unwrap());
+/// assert_eq!(ramadhan[29].0.day, 30);
+/// ```
+pub struct HijriMonthIterator<'a> {
+    context: &'a RuleContext,
+    year: usize,
+    month: usize,
+    next: Option<NaiveDate>,
+}
+
+impl<'a> HijriMonthIterator<'a> {
+    /// Iterator over `month` `year` AH.
+    ///
+    /// # Errors
+    /// `ValidationError` for an invalid month; otherwise any error locating its first day.
+    pub fn new(year: usize, month: usize, context: &'a RuleContext) -> Result<Self, ShaumError> {
+        let start = context.hijri_month_start(year, month)?;
+        Ok(Self { context, year, month, next: Some(start) })
+    }
+}
+
+impl Iterator for HijriMonthIterator<'_> {
+    type Item = Result<(HijriYmd, NaiveDate), ShaumError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let date = self.next.take()?;
+        let h = match self.context.hijri(date) {
+            Ok(h) => h,
+            Err(e) => return Some(Err(e)),
+        };
+        if h.year != self.year || h.month != self.month {
+            return None;
+        }
+        self.next = date.succ_opt();
+        Some(Ok((h, date)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.next.is_some() { (0, Some(30)) } else { (0, Some(0)) }
+    }
+}
+
+impl std::iter::FusedIterator for HijriMonthIterator<'_> {}
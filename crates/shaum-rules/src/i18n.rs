@@ -1,10 +1,33 @@
-use shaum_types::{FastingAnalysis, FastingStatus, FastingType};
+use chrono::Weekday;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+use shaum_calendar::{hijri_month_from_name, HijriYmd};
+use shaum_types::ShaumError;
+use shaum_types::{Compensation, Emphasis, FastingAnalysis, FastingStatus, FastingType, RuleTrace};
+
+const GREGORIAN_MONTHS: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+
+const WEEKDAYS: [&str; 7] = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
 
 pub trait Localizer {
     fn month_name(&self, month: usize) -> String;
     fn status_name(&self, status: FastingStatus) -> String;
     fn type_name(&self, f_type: FastingType) -> String;
     fn format_description(&self, analysis: &FastingAnalysis) -> String;
+
+    /// Gregorian month name (1-12). Default: English.
+    fn gregorian_month_name(&self, month: u32) -> String {
+        GREGORIAN_MONTHS.get(month.wrapping_sub(1) as usize).copied().unwrap_or("Unknown").to_string()
+    }
+
+    /// Weekday name. Default: English.
+    fn weekday_name(&self, weekday: Weekday) -> String {
+        WEEKDAYS[weekday.num_days_from_monday() as usize].to_string()
+    }
 }
 
 pub struct EnglishLocalizer;
@@ -31,3 +54,176 @@ impl Localizer for EnglishLocalizer {
         )
     }
 }
+
+/// What a cached description may depend on: everything rendered except the
+/// Gregorian date.
+type DescriptionKey = (
+    (usize, usize, usize),
+    FastingStatus,
+    Vec<(FastingType, Option<Emphasis>, FastingStatus)>,
+    Vec<RuleTrace>,
+    Option<Compensation>,
+);
+
+/// Strings kept per kind by [`CachedLocalizer::new`].
+pub const DEFAULT_LOCALIZER_CAPACITY: usize = 1024;
+
+/// Memoizing wrapper around another [`Localizer`].
+///
+/// Each name and description is formatted once and then cloned, so rendering
+/// a year-long calendar (or re-rendering it) does not re-run the inner
+/// localizer's templates for every cell. Descriptions are keyed by the Hijri
+/// date, status, reasons with their emphasis and status, traces with their
+/// payloads, and compensation: the inner localizer must not depend on the
+/// Gregorian date. Each kind of string holds at most `capacity` entries and
+/// is emptied when full. Not `Sync`: use one per rendering thread.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_rules::{check, RuleContext};
+/// use shaum_rules::i18n::{CachedLocalizer, EnglishLocalizer, Localizer};
+///
+/// let cached = CachedLocalizer::new(EnglishLocalizer);
+/// let analysis = check(NaiveDate::from_ymd_opt(2024, 3, 15).unwrap(), &RuleContext::default()).unwrap();
+/// assert_eq!(cached.format_description(&analysis), EnglishLocalizer.format_description(&analysis));
+/// assert_eq!(cached.month_name(9), "Ramadhan");
+/// assert_eq!(cached.len(), 2);
+/// ```
+#[derive(Debug)]
+pub struct CachedLocalizer<L> {
+    inner: L,
+    capacity: usize,
+    months: RefCell<HashMap<usize, String>>,
+    gregorian_months: RefCell<HashMap<u32, String>>,
+    weekdays: RefCell<HashMap<Weekday, String>>,
+    statuses: RefCell<HashMap<FastingStatus, String>>,
+    types: RefCell<HashMap<FastingType, String>>,
+    descriptions: RefCell<HashMap<DescriptionKey, String>>,
+}
+
+impl<L: Localizer> CachedLocalizer<L> {
+    /// Caches up to [`DEFAULT_LOCALIZER_CAPACITY`] strings of each kind.
+    pub fn new(inner: L) -> Self { Self::with_capacity(inner, DEFAULT_LOCALIZER_CAPACITY) }
+
+    /// Caches up to `capacity` strings of each kind (at least one).
+    pub fn with_capacity(inner: L, capacity: usize) -> Self {
+        Self {
+            inner,
+            capacity: capacity.max(1),
+            months: RefCell::default(),
+            gregorian_months: RefCell::default(),
+            weekdays: RefCell::default(),
+            statuses: RefCell::default(),
+            types: RefCell::default(),
+            descriptions: RefCell::default(),
+        }
+    }
+
+    /// The wrapped localizer.
+    pub fn inner(&self) -> &L { &self.inner }
+
+    /// Number of cached strings.
+    pub fn len(&self) -> usize {
+        self.months.borrow().len() + self.gregorian_months.borrow().len() + self.weekdays.borrow().len()
+            + self.statuses.borrow().len() + self.types.borrow().len() + self.descriptions.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Drops every cached string.
+    pub fn clear(&self) {
+        self.months.borrow_mut().clear();
+        self.gregorian_months.borrow_mut().clear();
+        self.weekdays.borrow_mut().clear();
+        self.statuses.borrow_mut().clear();
+        self.types.borrow_mut().clear();
+        self.descriptions.borrow_mut().clear();
+    }
+}
+
+impl<L: Localizer + Default> Default for CachedLocalizer<L> {
+    fn default() -> Self { Self::new(L::default()) }
+}
+
+impl<L> CachedLocalizer<L> {
+    /// Looks `key` up in `cache`, formatting and storing it on a miss.
+    fn memoized<K: Hash + Eq>(&self, cache: &RefCell<HashMap<K, String>>, key: K, format: impl FnOnce() -> String) -> String {
+        if let Some(hit) = cache.borrow().get(&key) {
+            return hit.clone();
+        }
+        let value = format();
+        let mut cache = cache.borrow_mut();
+        if cache.len() >= self.capacity {
+            cache.clear();
+        }
+        cache.insert(key, value.clone());
+        value
+    }
+}
+
+impl<L: Localizer> Localizer for CachedLocalizer<L> {
+    fn month_name(&self, month: usize) -> String {
+        self.memoized(&self.months, month, || self.inner.month_name(month))
+    }
+
+    fn status_name(&self, status: FastingStatus) -> String {
+        self.memoized(&self.statuses, status, || self.inner.status_name(status))
+    }
+
+    fn type_name(&self, f_type: FastingType) -> String {
+        self.memoized(&self.types, f_type.clone(), || self.inner.type_name(f_type))
+    }
+
+    fn format_description(&self, analysis: &FastingAnalysis) -> String {
+        let key = (
+            (analysis.hijri_year, analysis.hijri_month, analysis.hijri_day),
+            analysis.primary_status,
+            analysis.reasons_with_emphasis()
+                .zip(analysis.reason_statuses())
+                .map(|((t, emphasis), (_, status))| (t.clone(), emphasis, status))
+                .collect(),
+            analysis.traces().cloned().collect(),
+            analysis.compensation(),
+        );
+        self.memoized(&self.descriptions, key, || self.inner.format_description(analysis))
+    }
+
+    fn gregorian_month_name(&self, month: u32) -> String {
+        self.memoized(&self.gregorian_months, month, || self.inner.gregorian_month_name(month))
+    }
+
+    fn weekday_name(&self, weekday: Weekday) -> String {
+        self.memoized(&self.weekdays, weekday, || self.inner.weekday_name(weekday))
+    }
+}
+
+/// Built-in localizer for a BCP 47 tag such as `en` or `en-GB`, matched on the
+/// primary language. `None` if the language is not available.
+pub fn localizer(locale: &str) -> Option<&'static dyn Localizer> {
+    let language = locale.split(['-', '_']).next().unwrap_or_default();
+    if language.eq_ignore_ascii_case("en") { Some(&EnglishLocalizer) } else { None }
+}
+
+/// Parses a Hijri date like [`HijriYmd::from_str`](std::str::FromStr), also
+/// accepting the month names produced by `localizer`.
+///
+/// ```rust
+/// use shaum_calendar::HijriYmd;
+/// use shaum_rules::i18n::{parse_hijri, EnglishLocalizer};
+///
+/// let h = parse_hijri("15 Sha'ban 1446", &EnglishLocalizer).unwrap();
+/// assert_eq!(h, HijriYmd::new(1446, 8, 15));
+/// ```
+pub fn parse_hijri(input: &str, localizer: &dyn Localizer) -> Result<HijriYmd, ShaumError> {
+    HijriYmd::parse_with(input, |name| {
+        (1..=12)
+            .find(|&m| {
+                let localized: String = localizer.month_name(m).chars()
+                    .filter(|c| c.is_alphanumeric())
+                    .flat_map(char::to_lowercase)
+                    .collect();
+                localized == name
+            })
+            .or_else(|| hijri_month_from_name(name))
+    })
+}
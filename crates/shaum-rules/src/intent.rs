@@ -0,0 +1,305 @@
+//! Cross-checks a planned fast's intention (niyyah) against the rules.
+//!
+//! The engine says what a day *is*; apps that log planned fasts also need to
+//! know whether what the user *intends* fits that day: a make-up fast on an
+//! Eid, Shawwal's six outside Shawwal, or a combined make-up and Shawwal
+//! intention, which the schools answer differently. [`check_intention`]
+//! returns structured [`Guidance`] with the evidence for each point.
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Serialize, Deserialize};
+use shaum_types::{Compensation, FastingAnalysis, FastingType, Madhab, ShaumError};
+
+use crate::constants::MONTH_SHAWWAL;
+use crate::rules::{check, RuleContext};
+
+/// Declared purpose of a fast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Intention {
+    /// Making up a missed Ramadhan fast.
+    Qadha,
+    /// An expiation (kaffarah) fast, e.g. for a broken oath.
+    Kaffarah,
+    /// One of the six days of Shawwal.
+    Shawwal,
+    /// Part of the alternate-day fast of Dawud.
+    Daud,
+    /// Any other voluntary fast.
+    Nafl,
+}
+
+impl Intention {
+    /// Whether the fast is owed rather than voluntary ([`Qadha`](Self::Qadha), [`Kaffarah`](Self::Kaffarah)).
+    pub fn is_obligatory(self) -> bool {
+        matches!(self, Self::Qadha | Self::Kaffarah)
+    }
+}
+
+/// How strongly a point affects the plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuidanceLevel {
+    /// For information; the plan is fine.
+    Info,
+    /// Valid but disliked or disputed; the user may want to reconsider.
+    Caution,
+    /// The fast cannot be made with this intention on this day.
+    Invalid,
+}
+
+/// One finding about a planned fast.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Guidance {
+    pub level: GuidanceLevel,
+    /// Stable snake_case identifier, e.g. `"qadha_in_ramadhan"`.
+    pub code: String,
+    pub message: String,
+    /// Source of the ruling.
+    pub evidence: String,
+}
+
+impl Guidance {
+    fn new(level: GuidanceLevel, code: &str, message: &str, evidence: &str) -> Self {
+        Self { level, code: code.to_string(), message: message.to_string(), evidence: evidence.to_string() }
+    }
+}
+
+/// Result of [`check_intention`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntentReport {
+    pub date: NaiveDate,
+    pub intentions: Vec<Intention>,
+    pub madhab: Madhab,
+    pub analysis: FastingAnalysis,
+    pub guidance: Vec<Guidance>,
+}
+
+impl IntentReport {
+    /// No [`GuidanceLevel::Invalid`] finding.
+    pub fn is_valid(&self) -> bool {
+        self.guidance.iter().all(|g| g.level < GuidanceLevel::Invalid)
+    }
+
+    /// Most serious finding, if any.
+    pub fn max_level(&self) -> Option<GuidanceLevel> {
+        self.guidance.iter().map(|g| g.level).max()
+    }
+}
+
+const EVIDENCE_EID: &str = "Bukhari and Muslim, from Abu Sa'id al-Khudri: the Prophet forbade fasting on the days of Fitr and Nahr";
+const EVIDENCE_TASHRIQ: &str = "Muslim, from Nubaishah al-Hudhali: the days of Tashriq are days of eating and drinking";
+const EVIDENCE_HAID: &str = "Bukhari and Muslim, from Aishah: we were ordered to make up the fasts but not the prayers";
+const EVIDENCE_SAFAR: &str = "Qur'an 2:184: \"whoever among you is ill or on a journey, then an equal number of other days\"";
+const EVIDENCE_MATERNAL: &str = "Abu Dawud and Tirmidhi, from Anas ibn Malik al-Ka'bi: Allah has relieved the traveler of half the prayer, and the pregnant and nursing woman of fasting; Qur'an 2:184: \"upon those who can fast only with hardship, a ransom: feeding a poor person\"";
+const EVIDENCE_AJZ: &str = "Qur'an 2:184: \"upon those who can fast only with hardship, a ransom: feeding a poor person\"; Bukhari, from Ibn Abbas: it is for the old man and woman who cannot fast";
+const EVIDENCE_QADHA: &str = "Qur'an 2:184: \"then an equal number of other days\"";
+const EVIDENCE_SHAWWAL: &str = "Muslim, from Abu Ayyub al-Ansari: \"Whoever fasts Ramadhan and follows it with six of Shawwal\"";
+const EVIDENCE_PRE_RAMADHAN: &str = "Bukhari and Muslim, from Abu Hurairah: do not precede Ramadhan by fasting a day or two, unless it is a fast one habitually keeps";
+const EVIDENCE_NISF_SHABAN: &str = "Ibn Majah, from Ali: when it is the middle night of Sha'ban, stand in prayer by night and fast its day (weak chain)";
+const EVIDENCE_LATE_SHABAN: &str = "Abu Dawud and Tirmidhi, from Abu Hurairah: when half of Sha'ban has passed, do not fast";
+const EVIDENCE_DOUBT: &str = "Abu Dawud and Tirmidhi, from Ammar ibn Yasir: whoever fasts the day of doubt has disobeyed Abu al-Qasim; Bukhari and Muslim, from Abu Hurairah: do not precede Ramadhan by a day or two, unless it is a fast one habitually keeps";
+const EVIDENCE_ARAFAH_PILGRIM: &str = "Bukhari and Muslim, from Umm al-Fadl: the Prophet was sent a cup of milk while standing at Arafah and drank it; Abu Dawud, from Abu Hurairah: he forbade fasting the day of Arafah at Arafah";
+const EVIDENCE_DAUD: &str = "Bukhari and Muslim, from Abdullah ibn Amr: Dawud fasted one day and broke his fast the next";
+const EVIDENCE_FRIDAY: &str = "Bukhari and Muslim, from Abu Hurairah: do not fast Friday unless with a day before or after it";
+const EVIDENCE_SATURDAY: &str = "Abu Dawud and Tirmidhi, from as-Samma' bint Busr: do not fast Saturday except what is obligatory";
+
+/// Validates fasting `date` with `intentions` under `context` (madhab, calendar).
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_rules::intent::{check_intention, GuidanceLevel, Intention};
+/// use shaum_rules::RuleContext;
+///
+/// // 2024-04-15: 6 Shawwal 1445.
+/// let date = NaiveDate::from_ymd_opt(2024, 4, 15).unwrap();
+/// let report = check_intention(date, &[Intention::Qadha, Intention::Shawwal], &RuleContext::default()).unwrap();
+/// assert!(report.is_valid());
+/// assert_eq!(report.max_level(), Some(GuidanceLevel::Caution));
+/// assert_eq!(report.guidance[0].code, "combined_qadha_shawwal");
+/// ```
+pub fn check_intention(date: NaiveDate, intentions: &[Intention], context: &RuleContext) -> Result<IntentReport, ShaumError> {
+    use GuidanceLevel::*;
+
+    if intentions.is_empty() {
+        return Err(ShaumError::ValidationError("At least one intention is required".to_string()));
+    }
+    let analysis = check(date, context)?;
+    let has = |i: Intention| intentions.contains(&i);
+    let voluntary = intentions.iter().any(|i| !i.is_obligatory());
+    let mut guidance = Vec::new();
+
+    if analysis.is_eid() {
+        guidance.push(Guidance::new(Invalid, "fasting_forbidden", "Fasting on Eid is forbidden with any intention.", EVIDENCE_EID));
+    } else if analysis.is_tashriq() {
+        guidance.push(Guidance::new(Invalid, "fasting_forbidden", "Fasting on the days of Tashriq is forbidden.", EVIDENCE_TASHRIQ));
+    }
+
+    if analysis.is_personally_exempt() {
+        let message = if analysis.requires_qadha() {
+            "Fasting is not valid during menstruation or postnatal bleeding; this Ramadhan day is made up later."
+        } else {
+            "Fasting is not valid during menstruation or postnatal bleeding."
+        };
+        guidance.push(Guidance::new(Invalid, "personal_exemption", message, EVIDENCE_HAID));
+    } else if analysis.is_ramadhan() {
+        let message = if has(Intention::Qadha) {
+            "Ramadhan days are for the current Ramadhan; missed fasts are made up after it."
+        } else {
+            "Only the Ramadhan fast can be intended during Ramadhan."
+        };
+        let code = if has(Intention::Qadha) { "qadha_in_ramadhan" } else { "voluntary_in_ramadhan" };
+        guidance.push(Guidance::new(Invalid, code, message, EVIDENCE_QADHA));
+    }
+
+    if analysis.has_reason(&FastingType::MARADH) {
+        let (level, code, message) = if analysis.is_ramadhan() {
+            (Info, "illness_excused", "Illness excuses the Ramadhan fast; missed days are made up once recovered.")
+        } else {
+            (Caution, "illness_voluntary", "Voluntary fasting while ill is discouraged.")
+        };
+        guidance.push(Guidance::new(level, code, message, EVIDENCE_SAFAR));
+    }
+
+    if analysis.is_ramadhan() && analysis.has_reason(&FastingType::AJZ) {
+        guidance.push(Guidance::new(
+            Info,
+            "fidyah_due",
+            "Permanent inability excuses the Ramadhan fast; fidyah is paid for the day instead of making it up.",
+            EVIDENCE_AJZ,
+        ));
+    }
+
+    if let Some(compensation) = analysis.compensation()
+        && (analysis.has_reason(&FastingType::HAML) || analysis.has_reason(&FastingType::RADHA))
+    {
+        let message = match compensation {
+            Compensation::Qadha => "Pregnancy or nursing excuses the Ramadhan fast; the day is made up later.",
+            Compensation::QadhaAndFidyah => "Pregnancy or nursing excuses the Ramadhan fast; the day is made up later and fidyah is paid for it.",
+            Compensation::Fidyah => "Pregnancy or nursing excuses the Ramadhan fast; fidyah is paid for the day instead of making it up.",
+        };
+        guidance.push(Guidance::new(Info, "maternal_excused", message, EVIDENCE_MATERNAL));
+    }
+
+    if analysis.has_rukhsah() {
+        guidance.push(Guidance::new(
+            Info,
+            "safar_rukhsah",
+            "A traveler may break the Ramadhan fast and make the day up later; fasting remains valid.",
+            EVIDENCE_SAFAR,
+        ));
+    }
+
+    if analysis.is_day_of_doubt() {
+        if has(Intention::Daud) || !voluntary {
+            guidance.push(Guidance::new(
+                Info,
+                "day_of_doubt_permitted",
+                "Make-up and habitual fasts may be kept on the day of doubt.",
+                EVIDENCE_DOUBT,
+            ));
+        } else {
+            let (level, message) = match context.madhab {
+                Madhab::Shafi => (Invalid, "The Shafi'i school forbids a voluntary fast on the day of doubt unless it is habitual."),
+                _ => (Caution, "A voluntary fast on the day of doubt is disliked unless it is habitual."),
+            };
+            guidance.push(Guidance::new(level, "day_of_doubt", message, EVIDENCE_DOUBT));
+        }
+    }
+
+    if analysis.is_nisf_shaban() && voluntary {
+        guidance.push(Guidance::new(
+            Info,
+            "nisf_shaban_disputed",
+            "Fasting 15 Sha'ban for its own sake is disputed; it is in any case one of the white days.",
+            EVIDENCE_NISF_SHABAN,
+        ));
+    }
+
+    if analysis.is_arafah_pilgrim() && voluntary {
+        guidance.push(Guidance::new(
+            Caution,
+            "arafah_pilgrim",
+            "Pilgrims standing at Arafah should not fast the day; the Arafah fast is for those not performing Hajj.",
+            EVIDENCE_ARAFAH_PILGRIM,
+        ));
+    }
+
+    if analysis.is_pre_ramadhan() {
+        if has(Intention::Daud) || !voluntary {
+            guidance.push(Guidance::new(
+                Info,
+                "pre_ramadhan_permitted",
+                "Make-up and habitual fasts may be kept in the days before Ramadhan.",
+                EVIDENCE_PRE_RAMADHAN,
+            ));
+        } else {
+            let (level, message) = match context.madhab {
+                Madhab::Shafi => (Invalid, "The Shafi'i school forbids singling out the day or two before Ramadhan for a voluntary fast."),
+                _ => (Caution, "Singling out the day or two before Ramadhan for a voluntary fast is disliked."),
+            };
+            guidance.push(Guidance::new(level, "pre_ramadhan", message, EVIDENCE_PRE_RAMADHAN));
+        }
+    }
+
+    if analysis.is_late_shaban() {
+        if has(Intention::Daud) || !voluntary {
+            guidance.push(Guidance::new(
+                Info,
+                "late_shaban_permitted",
+                "Make-up and habitual fasts may be kept in the second half of Sha'ban.",
+                EVIDENCE_LATE_SHABAN,
+            ));
+        } else {
+            guidance.push(Guidance::new(
+                Invalid,
+                "late_shaban",
+                "The Shafi'i school forbids starting voluntary fasts after 15 Sha'ban unless they are habitual or joined to earlier days.",
+                EVIDENCE_LATE_SHABAN,
+            ));
+        }
+    }
+
+    if has(Intention::Shawwal) && analysis.hijri_month != MONTH_SHAWWAL {
+        guidance.push(Guidance::new(Invalid, "shawwal_outside_month", "The six days must fall in Shawwal.", EVIDENCE_SHAWWAL));
+    }
+
+    if has(Intention::Qadha) && has(Intention::Shawwal) {
+        let (level, message) = match context.madhab {
+            Madhab::Hanbali => (Invalid, "The Hanbali school requires the missed fasts to be made up before the six days, so one fast cannot count for both."),
+            Madhab::Maliki => (Caution, "The Maliki school counts the fast as qadha; the Shawwal reward for a combined intention is disputed."),
+            Madhab::Shafi => (Caution, "Later Shafi'i scholars hold the Shawwal reward is attained with a qadha fast, but less fully than with separate fasts; disputed."),
+            Madhab::Hanafi => (Caution, "The Hanafi school counts the fast as qadha; whether the Shawwal reward is attained is disputed."),
+        };
+        guidance.push(Guidance::new(level, "combined_qadha_shawwal", message, EVIDENCE_SHAWWAL));
+    }
+
+    if has(Intention::Daud) {
+        if matches!(date.weekday(), Weekday::Fri | Weekday::Sat) {
+            guidance.push(Guidance::new(
+                Info,
+                "daud_singling_out_exempt",
+                "A Daud fast that lands on a Friday or Saturday is not considered singling that day out.",
+                EVIDENCE_DAUD,
+            ));
+        }
+    } else if voluntary && !analysis.is_ramadhan() {
+        // Singling out applies to voluntary fasts without another reason for the day.
+        let singled = match date.weekday() {
+            Weekday::Fri => analysis.has_reason(&FastingType::FRIDAY_EXCLUSIVE).then_some(EVIDENCE_FRIDAY),
+            Weekday::Sat => analysis.has_reason(&FastingType::SATURDAY_EXCLUSIVE).then_some(EVIDENCE_SATURDAY),
+            _ => None,
+        };
+        if let Some(evidence) = singled {
+            guidance.push(Guidance::new(
+                Caution,
+                "singled_out_day",
+                "Fasting this day alone is disliked; add the day before or after.",
+                evidence,
+            ));
+        }
+    }
+
+    guidance.sort_by_key(|g| std::cmp::Reverse(g.level));
+    Ok(IntentReport { date, intentions: intentions.to_vec(), madhab: context.madhab, analysis, guidance })
+}
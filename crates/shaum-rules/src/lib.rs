@@ -9,11 +9,51 @@ pub mod i18n;
 pub mod macros;
 pub mod constants;
 pub mod daud_util;
+#[cfg(feature = "astronomy")]
+pub mod prayer;
+pub mod classify;
+pub mod export;
+pub mod format;
+pub mod month_view;
+pub mod compat;
+pub mod coverage;
+pub mod recurrence;
+pub mod hijri_month;
+pub mod year_grid;
+pub mod intent;
+pub mod schedule;
+pub mod personal;
+pub mod watcher;
+pub mod occasions;
+pub mod calibrate;
+pub mod qada;
+pub mod vow;
+pub mod registry;
+#[cfg(feature = "async")]
+pub use shaum_network::http;
+#[cfg(feature = "async")]
+pub mod bulk;
 
 // Re-export main items from rules module
-pub use rules::{analyze, check, RuleContext, MoonProvider, SunsetProvider, DefaultSunsetProvider};
-pub use rules::{FixedAdjustment, NoAdjustment};
+pub use rules::{analyze, check, is_fastable, RuleContext, MoonProvider, SunsetProvider, DefaultSunsetProvider};
+pub use rules::{analyze_today, analyze_local, LocalDay};
+pub use rules::{FixedAdjustment, NoAdjustment, HybridMoonProvider, MonthSightings};
+pub use rules::{adjustment_to_month_offset, month_offset_to_adjustment};
+pub use rules::{RULESET_VERSION, RULESET_CHANGES, RulesetChange, RulesetChangeKind};
+#[cfg(feature = "async")]
+pub use rules::{RemoteMoonProvider, StatusCallback};
 
-pub use query::{FastingQuery, QueryExt};
+pub use query::{FastingQuery, Obligations, QueryExt};
 pub use extension::ShaumDateExt;
+#[cfg(feature = "astronomy")]
+pub use prayer::{prayer_times, is_ramadhan, niyyah_deadline, niyyah_deadline_for};
+pub use classify::classify;
+pub use month_view::{MonthView, MonthViewBuilder, MonthAnchor, CalendarDay, CalendarWeek};
 pub use daud_util::{DaudIterator, generate_daud_schedule, DaudScheduleBuilder};
+pub use hijri_month::HijriMonthIterator;
+pub use year_grid::{YearGrid, GridDay};
+pub use schedule::PersonalSchedule;
+pub use vow::{Vow, VowDay, Vows};
+pub use registry::{Rule, RuleInput, RuleRegistry, RuleState};
+pub use personal::{MaternalConcern, MaternalState, PersonalContext, PersonalState};
+pub use watcher::{MonthWatcher, MonthEvent, Clock, SystemClock, ManualClock};
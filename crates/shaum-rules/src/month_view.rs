@@ -0,0 +1,259 @@
+//! Month grids for calendar widgets.
+//!
+//! A [`MonthView`] is a list of full weeks: leading and trailing cells are the
+//! real days of the adjacent months (analysed like any other day and flagged
+//! with `in_month == false`), and each row carries its ISO week number.
+//! Views are anchored on a Gregorian or a Hijri month. Where the calendar's
+//! supported range ends inside a view, the month and the grid end with it.
+
+use std::sync::Arc;
+
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+use shaum_types::{FastingAnalysis, ShaumError};
+
+use crate::rules::{check, RuleContext};
+
+/// Month a view is built around.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonthAnchor {
+    Gregorian { year: i32, month: u32 },
+    /// Hijri month as resolved by the view's [`RuleContext`] (calendar and adjustment).
+    Hijri { year: usize, month: usize },
+}
+
+impl MonthAnchor {
+    /// The following month on the same calendar.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Gregorian { year, month: 12 } => Self::Gregorian { year: year + 1, month: 1 },
+            Self::Gregorian { year, month } => Self::Gregorian { year, month: month + 1 },
+            Self::Hijri { year, month: 12 } => Self::Hijri { year: year + 1, month: 1 },
+            Self::Hijri { year, month } => Self::Hijri { year, month: month + 1 },
+        }
+    }
+
+    /// The preceding month on the same calendar.
+    pub fn prev(self) -> Self {
+        match self {
+            Self::Gregorian { year, month: 1 } => Self::Gregorian { year: year - 1, month: 12 },
+            Self::Gregorian { year, month } => Self::Gregorian { year, month: month - 1 },
+            Self::Hijri { year, month: 1 } => Self::Hijri { year: year - 1, month: 12 },
+            Self::Hijri { year, month } => Self::Hijri { year, month: month - 1 },
+        }
+    }
+}
+
+/// One cell of the grid.
+#[derive(Debug, Clone)]
+pub struct CalendarDay {
+    pub date: NaiveDate,
+    /// `false` for padding days from the previous or next month.
+    pub in_month: bool,
+    pub analysis: FastingAnalysis,
+}
+
+/// One row of the grid: seven days starting on the view's week start (fewer
+/// only in the last row before the calendar's range ends).
+#[derive(Debug, Clone)]
+pub struct CalendarWeek {
+    /// ISO 8601 week number of the row's Thursday.
+    pub iso_week: u32,
+    /// ISO week-numbering year of the row's Thursday (differs from the calendar
+    /// year around New Year).
+    pub iso_year: i32,
+    pub days: Vec<CalendarDay>,
+}
+
+/// A month laid out as full weeks.
+#[derive(Debug, Clone)]
+pub struct MonthView {
+    pub anchor: MonthAnchor,
+    /// First and last Gregorian day of the anchored month.
+    pub first: NaiveDate,
+    pub last: NaiveDate,
+    pub week_start: Weekday,
+    pub weeks: Vec<CalendarWeek>,
+    fixed_rows: bool,
+    /// Shared with the views built by [`MonthView::next`] and friends, so
+    /// custom rules and the sunset provider carry over.
+    context: Arc<RuleContext>,
+}
+
+impl MonthView {
+    /// Starts building the view of Gregorian `year`-`month`.
+    pub fn builder(year: i32, month: u32) -> MonthViewBuilder { MonthViewBuilder::new(year, month) }
+
+    /// Starts building the view of Hijri `month` `year` AH.
+    pub fn hijri_builder(year: usize, month: usize) -> MonthViewBuilder { MonthViewBuilder::hijri(year, month) }
+
+    /// Cells of the month itself, without padding.
+    pub fn days_in_month(&self) -> impl Iterator<Item = &CalendarDay> {
+        self.weeks.iter().flat_map(|w| w.days.iter()).filter(|d| d.in_month)
+    }
+
+    /// The following month on the anchor's calendar, with the same settings.
+    pub fn next(&self) -> Result<MonthView, ShaumError> { self.rebuild(self.anchor.next()) }
+
+    /// The preceding month on the anchor's calendar, with the same settings.
+    pub fn prev(&self) -> Result<MonthView, ShaumError> { self.rebuild(self.anchor.prev()) }
+
+    /// Hijri-anchored view of the Hijri month after the one in effect on [`MonthView::first`].
+    pub fn next_hijri_month(&self) -> Result<MonthView, ShaumError> {
+        self.rebuild(self.hijri_anchor()?.next())
+    }
+
+    /// Hijri-anchored view of the Hijri month before the one in effect on [`MonthView::first`].
+    pub fn prev_hijri_month(&self) -> Result<MonthView, ShaumError> {
+        self.rebuild(self.hijri_anchor()?.prev())
+    }
+
+    fn hijri_anchor(&self) -> Result<MonthAnchor, ShaumError> {
+        let h = self.context.hijri(self.first)?;
+        Ok(MonthAnchor::Hijri { year: h.year, month: h.month })
+    }
+
+    fn rebuild(&self, anchor: MonthAnchor) -> Result<MonthView, ShaumError> {
+        MonthViewBuilder {
+            anchor,
+            week_start: self.week_start,
+            fixed_rows: self.fixed_rows,
+            context: self.context.clone(),
+        }
+        .build()
+    }
+}
+
+/// Builder for [`MonthView`].
+///
+/// ```rust
+/// use chrono::Weekday;
+/// use shaum_rules::MonthView;
+///
+/// let view = MonthView::builder(2024, 3).week_start(Weekday::Sun).fixed_rows().build().unwrap();
+/// assert_eq!(view.weeks.len(), 6);
+/// assert_eq!(view.days_in_month().count(), 31);
+///
+/// // Ramadhan 1445 as its own grid, then Shawwal.
+/// let ramadhan = MonthView::hijri_builder(1445, 9).build().unwrap();
+/// assert_eq!(ramadhan.first.to_string(), "2024-03-11");
+/// let shawwal = ramadhan.next().unwrap();
+/// assert_eq!(shawwal.first.to_string(), "2024-04-10");
+/// ```
+#[derive(Debug, Clone)]
+pub struct MonthViewBuilder {
+    anchor: MonthAnchor,
+    week_start: Weekday,
+    fixed_rows: bool,
+    context: Arc<RuleContext>,
+}
+
+impl MonthViewBuilder {
+    pub fn new(year: i32, month: u32) -> Self {
+        Self::anchored(MonthAnchor::Gregorian { year, month })
+    }
+
+    pub fn hijri(year: usize, month: usize) -> Self {
+        Self::anchored(MonthAnchor::Hijri { year, month })
+    }
+
+    pub fn anchored(anchor: MonthAnchor) -> Self {
+        Self {
+            anchor,
+            week_start: Weekday::Mon,
+            fixed_rows: false,
+            context: Arc::new(RuleContext::default()),
+        }
+    }
+
+    /// First column of each row (default Monday).
+    pub fn week_start(mut self, weekday: Weekday) -> Self {
+        self.week_start = weekday;
+        self
+    }
+
+    /// Always emit six rows so the grid height never changes between months.
+    pub fn fixed_rows(mut self) -> Self {
+        self.fixed_rows = true;
+        self
+    }
+
+    pub fn with_context(mut self, ctx: RuleContext) -> Self {
+        self.context = Arc::new(ctx);
+        self
+    }
+
+    pub fn build(self) -> Result<MonthView, ShaumError> {
+        let (first, last) = self.bounds()?;
+
+        let lead = first.weekday().days_since(self.week_start);
+        let grid_start = first - Duration::days(i64::from(lead));
+        let used = (i64::from(lead) + (last - first).num_days()) / 7 + 1;
+        let rows = if self.fixed_rows { used.max(6) } else { used };
+
+        let mut weeks = Vec::with_capacity(rows as usize);
+        for row in 0..rows {
+            let row_start = grid_start + Duration::weeks(row);
+            let mut days = Vec::with_capacity(7);
+            for date in (0..7).map(|i| row_start + Duration::days(i)) {
+                let analysis = match check(date, &self.context) {
+                    // The calendar's range ends in the trailing padding.
+                    Err(ShaumError::DateOutOfRange { .. }) if date > last => break,
+                    result => result?,
+                };
+                days.push(CalendarDay { date, in_month: (first..=last).contains(&date), analysis });
+            }
+            let complete = days.len() == 7;
+            if !days.is_empty() {
+                let thursday = row_start + Duration::days(i64::from(Weekday::Thu.days_since(self.week_start)));
+                let iso = thursday.iso_week();
+                weeks.push(CalendarWeek { iso_week: iso.week(), iso_year: iso.year(), days });
+            }
+            if !complete {
+                break;
+            }
+        }
+
+        Ok(MonthView {
+            anchor: self.anchor,
+            first,
+            last,
+            week_start: self.week_start,
+            weeks,
+            fixed_rows: self.fixed_rows,
+            context: self.context,
+        })
+    }
+
+    /// First and last Gregorian day of the anchored month.
+    fn bounds(&self) -> Result<(NaiveDate, NaiveDate), ShaumError> {
+        match self.anchor {
+            MonthAnchor::Gregorian { year, month } => {
+                let first = NaiveDate::from_ymd_opt(year, month, 1).ok_or_else(|| {
+                    ShaumError::ValidationError(format!("Invalid month: {}-{}", year, month))
+                })?;
+                let last = first
+                    .checked_add_months(Months::new(1))
+                    .and_then(|d| d.pred_opt())
+                    .ok_or_else(|| ShaumError::date_out_of_range(first))?;
+                Ok((first, last))
+            }
+            MonthAnchor::Hijri { year, month } => {
+                let first = self.context.hijri_month_start(year, month)?;
+                // Hijri months have 29 or 30 days.
+                let day_30 = first + Duration::days(29);
+                let last = match self.context.hijri(day_30) {
+                    Ok(h) if h.month == month => day_30,
+                    Ok(_) => day_30 - Duration::days(1),
+                    // The calendar's range ends within the month: so does the view.
+                    Err(ShaumError::DateOutOfRange { .. }) => (0..29)
+                        .rev()
+                        .map(|d| first + Duration::days(d))
+                        .find(|d| self.context.hijri(*d).is_ok())
+                        .unwrap_or(first),
+                    Err(e) => return Err(e),
+                };
+                Ok((first, last))
+            }
+        }
+    }
+}
@@ -0,0 +1,178 @@
+//! Islamic occasions for calendar displays.
+//!
+//! Occasions are commemorations and holidays (Islamic New Year, Isra Mi'raj,
+//! Mawlid, ...). They carry no fasting ruling and never change a
+//! [`FastingAnalysis`](shaum_types::FastingAnalysis); they are resolved
+//! through the same [`RuleContext`] as the analysis, so both agree on the
+//! Hijri date. Which occasions are listed varies by region: start from a
+//! preset and add or remove entries.
+
+use std::borrow::Cow;
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use shaum_types::ShaumError;
+
+use crate::rules::RuleContext;
+
+/// A yearly occasion on a fixed Hijri date.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Occasion {
+    /// Stable snake_case identifier, e.g. `"mawlid"`.
+    pub id: Cow<'static, str>,
+    /// English display name.
+    pub name: Cow<'static, str>,
+    pub month: usize,
+    pub day: usize,
+}
+
+impl Occasion {
+    pub const ISLAMIC_NEW_YEAR: Self = Self::builtin("islamic_new_year", "Islamic New Year", 1, 1);
+    pub const ASHURA: Self = Self::builtin("ashura", "Day of Ashura", 1, 10);
+    pub const MAWLID: Self = Self::builtin("mawlid", "Mawlid an-Nabi", 3, 12);
+    pub const ISRA_MIRAJ: Self = Self::builtin("isra_miraj", "Isra' and Mi'raj", 7, 27);
+    pub const NISF_SHABAN: Self = Self::builtin("nisf_shaban", "Nisf Sha'ban", 8, 15);
+    pub const RAMADHAN_START: Self = Self::builtin("ramadhan_start", "First of Ramadhan", 9, 1);
+    pub const NUZUL_QURAN: Self = Self::builtin("nuzul_quran", "Nuzul al-Qur'an", 9, 17);
+    pub const LAYLAT_AL_QADR: Self = Self::builtin("laylat_al_qadr", "Laylat al-Qadr (27th night)", 9, 27);
+    pub const EID_AL_FITR: Self = Self::builtin("eid_al_fitr", "Eid al-Fitr", 10, 1);
+    pub const ARAFAH: Self = Self::builtin("arafah", "Day of Arafah", 12, 9);
+    pub const EID_AL_ADHA: Self = Self::builtin("eid_al_adha", "Eid al-Adha", 12, 10);
+
+    /// Every built-in occasion, in Hijri year order.
+    pub const ALL: [Self; 11] = [
+        Self::ISLAMIC_NEW_YEAR, Self::ASHURA, Self::MAWLID, Self::ISRA_MIRAJ, Self::NISF_SHABAN,
+        Self::RAMADHAN_START, Self::NUZUL_QURAN, Self::LAYLAT_AL_QADR, Self::EID_AL_FITR,
+        Self::ARAFAH, Self::EID_AL_ADHA,
+    ];
+
+    const fn builtin(id: &'static str, name: &'static str, month: usize, day: usize) -> Self {
+        Self { id: Cow::Borrowed(id), name: Cow::Borrowed(name), month, day }
+    }
+
+    /// A custom occasion.
+    ///
+    /// # Errors
+    /// `ValidationError` for a month outside 1-12 or a day outside 1-30.
+    pub fn new(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        month: usize,
+        day: usize,
+    ) -> Result<Self, ShaumError> {
+        if !(1..=12).contains(&month) || !(1..=30).contains(&day) {
+            return Err(ShaumError::ValidationError(format!("Invalid Hijri occasion date {}-{}", month, day)));
+        }
+        Ok(Self { id: Cow::Owned(id.into()), name: Cow::Owned(name.into()), month, day })
+    }
+}
+
+/// The occasions a calendar shows.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_rules::occasions::{Occasion, OccasionSet};
+/// use shaum_rules::RuleContext;
+///
+/// // Eid al-Fitr 1446 follows the context's calendar: 31 March 2025 in the
+/// // Kemenag taqwim, a day after Umm al-Qura.
+/// let eid = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+/// let set = OccasionSet::indonesia();
+/// assert_eq!(set.on(eid, &RuleContext::indonesia()).unwrap(), vec![&Occasion::EID_AL_FITR]);
+/// assert!(set.on(eid, &RuleContext::default()).unwrap().is_empty());
+/// assert!(!OccasionSet::saudi_arabia().contains("mawlid"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OccasionSet {
+    occasions: Vec<Occasion>,
+}
+
+impl OccasionSet {
+    /// An empty set.
+    pub fn new() -> Self { Self::default() }
+
+    /// Every built-in occasion ([`Occasion::ALL`]).
+    pub fn standard() -> Self {
+        Occasion::ALL.into_iter().collect()
+    }
+
+    /// Indonesian national holidays and observances.
+    pub fn indonesia() -> Self {
+        [Occasion::ISLAMIC_NEW_YEAR, Occasion::MAWLID, Occasion::ISRA_MIRAJ, Occasion::NUZUL_QURAN,
+            Occasion::EID_AL_FITR, Occasion::EID_AL_ADHA].into_iter().collect()
+    }
+
+    /// Turkish religious days (kandil nights and bayrams).
+    pub fn turkey() -> Self {
+        [Occasion::ISLAMIC_NEW_YEAR, Occasion::ASHURA, Occasion::MAWLID, Occasion::ISRA_MIRAJ,
+            Occasion::NISF_SHABAN, Occasion::RAMADHAN_START, Occasion::LAYLAT_AL_QADR,
+            Occasion::EID_AL_FITR, Occasion::ARAFAH, Occasion::EID_AL_ADHA].into_iter().collect()
+    }
+
+    /// Saudi observances: the two Eids and the Day of Arafah only.
+    pub fn saudi_arabia() -> Self {
+        [Occasion::ARAFAH, Occasion::EID_AL_FITR, Occasion::EID_AL_ADHA].into_iter().collect()
+    }
+
+    /// Adds `occasion`, replacing one with the same id.
+    pub fn with(mut self, occasion: Occasion) -> Self {
+        self.occasions.retain(|o| o.id != occasion.id);
+        self.occasions.push(occasion);
+        self
+    }
+
+    /// Removes the occasion with `id`, if present.
+    pub fn without(mut self, id: &str) -> Self {
+        self.occasions.retain(|o| o.id != id);
+        self
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.occasions.iter().any(|o| o.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Occasion> {
+        self.occasions.iter()
+    }
+
+    pub fn len(&self) -> usize { self.occasions.len() }
+
+    pub fn is_empty(&self) -> bool { self.occasions.is_empty() }
+
+    /// Occasions falling on `date` under `context`'s calendar.
+    ///
+    /// # Errors
+    /// Any error converting `date`.
+    pub fn on(&self, date: NaiveDate, context: &RuleContext) -> Result<Vec<&Occasion>, ShaumError> {
+        let h = context.hijri(date)?;
+        Ok(self.occasions.iter().filter(|o| o.month == h.month && o.day == h.day).collect())
+    }
+
+    /// Occasions from `start` to `end` inclusive, by date.
+    ///
+    /// # Errors
+    /// `ValidationError` if `end` is before `start`, or any conversion error.
+    pub fn between(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        context: &RuleContext,
+    ) -> Result<Vec<(NaiveDate, &Occasion)>, ShaumError> {
+        if end < start {
+            return Err(ShaumError::ValidationError(format!("Range end {} is before start {}", end, start)));
+        }
+        let mut found = Vec::new();
+        let mut date = start;
+        while date <= end {
+            found.extend(self.on(date, context)?.into_iter().map(|o| (date, o)));
+            date += Duration::days(1);
+        }
+        Ok(found)
+    }
+}
+
+impl FromIterator<Occasion> for OccasionSet {
+    fn from_iter<I: IntoIterator<Item = Occasion>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::new(), Self::with)
+    }
+}
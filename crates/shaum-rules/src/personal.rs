@@ -0,0 +1,270 @@
+//! Personal circumstances that override the calendar.
+//!
+//! Fasting is forbidden during menstruation (haid) and postnatal bleeding
+//! (nifas) whatever the day, and Ramadhan days missed this way are made up
+//! later. A traveler (musafir) may break a Ramadhan fast and make it up,
+//! and an ill person (marid) is excused from it. Pregnant and nursing
+//! women are excused too; what they owe for the day differs by madhab.
+//! Those permanently unable to fast (the elderly, the chronically ill) owe
+//! fidyah for each Ramadhan day instead of making it up.
+//! Set a [`PersonalContext`] on the [`RuleContext`](crate::RuleContext) for
+//! the days it applies to.
+
+use serde::{Deserialize, Serialize};
+use shaum_types::{Compensation, FastingType, Madhab, TraceCode};
+
+use crate::constants::SAFAR_MIN_KM;
+
+/// A state in which fasting is not valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PersonalState {
+    /// Menstruation.
+    Haid,
+    /// Postnatal bleeding.
+    Nifas,
+}
+
+impl PersonalState {
+    /// Reason added to the analysis while in this state.
+    pub fn fasting_type(&self) -> FastingType {
+        match self {
+            Self::Haid => FastingType::HAID,
+            Self::Nifas => FastingType::NIFAS,
+        }
+    }
+
+    pub fn trace_code(&self) -> TraceCode {
+        match self {
+            Self::Haid => TraceCode::Haid,
+            Self::Nifas => TraceCode::Nifas,
+        }
+    }
+}
+
+/// Pregnancy or nursing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MaternalState {
+    Pregnant,
+    Nursing,
+}
+
+impl MaternalState {
+    /// Reason added to a Ramadhan analysis while in this state.
+    pub fn fasting_type(&self) -> FastingType {
+        match self {
+            Self::Pregnant => FastingType::HAML,
+            Self::Nursing => FastingType::RADHA,
+        }
+    }
+
+    pub fn trace_code(&self) -> TraceCode {
+        match self {
+            Self::Pregnant => TraceCode::Haml,
+            Self::Nursing => TraceCode::Radha,
+        }
+    }
+}
+
+/// Whom a pregnant or nursing woman fears harm for by fasting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MaternalConcern {
+    /// Herself (or herself and the child): treated like illness.
+    #[default]
+    Own,
+    /// The child alone.
+    Child,
+}
+
+/// The person's state on the analysed day.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_rules::{check, PersonalContext, RuleContext};
+/// use shaum_types::FastingStatus;
+///
+/// // 2024-03-20: 10 Ramadhan 1445.
+/// let date = NaiveDate::from_ymd_opt(2024, 3, 20).unwrap();
+/// let analysis = check(date, &RuleContext::new().with_personal(PersonalContext::haid())).unwrap();
+/// assert_eq!(analysis.primary_status, FastingStatus::Haram);
+/// assert!(analysis.requires_qadha());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PersonalContext {
+    pub state: Option<PersonalState>,
+    /// On a journey (safar).
+    #[serde(default)]
+    pub traveling: bool,
+    /// Length of the journey, if known. Journeys shorter than
+    /// [`SAFAR_MIN_KM`] do not permit the concession.
+    #[serde(default)]
+    pub journey_km: Option<f64>,
+    /// Ill: Ramadhan days are excused and owed as qadha.
+    #[serde(default)]
+    pub ill: bool,
+    /// While [`Self::ill`], voluntary fasts are Makruh rather than keeping
+    /// their usual ruling.
+    #[serde(default)]
+    pub ill_discourages_voluntary: bool,
+    /// Permanently unable to fast: Ramadhan days are excused with fidyah.
+    #[serde(default)]
+    pub permanently_unable: bool,
+    /// Pregnant or nursing: Ramadhan days are excused, see
+    /// [`Self::maternal_compensation`].
+    #[serde(default)]
+    pub maternal: Option<MaternalState>,
+    #[serde(default)]
+    pub maternal_concern: MaternalConcern,
+    /// Overrides the madhab's ruling for [`Self::maternal`], e.g.
+    /// [`Compensation::Fidyah`] for the view of Ibn Abbas and Ibn Umar.
+    #[serde(default)]
+    pub maternal_ruling: Option<Compensation>,
+}
+
+impl PersonalContext {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn haid() -> Self { Self::with_state(PersonalState::Haid) }
+
+    pub fn nifas() -> Self { Self::with_state(PersonalState::Nifas) }
+
+    pub fn with_state(state: PersonalState) -> Self {
+        Self { state: Some(state), ..Self::default() }
+    }
+
+    /// A traveler on a journey of unknown length.
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use shaum_rules::{check, PersonalContext, RuleContext};
+    /// use shaum_types::FastingStatus;
+    ///
+    /// // 2024-03-20: 10 Ramadhan 1445. Still Wajib, with the concession noted.
+    /// let date = NaiveDate::from_ymd_opt(2024, 3, 20).unwrap();
+    /// let analysis = check(date, &RuleContext::new().with_personal(PersonalContext::traveler())).unwrap();
+    /// assert_eq!(analysis.primary_status, FastingStatus::Wajib);
+    /// assert!(analysis.has_rukhsah());
+    ///
+    /// let short = PersonalContext::traveler().with_journey_km(40.0);
+    /// assert!(!check(date, &RuleContext::new().with_personal(short)).unwrap().has_rukhsah());
+    /// ```
+    pub fn traveler() -> Self {
+        Self { traveling: true, ..Self::default() }
+    }
+
+    /// Sets the journey length and marks the person as traveling.
+    pub fn with_journey_km(mut self, km: f64) -> Self {
+        self.traveling = true;
+        self.journey_km = Some(km);
+        self
+    }
+
+    /// Ill, optionally discouraging voluntary fasts as well.
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use shaum_rules::{check, PersonalContext, RuleContext};
+    /// use shaum_types::FastingStatus;
+    ///
+    /// // 2024-03-20: 10 Ramadhan 1445, excused and owed as qadha.
+    /// let ctx = RuleContext::new().with_personal(PersonalContext::ill(false));
+    /// let ramadhan = check(NaiveDate::from_ymd_opt(2024, 3, 20).unwrap(), &ctx).unwrap();
+    /// assert_eq!(ramadhan.primary_status, FastingStatus::Mubah);
+    /// assert!(ramadhan.requires_qadha());
+    ///
+    /// // 2024-04-15: 6 Shawwal 1445, Sunnah unless voluntary fasts are discouraged.
+    /// let shawwal = NaiveDate::from_ymd_opt(2024, 4, 15).unwrap();
+    /// assert!(check(shawwal, &ctx).unwrap().primary_status.is_sunnah());
+    /// let strict = RuleContext::new().with_personal(PersonalContext::ill(true));
+    /// assert!(check(shawwal, &strict).unwrap().primary_status.is_makruh());
+    /// ```
+    pub fn ill(discourage_voluntary: bool) -> Self {
+        Self { ill: true, ill_discourages_voluntary: discourage_voluntary, ..Self::default() }
+    }
+
+    /// Permanently unable to fast, through old age or chronic illness.
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use shaum_rules::{check, PersonalContext, RuleContext};
+    /// use shaum_types::{Compensation, FastingStatus};
+    ///
+    /// // 2024-03-20: 10 Ramadhan 1445.
+    /// let ctx = RuleContext::new().with_personal(PersonalContext::permanently_unable());
+    /// let analysis = check(NaiveDate::from_ymd_opt(2024, 3, 20).unwrap(), &ctx).unwrap();
+    /// assert_eq!(analysis.primary_status, FastingStatus::Mubah);
+    /// assert_eq!(analysis.compensation(), Some(Compensation::Fidyah));
+    /// assert!(!analysis.requires_qadha());
+    /// ```
+    pub fn permanently_unable() -> Self {
+        Self { permanently_unable: true, ..Self::default() }
+    }
+
+    /// Pregnant, fearing for herself.
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use shaum_rules::{check, PersonalContext, RuleContext};
+    /// use shaum_types::{Compensation, FastingStatus, Madhab};
+    ///
+    /// // 2024-03-20: 10 Ramadhan 1445.
+    /// let date = NaiveDate::from_ymd_opt(2024, 3, 20).unwrap();
+    /// let ctx = RuleContext::new().with_personal(PersonalContext::pregnant().fearing_for_child());
+    /// let analysis = check(date, &ctx).unwrap();
+    /// assert_eq!(analysis.primary_status, FastingStatus::Mubah);
+    /// assert_eq!(analysis.compensation(), Some(Compensation::QadhaAndFidyah));
+    ///
+    /// let hanafi = check(date, &ctx.madhab(Madhab::Hanafi)).unwrap();
+    /// assert_eq!(hanafi.compensation(), Some(Compensation::Qadha));
+    /// ```
+    pub fn pregnant() -> Self {
+        Self { maternal: Some(MaternalState::Pregnant), ..Self::default() }
+    }
+
+    /// Nursing, fearing for herself.
+    pub fn nursing() -> Self {
+        Self { maternal: Some(MaternalState::Nursing), ..Self::default() }
+    }
+
+    /// Fasting is feared to harm the child rather than the mother.
+    pub fn fearing_for_child(mut self) -> Self {
+        self.maternal_concern = MaternalConcern::Child;
+        self
+    }
+
+    /// Sets [`Self::maternal_ruling`].
+    pub fn with_maternal_ruling(mut self, ruling: Compensation) -> Self {
+        self.maternal_ruling = Some(ruling);
+        self
+    }
+
+    /// What a pregnant or nursing woman owes for a Ramadhan day under
+    /// `madhab`, or `None` if neither applies.
+    ///
+    /// Fearing for herself, she makes the day up in every school. Fearing
+    /// for the child, the Shafi'i and Hanbali schools add fidyah, the Maliki
+    /// school adds it for nursing only, and the Hanafi school requires qadha
+    /// alone.
+    pub fn maternal_compensation(&self, madhab: Madhab) -> Option<Compensation> {
+        let state = self.maternal?;
+        if let Some(ruling) = self.maternal_ruling {
+            return Some(ruling);
+        }
+        let fidyah = self.maternal_concern == MaternalConcern::Child && match madhab {
+            Madhab::Shafi | Madhab::Hanbali => true,
+            Madhab::Maliki => state == MaternalState::Nursing,
+            Madhab::Hanafi => false,
+        };
+        Some(if fidyah { Compensation::QadhaAndFidyah } else { Compensation::Qadha })
+    }
+
+    /// Whether fasting is forbidden regardless of the day.
+    pub fn is_exempt(&self) -> bool { self.state.is_some() }
+
+    /// Travelling far enough to break a Ramadhan fast (see [`SAFAR_MIN_KM`]).
+    pub fn is_musafir(&self) -> bool {
+        self.traveling && self.journey_km.is_none_or(|km| km >= SAFAR_MIN_KM)
+    }
+}
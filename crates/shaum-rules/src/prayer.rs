@@ -0,0 +1,93 @@
+//! Prayer times aware of the rules engine's Hijri calendar.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use shaum_astronomy::prayer::{calculate_prayer_times_in_ramadhan, solar_transit, PrayerTimes};
+use shaum_types::{GeoCoordinate, Madhab, SeasonalPrayerParams, ShaumError};
+use crate::constants::MONTH_RAMADHAN;
+use crate::intent::Intention;
+use crate::rules::{check, RuleContext};
+
+/// Calculates prayer times, switching to Ramadhan parameters and Isha intervals
+/// when `date` is in Ramadhan under `context` (including its Hijri adjustment).
+///
+/// Accepts plain [`PrayerParams`] or [`SeasonalPrayerParams`]. Uses the
+/// context's prayer cache when one is set. In strict mode `coords` must be valid.
+pub fn prayer_times(
+    date: NaiveDate,
+    coords: GeoCoordinate,
+    params: impl Into<SeasonalPrayerParams>,
+    context: &RuleContext,
+) -> Result<PrayerTimes, ShaumError> {
+    let coords = context.check_coords(coords)?;
+    let in_ramadhan = is_ramadhan(date, context)?;
+    let seasonal = params.into();
+    let params = seasonal.select(in_ramadhan);
+    match &context.prayer_cache {
+        Some(cache) => cache.get_or_calculate(date, coords, params, in_ramadhan),
+        None => calculate_prayer_times_in_ramadhan(date, coords, params, in_ramadhan),
+    }
+}
+
+/// Whether `date` falls in Ramadhan under `context`.
+pub fn is_ramadhan(date: NaiveDate, context: &RuleContext) -> Result<bool, ShaumError> {
+    Ok(context.hijri(date)?.month == MONTH_RAMADHAN)
+}
+
+/// Latest time the intention (niyyah) for fasting `date` may be made.
+///
+/// Obligatory fasts require intention before true dawn
+/// ([`PrayerTimes::suhoor_deadline`], not the adjusted Fajr). For voluntary
+/// fasts the deadline follows `context.madhab`:
+/// - Shafi: before zawal (solar transit).
+/// - Hanafi: before the midpoint between dawn and Maghrib; this also applies to Ramadhan.
+/// - Maliki: before dawn for every fast.
+/// - Hanbali: any time before Maghrib, provided nothing has broken the fast.
+///
+/// Returns `None` when fasting on `date` is forbidden. Use [`niyyah_deadline_for`]
+/// when the fast is a make-up or expiation rather than the day's own fast.
+pub fn niyyah_deadline(
+    date: NaiveDate,
+    coords: GeoCoordinate,
+    params: impl Into<SeasonalPrayerParams>,
+    context: &RuleContext,
+) -> Result<Option<DateTime<Utc>>, ShaumError> {
+    deadline(date, coords, params.into(), context, None)
+}
+
+/// Like [`niyyah_deadline`], for a fast made with `intention`.
+///
+/// Qadha and kaffarah fasts are owed but not tied to the day, so every madhab,
+/// Hanafi included, requires their intention before dawn.
+pub fn niyyah_deadline_for(
+    date: NaiveDate,
+    coords: GeoCoordinate,
+    params: impl Into<SeasonalPrayerParams>,
+    context: &RuleContext,
+    intention: Intention,
+) -> Result<Option<DateTime<Utc>>, ShaumError> {
+    deadline(date, coords, params.into(), context, Some(intention))
+}
+
+fn deadline(
+    date: NaiveDate,
+    coords: GeoCoordinate,
+    params: SeasonalPrayerParams,
+    context: &RuleContext,
+    intention: Option<Intention>,
+) -> Result<Option<DateTime<Utc>>, ShaumError> {
+    let status = check(date, context)?.primary_status;
+    if status.is_haram() {
+        return Ok(None);
+    }
+    let times = prayer_times(date, coords, params, context)?;
+    let dawn = times.suhoor_deadline;
+    let owed = intention.is_some_and(Intention::is_obligatory);
+    let deadline = match (context.madhab, status.is_wajib()) {
+        _ if owed => dawn,
+        (Madhab::Hanafi, _) => dawn + (times.maghrib - dawn) / 2,
+        (Madhab::Maliki, _) | (_, true) => dawn,
+        (Madhab::Shafi, false) => solar_transit(date, coords)?,
+        (Madhab::Hanbali, false) => times.maghrib,
+    };
+    Ok(Some(deadline))
+}
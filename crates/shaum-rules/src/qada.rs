@@ -0,0 +1,377 @@
+//! Tracking and scheduling makeup (qada) fasts.
+//!
+//! A [`QadaLedger`] records the Ramadhan days a user missed and the days they
+//! made up. Its [`QadaPlanner`] lays the outstanding days on permissible
+//! dates (never a Haram day, never in Ramadhan) and, when asked to, fits the
+//! six fasts of Shawwal around them following the madhab: the Hanbali school
+//! requires qada first and never counts one fast for both, the Maliki school
+//! prefers qada first, and the Shafi'i and Hanafi schools allow the time-bound
+//! Shawwal fasts first (or, when chosen, a combined intention).
+//! [`QadaLedger::deadline`] warns when the days left before the next
+//! Ramadhan, the deadline for making them up, are running out.
+
+use std::collections::BTreeSet;
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use shaum_types::{Compensation, FastingAnalysis, Madhab, ShaumError};
+
+use crate::constants::{MONTH_RAMADHAN, MONTH_SHAWWAL, QADA_TIGHT_MARGIN, SHAWWAL_FASTS};
+use crate::intent::Intention;
+use crate::rules::{is_haram_day, RuleContext};
+use crate::schedule::PersonalSchedule;
+
+/// Missed Ramadhan days and their makeup fasts.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_rules::qada::QadaLedger;
+///
+/// let mut ledger = QadaLedger::new();
+/// ledger.record_missed(NaiveDate::from_ymd_opt(2024, 3, 20).unwrap());
+/// ledger.record_missed(NaiveDate::from_ymd_opt(2024, 3, 21).unwrap());
+/// ledger.record_made_up(NaiveDate::from_ymd_opt(2024, 5, 2).unwrap());
+/// assert_eq!(ledger.outstanding(), 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QadaLedger {
+    missed: BTreeSet<NaiveDate>,
+    made_up: BTreeSet<NaiveDate>,
+}
+
+impl QadaLedger {
+    pub fn new() -> Self { Self::default() }
+
+    /// Records a missed Ramadhan day; `false` if already recorded.
+    pub fn record_missed(&mut self, date: NaiveDate) -> bool {
+        self.missed.insert(date)
+    }
+
+    /// Records the day if `analysis` says it is owed as qada
+    /// ([`FastingAnalysis::requires_qadha`]); returns whether it was.
+    pub fn record_analysis(&mut self, analysis: &FastingAnalysis) -> bool {
+        analysis.requires_qadha() && self.record_missed(analysis.date.date_naive())
+    }
+
+    /// Records a completed makeup fast; `false` if already recorded.
+    pub fn record_made_up(&mut self, date: NaiveDate) -> bool {
+        self.made_up.insert(date)
+    }
+
+    /// Days still owed.
+    pub fn outstanding(&self) -> usize {
+        self.missed.len().saturating_sub(self.made_up.len())
+    }
+
+    pub fn missed(&self) -> impl Iterator<Item = NaiveDate> + '_ { self.missed.iter().copied() }
+
+    pub fn made_up(&self) -> impl Iterator<Item = NaiveDate> + '_ { self.made_up.iter().copied() }
+
+    /// Planner for the outstanding days, starting on `start`.
+    pub fn planner(&self, start: NaiveDate) -> QadaPlanner {
+        QadaPlanner::new(start, self.outstanding())
+    }
+
+    /// How the outstanding days stand against the next Ramadhan as of `today`.
+    ///
+    /// Makeup fasts are taken to settle the oldest missed days first, so the
+    /// outstanding days are the most recently missed ones; those whose own
+    /// deadline (the Ramadhan after they were missed) has passed are
+    /// [`QadaDeadline::overdue`].
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use shaum_rules::qada::{DeadlineUrgency, QadaLedger};
+    /// use shaum_rules::RuleContext;
+    ///
+    /// let mut ledger = QadaLedger::new();
+    /// for day in 20..=24 {
+    ///     ledger.record_missed(NaiveDate::from_ymd_opt(2024, 3, day).unwrap());
+    /// }
+    /// // Ramadhan 1446 begins on 1 March 2025.
+    /// let today = NaiveDate::from_ymd_opt(2025, 2, 20).unwrap();
+    /// let deadline = ledger.deadline(today, &RuleContext::default()).unwrap();
+    /// assert_eq!(deadline.next_ramadhan, NaiveDate::from_ymd_opt(2025, 3, 1).unwrap());
+    /// assert_eq!(deadline.urgency, DeadlineUrgency::Tight);
+    /// assert!(deadline.warning().unwrap().contains("2025-03-01"));
+    /// ```
+    ///
+    /// # Errors
+    /// Any conversion error, e.g. for a date past the calendar's coverage.
+    pub fn deadline(&self, today: NaiveDate, context: &RuleContext) -> Result<QadaDeadline, ShaumError> {
+        let outstanding = self.outstanding();
+        let h = context.hijri(today)?;
+        let year = if h.month < MONTH_RAMADHAN { h.year } else { h.year + 1 };
+        let next_ramadhan = context.hijri_month_start(year, MONTH_RAMADHAN)?;
+
+        let mut permissible = Vec::new();
+        let mut date = today;
+        while date < next_ramadhan {
+            if context.hijri(date)?.month != MONTH_RAMADHAN && !is_haram_day(date, context)? {
+                permissible.push(date);
+            }
+            match date.succ_opt() {
+                Some(next) => date = next,
+                None => break,
+            }
+        }
+        let latest_start = (outstanding > 0 && permissible.len() >= outstanding)
+            .then(|| permissible[permissible.len() - outstanding]);
+
+        let mut overdue = 0;
+        for missed in self.missed.iter().rev().take(outstanding) {
+            let h = context.hijri(*missed)?;
+            let year = if h.month < MONTH_RAMADHAN { h.year } else { h.year + 1 };
+            overdue += usize::from(context.hijri_month_start(year, MONTH_RAMADHAN)? <= today);
+        }
+
+        let available_days = permissible.len();
+        let urgency = if outstanding == 0 {
+            DeadlineUrgency::Clear
+        } else if available_days < outstanding {
+            DeadlineUrgency::Insufficient
+        } else if available_days - outstanding < QADA_TIGHT_MARGIN {
+            DeadlineUrgency::Tight
+        } else {
+            DeadlineUrgency::OnTrack
+        };
+        let delay_ruling = match context.madhab {
+            Madhab::Hanafi => Compensation::Qadha,
+            _ => Compensation::QadhaAndFidyah,
+        };
+        Ok(QadaDeadline {
+            today,
+            outstanding,
+            next_ramadhan,
+            available_days,
+            latest_start,
+            overdue,
+            urgency,
+            madhab: context.madhab,
+            delay_ruling,
+        })
+    }
+}
+
+/// How pressing the outstanding qada is; see [`QadaLedger::deadline`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeadlineUrgency {
+    /// Nothing is owed.
+    Clear,
+    OnTrack,
+    /// Fewer than [`QADA_TIGHT_MARGIN`] permissible days to spare.
+    Tight,
+    /// Not enough permissible days left before the next Ramadhan.
+    Insufficient,
+}
+
+/// Outstanding qada measured against the next Ramadhan.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QadaDeadline {
+    pub today: NaiveDate,
+    pub outstanding: usize,
+    /// First day of the next Ramadhan; the outstanding days are due before it.
+    pub next_ramadhan: NaiveDate,
+    /// Permissible days from `today` to the day before `next_ramadhan`,
+    /// excluding Haram days and what is left of a current Ramadhan.
+    pub available_days: usize,
+    /// Last day from which fasting every permissible day still settles the
+    /// outstanding days in time; `None` when nothing is owed or it is too late.
+    pub latest_start: Option<NaiveDate>,
+    /// Outstanding days already past the Ramadhan following them.
+    pub overdue: usize,
+    pub urgency: DeadlineUrgency,
+    pub madhab: Madhab,
+    /// What each day delayed past its deadline without an excuse requires:
+    /// [`Compensation::Qadha`] alone for the Hanafi school, which permits
+    /// the delay; qada and fidyah for the others, the Shafi'i school
+    /// repeating the fidyah for every further year.
+    pub delay_ruling: Compensation,
+}
+
+impl QadaDeadline {
+    /// A warning with dates when the deadline is tight or missed, else `None`.
+    pub fn warning(&self) -> Option<String> {
+        let delay = if self.delay_ruling.requires_fidyah() {
+            "each day delayed past it without an excuse also requires fidyah"
+        } else {
+            "days delayed past it are still made up, without fidyah"
+        };
+        let mut warning = match self.urgency {
+            DeadlineUrgency::Clear | DeadlineUrgency::OnTrack => None,
+            DeadlineUrgency::Tight => Some(format!(
+                "{} qada day(s) owed and {} permissible day(s) left before Ramadhan begins on {}; start by {}",
+                self.outstanding,
+                self.available_days,
+                self.next_ramadhan,
+                self.latest_start.map_or_else(|| self.today.to_string(), |d| d.to_string()),
+            )),
+            DeadlineUrgency::Insufficient => Some(format!(
+                "{} qada day(s) owed but only {} permissible day(s) left before Ramadhan begins on {}; {}",
+                self.outstanding, self.available_days, self.next_ramadhan, delay,
+            )),
+        };
+        if self.overdue > 0 {
+            let overdue = format!(
+                "{} qada day(s) already delayed past a Ramadhan; {}",
+                self.overdue,
+                if self.delay_ruling.requires_fidyah() { "fidyah is due for each" } else { "make them up without fidyah" },
+            );
+            warning = Some(match warning {
+                Some(w) => format!("{w}. {overdue}"),
+                None => overdue,
+            });
+        }
+        warning
+    }
+}
+
+/// One planned fast and what it is intended as.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedFast {
+    pub date: NaiveDate,
+    /// [`Intention::Qadha`], [`Intention::Shawwal`], or both for a combined fast.
+    pub intentions: Vec<Intention>,
+}
+
+impl PlannedFast {
+    /// Counts towards the qada owed.
+    pub fn is_qadha(&self) -> bool { self.intentions.contains(&Intention::Qadha) }
+
+    pub fn is_shawwal(&self) -> bool { self.intentions.contains(&Intention::Shawwal) }
+}
+
+/// Result of [`QadaPlanner::plan`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QadaPlan {
+    pub fasts: Vec<PlannedFast>,
+    /// Qada days that did not fit before the end date.
+    pub unscheduled_qadha: usize,
+    /// Shawwal fasts that did not fit in the month (or before the end date).
+    pub unscheduled_shawwal: usize,
+}
+
+impl QadaPlan {
+    /// Everything requested was scheduled.
+    pub fn is_complete(&self) -> bool { self.unscheduled_qadha == 0 && self.unscheduled_shawwal == 0 }
+
+    /// The planned dates, for [`RuleContext::with_schedule`] (a planned fast
+    /// next to a Friday keeps it from being singled out).
+    pub fn schedule(&self) -> PersonalSchedule {
+        self.fasts.iter().map(|f| f.date).collect()
+    }
+}
+
+/// Schedules makeup fasts on permissible days.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_rules::qada::QadaPlanner;
+/// use shaum_rules::intent::Intention;
+/// use shaum_rules::RuleContext;
+/// use shaum_types::Madhab;
+///
+/// // From Eid al-Fitr 1445 (10 April 2024): two qada days and the six of Shawwal.
+/// let eid = NaiveDate::from_ymd_opt(2024, 4, 10).unwrap();
+/// let plan = QadaPlanner::new(eid, 2)
+///     .with_context(RuleContext::new().madhab(Madhab::Hanbali))
+///     .with_shawwal()
+///     .plan()
+///     .unwrap();
+/// assert!(plan.is_complete());
+/// assert_eq!(plan.fasts[0].date, eid.succ_opt().unwrap()); // never on Eid
+/// assert_eq!(plan.fasts[0].intentions, [Intention::Qadha]); // qada first
+/// assert_eq!(plan.fasts.len(), 8);
+/// ```
+#[derive(Debug, Clone)]
+pub struct QadaPlanner {
+    start: NaiveDate,
+    end: Option<NaiveDate>,
+    days: usize,
+    shawwal: bool,
+    combine: bool,
+    context: RuleContext,
+}
+
+impl QadaPlanner {
+    /// Plans `days` makeup fasts from `start`.
+    pub fn new(start: NaiveDate, days: usize) -> Self {
+        Self { start, end: None, days, shawwal: false, combine: false, context: RuleContext::default() }
+    }
+
+    /// Last day to plan on (default: a year after the start).
+    pub fn until(mut self, end: NaiveDate) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    pub fn with_context(mut self, ctx: RuleContext) -> Self {
+        self.context = ctx;
+        self
+    }
+
+    /// Also plans the six fasts of the next Shawwal.
+    pub fn with_shawwal(mut self) -> Self {
+        self.shawwal = true;
+        self
+    }
+
+    /// Lets a Shawwal day count as qada too, where the madhab accepts it
+    /// (not Hanbali). The reward of the combination is disputed; see
+    /// [`check_intention`](crate::intent::check_intention).
+    pub fn combine_with_shawwal(mut self) -> Self {
+        self.combine = true;
+        self
+    }
+
+    /// # Errors
+    /// Any error checking a day, e.g. a range past the calendar's coverage.
+    pub fn plan(&self) -> Result<QadaPlan, ShaumError> {
+        let madhab = self.context.madhab;
+        let combine = self.combine && madhab != Madhab::Hanbali;
+        let qadha_first = matches!(madhab, Madhab::Hanbali | Madhab::Maliki);
+        let end = self.end
+            .or_else(|| self.start.checked_add_signed(Duration::days(365)))
+            .unwrap_or(NaiveDate::MAX);
+
+        let mut plan = QadaPlan::default();
+        let mut qadha = self.days;
+        let mut shawwal = if self.shawwal { SHAWWAL_FASTS } else { 0 };
+        let mut seen_shawwal = false;
+        let mut date = self.start;
+        while (qadha > 0 || shawwal > 0) && date <= end {
+            let h = self.context.hijri(date)?;
+            let in_shawwal = h.month == MONTH_SHAWWAL;
+            if seen_shawwal && !in_shawwal {
+                // The month is over: what is left of the six cannot be kept.
+                plan.unscheduled_shawwal += shawwal;
+                shawwal = 0;
+            }
+            seen_shawwal |= in_shawwal;
+
+            if h.month != MONTH_RAMADHAN && !is_haram_day(date, &self.context)? {
+                let intentions = match (in_shawwal && shawwal > 0, qadha > 0) {
+                    (true, true) if combine => vec![Intention::Qadha, Intention::Shawwal],
+                    (true, true) if qadha_first => vec![Intention::Qadha],
+                    (true, _) => vec![Intention::Shawwal],
+                    (false, true) => vec![Intention::Qadha],
+                    (false, false) => Vec::new(),
+                };
+                if !intentions.is_empty() {
+                    let fast = PlannedFast { date, intentions };
+                    qadha -= usize::from(fast.is_qadha());
+                    shawwal -= usize::from(fast.is_shawwal());
+                    plan.fasts.push(fast);
+                }
+            }
+            match date.succ_opt() {
+                Some(next) => date = next,
+                None => break,
+            }
+        }
+        plan.unscheduled_qadha += qadha;
+        plan.unscheduled_shawwal += shawwal;
+        Ok(plan)
+    }
+}
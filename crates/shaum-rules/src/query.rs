@@ -1,6 +1,7 @@
 //! Fluent query engine for finding fasting dates.
  
 use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
 use crate::rules::{check, RuleContext};
 use shaum_types::{FastingAnalysis, FastingType};
 use shaum_types::ShaumError;
@@ -16,6 +17,15 @@ pub enum FilterMode {
     Mubah,
 }
 
+/// Excused Ramadhan days still owed, from [`FastingQuery::obligations`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Obligations {
+    /// Days to make up.
+    pub qadha_days: u32,
+    /// Days for which a poor person is fed.
+    pub fidyah_days: u32,
+}
+
 /// Fluent query builder for fasting dates.
 #[derive(Debug, Clone)]
 pub struct FastingQuery {
@@ -69,10 +79,44 @@ impl FastingQuery {
     /// Requires specific fasting type.
     pub fn with_type(mut self, ftype: FastingType) -> Self { self.require_type = Some(ftype); self }
 
+    /// Totals the qadha and fidyah owed for the matching days, per their
+    /// [`FastingAnalysis::compensation`]. Needs an end date.
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use shaum_rules::query::FastingQuery;
+    /// use shaum_rules::{PersonalContext, RuleContext};
+    ///
+    /// // Ramadhan 1445: 11 March to 9 April 2024.
+    /// let start = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+    /// let owed = FastingQuery::starting_from(start)
+    ///     .until(NaiveDate::from_ymd_opt(2024, 4, 30).unwrap())
+    ///     .with_context(RuleContext::new().with_personal(PersonalContext::permanently_unable()))
+    ///     .obligations()
+    ///     .unwrap();
+    /// assert_eq!((owed.qadha_days, owed.fidyah_days), (0, 30));
+    /// ```
+    ///
+    /// # Errors
+    /// `ValidationError` without an end date, or any error from the days checked.
+    pub fn obligations(self) -> Result<Obligations, ShaumError> {
+        if self.end.is_none() {
+            return Err(ShaumError::ValidationError("obligations() needs an end date (until)".to_string()));
+        }
+        self.into_iter().try_fold(Obligations::default(), |mut owed, analysis| {
+            let analysis = analysis?;
+            if let Some(c) = analysis.compensation() {
+                owed.qadha_days += u32::from(c.requires_qadha());
+                owed.fidyah_days += u32::from(c.requires_fidyah());
+            }
+            Ok(owed)
+        })
+    }
+
     fn matches(&self, analysis: &FastingAnalysis) -> bool {
         if self.exclude_haram && analysis.primary_status.is_haram() { return false; }
         if self.exclude_makruh && analysis.primary_status.is_makruh() { return false; }
-        if let Some(ref t) = self.require_type { if !analysis.has_reason(t) { return false; } }
+        if let Some(ref t) = self.require_type && !analysis.has_reason(t) { return false; }
 
         match self.filter {
             FilterMode::All => true,
@@ -90,7 +134,7 @@ impl Iterator for FastingQuery {
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
-            if let Some(end) = self.end { if self.current > end { return None; } }
+            if let Some(end) = self.end && self.current > end { return None; }
             let date = self.current;
             self.current = self.current.succ_opt()?;
 
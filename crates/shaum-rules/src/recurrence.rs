@@ -0,0 +1,147 @@
+//! Custom rules that recur on the Gregorian (civil) calendar.
+//!
+//! For organizational conventions such as "the first Monday of every month"
+//! or "every 17 August", which [`CustomFastingRule`] would otherwise have to
+//! derive from the date by hand.
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use shaum_types::{FastingStatus, FastingType, ShaumError};
+
+use crate::rules::CustomFastingRule;
+
+/// When a [`RecurringRule`] fires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GregorianRecurrence {
+    /// Same month and day every year. 29 February fires only in leap years.
+    Annual { month: u32, day: u32 },
+    /// The `n`th `weekday` of `month` (every month when `None`). `n` is 1-5,
+    /// or -1 for the last one.
+    NthWeekday { n: i8, weekday: Weekday, month: Option<u32> },
+}
+
+impl GregorianRecurrence {
+    /// Whether `date` is an occurrence.
+    pub fn matches(&self, date: NaiveDate) -> bool {
+        match *self {
+            Self::Annual { month, day } => date.month() == month && date.day() == day,
+            Self::NthWeekday { n, weekday, month } => {
+                if date.weekday() != weekday || month.is_some_and(|m| m != date.month()) {
+                    return false;
+                }
+                if n < 0 {
+                    (date + Duration::days(7)).month() != date.month()
+                } else {
+                    (date.day() - 1) / 7 + 1 == n as u32
+                }
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), ShaumError> {
+        let invalid = |why: String| Err(ShaumError::ValidationError(why));
+        match *self {
+            Self::Annual { month, day } => {
+                // 2024 is a leap year, so 29 February is accepted.
+                if NaiveDate::from_ymd_opt(2024, month, day).is_none() {
+                    return invalid(format!("Invalid annual date {}-{}", month, day));
+                }
+            }
+            Self::NthWeekday { n, month, .. } => {
+                if !(n == -1 || (1..=5).contains(&n)) {
+                    return invalid(format!("Occurrence {} must be 1-5 or -1", n));
+                }
+                if month.is_some_and(|m| !(1..=12).contains(&m)) {
+                    return invalid(format!("Invalid month {:?}", month));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A custom rule on a Gregorian recurrence.
+///
+/// ```rust
+/// use chrono::{NaiveDate, Weekday};
+/// use shaum_rules::recurrence::RecurringRule;
+/// use shaum_rules::{check, RuleContext};
+/// use shaum_types::{FastingStatus, FastingType};
+///
+/// let mut ctx = RuleContext::new();
+/// ctx.custom_rules.push(Box::new(
+///     RecurringRule::nth_weekday(1, Weekday::Thu, None, FastingStatus::Sunnah, FastingType::new("OfficeFast")).unwrap(),
+/// ));
+/// // 2024-02-01 is the first Thursday of February.
+/// let analysis = check(NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(), &ctx).unwrap();
+/// assert!(analysis.has_reason(&FastingType::new("OfficeFast")));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecurringRule {
+    pub recurrence: GregorianRecurrence,
+    pub status: FastingStatus,
+    pub fasting_type: FastingType,
+}
+
+impl RecurringRule {
+    /// Validates `recurrence` and creates the rule.
+    ///
+    /// # Errors
+    /// `ValidationError` for impossible dates, months or occurrences.
+    pub fn new(recurrence: GregorianRecurrence, status: FastingStatus, fasting_type: FastingType) -> Result<Self, ShaumError> {
+        recurrence.validate()?;
+        Ok(Self { recurrence, status, fasting_type })
+    }
+
+    /// Every year on `month`/`day`.
+    pub fn annual(month: u32, day: u32, status: FastingStatus, fasting_type: FastingType) -> Result<Self, ShaumError> {
+        Self::new(GregorianRecurrence::Annual { month, day }, status, fasting_type)
+    }
+
+    /// The `n`th (or last, with -1) `weekday` of `month`, or of every month.
+    pub fn nth_weekday(
+        n: i8,
+        weekday: Weekday,
+        month: Option<u32>,
+        status: FastingStatus,
+        fasting_type: FastingType,
+    ) -> Result<Self, ShaumError> {
+        Self::new(GregorianRecurrence::NthWeekday { n, weekday, month }, status, fasting_type)
+    }
+}
+
+impl CustomFastingRule for RecurringRule {
+    fn evaluate(&self, date: NaiveDate, _: usize, _: usize, _: usize) -> Option<(FastingStatus, FastingType)> {
+        self.recurrence.matches(date).then(|| (self.status, self.fasting_type.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate { NaiveDate::from_ymd_opt(y, m, d).unwrap() }
+
+    #[test]
+    fn test_nth_and_last_weekday() {
+        let second_monday = GregorianRecurrence::NthWeekday { n: 2, weekday: Weekday::Mon, month: None };
+        assert!(second_monday.matches(date(2024, 1, 8)));
+        assert!(!second_monday.matches(date(2024, 1, 1)));
+        assert!(!second_monday.matches(date(2024, 1, 15)));
+
+        let last_friday_of_march = GregorianRecurrence::NthWeekday { n: -1, weekday: Weekday::Fri, month: Some(3) };
+        assert!(last_friday_of_march.matches(date(2024, 3, 29)));
+        assert!(!last_friday_of_march.matches(date(2024, 3, 22)));
+        assert!(!last_friday_of_march.matches(date(2024, 5, 31)));
+    }
+
+    #[test]
+    fn test_annual_and_validation() {
+        let leap_day = RecurringRule::annual(2, 29, FastingStatus::Sunnah, FastingType::new("Leap")).unwrap();
+        assert!(leap_day.recurrence.matches(date(2024, 2, 29)));
+        assert!(!leap_day.recurrence.matches(date(2023, 3, 1)));
+
+        assert!(RecurringRule::annual(2, 30, FastingStatus::Sunnah, FastingType::new("x")).is_err());
+        assert!(RecurringRule::nth_weekday(0, Weekday::Mon, None, FastingStatus::Sunnah, FastingType::new("x")).is_err());
+        assert!(RecurringRule::nth_weekday(1, Weekday::Mon, Some(13), FastingStatus::Sunnah, FastingType::new("x")).is_err());
+    }
+}
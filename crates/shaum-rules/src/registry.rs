@@ -0,0 +1,882 @@
+//! Named, prioritised rules behind [`analyze`](crate::analyze).
+//!
+//! Every ruling comes from a [`Rule`] in a [`RuleRegistry`]. Rules run in
+//! ascending priority (ties keep registration order) and build up a
+//! [`RuleState`]; a rule may settle the day outright, which skips every
+//! later rule (Eid is Haram whatever else falls on it). The
+//! [`CustomFastingRule`]s on the context run in the same sequence at
+//! [`CUSTOM_PRIORITY`], after the built-in rules.
+//!
+//! The built-in rules also carry the [`RuleInfo`] catalogue behind
+//! [`crate::coverage`], and their day conditions are the `const fn`s that
+//! [`crate::classify`] combines, so the three cannot drift apart.
+//!
+//! [`RuleRegistry::builtin`] is the engine's own rule set; a copy can be
+//! listed, reordered, trimmed or given replacement rules, and attached with
+//! [`RuleContext::with_registry`]:
+//!
+//! ```rust
+//! use chrono::NaiveDate;
+//! use shaum_rules::registry::{Rule, RuleInput, RuleRegistry, RuleState};
+//! use shaum_rules::{check, RuleContext};
+//! use shaum_types::{FastingStatus, FastingType, RuleTrace, ShaumError, TraceCode};
+//!
+//! /// A community that treats every Wednesday as recommended.
+//! #[derive(Debug)]
+//! struct Wednesday;
+//! impl Rule for Wednesday {
+//!     fn name(&self) -> &str { "wednesday" }
+//!     fn priority(&self) -> i32 { 195 }
+//!     fn apply(&self, input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+//!         if input.date.format("%a").to_string() == "Wed" {
+//!             state.push_with_status(FastingType::new("Wednesday"), FastingStatus::Sunnah);
+//!             state.trace(RuleTrace::simple(TraceCode::Custom));
+//!             state.raise(FastingStatus::Sunnah);
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let registry = RuleRegistry::builtin().with_rule(Wednesday).without("monday").unwrap();
+//! assert!(registry.names().any(|n| n == "wednesday"));
+//! let ctx = RuleContext::new().with_registry(registry);
+//! let wednesday = NaiveDate::from_ymd_opt(2024, 7, 3).unwrap();
+//! assert_eq!(check(wednesday, &ctx).unwrap().primary_status, FastingStatus::Sunnah);
+//! let monday = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+//! assert_eq!(check(monday, &ctx).unwrap().primary_status, FastingStatus::Mubah);
+//! ```
+
+use std::sync::{Arc, OnceLock};
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+use shaum_calendar::HijriYmd;
+use shaum_types::{
+    Compensation, Emphasis, FastingAnalysis, FastingStatus, FastingType, Madhab, RuleTrace, ShaumError, TraceCode,
+    TracePayload,
+};
+use smallvec::SmallVec;
+
+use crate::constants::*;
+use crate::coverage::{madhab_rule, rule, RuleInfo};
+use crate::rules::{CustomFastingRule, RuleContext};
+
+/// Priority the context's [`CustomFastingRule`]s run at.
+pub const CUSTOM_PRIORITY: i32 = 1000;
+
+/// The day a rule is asked about.
+#[derive(Debug, Clone, Copy)]
+pub struct RuleInput<'a> {
+    /// Effective Gregorian date (after the Maghrib boundary).
+    pub date: NaiveDate,
+    pub hijri: HijriYmd,
+    pub context: &'a RuleContext,
+}
+
+impl RuleInput<'_> {
+    /// Shorthand for [`RuleContext::is_rule_enabled`].
+    pub fn enabled(&self, fasting_type: &FastingType) -> bool {
+        self.context.is_rule_enabled(fasting_type)
+    }
+}
+
+/// The ruling built up by the rules run so far.
+#[derive(Debug, Clone)]
+pub struct RuleState {
+    status: FastingStatus,
+    /// Reasons in insertion order, with a status when it is not the one
+    /// implied by the type.
+    reasons: SmallVec<[(FastingType, Option<FastingStatus>); 4]>,
+    traces: SmallVec<[RuleTrace; 2]>,
+    compensation: Option<Compensation>,
+    settled: bool,
+    /// Set by [`Self::prohibit`]: voluntary rules may not lift the status.
+    prohibited: bool,
+    hijri_month: usize,
+}
+
+impl RuleState {
+    fn new(hijri_month: usize, traces: SmallVec<[RuleTrace; 2]>) -> Self {
+        Self {
+            status: FastingStatus::Mubah,
+            reasons: SmallVec::new(),
+            traces,
+            compensation: None,
+            settled: false,
+            prohibited: false,
+            hijri_month,
+        }
+    }
+
+    pub fn status(&self) -> FastingStatus { self.status }
+
+    /// Overrides the status, whatever it was.
+    pub fn set_status(&mut self, status: FastingStatus) {
+        self.status = status;
+    }
+
+    /// Raises a non-Wajib status to `status`; lower ones, Wajib and a
+    /// [prohibition](Self::prohibit) are kept.
+    pub fn raise(&mut self, status: FastingStatus) {
+        if !self.status.is_wajib() && !self.prohibited && self.status < status {
+            self.status = status;
+        }
+    }
+
+    /// Sets a status that voluntary rules cannot lift with [`Self::raise`],
+    /// e.g. the Makruh of late Sha'ban over a Monday. Only
+    /// [`Self::set_status`], as for an obligation, overrides it.
+    pub fn prohibit(&mut self, status: FastingStatus) {
+        self.status = status;
+        self.prohibited = true;
+    }
+
+    /// Whether the status was set by [`Self::prohibit`].
+    pub fn is_prohibited(&self) -> bool { self.prohibited }
+
+    pub fn has_reason(&self, fasting_type: &FastingType) -> bool {
+        self.reasons.iter().any(|(t, _)| t == fasting_type)
+    }
+
+    /// Adds a reason with the status its type implies, and its trace.
+    pub fn push(&mut self, reason: FastingType, code: TraceCode) {
+        self.reasons.push((reason, None));
+        self.traces.push(RuleTrace::simple(code));
+    }
+
+    /// Adds a reason with the status its type implies, without a trace.
+    pub fn push_reason(&mut self, reason: FastingType) {
+        self.reasons.push((reason, None));
+    }
+
+    /// Adds a reason with its own status; a reason of the same type already
+    /// present combines the two instead.
+    pub fn push_with_status(&mut self, reason: FastingType, status: FastingStatus) {
+        let month = self.hijri_month;
+        match self.reasons.iter_mut().find(|(t, _)| *t == reason) {
+            Some((t, existing)) => *existing = Some(existing.unwrap_or_else(|| own_status(t, month)).combine(status)),
+            None => self.reasons.push((reason, Some(status))),
+        }
+    }
+
+    pub fn trace(&mut self, trace: RuleTrace) {
+        self.traces.push(trace);
+    }
+
+    /// Ends the evaluation with `status`: later rules do not run, and the
+    /// reasons are reported in the order they were added.
+    pub fn settle(&mut self, status: FastingStatus, compensation: Option<Compensation>) {
+        self.status = status;
+        self.compensation = compensation;
+        self.settled = true;
+    }
+
+    pub fn is_settled(&self) -> bool { self.settled }
+
+    fn into_analysis(self, datetime: DateTime<Utc>, hijri: HijriYmd) -> FastingAnalysis {
+        let hijri = (hijri.year, hijri.month, hijri.day);
+        if self.settled {
+            let reasons = self.reasons.into_iter().map(|(t, _)| t).collect();
+            let analysis = FastingAnalysis::with_traces(datetime, self.status, reasons, hijri, self.traces);
+            return match self.compensation {
+                Some(compensation) => analysis.with_compensation(compensation),
+                None => analysis,
+            };
+        }
+        let month = self.hijri_month;
+        // Under a prohibition, voluntary reasons contribute no more than it.
+        let cap = self.prohibited.then_some(self.status);
+        let ranked = self.reasons.into_iter()
+            .map(|(t, s)| {
+                let s = s.unwrap_or_else(|| own_status(&t, month));
+                match cap {
+                    Some(cap) if s > cap && !s.is_wajib() && !s.is_haram() => (cap, t),
+                    _ => (s, t),
+                }
+            })
+            .collect();
+        let (reasons, statuses, emphasis) = order_reasons(ranked);
+        let has_custom = reasons.iter().any(|t| t.canonical_index().is_none());
+        let derived = reasons.iter().zip(&statuses).all(|(t, s)| t.implied_status() == Some(*s));
+        let mut analysis = FastingAnalysis::with_traces(datetime, self.status, reasons, hijri, self.traces);
+        // Standard reasons derive their emphasis and status from the type; custom ones from their status.
+        if has_custom {
+            analysis = analysis.with_emphasis(emphasis);
+        }
+        if !derived {
+            analysis = analysis.with_reason_statuses(statuses);
+        }
+        match self.compensation {
+            Some(compensation) => analysis.with_compensation(compensation),
+            None => analysis,
+        }
+    }
+}
+
+/// Status a reason contributes on its own. Illness outside Ramadhan only
+/// appears when it discourages the fast.
+fn own_status(t: &FastingType, hijri_month: usize) -> FastingStatus {
+    match t.implied_status() {
+        _ if *t == FastingType::MARADH && hijri_month != MONTH_RAMADHAN => FastingStatus::Makruh,
+        implied => implied.unwrap_or(FastingStatus::Mubah),
+    }
+}
+
+/// Reasons with their parallel statuses and emphasis.
+type OrderedReasons = (SmallVec<[FastingType; 2]>, SmallVec<[FastingStatus; 2]>, SmallVec<[Option<Emphasis>; 2]>);
+
+/// Sorts reasons by severity, then canonical order; custom types keep their
+/// insertion order (the sort is stable). Also returns each reason's status
+/// and emphasis.
+fn order_reasons(mut ranked: SmallVec<[(FastingStatus, FastingType); 4]>) -> OrderedReasons {
+    ranked.sort_by(|(sa, ta), (sb, tb)| {
+        sb.cmp(sa).then_with(|| {
+            ta.canonical_index().unwrap_or(usize::MAX).cmp(&tb.canonical_index().unwrap_or(usize::MAX))
+        })
+    });
+    let emphasis = ranked.iter()
+        .map(|(s, t)| if t.canonical_index().is_some() { t.emphasis() } else { Emphasis::from_status(*s) })
+        .collect();
+    let statuses = ranked.iter().map(|(s, _)| *s).collect();
+    (ranked.into_iter().map(|(_, t)| t).collect(), statuses, emphasis)
+}
+
+/// One named step of the evaluation.
+pub trait Rule: std::fmt::Debug + Send + Sync {
+    /// Unique, stable name, e.g. `"eid_al_fitr"`.
+    fn name(&self) -> &str;
+
+    /// Default position; lower runs first. [`RuleRegistry::with_priority`]
+    /// overrides it.
+    fn priority(&self) -> i32;
+
+    fn apply(&self, input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError>;
+
+    /// What the rule can report, for [`crate::coverage`] and
+    /// [`RuleContext::disable_trace_code`]. Empty by default.
+    fn reports(&self) -> &[RuleInfo] { &[] }
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    priority: i32,
+    rule: Arc<dyn Rule>,
+}
+
+/// Ordered set of named rules.
+#[derive(Debug, Clone, Default)]
+pub struct RuleRegistry {
+    entries: Vec<Entry>,
+}
+
+impl RuleRegistry {
+    /// A registry with no rules: every day is Mubah.
+    pub fn empty() -> Self { Self::default() }
+
+    /// The built-in rule set, in evaluation order.
+    pub fn builtin() -> Self {
+        builtin_registry().clone()
+    }
+
+    /// Adds `rule` at its own priority, replacing any rule of the same name.
+    pub fn with_rule(mut self, rule: impl Rule + 'static) -> Self {
+        self.entries.retain(|e| e.rule.name() != rule.name());
+        self.insert(Entry { priority: rule.priority(), rule: Arc::new(rule) });
+        self
+    }
+
+    /// Replaces the rule called `name` with `rule`, at the same priority.
+    ///
+    /// # Errors
+    /// `ValidationError` if no rule is called `name`.
+    pub fn replace(mut self, name: &str, rule: impl Rule + 'static) -> Result<Self, ShaumError> {
+        let index = self.index(name)?;
+        self.entries[index].rule = Arc::new(rule);
+        Ok(self)
+    }
+
+    /// Removes the rule called `name`.
+    ///
+    /// # Errors
+    /// `ValidationError` if no rule is called `name`.
+    pub fn without(mut self, name: &str) -> Result<Self, ShaumError> {
+        let index = self.index(name)?;
+        self.entries.remove(index);
+        Ok(self)
+    }
+
+    /// Moves the rule called `name` to `priority`.
+    ///
+    /// # Errors
+    /// `ValidationError` if no rule is called `name`.
+    pub fn with_priority(mut self, name: &str, priority: i32) -> Result<Self, ShaumError> {
+        let index = self.index(name)?;
+        let mut entry = self.entries.remove(index);
+        entry.priority = priority;
+        self.insert(entry);
+        Ok(self)
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Rule>> {
+        self.entries.iter().find(|e| e.rule.name() == name).map(|e| e.rule.clone())
+    }
+
+    /// Priority of the rule called `name`.
+    pub fn priority(&self, name: &str) -> Option<i32> {
+        self.entries.iter().find(|e| e.rule.name() == name).map(|e| e.priority)
+    }
+
+    /// Rule names in evaluation order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|e| e.rule.name())
+    }
+
+    /// Rules with their priorities, in evaluation order.
+    pub fn iter(&self) -> impl Iterator<Item = (i32, &dyn Rule)> {
+        self.entries.iter().map(|e| (e.priority, &*e.rule))
+    }
+
+    /// What the rules can report, in evaluation order, once per trace code.
+    pub fn reports(&self) -> impl Iterator<Item = &RuleInfo> {
+        let mut seen = Vec::new();
+        self.entries.iter()
+            .flat_map(|e| e.rule.reports())
+            .filter(move |info| {
+                let new = !seen.contains(&info.trace_code);
+                seen.push(info.trace_code);
+                new
+            })
+    }
+
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+
+    /// Inserts after every entry of equal or lower priority.
+    fn insert(&mut self, entry: Entry) {
+        let at = self.entries.partition_point(|e| e.priority <= entry.priority);
+        self.entries.insert(at, entry);
+    }
+
+    fn index(&self, name: &str) -> Result<usize, ShaumError> {
+        self.entries.iter().position(|e| e.rule.name() == name)
+            .ok_or_else(|| ShaumError::ValidationError(format!("Unknown rule: {}", name)))
+    }
+
+    /// Runs the rules and the context's custom rules for one day.
+    pub(crate) fn evaluate(
+        &self,
+        datetime: DateTime<Utc>,
+        input: RuleInput<'_>,
+        traces: SmallVec<[RuleTrace; 2]>,
+    ) -> Result<FastingAnalysis, ShaumError> {
+        let custom: Vec<Custom<'_>> = input.context.custom_rules.iter().map(|r| Custom(&**r)).collect();
+        let split = self.entries.partition_point(|e| e.priority <= CUSTOM_PRIORITY);
+        let (before, after) = self.entries.split_at(split);
+        let rules = before.iter().map(|e| &*e.rule)
+            .chain(custom.iter().map(|c| c as &dyn Rule))
+            .chain(after.iter().map(|e| &*e.rule));
+
+        let mut state = RuleState::new(input.hijri.month, traces);
+        for rule in rules {
+            if state.is_settled() {
+                break;
+            }
+            rule.apply(&input, &mut state)?;
+        }
+        Ok(state.into_analysis(datetime, input.hijri))
+    }
+}
+
+/// A [`CustomFastingRule`] from the context, run at [`CUSTOM_PRIORITY`].
+#[derive(Debug)]
+struct Custom<'a>(&'a dyn CustomFastingRule);
+
+impl Rule for Custom<'_> {
+    fn name(&self) -> &str { "custom" }
+
+    fn priority(&self) -> i32 { CUSTOM_PRIORITY }
+
+    fn apply(&self, input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+        let h = input.hijri;
+        if let Some((status, reason)) = self.0.evaluate(input.date, h.year, h.month, h.day) {
+            state.trace(RuleTrace::new(TraceCode::Custom, TracePayload::CustomReason(reason.to_string())));
+            state.status = state.status.combine(status);
+            state.push_with_status(reason, status);
+        }
+        Ok(())
+    }
+}
+
+/// A built-in rule.
+#[derive(Debug)]
+struct Builtin {
+    name: &'static str,
+    priority: i32,
+    apply: BuiltinFn,
+    reports: &'static [RuleInfo],
+}
+
+impl Rule for Builtin {
+    fn name(&self) -> &str { self.name }
+
+    fn priority(&self) -> i32 { self.priority }
+
+    fn apply(&self, input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+        (self.apply)(input, state)
+    }
+
+    fn reports(&self) -> &[RuleInfo] { self.reports }
+}
+
+/// The shared built-in registry, used when a context has none of its own.
+pub(crate) fn builtin_registry() -> &'static RuleRegistry {
+    static BUILTIN: OnceLock<RuleRegistry> = OnceLock::new();
+    BUILTIN.get_or_init(|| RuleRegistry {
+        entries: BUILTIN_RULES.iter()
+            .map(|&(name, priority, apply, reports)| {
+                Entry { priority, rule: Arc::new(Builtin { name, priority, apply, reports }) }
+            })
+            .collect(),
+    })
+}
+
+type BuiltinFn = fn(&RuleInput<'_>, &mut RuleState) -> Result<(), ShaumError>;
+
+/// Illness, reported by both the Ramadhan excuse and the voluntary ruling.
+const MARADH: RuleInfo = rule(
+    TraceCode::Maradh, FastingType::MARADH, &[FastingStatus::Mubah, FastingStatus::Makruh],
+    "Illness: Ramadhan is excused; voluntary fasts are Makruh when so configured",
+);
+
+/// Built-in rules: name, priority, body and what it reports. Priorities
+/// leave room in between.
+const BUILTIN_RULES: [(&str, i32, BuiltinFn, &[RuleInfo]); 25] = [
+    ("personal_state", 10, personal_state, &[
+        rule(TraceCode::Haid, FastingType::HAID, &[FastingStatus::Haram], "Any day during menstruation"),
+        rule(TraceCode::Nifas, FastingType::NIFAS, &[FastingStatus::Haram], "Any day during postnatal bleeding"),
+    ]),
+    ("eid_al_fitr", 20, eid_al_fitr, &[
+        rule(TraceCode::EidAlFitr, FastingType::EID_AL_FITR, &[FastingStatus::Haram], "1 Shawwal"),
+    ]),
+    ("eid_al_adha", 30, eid_al_adha, &[
+        rule(TraceCode::EidAlAdha, FastingType::EID_AL_ADHA, &[FastingStatus::Haram], "10 Dhu al-Hijjah"),
+    ]),
+    ("tashriq", 40, tashriq, &[
+        rule(TraceCode::Tashriq, FastingType::TASHRIQ, &[FastingStatus::Haram], "11-13 Dhu al-Hijjah"),
+    ]),
+    ("day_of_doubt", 50, day_of_doubt, &[
+        rule(TraceCode::DayOfDoubt, FastingType::DAY_OF_DOUBT, &[FastingStatus::Makruh], "30 Sha'ban (day of doubt)"),
+    ]),
+    ("late_shaban", 60, late_shaban, &[madhab_rule(
+        &[Madhab::Shafi], TraceCode::LateShaban, FastingType::LATE_SHABAN, &[FastingStatus::Makruh],
+        "16-29 Sha'ban without a habit or connected fast",
+    )]),
+    ("pre_ramadhan", 70, pre_ramadhan, &[rule(
+        TraceCode::PreRamadhan, FastingType::PRE_RAMADHAN, &[FastingStatus::Makruh],
+        "Last one or two days before Ramadhan without a habit or connected fast",
+    )]),
+    ("ajz", 80, ajz, &[rule(
+        TraceCode::Ajz, FastingType::AJZ, &[FastingStatus::Mubah],
+        "Ramadhan while permanently unable to fast (fidyah instead of qadha)",
+    )]),
+    ("maternal", 90, maternal, &[
+        rule(
+            TraceCode::Haml, FastingType::HAML, &[FastingStatus::Mubah],
+            "Ramadhan while pregnant (qadha, with fidyah in some madhabs)",
+        ),
+        rule(
+            TraceCode::Radha, FastingType::RADHA, &[FastingStatus::Mubah],
+            "Ramadhan while nursing (qadha, with fidyah in some madhabs)",
+        ),
+    ]),
+    ("maradh", 100, maradh, &[MARADH]),
+    ("ramadhan", 110, ramadhan, &[
+        rule(TraceCode::Ramadhan, FastingType::RAMADHAN, &[FastingStatus::Wajib], "Every day of Ramadhan"),
+        rule(
+            TraceCode::Safar, FastingType::SAFAR, &[FastingStatus::Wajib],
+            "Ramadhan while traveling at least 81 km (still Wajib; may be broken, and a broken day is made up as qadha)",
+        ),
+    ]),
+    ("nadhar", 120, nadhar, &[
+        rule(TraceCode::Nadhar, FastingType::NADHAR, &[FastingStatus::Wajib], "A day named by a registered vow, outside Ramadhan"),
+    ]),
+    ("arafah", 130, arafah, &[
+        rule(TraceCode::Arafah, FastingType::ARAFAH, &[FastingStatus::SunnahMuakkadah], "9 Dhu al-Hijjah"),
+    ]),
+    ("ashura", 140, ashura, &[
+        rule(TraceCode::Ashura, FastingType::ASHURA, &[FastingStatus::SunnahMuakkadah], "10 Muharram"),
+    ]),
+    ("tasua", 150, tasua, &[
+        rule(TraceCode::Tasua, FastingType::TASUA, &[FastingStatus::Sunnah], "9 Muharram"),
+    ]),
+    ("ayyamul_bidh", 160, ayyamul_bidh, &[
+        rule(TraceCode::AyyamulBidh, FastingType::AYYAMUL_BIDH, &[FastingStatus::Sunnah], "13-15 of any month"),
+    ]),
+    ("nisf_shaban", 170, nisf_shaban, &[rule(
+        TraceCode::NisfShaban, FastingType::NISF_SHABAN, &[FastingStatus::Sunnah],
+        "15 Sha'ban, when enabled (disputed)",
+    )]),
+    ("monday", 180, monday, &[
+        rule(TraceCode::Monday, FastingType::MONDAY, &[FastingStatus::Sunnah], "Every Monday"),
+    ]),
+    ("thursday", 190, thursday, &[
+        rule(TraceCode::Thursday, FastingType::THURSDAY, &[FastingStatus::Sunnah], "Every Thursday"),
+    ]),
+    ("shawwal", 200, shawwal, &[
+        rule(TraceCode::Shawwal, FastingType::SHAWWAL, &[FastingStatus::Sunnah], "2-30 Shawwal (six days)"),
+    ]),
+    ("dhul_hijjah_ten", 210, dhul_hijjah_ten, &[
+        rule(TraceCode::DhulHijjahTen, FastingType::DHUL_HIJJAH_TEN, &[FastingStatus::Sunnah], "1-8 Dhu al-Hijjah"),
+    ]),
+    ("arafah_pilgrim", 220, arafah_pilgrim, &[rule(
+        TraceCode::ArafahPilgrim, FastingType::ARAFAH_PILGRIM, &[FastingStatus::Makruh],
+        "9 Dhu al-Hijjah for a pilgrim at Arafah",
+    )]),
+    ("maradh_voluntary", 230, maradh_voluntary, &[MARADH]),
+    ("friday_exclusive", 240, friday_exclusive, &[rule(
+        TraceCode::FridaySingledOut, FastingType::FRIDAY_EXCLUSIVE, &[FastingStatus::Makruh],
+        "Friday with no other reason to fast",
+    )]),
+    ("saturday_exclusive", 250, saturday_exclusive, &[madhab_rule(
+        &[Madhab::Shafi, Madhab::Hanafi, Madhab::Hanbali],
+        TraceCode::SaturdaySingledOut, FastingType::SATURDAY_EXCLUSIVE, &[FastingStatus::Makruh],
+        "Saturday with no other reason to fast",
+    )]),
+];
+
+// Day conditions of the calendar rules, shared with `classify`.
+
+pub(crate) const fn is_eid_al_fitr(month: usize, day: usize) -> bool {
+    month == MONTH_SHAWWAL && day == 1
+}
+
+pub(crate) const fn is_eid_al_adha(month: usize, day: usize) -> bool {
+    month == MONTH_DHUL_HIJJAH && day == 10
+}
+
+pub(crate) const fn is_tashriq(month: usize, day: usize) -> bool {
+    month == MONTH_DHUL_HIJJAH && day >= 11 && day <= 13
+}
+
+pub(crate) const fn is_day_of_doubt(month: usize, day: usize) -> bool {
+    month == MONTH_SHABAN && day == DAY_OF_DOUBT
+}
+
+pub(crate) const fn is_late_shaban(month: usize, day: usize) -> bool {
+    month == MONTH_SHABAN && day >= DAY_LATE_SHABAN && day < DAY_OF_DOUBT
+}
+
+pub(crate) const fn is_ramadhan(month: usize) -> bool {
+    month == MONTH_RAMADHAN
+}
+
+pub(crate) const fn is_arafah(month: usize, day: usize) -> bool {
+    month == MONTH_DHUL_HIJJAH && day == DAY_ARAFAH
+}
+
+pub(crate) const fn is_ashura(month: usize, day: usize) -> bool {
+    month == MONTH_MUHARRAM && day == DAY_ASHURA
+}
+
+pub(crate) const fn is_tasua(month: usize, day: usize) -> bool {
+    month == MONTH_MUHARRAM && day == DAY_TASUA
+}
+
+pub(crate) const fn is_white_day(day: usize) -> bool {
+    day >= 13 && day <= 15
+}
+
+pub(crate) const fn is_nisf_shaban(month: usize, day: usize) -> bool {
+    month == MONTH_SHABAN && day == DAY_NISF_SHABAN
+}
+
+/// 2-30 Shawwal, when the six days may be fasted.
+pub(crate) const fn is_shawwal_fast(month: usize, day: usize) -> bool {
+    month == MONTH_SHAWWAL && day > 1
+}
+
+pub(crate) const fn is_dhul_hijjah_ten(month: usize, day: usize) -> bool {
+    month == MONTH_DHUL_HIJJAH && day < DAY_ARAFAH
+}
+
+/// Personal state comes before the calendar; Ramadhan is kept as a reason
+/// so the missed day shows up as qadha.
+fn personal_state(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    if let Some(personal) = input.context.personal.state {
+        state.push(personal.fasting_type(), personal.trace_code());
+        let ramadhan = input.hijri.month == MONTH_RAMADHAN;
+        if ramadhan {
+            state.push(FastingType::RAMADHAN, TraceCode::Ramadhan);
+        }
+        state.settle(FastingStatus::Haram, ramadhan.then_some(Compensation::Qadha));
+    }
+    Ok(())
+}
+
+fn eid_al_fitr(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    let h = input.hijri;
+    if is_eid_al_fitr(h.month, h.day) && input.enabled(&FastingType::EID_AL_FITR) {
+        state.push(FastingType::EID_AL_FITR, TraceCode::EidAlFitr);
+        state.settle(FastingStatus::Haram, None);
+    }
+    Ok(())
+}
+
+fn eid_al_adha(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    let h = input.hijri;
+    if is_eid_al_adha(h.month, h.day) && input.enabled(&FastingType::EID_AL_ADHA) {
+        state.push(FastingType::EID_AL_ADHA, TraceCode::EidAlAdha);
+        state.settle(FastingStatus::Haram, None);
+    }
+    Ok(())
+}
+
+fn tashriq(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    let h = input.hijri;
+    if is_tashriq(h.month, h.day) && input.enabled(&FastingType::TASHRIQ) {
+        state.push(FastingType::TASHRIQ, TraceCode::Tashriq);
+        state.settle(FastingStatus::Haram, None);
+    }
+    Ok(())
+}
+
+/// Yawm al-Shakk: the day that would have been 1 Ramadhan had the crescent
+/// been confirmed. Makruh by default; see `intent` for stricter rulings.
+fn day_of_doubt(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    let h = input.hijri;
+    if is_day_of_doubt(h.month, h.day) && input.enabled(&FastingType::DAY_OF_DOUBT) {
+        state.push(FastingType::DAY_OF_DOUBT, TraceCode::DayOfDoubt);
+        state.prohibit(FastingStatus::Makruh);
+    }
+    Ok(())
+}
+
+/// Second half of Sha'ban: the Shafi'i school forbids starting voluntary
+/// fasts after the 15th unless they are habitual or connected to earlier days.
+fn late_shaban(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    let (h, ctx) = (input.hijri, input.context);
+    if is_late_shaban(h.month, h.day)
+        && ctx.madhab == Madhab::Shafi && !ctx.connected_fast && input.enabled(&FastingType::LATE_SHABAN)
+    {
+        state.push(FastingType::LATE_SHABAN, TraceCode::LateShaban);
+        // Not lifted by a Monday, Thursday or white day falling on it.
+        state.prohibit(FastingStatus::Makruh);
+    }
+    Ok(())
+}
+
+/// "Do not precede Ramadhan by a day or two": the boundary comes from the
+/// calendar, since Sha'ban may have 29 or 30 days.
+fn pre_ramadhan(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    let ctx = input.context;
+    if input.hijri.month == MONTH_SHABAN && !ctx.connected_fast && input.enabled(&FastingType::PRE_RAMADHAN)
+        && (1..=PRE_RAMADHAN_DAYS).any(|offset| {
+            input.date.checked_add_signed(Duration::days(offset))
+                .and_then(|d| ctx.hijri(d).ok())
+                .is_some_and(|h| h.month == MONTH_RAMADHAN && h.day == 1)
+        })
+    {
+        state.push(FastingType::PRE_RAMADHAN, TraceCode::PreRamadhan);
+        state.prohibit(FastingStatus::Makruh);
+    }
+    Ok(())
+}
+
+/// Permanent inability: fidyah replaces the fast, with nothing to make up.
+fn ajz(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    if input.context.personal.permanently_unable && input.hijri.month == MONTH_RAMADHAN
+        && input.enabled(&FastingType::RAMADHAN) && input.enabled(&FastingType::AJZ)
+    {
+        state.push(FastingType::AJZ, TraceCode::Ajz);
+        state.push_reason(FastingType::RAMADHAN);
+        state.settle(FastingStatus::Mubah, Some(Compensation::Fidyah));
+    }
+    Ok(())
+}
+
+/// Pregnancy and nursing excuse Ramadhan; what is owed depends on the madhab.
+fn maternal(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    let personal = &input.context.personal;
+    if let Some(maternal) = personal.maternal
+        && let Some(compensation) = personal.maternal_compensation(input.context.madhab)
+        && input.hijri.month == MONTH_RAMADHAN && input.enabled(&FastingType::RAMADHAN)
+        && input.enabled(&maternal.fasting_type())
+    {
+        state.push(maternal.fasting_type(), maternal.trace_code());
+        state.push_reason(FastingType::RAMADHAN);
+        state.settle(FastingStatus::Mubah, Some(compensation));
+    }
+    Ok(())
+}
+
+/// Illness excuses Ramadhan: the day is owed as qadha rather than obligatory.
+fn maradh(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    if input.context.personal.ill && input.hijri.month == MONTH_RAMADHAN
+        && input.enabled(&FastingType::RAMADHAN) && input.enabled(&FastingType::MARADH)
+    {
+        state.push(FastingType::MARADH, TraceCode::Maradh);
+        state.push_reason(FastingType::RAMADHAN);
+        state.settle(FastingStatus::Mubah, Some(Compensation::Qadha));
+    }
+    Ok(())
+}
+
+fn ramadhan(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    if is_ramadhan(input.hijri.month) && input.enabled(&FastingType::RAMADHAN) {
+        state.push(FastingType::RAMADHAN, TraceCode::Ramadhan);
+        state.set_status(FastingStatus::Wajib);
+        // The concession does not lift the obligation, only allows deferring it.
+        if input.context.personal.is_musafir() && input.enabled(&FastingType::SAFAR) {
+            state.push(FastingType::SAFAR, TraceCode::Safar);
+        }
+    }
+    Ok(())
+}
+
+/// A vowed fast is obligatory, which also lifts the Makruh of the end of Sha'ban.
+fn nadhar(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    if input.context.vows.find(input.date, input.hijri).is_some() && input.enabled(&FastingType::NADHAR) {
+        state.push(FastingType::NADHAR, TraceCode::Nadhar);
+        state.set_status(FastingStatus::Wajib);
+    }
+    Ok(())
+}
+
+/// Pilgrims at Arafah should not fast; see [`arafah_pilgrim`].
+fn pilgrim_at_arafah(input: &RuleInput<'_>) -> bool {
+    input.context.is_pilgrim && is_arafah(input.hijri.month, input.hijri.day)
+        && input.enabled(&FastingType::ARAFAH_PILGRIM)
+}
+
+fn arafah(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    let h = input.hijri;
+    if is_arafah(h.month, h.day) && !pilgrim_at_arafah(input) && input.enabled(&FastingType::ARAFAH) {
+        state.push(FastingType::ARAFAH, TraceCode::Arafah);
+        state.raise(FastingStatus::SunnahMuakkadah);
+    }
+    Ok(())
+}
+
+fn ashura(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    let h = input.hijri;
+    if is_ashura(h.month, h.day) && input.enabled(&FastingType::ASHURA) {
+        state.push(FastingType::ASHURA, TraceCode::Ashura);
+        state.raise(FastingStatus::SunnahMuakkadah);
+    }
+    Ok(())
+}
+
+fn tasua(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    let h = input.hijri;
+    if is_tasua(h.month, h.day) && input.enabled(&FastingType::TASUA) {
+        state.push(FastingType::TASUA, TraceCode::Tasua);
+        state.raise(FastingStatus::Sunnah);
+    }
+    Ok(())
+}
+
+fn ayyamul_bidh(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    if is_white_day(input.hijri.day) && input.enabled(&FastingType::AYYAMUL_BIDH) {
+        state.push(FastingType::AYYAMUL_BIDH, TraceCode::AyyamulBidh);
+        state.raise(FastingStatus::Sunnah);
+    }
+    Ok(())
+}
+
+fn nisf_shaban(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    let h = input.hijri;
+    if input.context.nisf_shaban && is_nisf_shaban(h.month, h.day)
+        && input.enabled(&FastingType::NISF_SHABAN)
+    {
+        state.push(FastingType::NISF_SHABAN, TraceCode::NisfShaban);
+        state.raise(FastingStatus::Sunnah);
+    }
+    Ok(())
+}
+
+fn monday(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    if input.date.weekday() == Weekday::Mon && input.enabled(&FastingType::MONDAY) {
+        state.push(FastingType::MONDAY, TraceCode::Monday);
+        state.raise(FastingStatus::Sunnah);
+    }
+    Ok(())
+}
+
+fn thursday(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    if input.date.weekday() == Weekday::Thu && input.enabled(&FastingType::THURSDAY) {
+        state.push(FastingType::THURSDAY, TraceCode::Thursday);
+        state.raise(FastingStatus::Sunnah);
+    }
+    Ok(())
+}
+
+fn shawwal(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    let h = input.hijri;
+    if is_shawwal_fast(h.month, h.day) && input.enabled(&FastingType::SHAWWAL) {
+        state.push(FastingType::SHAWWAL, TraceCode::Shawwal);
+        state.raise(FastingStatus::Sunnah);
+    }
+    Ok(())
+}
+
+fn dhul_hijjah_ten(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    let h = input.hijri;
+    if is_dhul_hijjah_ten(h.month, h.day) && input.enabled(&FastingType::DHUL_HIJJAH_TEN) {
+        state.push(FastingType::DHUL_HIJJAH_TEN, TraceCode::DhulHijjahTen);
+        state.raise(FastingStatus::Sunnah);
+    }
+    Ok(())
+}
+
+/// Overrides a Monday or Thursday falling on the day: the pilgrim's
+/// strength for supplication at Arafah comes first.
+fn arafah_pilgrim(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    if pilgrim_at_arafah(input) && !state.status().is_wajib() {
+        state.push(FastingType::ARAFAH_PILGRIM, TraceCode::ArafahPilgrim);
+        state.set_status(FastingStatus::Makruh);
+    }
+    Ok(())
+}
+
+fn maradh_voluntary(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    let personal = &input.context.personal;
+    if personal.ill && personal.ill_discourages_voluntary && !state.status().is_wajib()
+        && input.enabled(&FastingType::MARADH)
+    {
+        state.push(FastingType::MARADH, TraceCode::Maradh);
+        state.set_status(FastingStatus::Makruh);
+    }
+    Ok(())
+}
+
+/// Friday singled out, i.e. not next to another planned fast.
+fn friday_exclusive(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    if state.status() == FastingStatus::Mubah && input.date.weekday() == Weekday::Fri
+        && !input.context.schedule.has_adjacent(input.date) && input.enabled(&FastingType::FRIDAY_EXCLUSIVE)
+    {
+        state.push(FastingType::FRIDAY_EXCLUSIVE, TraceCode::FridaySingledOut);
+        state.set_status(FastingStatus::Makruh);
+    }
+    Ok(())
+}
+
+fn saturday_exclusive(input: &RuleInput<'_>, state: &mut RuleState) -> Result<(), ShaumError> {
+    let ctx = input.context;
+    if state.status() == FastingStatus::Mubah && input.date.weekday() == Weekday::Sat
+        && !ctx.schedule.has_adjacent(input.date) && input.enabled(&FastingType::SATURDAY_EXCLUSIVE)
+        && saturday_is_makruh(ctx)
+    {
+        state.push_reason(FastingType::SATURDAY_EXCLUSIVE);
+        state.trace(RuleTrace::new(TraceCode::SaturdaySingledOut, TracePayload::MadhabRuling(ctx.madhab)));
+        state.set_status(FastingStatus::Makruh);
+    }
+    Ok(())
+}
+
+/// Whether singling out a Saturday is disliked under `context`'s madhab.
+/// The Shafi'i and Hanbali schools dislike it, and the Hanafis mildly
+/// (tanzihi); the Maliki school does not act on the hadith and permits it.
+/// Contexts pinned before ruleset 3 keep the former uniform ruling.
+fn saturday_is_makruh(context: &RuleContext) -> bool {
+    context.madhab != Madhab::Maliki || context.effective_ruleset_version() < 3
+}
@@ -1,8 +1,21 @@
-use chrono::{Datelike, NaiveDate, Weekday, DateTime, Utc, TimeZone};
-use shaum_calendar::{to_hijri, HIJRI_MIN_YEAR, HIJRI_MAX_YEAR};
+use chrono::{Datelike, Timelike, NaiveDate, DateTime, Utc, TimeZone, FixedOffset, Duration};
+use std::sync::Arc;
+use shaum_calendar::{adjusted_date, to_hijri_with, HijriCache, HijriCalendar, HijriYmd, TableCalendar, UmmAlQura, HIJRI_MIN_YEAR, HIJRI_MAX_YEAR};
+#[cfg(feature = "astronomy")]
+use shaum_astronomy::PrayerCache;
+#[cfg(feature = "astronomy")]
+use shaum_astronomy::lunar_calendar::AstronomicalCalendar;
 use shaum_types::ShaumError;
-use shaum_types::{FastingAnalysis, FastingStatus, FastingType, Madhab, DaudStrategy, RuleTrace, TraceCode, GeoCoordinate, VisibilityCriteria, TracePayload};
-use crate::constants::*;
+use shaum_types::{FastingAnalysis, FastingStatus, FastingType, Madhab, DaudStrategy, RuleTrace, TraceCode, GeoCoordinate, VisibilityCriteria, TracePayload, MonthSource};
+#[cfg(feature = "async")]
+use shaum_types::{ProviderHealth, ProviderStatus};
+#[cfg(feature = "async")]
+use crate::http::HttpClient;
+use crate::schedule::PersonalSchedule;
+use crate::vow::Vows;
+use crate::registry::{builtin_registry, is_eid_al_adha, is_eid_al_fitr, is_tashriq, RuleInput, RuleRegistry};
+use crate::personal::PersonalContext;
+use crate::i18n::{EnglishLocalizer, Localizer};
 use serde::Serialize;
 #[cfg(feature = "async")]
 use serde::Deserialize;
@@ -12,6 +25,10 @@ use smallvec::SmallVec;
 /// 
 /// When the `async` feature is enabled, returns a pinned boxed future.
 /// Otherwise, returns a synchronous result.
+///
+/// A provider attached with [`RuleContext::with_moon_provider`] is consulted
+/// per Hijri month through [`month_offset`](Self::month_offset), so a sighting
+/// report moves only the month it applies to.
 pub trait MoonProvider: std::fmt::Debug + Send + Sync {
     #[cfg(feature = "async")]
     fn get_adjustment(
@@ -22,8 +39,42 @@ pub trait MoonProvider: std::fmt::Debug + Send + Sync {
     
     #[cfg(not(feature = "async"))]
     fn get_adjustment(&self, date: NaiveDate, coords: Option<GeoCoordinate>) -> Result<i64, ShaumError>;
+
+    /// Days by which the start of `month` `year` AH is moved relative to the
+    /// context's calendar (positive = the month starts later), or `None` to
+    /// keep the calculated start. This is the opposite sign of
+    /// [`get_adjustment`](Self::get_adjustment); convert with
+    /// [`adjustment_to_month_offset`]. Always synchronous: remote providers
+    /// answer from what they have fetched.
+    fn month_offset(&self, _year: usize, _month: usize) -> Option<i64> {
+        None
+    }
+
+    /// Which source decided the start of `month` `year` AH, for providers
+    /// that blend several. When `Some`, analyses of days in that month carry
+    /// a [`TraceCode::MonthStart`] trace naming it.
+    fn month_source(&self, _year: usize, _month: usize) -> Option<MonthSource> {
+        None
+    }
 }
 
+/// Converts an adjustment (positive = Hijri ahead, so the month starts
+/// earlier), as returned by [`MoonProvider::get_adjustment`] and taken by
+/// [`RuleContext::adjustment`], into a [`MoonProvider::month_offset`]
+/// (positive = the month starts later).
+pub const fn adjustment_to_month_offset(adjustment: i64) -> i64 {
+    adjustment.saturating_neg()
+}
+
+/// Inverse of [`adjustment_to_month_offset`].
+pub const fn month_offset_to_adjustment(offset: i64) -> i64 {
+    offset.saturating_neg()
+}
+
+/// Largest per-month shift applied from [`MoonProvider::month_offset`] when
+/// a month moves apart from its neighbours.
+const MAX_MONTH_OFFSET: i64 = 2;
+
 /// Fixed day offset for all dates.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct FixedAdjustment(pub i64);
@@ -47,6 +98,71 @@ impl MoonProvider for FixedAdjustment {
     fn get_adjustment(&self, _date: NaiveDate, _coords: Option<GeoCoordinate>) -> Result<i64, ShaumError> {
         Ok(self.0)
     }
+
+    fn month_offset(&self, _year: usize, _month: usize) -> Option<i64> {
+        Some(adjustment_to_month_offset(self.0))
+    }
+}
+
+/// Per-month sighting results: the offset of each listed month's start from
+/// the calculated one. Unlisted months keep the calculated start.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_rules::{MonthSightings, RuleContext};
+///
+/// // Ramadhan 1445 sighted a day late; Shawwal unaffected.
+/// let ctx = RuleContext::new().with_moon_provider(MonthSightings::new().with_month(1445, 9, 1));
+/// let hijri = ctx.hijri(NaiveDate::from_ymd_opt(2024, 3, 11).unwrap()).unwrap();
+/// assert_eq!((hijri.month, hijri.day), (8, 30));
+/// let eid = ctx.hijri(NaiveDate::from_ymd_opt(2024, 4, 10).unwrap()).unwrap();
+/// assert_eq!((eid.month, eid.day), (10, 1));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MonthSightings {
+    offsets: std::collections::BTreeMap<(usize, usize), i64>,
+}
+
+impl MonthSightings {
+    pub fn new() -> Self { Self::default() }
+
+    /// Moves the start of `month` `year` AH by `offset` days.
+    pub fn with_month(mut self, year: usize, month: usize, offset: i64) -> Self {
+        self.offsets.insert((year, month), offset);
+        self
+    }
+
+    /// Whether `month` `year` AH has an entry.
+    pub fn contains(&self, year: usize, month: usize) -> bool { self.offsets.contains_key(&(year, month)) }
+
+    pub fn is_empty(&self) -> bool { self.offsets.is_empty() }
+
+    /// Adjustment for the Umm al-Qura month containing `date`; 0 if unlisted.
+    fn adjustment_at(&self, date: NaiveDate) -> Result<i64, ShaumError> {
+        let h = to_hijri_with(date, 0, &UmmAlQura)?;
+        Ok(self.month_offset(h.year, h.month).map_or(0, month_offset_to_adjustment))
+    }
+}
+
+impl MoonProvider for MonthSightings {
+    #[cfg(feature = "async")]
+    fn get_adjustment(
+        &self,
+        date: NaiveDate,
+        _coords: Option<GeoCoordinate>,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<i64, ShaumError>> + Send + '_>> {
+        let result = self.adjustment_at(date);
+        Box::pin(async move { result })
+    }
+
+    #[cfg(not(feature = "async"))]
+    fn get_adjustment(&self, date: NaiveDate, _coords: Option<GeoCoordinate>) -> Result<i64, ShaumError> {
+        self.adjustment_at(date)
+    }
+
+    fn month_offset(&self, year: usize, month: usize) -> Option<i64> {
+        self.offsets.get(&(year, month)).copied()
+    }
 }
 
 /// No adjustment (use astronomical calculation).
@@ -69,12 +185,54 @@ impl MoonProvider for NoAdjustment {
     }
 }
 
+/// Scripted adjustments for tests; see [`shaum_network::mock`].
+#[cfg(feature = "async")]
+impl MoonProvider for shaum_network::mock::MockMoonProvider {
+    fn get_adjustment(
+        &self,
+        _date: NaiveDate,
+        _coords: Option<GeoCoordinate>,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<i64, ShaumError>> + Send + '_>> {
+        self.respond()
+    }
+}
+
+/// Callback invoked whenever a provider's status changes.
+#[cfg(feature = "async")]
+pub type StatusCallback = std::sync::Arc<dyn Fn(&ProviderStatus) + Send + Sync>;
+
 /// Remote moon provider fetching adjustment from an API.
+///
+/// Successful adjustments are cached by the Umm al-Qura month containing the
+/// requested date. When a fetch fails, that month's cached value is served
+/// and the provider reports itself as degraded; with nothing cached for the
+/// month it is offline and the fetch error is returned. See [`status`](Self::status).
+/// Cached months also answer [`month_offset`](MoonProvider::month_offset).
 #[cfg(feature = "async")]
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct RemoteMoonProvider {
     endpoint: String,
-    client: reqwest::Client,
+    client: std::sync::Arc<dyn HttpClient>,
+    state: std::sync::Arc<std::sync::Mutex<RemoteState>>,
+    on_status: Option<StatusCallback>,
+}
+
+#[cfg(feature = "async")]
+#[derive(Debug, Default)]
+struct RemoteState {
+    /// Fetched adjustments by the Umm al-Qura month they were requested for.
+    months: std::collections::BTreeMap<(usize, usize), i64>,
+    status: ProviderStatus,
+}
+
+#[cfg(feature = "async")]
+impl std::fmt::Debug for RemoteMoonProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RemoteMoonProvider")
+            .field("endpoint", &self.endpoint)
+            .field("status", &self.status())
+            .finish()
+    }
 }
 
 #[cfg(feature = "async")]
@@ -82,16 +240,73 @@ impl RemoteMoonProvider {
     pub fn new(endpoint: impl Into<String>) -> Self {
         Self {
             endpoint: endpoint.into(),
-            client: reqwest::Client::new(),
+            client: crate::http::default_client(),
+            state: Default::default(),
+            on_status: None,
         }
     }
+
+    /// Uses a custom HTTP client (e.g. a mock, or one with extra headers).
+    pub fn with_client(mut self, client: impl HttpClient + 'static) -> Self {
+        self.client = std::sync::Arc::new(client);
+        self
+    }
+
+    /// Registers a callback fired after every fetch attempt.
+    pub fn on_status(mut self, callback: impl Fn(&ProviderStatus) + Send + Sync + 'static) -> Self {
+        self.on_status = Some(std::sync::Arc::new(callback));
+        self
+    }
+
+    /// Current health, last successful fetch and last error.
+    pub fn status(&self) -> ProviderStatus {
+        self.state.lock().map(|s| s.status.clone()).unwrap_or_default()
+    }
+
+    fn record(&self, date: NaiveDate, result: Result<i64, ShaumError>) -> Result<i64, ShaumError> {
+        let (result, status) = {
+            let mut state = self.state.lock()
+                .map_err(|_| ShaumError::MoonProviderError("status lock poisoned".into()))?;
+            let month = to_hijri_with(date, 0, &UmmAlQura).ok().map(|h| (h.year, h.month));
+            let result = match result {
+                Ok(adj) => {
+                    if let Some(month) = month {
+                        state.months.insert(month, adj);
+                    }
+                    state.status.health = ProviderHealth::Healthy;
+                    state.status.last_success = Some(Utc::now());
+                    state.status.last_error = None;
+                    Ok(adj)
+                }
+                Err(e) => {
+                    let reason = e.to_string();
+                    state.status.last_error = Some(reason.clone());
+                    match month.and_then(|m| state.months.get(&m).copied()) {
+                        Some(adj) => {
+                            state.status.health = ProviderHealth::Degraded;
+                            Ok(adj)
+                        }
+                        None => {
+                            state.status.health = ProviderHealth::Offline;
+                            Err(ShaumError::ProviderUnavailable { reason, status: state.status.clone() })
+                        }
+                    }
+                }
+            };
+            (result, state.status.clone())
+        };
+        if let Some(cb) = &self.on_status {
+            cb(&status);
+        }
+        result
+    }
 }
 
 #[cfg(feature = "async")]
 impl MoonProvider for RemoteMoonProvider {
     fn get_adjustment(
         &self,
-        _date: NaiveDate,
+        date: NaiveDate,
         _coords: Option<GeoCoordinate>,
     ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<i64, ShaumError>> + Send + '_>> {
         let endpoint = self.endpoint.clone();
@@ -103,20 +318,125 @@ impl MoonProvider for RemoteMoonProvider {
                 adjustment: i64,
             }
 
-            let resp = client.get(&endpoint)
-                .send()
-                .await
-                .map_err(|e| ShaumError::NetworkError(e.to_string()))?;
-                
-            let data = resp.json::<AdjustmentResponse>()
+            let fetched = crate::http::get_json::<AdjustmentResponse>(&*client, &endpoint)
                 .await
-                .map_err(|e| ShaumError::NetworkError(e.to_string()))?;
-                
-            Ok(data.adjustment)
+                .map(|data| data.adjustment);
+
+            self.record(date, fetched)
         })
     }
+
+    fn month_offset(&self, year: usize, month: usize) -> Option<i64> {
+        self.state.lock().ok()?.months.get(&(year, month)).copied().map(adjustment_to_month_offset)
+    }
+}
+
+/// Blends astronomical prediction with official announcements.
+///
+/// Precedence: in overridden months (all months by default) a successful
+/// announcement wins over the calculation; everywhere else the calculation is used.
+/// If the announcement provider fails, the calculation is used unless
+/// `fallback_to_calculation(false)` is set, in which case the error is returned.
+///
+/// The month is identified using the calculated adjustment. In a context,
+/// analyses record the deciding source as a [`TraceCode::MonthStart`] trace;
+/// [`resolve`](Self::resolve) reports it for a single lookup.
+#[derive(Debug, Clone)]
+pub struct HybridMoonProvider<C, A> {
+    calculated: C,
+    announced: A,
+    override_months: Option<Vec<usize>>,
+    fallback_to_calculation: bool,
+}
+
+impl<C: MoonProvider, A: MoonProvider> HybridMoonProvider<C, A> {
+    pub fn new(calculated: C, announced: A) -> Self {
+        Self { calculated, announced, override_months: None, fallback_to_calculation: true }
+    }
+
+    /// Restricts announcement overrides to the given Hijri months (1-12).
+    pub fn override_months(mut self, months: impl IntoIterator<Item = usize>) -> Self {
+        self.override_months = Some(months.into_iter().collect());
+        self
+    }
+
+    /// Whether to fall back to calculation when the announcement provider fails. Default: true.
+    pub fn fallback_to_calculation(mut self, fallback: bool) -> Self {
+        self.fallback_to_calculation = fallback;
+        self
+    }
+
+    fn overrides_month(&self, month: usize) -> bool {
+        self.override_months.as_ref().is_none_or(|months| months.contains(&month))
+    }
+
+    fn overrides(&self, date: NaiveDate, calculated: i64) -> Result<bool, ShaumError> {
+        match &self.override_months {
+            None => Ok(true),
+            Some(months) => Ok(months.contains(&to_hijri_with(date, calculated, &UmmAlQura)?.month)),
+        }
+    }
+
+    fn pick(&self, calculated: i64, announced: Result<i64, ShaumError>) -> Result<(i64, RuleTrace), ShaumError> {
+        match announced {
+            Ok(adj) => Ok((adj, Self::trace(MonthSource::Announcement, adj))),
+            Err(_) if self.fallback_to_calculation => Ok((calculated, Self::trace(MonthSource::Calculation, calculated))),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn trace(source: MonthSource, adjustment: i64) -> RuleTrace {
+        RuleTrace::new(TraceCode::MonthStart, TracePayload::MonthSource { source, adjustment })
+    }
+
+    /// Resolves the adjustment together with a trace naming the deciding source.
+    #[cfg(not(feature = "async"))]
+    pub fn resolve(&self, date: NaiveDate, coords: Option<GeoCoordinate>) -> Result<(i64, RuleTrace), ShaumError> {
+        let calculated = self.calculated.get_adjustment(date, coords)?;
+        if !self.overrides(date, calculated)? {
+            return Ok((calculated, Self::trace(MonthSource::Calculation, calculated)));
+        }
+        self.pick(calculated, self.announced.get_adjustment(date, coords))
+    }
+
+    /// Resolves the adjustment together with a trace naming the deciding source.
+    #[cfg(feature = "async")]
+    pub async fn resolve(&self, date: NaiveDate, coords: Option<GeoCoordinate>) -> Result<(i64, RuleTrace), ShaumError> {
+        let calculated = self.calculated.get_adjustment(date, coords).await?;
+        if !self.overrides(date, calculated)? {
+            return Ok((calculated, Self::trace(MonthSource::Calculation, calculated)));
+        }
+        self.pick(calculated, self.announced.get_adjustment(date, coords).await)
+    }
 }
 
+impl<C: MoonProvider, A: MoonProvider> MoonProvider for HybridMoonProvider<C, A> {
+    #[cfg(feature = "async")]
+    fn get_adjustment(
+        &self,
+        date: NaiveDate,
+        coords: Option<GeoCoordinate>,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<i64, ShaumError>> + Send + '_>> {
+        Box::pin(async move { self.resolve(date, coords).await.map(|(adj, _)| adj) })
+    }
+
+    #[cfg(not(feature = "async"))]
+    fn get_adjustment(&self, date: NaiveDate, coords: Option<GeoCoordinate>) -> Result<i64, ShaumError> {
+        self.resolve(date, coords).map(|(adj, _)| adj)
+    }
+
+    fn month_offset(&self, year: usize, month: usize) -> Option<i64> {
+        let announced = self.overrides_month(month).then(|| self.announced.month_offset(year, month)).flatten();
+        announced.or_else(|| self.calculated.month_offset(year, month))
+    }
+
+    fn month_source(&self, year: usize, month: usize) -> Option<MonthSource> {
+        let announced = self.overrides_month(month) && self.announced.month_offset(year, month).is_some();
+        Some(if announced { MonthSource::Announcement } else { MonthSource::Calculation })
+    }
+}
+
+
 /// Interface for calculating sunset time.
 pub trait SunsetProvider: std::fmt::Debug + Send + Sync {
     /// Returns the sunset timestamp for a given date and coordinate.
@@ -124,14 +444,23 @@ pub trait SunsetProvider: std::fmt::Debug + Send + Sync {
 }
 
 /// Default sunset calculator using VSOP87 astronomy engine.
+///
+/// Without the `astronomy` feature it returns `CapabilityUnavailable`, and
+/// [`analyze`] falls back to a midnight day boundary (an error in strict mode).
 #[derive(Debug, Default, Clone, Copy)]
 pub struct DefaultSunsetProvider;
 
 impl SunsetProvider for DefaultSunsetProvider {
+    #[cfg(feature = "astronomy")]
     fn get_sunset(&self, date: NaiveDate, coords: GeoCoordinate) -> Result<DateTime<Utc>, ShaumError> {
         // Use the astronomy engine for accurate sunset calculation
         shaum_astronomy::visibility::estimate_sunset(date, coords)
     }
+
+    #[cfg(not(feature = "astronomy"))]
+    fn get_sunset(&self, _date: NaiveDate, _coords: GeoCoordinate) -> Result<DateTime<Utc>, ShaumError> {
+        Err(ShaumError::capability_unavailable("sunset", "astronomy"))
+    }
 }
 
 /// Custom rule trait.
@@ -140,20 +469,144 @@ pub trait CustomFastingRule: std::fmt::Debug + Send + Sync {
         -> Option<(FastingStatus, FastingType)>;
 }
 
+/// Version of the built-in rule set. Bumped whenever a default ruling
+/// changes; see [`RULESET_CHANGES`] and [`RuleContext::ruleset_version`].
+pub const RULESET_VERSION: u32 = 3;
+
+/// Whether a ruleset version added a rule or changed an existing one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum RulesetChangeKind {
+    Added,
+    Changed,
+}
+
+/// A default ruling introduced or changed in a ruleset version.
+#[derive(Debug, Clone, Serialize)]
+pub struct RulesetChange {
+    pub version: u32,
+    pub kind: RulesetChangeKind,
+    /// Reason the rule produces.
+    pub fasting_type: FastingType,
+    pub summary: &'static str,
+}
+
+/// Changes after version 1, oldest first. Contexts pinned to an earlier
+/// version skip added rules and keep the former behaviour of changed ones.
+pub static RULESET_CHANGES: [RulesetChange; 5] = [
+    RulesetChange {
+        version: 2, kind: RulesetChangeKind::Added, fasting_type: FastingType::DAY_OF_DOUBT,
+        summary: "30 Sha'ban is Makruh",
+    },
+    RulesetChange {
+        version: 2, kind: RulesetChangeKind::Added, fasting_type: FastingType::LATE_SHABAN,
+        summary: "16-29 Sha'ban is Makruh for Shafi'is",
+    },
+    RulesetChange {
+        version: 2, kind: RulesetChangeKind::Added, fasting_type: FastingType::PRE_RAMADHAN,
+        summary: "The day or two before Ramadhan are Makruh",
+    },
+    RulesetChange {
+        version: 2, kind: RulesetChangeKind::Added, fasting_type: FastingType::DHUL_HIJJAH_TEN,
+        summary: "1-8 Dhu al-Hijjah are Sunnah",
+    },
+    RulesetChange {
+        version: 3, kind: RulesetChangeKind::Changed, fasting_type: FastingType::SATURDAY_EXCLUSIVE,
+        summary: "A lone Saturday is no longer Makruh for Malikis",
+    },
+];
+
+/// Ruleset version that introduced the built-in rule producing `fasting_type`
+/// (1 for the original rules and for custom types).
+pub fn introduced_in(fasting_type: &FastingType) -> u32 {
+    RULESET_CHANGES.iter()
+        .find(|c| c.kind == RulesetChangeKind::Added && c.fasting_type == *fasting_type)
+        .map_or(1, |c| c.version)
+}
+
+/// Whether the built-in rule producing `fasting_type` rules a day Haram or
+/// Wajib (the Eids, Tashriq, Haid, Nifas, Ramadhan, vows). These cannot be
+/// disabled: switching them off would report forbidden days as fastable and
+/// obligatory ones as optional. The travel concession is Wajib only as part
+/// of Ramadhan, which stays in force without it, so it can be disabled.
+pub fn is_mandatory_rule(fasting_type: &FastingType) -> bool {
+    *fasting_type != FastingType::SAFAR
+        && matches!(fasting_type.implied_status(), Some(FastingStatus::Haram | FastingStatus::Wajib))
+}
+
+fn mandatory_rule_error(rule: impl std::fmt::Display) -> ShaumError {
+    ShaumError::ValidationError(format!("Rule '{}' is Haram or Wajib and cannot be disabled", rule))
+}
+
 /// Rule engine configuration.
 #[derive(Debug, Serialize)] // Removing Deserialize because dynamic traits (SunsetProvider) are hard to deserialize without specific logic
 pub struct RuleContext {
-    /// Hijri day offset. Clamped to [-30, 30].
+    /// Hijri day offset. Clamped to [-30, 30] when used (rejected in strict mode).
     pub adjustment: i64,
     pub madhab: Madhab,
     pub daud_strategy: DaudStrategy,
+    /// "No silent guesses" mode. When set, every place that would otherwise
+    /// substitute a value fails instead:
+    /// - dates outside the Umm al-Qura range are rejected for every backend;
+    /// - adjustments outside [-30, 30] are rejected instead of clamped;
+    /// - an adjustment that moves a date out of the supported range is
+    ///   rejected instead of saturated (see [`Self::applied_adjustment`]);
+    /// - coordinates passed to the engine must pass [`GeoCoordinate::validate`];
+    /// - [`Self::localizer`] rejects unknown locales instead of using English;
+    /// - an impossible Hijri date from the calendar backend is an error instead
+    ///   of matching no rule and falling through to Mubah.
     pub strict: bool,
+    /// The person fasts by habit (e.g. every Monday) or is continuing fasts
+    /// begun before mid-Sha'ban, which exempts them from the Shafi'i
+    /// late-Sha'ban rule ([`FastingType::LATE_SHABAN`]) and from the days
+    /// before Ramadhan ([`FastingType::PRE_RAMADHAN`]).
+    pub connected_fast: bool,
+    /// Marks 15 Sha'ban with its own [`FastingType::NISF_SHABAN`] reason.
+    /// Off by default: fasting it for its own sake is disputed.
+    pub nisf_shaban: bool,
+    /// The person is performing Hajj. Pilgrims at Arafah should not fast
+    /// the day, so 9 Dhu al-Hijjah becomes [`FastingType::ARAFAH_PILGRIM`]
+    /// (Makruh) instead of [`FastingType::ARAFAH`].
+    pub is_pilgrim: bool,
+    /// Menstruation or postnatal bleeding on the analysed day. Overrides the
+    /// calendar: every day is Haram, and Ramadhan days need qadha.
+    pub personal: PersonalContext,
+    /// Planned or completed fasts. A Friday or Saturday next to one of them
+    /// is not singled out.
+    pub schedule: PersonalSchedule,
+    /// Vowed fasts: their days are Wajib with a [`FastingType::NADHAR`] reason.
+    #[serde(default)]
+    pub vows: Vows,
+    /// Built-in rule set to follow, from 1 to [`RULESET_VERSION`] (the
+    /// default). Pinning an older version keeps its defaults when the engine
+    /// gains new rulings.
+    pub ruleset_version: u32,
     /// Moon visibility criteria for hilal observation.
     pub visibility_criteria: VisibilityCriteria,
+    /// Built-in rules that are skipped, by the type they produce
+    /// (e.g. [`FastingType::SATURDAY_EXCLUSIVE`]). Custom rules are unaffected,
+    /// and Haram or Wajib rules (see [`is_mandatory_rule`]) always run.
+    pub disabled_rules: Vec<FastingType>,
     #[serde(skip)]
     pub custom_rules: Vec<Box<dyn CustomFastingRule>>,
     #[serde(skip)]
     pub sunset_provider: Box<dyn SunsetProvider>,
+    /// Gregorian to Hijri backend. Default: [`UmmAlQura`].
+    #[serde(skip)]
+    pub calendar: Arc<dyn HijriCalendar>,
+    /// Conversion cache. Defaults to one process-wide cache shared by every
+    /// context; `None` converts each date afresh.
+    #[serde(skip)]
+    pub cache: Option<HijriCache>,
+    /// Optional prayer time cache used by [`crate::prayer_times`].
+    #[cfg(feature = "astronomy")]
+    #[serde(skip)]
+    pub prayer_cache: Option<PrayerCache>,
+    /// Per-month sighting corrections on top of the calendar and adjustment.
+    #[serde(skip)]
+    pub moon_provider: Option<Arc<dyn MoonProvider>>,
+    /// Rules to evaluate; `None` runs [`RuleRegistry::builtin`].
+    #[serde(skip)]
+    pub registry: Option<Arc<RuleRegistry>>,
 }
 
 impl Clone for RuleContext {
@@ -163,13 +616,39 @@ impl Clone for RuleContext {
             madhab: self.madhab,
             daud_strategy: self.daud_strategy,
             strict: self.strict,
+            connected_fast: self.connected_fast,
+            nisf_shaban: self.nisf_shaban,
+            is_pilgrim: self.is_pilgrim,
+            personal: self.personal,
+            schedule: self.schedule.clone(),
+            vows: self.vows.clone(),
+            ruleset_version: self.ruleset_version,
             visibility_criteria: self.visibility_criteria,
+            disabled_rules: self.disabled_rules.clone(),
             custom_rules: Vec::new(),
             sunset_provider: Box::new(DefaultSunsetProvider), // Resetting provider on clone as we can't clone trait object easily without `dyn Clone`
+            calendar: self.calendar.clone(),
+            cache: self.cache.clone(),
+            #[cfg(feature = "astronomy")]
+            prayer_cache: self.prayer_cache.clone(),
+            moon_provider: self.moon_provider.clone(),
+            registry: self.registry.clone(),
         }
     }
 }
 
+/// The Umm al-Qura backend, one instance shared by every context.
+fn default_calendar() -> Arc<dyn HijriCalendar> {
+    static UMM_AL_QURA: std::sync::LazyLock<Arc<dyn HijriCalendar>> = std::sync::LazyLock::new(|| Arc::new(UmmAlQura));
+    UMM_AL_QURA.clone()
+}
+
+/// Process-wide conversion cache used unless a context brings its own.
+fn default_cache() -> HijriCache {
+    static CACHE: std::sync::LazyLock<HijriCache> = std::sync::LazyLock::new(HijriCache::new);
+    CACHE.clone()
+}
+
 impl Default for RuleContext {
     fn default() -> Self {
         Self {
@@ -177,9 +656,23 @@ impl Default for RuleContext {
             madhab: Madhab::default(),
             daud_strategy: DaudStrategy::default(),
             strict: false,
+            connected_fast: false,
+            nisf_shaban: false,
+            is_pilgrim: false,
+            personal: PersonalContext::default(),
+            schedule: PersonalSchedule::default(),
+            vows: Vows::default(),
+            ruleset_version: RULESET_VERSION,
             visibility_criteria: VisibilityCriteria::default(),
+            disabled_rules: Vec::new(),
             custom_rules: Vec::new(),
             sunset_provider: Box::new(DefaultSunsetProvider),
+            calendar: default_calendar(),
+            cache: Some(default_cache()),
+            #[cfg(feature = "astronomy")]
+            prayer_cache: None,
+            moon_provider: None,
+            registry: None,
         }
     }
 }
@@ -187,8 +680,21 @@ impl Default for RuleContext {
 impl RuleContext {
     pub fn new() -> Self { Self::default() }
 
+    /// Indonesian preset: the [`TableCalendar::kemenag`] taqwim with the Shafi'i madhab.
+    pub fn indonesia() -> Self {
+        Self::new().madhab(Madhab::Shafi).with_calendar(TableCalendar::kemenag())
+    }
+
+    /// Turkish preset: the Diyanet calendar ([`AstronomicalCalendar::diyanet`])
+    /// with the Hanafi madhab. Month starts are computed, so the first lookup
+    /// per month is slower than with table-based calendars.
+    #[cfg(feature = "astronomy")]
+    pub fn turkey() -> Self {
+        Self::new().madhab(Madhab::Hanafi).with_calendar(AstronomicalCalendar::diyanet())
+    }
+
     pub fn adjustment(mut self, adjustment: i64) -> Self {
-        self.adjustment = adjustment.clamp(-30, 30);
+        self.adjustment = adjustment;
         self
     }
 
@@ -207,6 +713,30 @@ impl RuleContext {
         self
     }
 
+    /// Sets [`Self::connected_fast`].
+    pub fn connected_fast(mut self, connected: bool) -> Self {
+        self.connected_fast = connected;
+        self
+    }
+
+    /// Sets [`Self::nisf_shaban`].
+    pub fn nisf_shaban(mut self, enabled: bool) -> Self {
+        self.nisf_shaban = enabled;
+        self
+    }
+
+    /// Sets [`Self::is_pilgrim`].
+    pub fn pilgrim(mut self, pilgrim: bool) -> Self {
+        self.is_pilgrim = pilgrim;
+        self
+    }
+
+    /// Sets [`Self::personal`].
+    pub fn with_personal(mut self, personal: PersonalContext) -> Self {
+        self.personal = personal;
+        self
+    }
+
     pub fn with_sunset_provider<P: SunsetProvider + 'static>(mut self, provider: P) -> Self {
         self.sunset_provider = Box::new(provider);
         self
@@ -217,6 +747,343 @@ impl RuleContext {
         self.visibility_criteria = criteria;
         self
     }
+
+    /// Sets the Hijri calendar backend.
+    pub fn with_calendar<C: HijriCalendar + 'static>(mut self, calendar: C) -> Self {
+        self.calendar = Arc::new(calendar);
+        self
+    }
+
+    /// Caches conversions in `cache`. Clones of one cache may be handed to many
+    /// contexts; entries are kept apart per calendar configuration
+    /// ([`HijriCalendar::cache_id`]).
+    pub fn with_shared_cache(mut self, cache: HijriCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Caches prayer times in `cache` (coordinates snapped to about 1 km).
+    #[cfg(feature = "astronomy")]
+    pub fn with_prayer_cache(mut self, cache: PrayerCache) -> Self {
+        self.prayer_cache = Some(cache);
+        self
+    }
+
+    /// Moves individual month starts as reported by `provider` (see
+    /// [`MoonProvider::month_offset`]). An offset shared by a month and both
+    /// its neighbours is applied like [`Self::adjustment`] and clamped to ±30
+    /// days; otherwise offsets are clamped to ±2 days and so that every month
+    /// keeps 29 or 30 days. Strict mode rejects them instead of clamping.
+    pub fn with_moon_provider<P: MoonProvider + 'static>(mut self, provider: P) -> Self {
+        self.moon_provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// Pins the built-in rule set (see [`Self::ruleset_version`]). Versions
+    /// outside 1..=[`RULESET_VERSION`] are clamped when used.
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use shaum_rules::{check, RuleContext};
+    ///
+    /// // 1 Dhu al-Hijjah 1445 (7 June 2024) became Sunnah in version 2.
+    /// let date = NaiveDate::from_ymd_opt(2024, 6, 7).unwrap();
+    /// assert!(check(date, &RuleContext::new()).unwrap().is_dhul_hijjah_ten());
+    /// assert!(!check(date, &RuleContext::new().ruleset_version(1)).unwrap().is_dhul_hijjah_ten());
+    /// ```
+    pub fn ruleset_version(mut self, version: u32) -> Self {
+        self.ruleset_version = version;
+        self
+    }
+
+    /// Sets the user's planned or completed fasts (see [`Self::schedule`]).
+    pub fn with_schedule(mut self, schedule: PersonalSchedule) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    /// Registers the user's vowed fasts (see [`Self::vows`]).
+    pub fn with_vows(mut self, vows: Vows) -> Self {
+        self.vows = vows;
+        self
+    }
+
+    /// Evaluates `registry` instead of the built-in rules (see
+    /// [`crate::registry`]).
+    pub fn with_registry(mut self, registry: RuleRegistry) -> Self {
+        self.registry = Some(Arc::new(registry));
+        self
+    }
+
+    /// The rules this context evaluates.
+    pub fn registry(&self) -> &RuleRegistry {
+        self.registry.as_deref().unwrap_or_else(|| builtin_registry())
+    }
+
+    /// Skips the built-in rule producing `fasting_type`.
+    ///
+    /// # Errors
+    /// `ValidationError` for a Haram or Wajib rule (see [`is_mandatory_rule`]).
+    pub fn disable_rule(mut self, fasting_type: FastingType) -> Result<Self, ShaumError> {
+        if is_mandatory_rule(&fasting_type) {
+            return Err(mandatory_rule_error(&fasting_type));
+        }
+        if !self.disabled_rules.contains(&fasting_type) {
+            self.disabled_rules.push(fasting_type);
+        }
+        Ok(self)
+    }
+
+    /// Skips the rules in [`Self::registry`] reporting `code` in their traces
+    /// (e.g. [`TraceCode::SaturdaySingledOut`], [`TraceCode::AyyamulBidh`]).
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use shaum_rules::{check, RuleContext};
+    /// use shaum_types::TraceCode;
+    ///
+    /// let ctx = RuleContext::new().disable_trace_code(TraceCode::SaturdaySingledOut).unwrap();
+    /// let saturday = NaiveDate::from_ymd_opt(2024, 1, 6).unwrap();
+    /// assert!(check(saturday, &ctx).unwrap().primary_status.is_mubah());
+    /// ```
+    ///
+    /// # Errors
+    /// `ValidationError` for a code no rule in the registry reports, such as
+    /// [`TraceCode::Custom`], or one reported by a Haram or Wajib rule (see
+    /// [`is_mandatory_rule`]).
+    pub fn disable_trace_code(mut self, code: TraceCode) -> Result<Self, ShaumError> {
+        let types: Vec<FastingType> = self.registry().reports()
+            .filter(|info| info.trace_code == code)
+            .map(|info| info.fasting_type.clone())
+            .collect();
+        if types.is_empty() {
+            return Err(ShaumError::ValidationError(format!("No rule reports '{}'", code.code())));
+        }
+        if types.iter().any(is_mandatory_rule) {
+            return Err(mandatory_rule_error(code.code()));
+        }
+        for fasting_type in types {
+            self = self.disable_rule(fasting_type)?;
+        }
+        Ok(self)
+    }
+
+    /// Disables built-in rules by type name (e.g. `"SaturdayExclusive"`) or
+    /// stable trace code (e.g. `"saturday_singled_out"`), the forms used by
+    /// the language bindings.
+    ///
+    /// # Errors
+    /// `ValidationError` for a name that is neither in [`FastingType::STANDARD`]
+    /// nor a trace code some built-in rule reports, or that names a Haram or
+    /// Wajib rule.
+    pub fn disable_rules_by_name<S: AsRef<str>>(mut self, names: impl IntoIterator<Item = S>) -> Result<Self, ShaumError> {
+        for name in names {
+            let name = name.as_ref();
+            self = match (FastingType::standard(name), TraceCode::from_code(name)) {
+                (Some(fasting_type), _) => self.disable_rule(fasting_type)?,
+                (None, Some(code)) => self.disable_trace_code(code)?,
+                (None, None) => return Err(ShaumError::ValidationError(format!("Unknown rule '{}'", name))),
+            };
+        }
+        Ok(self)
+    }
+
+    /// Whether the built-in rule producing `fasting_type` runs: it is not
+    /// disabled (or cannot be, see [`is_mandatory_rule`]) and exists in the
+    /// pinned ruleset version.
+    pub fn is_rule_enabled(&self, fasting_type: &FastingType) -> bool {
+        (is_mandatory_rule(fasting_type) || !self.disabled_rules.contains(fasting_type))
+            && introduced_in(fasting_type) <= self.effective_ruleset_version()
+    }
+
+    /// [`Self::ruleset_version`] clamped to 1..=[`RULESET_VERSION`].
+    pub fn effective_ruleset_version(&self) -> u32 {
+        self.ruleset_version.clamp(1, RULESET_VERSION)
+    }
+
+    /// Hijri date of `date` under this context's calendar, adjustment and
+    /// moon provider.
+    pub fn hijri(&self, date: NaiveDate) -> Result<HijriYmd, ShaumError> {
+        let h = self.calculated_hijri(date)?;
+        let h = match &self.moon_provider {
+            Some(provider) => self.sighted_hijri(date, h, &**provider)?,
+            None => h,
+        };
+        if self.strict && (!(1..=12).contains(&h.month) || !(1..=30).contains(&h.day)) {
+            return Err(ShaumError::HijriConversionError(format!(
+                "Calendar returned invalid Hijri date {}-{}-{} for {}", h.year, h.month, h.day, date
+            )));
+        }
+        Ok(h)
+    }
+
+    /// Hijri date from the calendar and adjustment alone.
+    fn calculated_hijri(&self, date: NaiveDate) -> Result<HijriYmd, ShaumError> {
+        let adjustment = self.applied_adjustment(date)?;
+        match &self.cache {
+            Some(cache) => cache.get_or_convert(adjusted_date(date, adjustment)?, &self.calendar),
+            None => to_hijri_with(date, adjustment, &*self.calendar),
+        }
+    }
+
+    /// Re-places `date` (calculated as `base`) after moving the starts of
+    /// `base`'s month and its neighbours by the provider's offsets.
+    fn sighted_hijri(&self, date: NaiveDate, base: HijriYmd, provider: &dyn MoonProvider) -> Result<HijriYmd, ShaumError> {
+        let (y, m) = (base.year, base.month);
+        let prev = if m == 1 { (y.saturating_sub(1), 12) } else { (y, m - 1) };
+        let next = if m == 12 { (y + 1, 1) } else { (y, m + 1) };
+        let months = [prev, (y, m), next];
+        let offsets = months.map(|(year, month)| provider.month_offset(year, month).unwrap_or(0));
+        if offsets == [0; 3] {
+            return Ok(base);
+        }
+        if offsets[0] == offsets[1] && offsets[1] == offsets[2] {
+            // Every start moves alike: a plain adjustment (e.g. `FixedAdjustment`).
+            let adjustment = month_offset_to_adjustment(offsets[1]);
+            if self.strict && !(-30..=30).contains(&adjustment) {
+                return Err(ShaumError::invalid_config(format!(
+                    "Adjustment {} from the moon provider outside [-30, 30]", adjustment
+                )));
+            }
+            let shifted = date.checked_add_signed(Duration::days(adjustment.clamp(-30, 30)))
+                .ok_or_else(|| ShaumError::date_out_of_range(date))?;
+            return self.calculated_hijri(shifted);
+        }
+        let mut clamped = [0; 3];
+        for (i, ((year, month), offset)) in months.into_iter().zip(offsets).enumerate() {
+            if self.strict && offset.abs() > MAX_MONTH_OFFSET {
+                return Err(ShaumError::invalid_config(format!(
+                    "Month offset {} for {}-{} outside [-{max}, {max}]", offset, year, month, max = MAX_MONTH_OFFSET
+                )));
+            }
+            clamped[i] = offset.clamp(-MAX_MONTH_OFFSET, MAX_MONTH_OFFSET);
+        }
+        let offsets = clamped;
+
+        // Calculated starts of the three months, from `base` alone plus one
+        // lookup on each side.
+        let start = date - Duration::days(base.day as i64 - 1);
+        let prev_len = self.calculated_hijri(start - Duration::days(1))?.day as i64;
+        let day_29 = start + Duration::days(29);
+        let next_start = if self.calculated_hijri(day_29)?.day == 1 { day_29 } else { day_29 + Duration::days(1) };
+        let calculated = [start - Duration::days(prev_len), start, next_start];
+
+        // Shift each start, keeping every month at 29 or 30 days after the one before it.
+        let mut sighted = [calculated[0] + Duration::days(offsets[0]); 3];
+        for i in 1..3 {
+            let wanted = calculated[i] + Duration::days(offsets[i]);
+            let earliest = sighted[i - 1] + Duration::days(29);
+            sighted[i] = wanted.clamp(earliest, earliest + Duration::days(1));
+            if self.strict && sighted[i] != wanted {
+                let (year, month) = months[i];
+                return Err(ShaumError::invalid_config(format!(
+                    "Month offset {} for {}-{} leaves the previous month outside 29-30 days", offsets[i], year, month
+                )));
+            }
+        }
+
+        for ((year, month), first) in months.into_iter().zip(sighted).rev() {
+            if first <= date {
+                return Ok(HijriYmd::new(year, month, (date - first).num_days() as usize + 1));
+            }
+        }
+        Err(ShaumError::HijriConversionError(format!("Cannot place {} around {}-{}", date, y, m)))
+    }
+
+    /// Gregorian date of `date` under this context's calendar and adjustment.
+    ///
+    /// # Errors
+    /// `ValidationError` if the day does not exist in that month (e.g. 30 in a 29-day month).
+    pub fn gregorian(&self, date: HijriYmd) -> Result<NaiveDate, ShaumError> {
+        let start = self.hijri_month_start(date.year, date.month)?;
+        let candidate = start + Duration::days(date.day as i64 - 1);
+        if date.day == 0 || self.hijri(candidate)? != date {
+            return Err(ShaumError::ValidationError(format!(
+                "{}-{}-{} does not exist in this calendar", date.year, date.month, date.day
+            )));
+        }
+        Ok(candidate)
+    }
+
+    /// The adjustment actually applied: clamped to [-30, 30], or an error in strict mode.
+    pub fn effective_adjustment(&self) -> Result<i64, ShaumError> {
+        if self.strict && !(-30..=30).contains(&self.adjustment) {
+            return Err(ShaumError::invalid_config(format!(
+                "Adjustment {} outside [-30, 30]", self.adjustment
+            )));
+        }
+        Ok(self.adjustment.clamp(-30, 30))
+    }
+
+    /// The adjustment applied to `date`: [`Self::effective_adjustment`],
+    /// saturated so that a date inside the supported range
+    /// ([`HIJRI_MIN_YEAR`]..=[`HIJRI_MAX_YEAR`]) is not shifted out of it.
+    /// Dates already outside the range are shifted unchanged.
+    ///
+    /// # Errors
+    /// `AdjustmentOutOfRange` in strict mode instead of saturating.
+    pub fn applied_adjustment(&self, date: NaiveDate) -> Result<i64, ShaumError> {
+        let adjustment = self.effective_adjustment()?;
+        let in_range = |d: NaiveDate| (HIJRI_MIN_YEAR..=HIJRI_MAX_YEAR).contains(&d.year());
+        let shifted = date.checked_add_signed(Duration::days(adjustment));
+        if !in_range(date) || shifted.is_some_and(in_range) {
+            return Ok(adjustment);
+        }
+        if self.strict {
+            return Err(ShaumError::adjustment_out_of_range(date, adjustment));
+        }
+        let edge = if adjustment > 0 {
+            NaiveDate::from_ymd_opt(HIJRI_MAX_YEAR, 12, 31)
+        } else {
+            NaiveDate::from_ymd_opt(HIJRI_MIN_YEAR, 1, 1)
+        };
+        Ok(edge.map_or(0, |edge| (edge - date).num_days()))
+    }
+
+    /// Validates `coords` in strict mode; passes them through otherwise.
+    pub fn check_coords(&self, coords: GeoCoordinate) -> Result<GeoCoordinate, ShaumError> {
+        if self.strict {
+            coords.validate()?;
+        }
+        Ok(coords)
+    }
+
+    /// Built-in localizer for `locale` (see [`crate::i18n::localizer`]). Unknown
+    /// locales fall back to English, or fail in strict mode.
+    pub fn localizer(&self, locale: &str) -> Result<&'static dyn Localizer, ShaumError> {
+        match crate::i18n::localizer(locale) {
+            Some(l) => Ok(l),
+            None if self.strict => Err(ShaumError::invalid_config(format!("Unsupported locale '{}'", locale))),
+            None => Ok(&EnglishLocalizer),
+        }
+    }
+
+    /// Every day of `month` `year` AH with its Gregorian date (see
+    /// [`crate::HijriMonthIterator`]).
+    pub fn hijri_month_days(&self, year: usize, month: usize) -> Result<crate::HijriMonthIterator<'_>, ShaumError> {
+        crate::HijriMonthIterator::new(year, month, self)
+    }
+
+    /// Gregorian date of 1 `month` `year` AH under this context's calendar and adjustment.
+    pub fn hijri_month_start(&self, year: usize, month: usize) -> Result<NaiveDate, ShaumError> {
+        if !(1..=12).contains(&month) {
+            return Err(ShaumError::ValidationError(format!("Invalid Hijri month: {}", month)));
+        }
+        // Mean-month estimate from the epoch (16 July 622 Julian), then correct
+        // by the observed month/day difference.
+        let epoch = NaiveDate::from_ymd_opt(622, 7, 19).expect("valid epoch");
+        let target = (year * 12 + month - 1) as i64;
+        let mut date = epoch + Duration::days(((target - 12) as f64 * 29.530_588_853).round() as i64);
+        for _ in 0..8 {
+            let h = self.hijri(date)?;
+            let months = target - (h.year * 12 + h.month - 1) as i64;
+            if months == 0 && h.day == 1 {
+                return Ok(date);
+            }
+            date += Duration::days((months as f64 * 29.530_588_853).round() as i64 - (h.day as i64 - 1));
+        }
+        Err(ShaumError::AnalysisError(format!("Could not locate start of Hijri month {}-{}", year, month)))
+    }
 }
 
 /// Builder with validation for `RuleContext`.
@@ -226,10 +1093,24 @@ pub struct RuleContextBuilder {
     madhab: Option<Madhab>,
     daud_strategy: Option<DaudStrategy>,
     custom_rules: Vec<Box<dyn CustomFastingRule>>,
+    disabled_rules: Vec<FastingType>,
     sunset_provider: Option<Box<dyn SunsetProvider>>,
     visibility_criteria: Option<VisibilityCriteria>,
+    calendar: Option<Arc<dyn HijriCalendar>>,
+    cache: Option<HijriCache>,
+    #[cfg(feature = "astronomy")]
+    prayer_cache: Option<PrayerCache>,
+    moon_provider: Option<Arc<dyn MoonProvider>>,
     strict_adjustment: bool,
     strict_mode: bool,
+    connected_fast: bool,
+    nisf_shaban: bool,
+    is_pilgrim: bool,
+    personal: PersonalContext,
+    schedule: PersonalSchedule,
+    vows: Vows,
+    registry: Option<Arc<RuleRegistry>>,
+    ruleset_version: Option<u32>,
 }
 
 impl RuleContextBuilder {
@@ -239,11 +1120,48 @@ impl RuleContextBuilder {
     pub fn madhab(mut self, madhab: Madhab) -> Self { self.madhab = Some(madhab); self }
     pub fn daud_strategy(mut self, strategy: DaudStrategy) -> Self { self.daud_strategy = Some(strategy); self }
     pub fn add_custom_rule(mut self, rule: Box<dyn CustomFastingRule>) -> Self { self.custom_rules.push(rule); self }
+    /// Skips the built-in rule producing `fasting_type`; [`build`](Self::build)
+    /// fails for a Haram or Wajib rule, as [`RuleContext::disable_rule`] does.
+    pub fn disable_rule(mut self, fasting_type: FastingType) -> Self {
+        if !self.disabled_rules.contains(&fasting_type) { self.disabled_rules.push(fasting_type); }
+        self
+    }
     pub fn with_sunset_provider<P: SunsetProvider + 'static>(mut self, provider: P) -> Self {
         self.sunset_provider = Some(Box::new(provider));
         self
     }
+    pub fn with_calendar<C: HijriCalendar + 'static>(mut self, calendar: C) -> Self {
+        self.calendar = Some(Arc::new(calendar));
+        self
+    }
+    pub fn with_shared_cache(mut self, cache: HijriCache) -> Self { self.cache = Some(cache); self }
+    #[cfg(feature = "astronomy")]
+    pub fn with_prayer_cache(mut self, cache: PrayerCache) -> Self { self.prayer_cache = Some(cache); self }
+    pub fn with_moon_provider<P: MoonProvider + 'static>(mut self, provider: P) -> Self {
+        self.moon_provider = Some(Arc::new(provider));
+        self
+    }
+    pub fn with_schedule(mut self, schedule: PersonalSchedule) -> Self { self.schedule = schedule; self }
+    pub fn with_vows(mut self, vows: Vows) -> Self { self.vows = vows; self }
+    pub fn with_registry(mut self, registry: RuleRegistry) -> Self { self.registry = Some(Arc::new(registry)); self }
+    /// Pins the built-in rule set; `build` rejects versions outside 1..=[`RULESET_VERSION`].
+    pub fn ruleset_version(mut self, version: u32) -> Self { self.ruleset_version = Some(version); self }
     
+    /// Enables strict mode (see [`RuleContext::strict`]).
+    pub fn strict(mut self, strict: bool) -> Self { self.strict_mode = strict; self }
+
+    /// Sets [`RuleContext::connected_fast`].
+    pub fn connected_fast(mut self, connected: bool) -> Self { self.connected_fast = connected; self }
+
+    /// Sets [`RuleContext::nisf_shaban`].
+    pub fn nisf_shaban(mut self, enabled: bool) -> Self { self.nisf_shaban = enabled; self }
+
+    /// Sets [`RuleContext::is_pilgrim`].
+    pub fn pilgrim(mut self, pilgrim: bool) -> Self { self.is_pilgrim = pilgrim; self }
+
+    /// Sets [`RuleContext::personal`].
+    pub fn with_personal(mut self, personal: PersonalContext) -> Self { self.personal = personal; self }
+
     /// Enables strict adjustment bounds [-2, 2].
     pub fn strict_adjustment(mut self, strict: bool) -> Self { self.strict_adjustment = strict; self }
 
@@ -257,20 +1175,50 @@ impl RuleContextBuilder {
     pub fn build(self) -> Result<RuleContext, ShaumError> {
         let adjustment = self.adjustment.unwrap_or(0);
         
-        if self.strict_adjustment && (adjustment < -2 || adjustment > 2) {
+        if self.strict_adjustment && !(-2..=2).contains(&adjustment) {
             return Err(ShaumError::invalid_config(format!(
                 "Adjustment {} outside strict bounds [-2, 2]", adjustment
             )));
         }
+        if self.strict_mode && !(-30..=30).contains(&adjustment) {
+            return Err(ShaumError::invalid_config(format!(
+                "Adjustment {} outside [-30, 30]", adjustment
+            )));
+        }
+
+        if let Some(mandatory) = self.disabled_rules.iter().find(|t| is_mandatory_rule(t)) {
+            return Err(mandatory_rule_error(mandatory));
+        }
+
+        let ruleset_version = self.ruleset_version.unwrap_or(RULESET_VERSION);
+        if !(1..=RULESET_VERSION).contains(&ruleset_version) {
+            return Err(ShaumError::invalid_config(format!(
+                "Ruleset version {} outside [1, {}]", ruleset_version, RULESET_VERSION
+            )));
+        }
 
         Ok(RuleContext {
             adjustment: adjustment.clamp(-30, 30),
             madhab: self.madhab.unwrap_or_default(),
             daud_strategy: self.daud_strategy.unwrap_or_default(),
             custom_rules: self.custom_rules,
+            disabled_rules: self.disabled_rules,
             strict: self.strict_mode,
+            connected_fast: self.connected_fast,
+            nisf_shaban: self.nisf_shaban,
+            is_pilgrim: self.is_pilgrim,
+            personal: self.personal,
+            schedule: self.schedule,
+            vows: self.vows,
+            registry: self.registry,
+            ruleset_version,
             visibility_criteria: self.visibility_criteria.unwrap_or_default(),
             sunset_provider: self.sunset_provider.unwrap_or_else(|| Box::new(DefaultSunsetProvider)),
+            calendar: self.calendar.unwrap_or_else(default_calendar),
+            cache: Some(self.cache.unwrap_or_else(default_cache)),
+            #[cfg(feature = "astronomy")]
+            prayer_cache: self.prayer_cache,
+            moon_provider: self.moon_provider,
         })
     }
 }
@@ -280,6 +1228,18 @@ impl RuleContextBuilder {
 /// * `datetime`: The checking time in UTC.
 /// * `context`: The rule configuration.
 /// * `coords`: Optional coordinates for sunset-aware calculation.
+///
+/// # Boundaries
+/// Instants are compared at whole-second precision so results are
+/// reproducible whatever precision the caller's clock or the sunset provider
+/// has:
+/// * With `coords`, the new day starts **at** sunset: an instant in the same
+///   second as the computed sunset, or later, is the next day; the second
+///   before it is still the civil day.
+/// * Without `coords`, the day ends at UTC midnight: 23:59:59.999999999 and a
+///   leap second (23:59:60) belong to the ending day, 00:00:00 to the next.
+/// * Local days ([`analyze_today`], [`LocalDay`]) follow the offset passed
+///   in; across a DST transition pass the offset in effect at the instant.
 pub fn analyze(
     datetime: DateTime<Utc>,
     context: &RuleContext,
@@ -288,146 +1248,165 @@ pub fn analyze(
     let mut traces: SmallVec<[RuleTrace; 2]> = SmallVec::new();
     
     // 1. Determine Effective Date (Maghrib Logic)
-    let mut effective_date = datetime.date_naive();
-    
-    if let Some(c) = coords {
-        // Use provider from context
-        let sunset = context.sunset_provider.get_sunset(effective_date, c)?;
-        if datetime > sunset {
-            effective_date = effective_date.succ_opt()
-                .ok_or_else(|| ShaumError::date_out_of_range(effective_date))?;
-            traces.push(RuleTrace::new(TraceCode::Debug, TracePayload::PostMaghribOffset));
-        }
-    }
-
-    // 2. Strict Mode Check (handled by to_hijri implicitly returning error if out of range)
-    // But we check bounds here too to be nice?
-    // Actually to_hijri will error out.
-    // If strict is OFF, we might want to handle error "gracefully" if it's purely a range issue?
-    // But the prompt says "NO PANICS: Remove unwrap... Use Result propagation".
-    // So if to_hijri fails, analyze fails.
-    
-    let year = effective_date.year();
-    if (year < HIJRI_MIN_YEAR || year > HIJRI_MAX_YEAR) && context.strict {
-         return Err(ShaumError::date_out_of_range(effective_date));
-    }
-
-    // This propagates error.
-    let h_date = to_hijri(effective_date, context.adjustment)?;
-    
-    let h_month = h_date.month();
-    let h_day = h_date.day();
-    let h_year = h_date.year() as usize;
-    let weekday = effective_date.weekday();
+    let effective_date = resolve_effective_date(datetime, datetime.date_naive(), context, coords, &mut traces)?;
 
-    let mut types: SmallVec<[FastingType; 2]> = SmallVec::new();
-    let mut status = FastingStatus::Mubah;
+    evaluate(datetime, effective_date, context, traces)
+}
 
-    // --- Rules ---
+/// Advances `civil_date` by one day when `datetime` is at or past its sunset
+/// at `coords` (see the boundary rules on [`analyze`]).
+///
+/// If the sunset provider reports `CapabilityUnavailable`, the day boundary
+/// is midnight and a `MidnightBoundary` trace records it; strict mode
+/// propagates the error instead.
+fn resolve_effective_date(
+    datetime: DateTime<Utc>,
+    civil_date: NaiveDate,
+    context: &RuleContext,
+    coords: Option<GeoCoordinate>,
+    traces: &mut SmallVec<[RuleTrace; 2]>,
+) -> Result<NaiveDate, ShaumError> {
+    let Some(c) = coords else { return Ok(civil_date) };
+    let c = context.check_coords(c)?;
 
-    // Haram Priority
-    if h_month == MONTH_SHAWWAL && h_day == 1 {
-        types.push(FastingType::EID_AL_FITR);
-        traces.push(RuleTrace::simple(TraceCode::EidAlFitr));
-        return Ok(FastingAnalysis::with_traces(datetime, FastingStatus::Haram, types, (h_year, h_month, h_day), traces));
+    // Use provider from context
+    let sunset = match context.sunset_provider.get_sunset(civil_date, c) {
+        Ok(sunset) => sunset,
+        Err(ShaumError::CapabilityUnavailable { .. }) if !context.strict => {
+            traces.push(RuleTrace::new(TraceCode::Debug, TracePayload::MidnightBoundary));
+            return Ok(civil_date);
+        }
+        Err(e) => return Err(e),
+    };
+    if to_second(datetime) >= to_second(sunset) {
+        traces.push(RuleTrace::new(TraceCode::Debug, TracePayload::PostMaghribOffset));
+        return civil_date.succ_opt()
+            .ok_or_else(|| ShaumError::date_out_of_range(civil_date));
     }
+    Ok(civil_date)
+}
 
-    if h_month == MONTH_DHUL_HIJJAH && h_day == 10 {
-        types.push(FastingType::EID_AL_ADHA);
-        traces.push(RuleTrace::simple(TraceCode::EidAlAdha));
-        return Ok(FastingAnalysis::with_traces(datetime, FastingStatus::Haram, types, (h_year, h_month, h_day), traces));
-    }
+/// Truncates to the whole second, folding a leap second (nanos >= 1e9) into
+/// 23:59:59 of the same day.
+fn to_second(instant: DateTime<Utc>) -> DateTime<Utc> {
+    instant.with_nanosecond(0).unwrap_or(instant)
+}
 
-    if h_month == MONTH_DHUL_HIJJAH && (11..=13).contains(&h_day) {
-        types.push(FastingType::TASHRIQ);
-        traces.push(RuleTrace::simple(TraceCode::Tashriq));
-        return Ok(FastingAnalysis::with_traces(datetime, FastingStatus::Haram, types, (h_year, h_month, h_day), traces));
+/// Runs the rule set against an already-resolved effective date.
+fn evaluate(
+    datetime: DateTime<Utc>,
+    effective_date: NaiveDate,
+    context: &RuleContext,
+    traces: SmallVec<[RuleTrace; 2]>,
+) -> Result<FastingAnalysis, ShaumError> {
+    // 2. Strict mode: range gate for every backend. Conversion errors always propagate.
+    let year = effective_date.year();
+    if !(HIJRI_MIN_YEAR..=HIJRI_MAX_YEAR).contains(&year) && context.strict {
+         return Err(ShaumError::date_out_of_range(effective_date));
     }
 
-    // Wajib
-    if h_month == MONTH_RAMADHAN {
-        types.push(FastingType::RAMADHAN);
-        traces.push(RuleTrace::simple(TraceCode::Ramadhan));
-        status = FastingStatus::Wajib;
-    }
+    // This propagates error.
+    let h_date = context.hijri(effective_date)?;
+    evaluate_hijri(datetime, effective_date, h_date, context, traces)
+}
 
-    // Sunnah Muakkadah
-    if h_month == MONTH_DHUL_HIJJAH && h_day == DAY_ARAFAH {
-        types.push(FastingType::ARAFAH);
-        traces.push(RuleTrace::simple(TraceCode::Arafah));
-        if !status.is_wajib() { status = FastingStatus::SunnahMuakkadah; }
+/// Runs the rule set for `effective_date`, whose Hijri date is already known.
+/// Records a saturated adjustment first, so every entry point traces it.
+fn evaluate_hijri(
+    datetime: DateTime<Utc>,
+    effective_date: NaiveDate,
+    h_date: HijriYmd,
+    context: &RuleContext,
+    mut traces: SmallVec<[RuleTrace; 2]>,
+) -> Result<FastingAnalysis, ShaumError> {
+    let requested = context.effective_adjustment()?;
+    let applied = context.applied_adjustment(effective_date)?;
+    if applied != requested {
+        traces.push(RuleTrace::new(TraceCode::Debug, TracePayload::SaturatedAdjustment { requested, applied }));
     }
-
-    if h_month == MONTH_MUHARRAM && h_day == DAY_ASHURA {
-        types.push(FastingType::ASHURA);
-        traces.push(RuleTrace::simple(TraceCode::Ashura));
-        if !status.is_wajib() { status = FastingStatus::SunnahMuakkadah; }
+    if let Some(provider) = &context.moon_provider
+        && let Some(source) = provider.month_source(h_date.year, h_date.month)
+    {
+        let offset = provider.month_offset(h_date.year, h_date.month).unwrap_or(0);
+        let adjustment = month_offset_to_adjustment(offset);
+        traces.push(RuleTrace::new(TraceCode::MonthStart, TracePayload::MonthSource { source, adjustment }));
     }
+    let input = RuleInput { date: effective_date, hijri: h_date, context };
+    context.registry().evaluate(datetime, input, traces)
+}
 
-    // Sunnah
-    if h_month == MONTH_MUHARRAM && h_day == DAY_TASUA {
-        types.push(FastingType::TASUA);
-        traces.push(RuleTrace::simple(TraceCode::Tasua));
-        if !status.is_wajib() && status != FastingStatus::SunnahMuakkadah { 
-            status = FastingStatus::Sunnah; 
-        }
-    }
+/// How [`analyze_today`] determines the user's civil date.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LocalDay {
+    /// Fixed UTC offset; the day changes at local midnight.
+    Offset(FixedOffset),
+    /// Observer location; the offset is approximated from longitude (15° per hour)
+    /// and the day changes at the computed Maghrib.
+    Coords(GeoCoordinate),
+}
 
-    if (13..=15).contains(&h_day) {
-        types.push(FastingType::AYYAMUL_BIDH);
-        traces.push(RuleTrace::simple(TraceCode::AyyamulBidh));
-        if !status.is_wajib() && status < FastingStatus::Sunnah {
-            status = FastingStatus::Sunnah;
-        }
-    }
+impl From<FixedOffset> for LocalDay {
+    fn from(offset: FixedOffset) -> Self { Self::Offset(offset) }
+}
 
-    match weekday {
-        Weekday::Mon => {
-            types.push(FastingType::MONDAY);
-            traces.push(RuleTrace::simple(TraceCode::Monday));
-            if !status.is_wajib() && status < FastingStatus::Sunnah { status = FastingStatus::Sunnah; }
-        },
-        Weekday::Thu => {
-            types.push(FastingType::THURSDAY);
-            traces.push(RuleTrace::simple(TraceCode::Thursday));
-            if !status.is_wajib() && status < FastingStatus::Sunnah { status = FastingStatus::Sunnah; }
-        },
-        _ => {}
-    }
+impl From<GeoCoordinate> for LocalDay {
+    fn from(coords: GeoCoordinate) -> Self { Self::Coords(coords) }
+}
 
-    if h_month == MONTH_SHAWWAL && h_day > 1 {
-        types.push(FastingType::SHAWWAL);
-        traces.push(RuleTrace::simple(TraceCode::Shawwal));
-        if !status.is_wajib() && status < FastingStatus::Sunnah { status = FastingStatus::Sunnah; }
+impl LocalDay {
+    /// Returns the civil (calendar) date at `now` for this locality.
+    pub fn civil_date(&self, now: DateTime<Utc>) -> NaiveDate {
+        let offset_minutes = match self {
+            Self::Offset(offset) => i64::from(offset.local_minus_utc()) / 60,
+            Self::Coords(c) => (c.lng / 15.0 * 60.0).round() as i64,
+        };
+        (now + Duration::minutes(offset_minutes)).date_naive()
     }
+}
 
-    // Makruh Checks
-    if status == FastingStatus::Mubah {
-        match context.madhab {
-            Madhab::Shafi | Madhab::Hanafi | Madhab::Maliki | Madhab::Hanbali => {
-                if weekday == Weekday::Fri {
-                    types.push(FastingType::FRIDAY_EXCLUSIVE);
-                    traces.push(RuleTrace::simple(TraceCode::FridaySingledOut));
-                    status = FastingStatus::Makruh;
-                } else if weekday == Weekday::Sat {
-                    types.push(FastingType::SATURDAY_EXCLUSIVE);
-                    traces.push(RuleTrace::simple(TraceCode::SaturdaySingledOut));
-                    status = FastingStatus::Makruh;
-                }
-            }
-        }
-    }
+/// Analyzes "today" as seen by the user rather than by UTC.
+///
+/// Using `Utc::now().date_naive()` directly reports yesterday's date for users
+/// east of Greenwich until UTC midnight (e.g. until 07:00 in Jakarta). This resolves
+/// the local civil date first and, when coordinates are given, applies the Maghrib
+/// boundary against that local date.
+///
+/// # Example
+/// ```rust
+/// use chrono::FixedOffset;
+/// use shaum_rules::{analyze_today, RuleContext};
+///
+/// let wib = FixedOffset::east_opt(7 * 3600).unwrap();
+/// let analysis = analyze_today(wib, &RuleContext::default()).unwrap();
+/// println!("{}", analysis.explain());
+/// ```
+pub fn analyze_today(local: impl Into<LocalDay>, context: &RuleContext) -> Result<FastingAnalysis, ShaumError> {
+    analyze_local(Utc::now(), local, context)
+}
 
-    // Custom rules evaluation
-    for rule in &context.custom_rules {
-        if let Some((custom_status, custom_type)) = rule.evaluate(effective_date, h_year, h_month, h_day) {
-            types.push(custom_type.clone());
-            traces.push(RuleTrace::new(TraceCode::Custom, TracePayload::CustomReason(custom_type.to_string())));
-            if custom_status > status { status = custom_status; }
-        }
-    }
+/// Like [`analyze_today`], but for an explicit instant instead of the system clock.
+pub fn analyze_local(
+    now: DateTime<Utc>,
+    local: impl Into<LocalDay>,
+    context: &RuleContext,
+) -> Result<FastingAnalysis, ShaumError> {
+    let mut traces: SmallVec<[RuleTrace; 2]> = SmallVec::new();
+    let effective_date = local_effective_date(now, local.into(), context, &mut traces)?;
+    evaluate(now, effective_date, context, traces)
+}
 
-    Ok(FastingAnalysis::with_traces(datetime, status, types, (h_year, h_month, h_day), traces))
+/// Local civil date at `now`, advanced past Maghrib when `local` has coordinates.
+pub(crate) fn local_effective_date(
+    now: DateTime<Utc>,
+    local: LocalDay,
+    context: &RuleContext,
+    traces: &mut SmallVec<[RuleTrace; 2]>,
+) -> Result<NaiveDate, ShaumError> {
+    let coords = match local {
+        LocalDay::Offset(_) => None,
+        LocalDay::Coords(c) => Some(c),
+    };
+    resolve_effective_date(now, local.civil_date(now), context, coords, traces)
 }
 
 /// Checks fasting status for a given date.
@@ -439,3 +1418,54 @@ pub fn check(g_date: NaiveDate, context: &RuleContext) -> Result<FastingAnalysis
     analyze(dt, context, None)
 }
 
+/// Whether fasting on `date` is permitted at all, i.e. [`check`] would not
+/// return Haram. Only the Haram rules run (Eid al-Fitr, Eid al-Adha, Tashriq,
+/// [`RuleContext::personal`], and custom rules such as personal haid ranges),
+/// so this is much cheaper than a full analysis; use it for form validation
+/// and scheduling.
+///
+/// Returns `false` if the date cannot be converted, since it cannot be
+/// confirmed as fastable.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_rules::{is_fastable, RuleContext};
+///
+/// let ctx = RuleContext::default();
+/// assert!(!is_fastable(NaiveDate::from_ymd_opt(2024, 4, 10).unwrap(), &ctx)); // Eid al-Fitr
+/// assert!(is_fastable(NaiveDate::from_ymd_opt(2024, 4, 11).unwrap(), &ctx));
+/// ```
+pub fn is_fastable(date: NaiveDate, context: &RuleContext) -> bool {
+    matches!(is_haram_day(date, context), Ok(false))
+}
+
+/// Haram-only evaluation behind [`is_fastable`]; conversion errors propagate.
+pub(crate) fn is_haram_day(date: NaiveDate, context: &RuleContext) -> Result<bool, ShaumError> {
+    if !(HIJRI_MIN_YEAR..=HIJRI_MAX_YEAR).contains(&date.year()) && context.strict {
+        return Err(ShaumError::date_out_of_range(date));
+    }
+    let h = context.hijri(date)?;
+    // Replaced rules may forbid other days: run them all.
+    if context.registry.is_some() {
+        return Ok(check_with_hijri(date, h, context)?.primary_status.is_haram());
+    }
+    let enabled = |t: &FastingType| context.is_rule_enabled(t);
+    let builtin = context.personal.is_exempt()
+        || (is_eid_al_fitr(h.month, h.day) && enabled(&FastingType::EID_AL_FITR))
+        || (is_eid_al_adha(h.month, h.day) && enabled(&FastingType::EID_AL_ADHA))
+        || (is_tashriq(h.month, h.day) && enabled(&FastingType::TASHRIQ));
+    Ok(builtin || context.custom_rules.iter().any(|rule| {
+        rule.evaluate(date, h.year, h.month, h.day).is_some_and(|(status, _)| status == FastingStatus::Haram)
+    }))
+}
+
+/// [`check`] with the Hijri date of `g_date` already resolved under `context`,
+/// for callers that walk consecutive days.
+pub(crate) fn check_with_hijri(g_date: NaiveDate, hijri: HijriYmd, context: &RuleContext) -> Result<FastingAnalysis, ShaumError> {
+    if !(HIJRI_MIN_YEAR..=HIJRI_MAX_YEAR).contains(&g_date.year()) && context.strict {
+        return Err(ShaumError::date_out_of_range(g_date));
+    }
+    let dt = Utc.from_utc_datetime(&g_date.and_hms_opt(12, 0, 0).unwrap());
+    evaluate_hijri(dt, g_date, hijri, context, SmallVec::new())
+}
+
@@ -0,0 +1,81 @@
+//! The user's own fasting plan.
+//!
+//! Some rulings depend on what the person fasts around a day: Friday and
+//! Saturday are only disliked when singled out, so fasting Thursday or
+//! Saturday alongside Friday lifts the Makruh flag. A [`PersonalSchedule`]
+//! on the [`RuleContext`](crate::RuleContext) tells the engine which days
+//! are planned (or were actually fasted).
+
+use std::collections::BTreeSet;
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Days the user plans to fast or has fasted.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use shaum_rules::{check, PersonalSchedule, RuleContext};
+///
+/// // Friday 5 January 2024, with Thursday 4 January also fasted.
+/// let friday = NaiveDate::from_ymd_opt(2024, 1, 5).unwrap();
+/// let alone = check(friday, &RuleContext::default()).unwrap();
+/// assert!(alone.primary_status.is_makruh());
+///
+/// let schedule: PersonalSchedule = [friday.pred_opt().unwrap()].into_iter().collect();
+/// let paired = check(friday, &RuleContext::new().with_schedule(schedule)).unwrap();
+/// assert!(!paired.primary_status.is_makruh());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersonalSchedule {
+    fasts: BTreeSet<NaiveDate>,
+}
+
+impl PersonalSchedule {
+    pub fn new() -> Self { Self::default() }
+
+    /// Adds a planned or completed fast.
+    pub fn with_fast(mut self, date: NaiveDate) -> Self {
+        self.fasts.insert(date);
+        self
+    }
+
+    pub fn insert(&mut self, date: NaiveDate) -> bool {
+        self.fasts.insert(date)
+    }
+
+    pub fn remove(&mut self, date: NaiveDate) -> bool {
+        self.fasts.remove(&date)
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.fasts.contains(&date)
+    }
+
+    /// Whether the day before or after `date` is fasted.
+    pub fn has_adjacent(&self, date: NaiveDate) -> bool {
+        [-1, 1].into_iter()
+            .filter_map(|offset| date.checked_add_signed(Duration::days(offset)))
+            .any(|d| self.contains(d))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.fasts.iter().copied()
+    }
+
+    pub fn len(&self) -> usize { self.fasts.len() }
+
+    pub fn is_empty(&self) -> bool { self.fasts.is_empty() }
+}
+
+impl FromIterator<NaiveDate> for PersonalSchedule {
+    fn from_iter<I: IntoIterator<Item = NaiveDate>>(iter: I) -> Self {
+        Self { fasts: iter.into_iter().collect() }
+    }
+}
+
+impl Extend<NaiveDate> for PersonalSchedule {
+    fn extend<I: IntoIterator<Item = NaiveDate>>(&mut self, iter: I) {
+        self.fasts.extend(iter);
+    }
+}
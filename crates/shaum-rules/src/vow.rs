@@ -0,0 +1,171 @@
+//! Vowed fasts (nadhar).
+//!
+//! A fast the user has vowed becomes obligatory on the days it names: a
+//! [`Vows`] registry on the [`RuleContext`](crate::RuleContext) makes the
+//! engine report those days as Wajib with a
+//! [`FastingType::NADHAR`](shaum_types::FastingType::NADHAR) reason.
+//! A vow cannot be kept on a Haram day (Eid, Tashriq, haid), which stays
+//! Haram; [`Vows::conflicts`] lists such days ahead of time. Ramadhan is
+//! already obligatory and never counts towards a vow.
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use shaum_calendar::HijriYmd;
+use shaum_types::{FastingAnalysis, ShaumError};
+
+use crate::constants::MONTH_RAMADHAN;
+use crate::rules::{check, RuleContext};
+
+/// The days a vow names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VowDay {
+    Date(NaiveDate),
+    /// Every such weekday.
+    Weekday(Weekday),
+    /// The `nth` (1-5) such weekday of each Hijri month, e.g. the first Thursday.
+    HijriMonthWeekday { nth: u8, weekday: Weekday },
+}
+
+impl VowDay {
+    fn matches(&self, date: NaiveDate, hijri: HijriYmd) -> bool {
+        match *self {
+            Self::Date(d) => d == date,
+            Self::Weekday(w) => date.weekday() == w,
+            Self::HijriMonthWeekday { nth, weekday } => {
+                date.weekday() == weekday && (hijri.day - 1) / 7 + 1 == usize::from(nth)
+            }
+        }
+    }
+}
+
+/// One vow, optionally bounded in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Vow {
+    pub day: VowDay,
+    /// First day the vow applies (inclusive).
+    #[serde(default)]
+    pub from: Option<NaiveDate>,
+    /// Last day the vow applies (inclusive).
+    #[serde(default)]
+    pub until: Option<NaiveDate>,
+}
+
+impl Vow {
+    pub fn new(day: VowDay) -> Self { Self { day, from: None, until: None } }
+
+    /// A fast vowed for one date.
+    pub fn on(date: NaiveDate) -> Self { Self::new(VowDay::Date(date)) }
+
+    /// A fast vowed for every `weekday`.
+    pub fn every(weekday: Weekday) -> Self { Self::new(VowDay::Weekday(weekday)) }
+
+    /// A fast vowed for the `nth` `weekday` of each Hijri month.
+    pub fn nth_of_hijri_month(nth: u8, weekday: Weekday) -> Self {
+        Self::new(VowDay::HijriMonthWeekday { nth, weekday })
+    }
+
+    pub fn from(mut self, date: NaiveDate) -> Self {
+        self.from = Some(date);
+        self
+    }
+
+    pub fn until(mut self, date: NaiveDate) -> Self {
+        self.until = Some(date);
+        self
+    }
+
+    /// Whether the vow names `date` (Hijri date `hijri`), Ramadhan excluded.
+    pub fn applies(&self, date: NaiveDate, hijri: HijriYmd) -> bool {
+        hijri.month != MONTH_RAMADHAN
+            && self.from.is_none_or(|from| date >= from)
+            && self.until.is_none_or(|until| date <= until)
+            && self.day.matches(date, hijri)
+    }
+}
+
+/// A vowed day that falls on a Haram day.
+#[derive(Debug, Clone, Serialize)]
+pub struct VowConflict {
+    pub date: NaiveDate,
+    pub vow: Vow,
+    /// The analysis of the day, whose reasons say why it is Haram.
+    pub analysis: FastingAnalysis,
+}
+
+/// The user's registered vows.
+///
+/// ```rust
+/// use chrono::{NaiveDate, Weekday};
+/// use shaum_rules::vow::{Vow, Vows};
+/// use shaum_rules::{check, RuleContext};
+/// use shaum_types::{FastingStatus, FastingType};
+///
+/// // Vowed: the first Thursday of each Hijri month.
+/// let vows = Vows::new().with_vow(Vow::nth_of_hijri_month(1, Weekday::Thu));
+/// let ctx = RuleContext::new().with_vows(vows);
+/// // Thursday 11 July 2024 is 5 Muharram 1446.
+/// let analysis = check(NaiveDate::from_ymd_opt(2024, 7, 11).unwrap(), &ctx).unwrap();
+/// assert_eq!(analysis.primary_status, FastingStatus::Wajib);
+/// assert!(analysis.has_reason(&FastingType::NADHAR));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Vows {
+    vows: Vec<Vow>,
+}
+
+impl Vows {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn with_vow(mut self, vow: Vow) -> Self {
+        self.vows.push(vow);
+        self
+    }
+
+    pub fn push(&mut self, vow: Vow) {
+        self.vows.push(vow);
+    }
+
+    /// The first vow naming `date`, if any.
+    pub fn find(&self, date: NaiveDate, hijri: HijriYmd) -> Option<&Vow> {
+        self.vows.iter().find(|v| v.applies(date, hijri))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Vow> { self.vows.iter() }
+
+    pub fn len(&self) -> usize { self.vows.len() }
+
+    pub fn is_empty(&self) -> bool { self.vows.is_empty() }
+
+    /// Vowed days from `start` to `end` (inclusive) on which fasting is Haram
+    /// under `context`, e.g. a vowed Thursday that is Eid al-Adha.
+    ///
+    /// # Errors
+    /// Any error analysing a day.
+    pub fn conflicts(&self, start: NaiveDate, end: NaiveDate, context: &RuleContext) -> Result<Vec<VowConflict>, ShaumError> {
+        let mut conflicts = Vec::new();
+        if self.is_empty() {
+            return Ok(conflicts);
+        }
+        for date in start.iter_days().take_while(|d| *d <= end) {
+            let Some(&vow) = self.find(date, context.hijri(date)?) else { continue };
+            let analysis = check(date, context)?;
+            if analysis.primary_status.is_haram() {
+                conflicts.push(VowConflict { date, vow, analysis });
+            }
+        }
+        Ok(conflicts)
+    }
+}
+
+impl FromIterator<Vow> for Vows {
+    fn from_iter<I: IntoIterator<Item = Vow>>(iter: I) -> Self {
+        Self { vows: iter.into_iter().collect() }
+    }
+}
+
+impl Extend<Vow> for Vows {
+    fn extend<I: IntoIterator<Item = Vow>>(&mut self, iter: I) {
+        self.vows.extend(iter);
+    }
+}
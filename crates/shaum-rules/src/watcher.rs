@@ -0,0 +1,186 @@
+//! Month-transition events for long-running services.
+//!
+//! A [`MonthWatcher`] tracks the local Hijri date under a [`RuleContext`] and
+//! reports when it enters a new month, when Ramadhan starts, and when Eid is
+//! confirmed, so bots and servers can react to events instead of diffing
+//! [`check`](crate::check) results every midnight. Sighting announcements
+//! arrive through the context's [`MoonProvider`](crate::MoonProvider): a
+//! provider that moves a month start is picked up on the next poll.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use shaum_calendar::HijriYmd;
+use shaum_types::{FastingType, ShaumError};
+use smallvec::SmallVec;
+
+use crate::constants::{MONTH_DHUL_HIJJAH, MONTH_RAMADHAN, MONTH_SHAWWAL};
+use crate::rules::{local_effective_date, LocalDay, RuleContext};
+
+/// Source of the current time.
+pub trait Clock: fmt::Debug + Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The system clock.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> { Utc::now() }
+}
+
+/// A clock that only moves when told to, for tests and replays.
+#[derive(Debug)]
+pub struct ManualClock(Mutex<DateTime<Utc>>);
+
+impl ManualClock {
+    pub fn new(now: DateTime<Utc>) -> Self { Self(Mutex::new(now)) }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = now;
+    }
+
+    pub fn advance(&self, by: Duration) {
+        let mut now = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        *now += by;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> { *self.0.lock().unwrap_or_else(|e| e.into_inner()) }
+}
+
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    fn now(&self) -> DateTime<Utc> { (**self).now() }
+}
+
+/// A change observed by [`MonthWatcher::poll`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MonthEvent {
+    /// The local date moved into another Hijri month; `date` is its first
+    /// day seen by the watcher.
+    NewHijriMonth { date: NaiveDate, hijri: HijriYmd },
+    /// The local date is in Ramadhan `year` after being outside it.
+    RamadhanStarted { date: NaiveDate, year: usize },
+    /// `date` (today or tomorrow) is Eid al-Fitr or Eid al-Adha under the
+    /// current context. Emitted again if an announcement moves Eid.
+    EidConfirmed { date: NaiveDate, eid: FastingType },
+}
+
+type Subscriber = Box<dyn Fn(&MonthEvent) + Send + Sync>;
+
+/// Emits [`MonthEvent`]s as time passes or month starts are revised.
+///
+/// The first poll records the current month without reporting it.
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use chrono::{Duration, FixedOffset, TimeZone, Utc};
+/// use shaum_rules::{ManualClock, MonthEvent, MonthWatcher, RuleContext};
+///
+/// // Noon in Jakarta on 10 March 2024, the last day of Sha'ban 1445.
+/// let clock = Arc::new(ManualClock::new(Utc.with_ymd_and_hms(2024, 3, 10, 5, 0, 0).unwrap()));
+/// let wib = FixedOffset::east_opt(7 * 3600).unwrap();
+/// let mut watcher = MonthWatcher::new(RuleContext::default(), wib).with_clock(clock.clone());
+/// assert!(watcher.poll().unwrap().is_empty());
+///
+/// clock.advance(Duration::days(1));
+/// let events = watcher.poll().unwrap();
+/// assert!(events.iter().any(|e| matches!(e, MonthEvent::RamadhanStarted { year: 1445, .. })));
+/// ```
+pub struct MonthWatcher {
+    context: RuleContext,
+    local: LocalDay,
+    clock: Arc<dyn Clock>,
+    subscribers: Vec<Subscriber>,
+    last: Option<HijriYmd>,
+    announced_eids: BTreeSet<NaiveDate>,
+}
+
+impl fmt::Debug for MonthWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MonthWatcher")
+            .field("local", &self.local)
+            .field("clock", &self.clock)
+            .field("subscribers", &self.subscribers.len())
+            .field("last", &self.last)
+            .finish()
+    }
+}
+
+impl MonthWatcher {
+    /// Watches `local` (a UTC offset, or coordinates for a Maghrib boundary)
+    /// under `context`, using the system clock.
+    pub fn new(context: RuleContext, local: impl Into<LocalDay>) -> Self {
+        Self {
+            context,
+            local: local.into(),
+            clock: Arc::new(SystemClock),
+            subscribers: Vec::new(),
+            last: None,
+            announced_eids: BTreeSet::new(),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Calls `callback` for every event emitted by [`Self::poll`].
+    pub fn subscribe(&mut self, callback: impl Fn(&MonthEvent) + Send + Sync + 'static) {
+        self.subscribers.push(Box::new(callback));
+    }
+
+    pub fn context(&self) -> &RuleContext { &self.context }
+
+    /// For applying a sighting announcement (e.g. a new
+    /// [`MonthSightings`](crate::MonthSightings)) before the next poll.
+    pub fn context_mut(&mut self) -> &mut RuleContext { &mut self.context }
+
+    /// Hijri date seen by the last poll.
+    pub fn last_seen(&self) -> Option<HijriYmd> { self.last }
+
+    /// Re-reads the clock and context, notifies subscribers and returns the
+    /// new events. Cheap enough to call every few minutes.
+    pub fn poll(&mut self) -> Result<Vec<MonthEvent>, ShaumError> {
+        let now = self.clock.now();
+        let today = local_effective_date(now, self.local, &self.context, &mut SmallVec::new())?;
+        let hijri = self.context.hijri(today)?;
+        let mut events = Vec::new();
+
+        if let Some(last) = self.last.filter(|l| (l.year, l.month) != (hijri.year, hijri.month)) {
+            events.push(MonthEvent::NewHijriMonth { date: today, hijri });
+            if hijri.month == MONTH_RAMADHAN && (last.year, last.month) != (hijri.year, MONTH_RAMADHAN) {
+                events.push(MonthEvent::RamadhanStarted { date: today, year: hijri.year });
+            }
+        }
+        self.last = Some(hijri);
+
+        self.announced_eids.retain(|d| *d >= today);
+        let tomorrow = today.succ_opt().ok_or_else(|| ShaumError::date_out_of_range(today))?;
+        for (date, h) in [(today, hijri), (tomorrow, self.context.hijri(tomorrow)?)] {
+            if let Some(eid) = eid_of(h) && self.announced_eids.insert(date) {
+                events.push(MonthEvent::EidConfirmed { date, eid });
+            }
+        }
+
+        for event in &events {
+            for subscriber in &self.subscribers {
+                subscriber(event);
+            }
+        }
+        Ok(events)
+    }
+}
+
+fn eid_of(h: HijriYmd) -> Option<FastingType> {
+    match (h.month, h.day) {
+        (MONTH_SHAWWAL, 1) => Some(FastingType::EID_AL_FITR),
+        (MONTH_DHUL_HIJJAH, 10) => Some(FastingType::EID_AL_ADHA),
+        _ => None,
+    }
+}
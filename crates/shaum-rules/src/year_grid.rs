@@ -0,0 +1,82 @@
+//! Dual-calendar grid for a whole Gregorian year.
+//!
+//! Every day of the year with its Hijri date and analysis, for printable
+//! calendars. Consecutive days share conversions: the Hijri date is advanced
+//! arithmetically and only looked up again around month ends (day 29 onwards),
+//! so slow backends such as the astronomical calendars convert a few dozen
+//! times per year instead of 365.
+
+use chrono::{Datelike, NaiveDate};
+use shaum_calendar::HijriYmd;
+use shaum_types::{FastingAnalysis, ShaumError};
+
+use crate::rules::{check_with_hijri, RuleContext};
+
+/// One Gregorian day with its Hijri date.
+#[derive(Debug, Clone)]
+pub struct GridDay {
+    pub date: NaiveDate,
+    pub hijri: HijriYmd,
+    pub analysis: FastingAnalysis,
+}
+
+/// All days of Gregorian `year`.
+///
+/// ```rust
+/// use shaum_rules::{RuleContext, YearGrid};
+///
+/// let grid = YearGrid::new(2024, &RuleContext::default()).unwrap();
+/// assert_eq!(grid.days.len(), 366);
+/// let march = grid.month(3);
+/// assert_eq!(march.len(), 31);
+/// // Hijri months beginning in 2024, e.g. Ramadhan 1445 on 11 March.
+/// assert!(grid.hijri_month_starts().any(|d| d.date.to_string() == "2024-03-11" && d.hijri.month == 9));
+/// ```
+#[derive(Debug, Clone)]
+pub struct YearGrid {
+    pub year: i32,
+    pub days: Vec<GridDay>,
+}
+
+impl YearGrid {
+    /// Builds the grid for `year` under `context`.
+    ///
+    /// # Errors
+    /// `ValidationError` for an unrepresentable year; otherwise the first
+    /// conversion or analysis error.
+    pub fn new(year: i32, context: &RuleContext) -> Result<Self, ShaumError> {
+        let invalid = || ShaumError::ValidationError(format!("Invalid year {}", year));
+        let first = NaiveDate::from_ymd_opt(year, 1, 1).ok_or_else(invalid)?;
+
+        let mut days = Vec::with_capacity(366);
+        let mut previous: Option<HijriYmd> = None;
+        for date in first.iter_days().take_while(|d| d.year() == year) {
+            let hijri = match previous {
+                // Months have at least 29 days, so only later days need a lookup.
+                Some(h) if h.day < 29 => HijriYmd::new(h.year, h.month, h.day + 1),
+                _ => context.hijri(date)?,
+            };
+            let analysis = check_with_hijri(date, hijri, context)?;
+            days.push(GridDay { date, hijri, analysis });
+            previous = Some(hijri);
+        }
+        Ok(Self { year, days })
+    }
+
+    /// Days of Gregorian `month` (1-12); empty for other values.
+    pub fn month(&self, month: u32) -> &[GridDay] {
+        let start = self.days.iter().position(|d| d.date.month() == month);
+        match start {
+            Some(start) => {
+                let len = self.days[start..].iter().take_while(|d| d.date.month() == month).count();
+                &self.days[start..start + len]
+            }
+            None => &[],
+        }
+    }
+
+    /// Days on which a Hijri month begins.
+    pub fn hijri_month_starts(&self) -> impl Iterator<Item = &GridDay> {
+        self.days.iter().filter(|d| d.hijri.day == 1)
+    }
+}
@@ -5,7 +5,8 @@ use smallvec::SmallVec;
 use std::borrow::Cow;
 use std::fmt;
 
-use super::status::FastingStatus;
+use super::status::{Emphasis, FastingStatus};
+use crate::Madhab;
 
 /// Extensible fasting type/reason.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -18,43 +19,144 @@ impl FastingType {
 
     // Standard fasting types
     pub const RAMADHAN: Self = Self(Cow::Borrowed("Ramadhan"));
+    /// A Ramadhan day on a journey: the fast stays obligatory, but the
+    /// traveler may break it and make it up later.
+    pub const SAFAR: Self = Self(Cow::Borrowed("Safar"));
+    /// Illness: excuses a Ramadhan day (to be made up), and optionally
+    /// discourages voluntary fasts.
+    pub const MARADH: Self = Self(Cow::Borrowed("Maradh"));
+    /// Permanent inability to fast (old age, chronic illness): a Ramadhan day
+    /// is excused with fidyah instead of qadha.
+    pub const AJZ: Self = Self(Cow::Borrowed("Ajz"));
+    /// A vowed fast: obligatory once the day it was vowed for arrives.
+    pub const NADHAR: Self = Self(Cow::Borrowed("Nadhar"));
+    /// Pregnancy: a Ramadhan day is excused, with a madhab-specific [`Compensation`].
+    pub const HAML: Self = Self(Cow::Borrowed("Haml"));
+    /// Nursing: a Ramadhan day is excused, with a madhab-specific [`Compensation`].
+    pub const RADHA: Self = Self(Cow::Borrowed("Radha"));
     pub const ARAFAH: Self = Self(Cow::Borrowed("Arafah"));
     pub const TASUA: Self = Self(Cow::Borrowed("Tasua"));
     pub const ASHURA: Self = Self(Cow::Borrowed("Ashura"));
     pub const AYYAMUL_BIDH: Self = Self(Cow::Borrowed("AyyamulBidh"));
+    /// 15 Sha'ban fasted for its own sake. Opt-in: the narrations singling it
+    /// out are weak, and many scholars count it only as a white day.
+    pub const NISF_SHABAN: Self = Self(Cow::Borrowed("NisfShaban"));
     pub const MONDAY: Self = Self(Cow::Borrowed("Monday"));
     pub const THURSDAY: Self = Self(Cow::Borrowed("Thursday"));
     pub const SHAWWAL: Self = Self(Cow::Borrowed("Shawwal"));
+    /// 1-8 Dhu al-Hijjah, the days before Arafah.
+    pub const DHUL_HIJJAH_TEN: Self = Self(Cow::Borrowed("DhulHijjahTen"));
+    /// 9 Dhu al-Hijjah for a pilgrim standing at Arafah, who should not fast.
+    pub const ARAFAH_PILGRIM: Self = Self(Cow::Borrowed("ArafahPilgrim"));
     pub const DAUD: Self = Self(Cow::Borrowed("Daud"));
     pub const EID_AL_FITR: Self = Self(Cow::Borrowed("EidAlFitr"));
     pub const EID_AL_ADHA: Self = Self(Cow::Borrowed("EidAlAdha"));
     pub const TASHRIQ: Self = Self(Cow::Borrowed("Tashriq"));
+    /// Menstruation: fasting is forbidden on any day.
+    pub const HAID: Self = Self(Cow::Borrowed("Haid"));
+    /// Postnatal bleeding: fasting is forbidden on any day.
+    pub const NIFAS: Self = Self(Cow::Borrowed("Nifas"));
+    /// Yawm al-Shakk: 30 Sha'ban, when the Ramadhan crescent was not confirmed.
+    pub const DAY_OF_DOUBT: Self = Self(Cow::Borrowed("DayOfDoubt"));
+    /// 16-29 Sha'ban under the Shafi'i school, without a habit or connected fast.
+    pub const LATE_SHABAN: Self = Self(Cow::Borrowed("LateShaban"));
+    /// One of the last two days before Ramadhan, without a habit or connected fast.
+    pub const PRE_RAMADHAN: Self = Self(Cow::Borrowed("PreRamadhan"));
     pub const FRIDAY_EXCLUSIVE: Self = Self(Cow::Borrowed("FridayExclusive"));
     pub const SATURDAY_EXCLUSIVE: Self = Self(Cow::Borrowed("SaturdayExclusive"));
 
     // Legacy constructors
     #[allow(non_snake_case)] pub fn Ramadhan() -> Self { Self::RAMADHAN }
+    #[allow(non_snake_case)] pub fn Safar() -> Self { Self::SAFAR }
+    #[allow(non_snake_case)] pub fn Maradh() -> Self { Self::MARADH }
+    #[allow(non_snake_case)] pub fn Ajz() -> Self { Self::AJZ }
+    #[allow(non_snake_case)] pub fn Nadhar() -> Self { Self::NADHAR }
+    #[allow(non_snake_case)] pub fn Haml() -> Self { Self::HAML }
+    #[allow(non_snake_case)] pub fn Radha() -> Self { Self::RADHA }
     #[allow(non_snake_case)] pub fn Arafah() -> Self { Self::ARAFAH }
     #[allow(non_snake_case)] pub fn Tasua() -> Self { Self::TASUA }
     #[allow(non_snake_case)] pub fn Ashura() -> Self { Self::ASHURA }
     #[allow(non_snake_case)] pub fn AyyamulBidh() -> Self { Self::AYYAMUL_BIDH }
+    #[allow(non_snake_case)] pub fn NisfShaban() -> Self { Self::NISF_SHABAN }
     #[allow(non_snake_case)] pub fn Monday() -> Self { Self::MONDAY }
     #[allow(non_snake_case)] pub fn Thursday() -> Self { Self::THURSDAY }
     #[allow(non_snake_case)] pub fn Shawwal() -> Self { Self::SHAWWAL }
+    #[allow(non_snake_case)] pub fn DhulHijjahTen() -> Self { Self::DHUL_HIJJAH_TEN }
+    #[allow(non_snake_case)] pub fn ArafahPilgrim() -> Self { Self::ARAFAH_PILGRIM }
     #[allow(non_snake_case)] pub fn Daud() -> Self { Self::DAUD }
     #[allow(non_snake_case)] pub fn EidAlFitr() -> Self { Self::EID_AL_FITR }
     #[allow(non_snake_case)] pub fn EidAlAdha() -> Self { Self::EID_AL_ADHA }
     #[allow(non_snake_case)] pub fn Tashriq() -> Self { Self::TASHRIQ }
+    #[allow(non_snake_case)] pub fn Haid() -> Self { Self::HAID }
+    #[allow(non_snake_case)] pub fn Nifas() -> Self { Self::NIFAS }
+    #[allow(non_snake_case)] pub fn DayOfDoubt() -> Self { Self::DAY_OF_DOUBT }
+    #[allow(non_snake_case)] pub fn LateShaban() -> Self { Self::LATE_SHABAN }
+    #[allow(non_snake_case)] pub fn PreRamadhan() -> Self { Self::PRE_RAMADHAN }
     #[allow(non_snake_case)] pub fn FridayExclusive() -> Self { Self::FRIDAY_EXCLUSIVE }
     #[allow(non_snake_case)] pub fn SaturdayExclusive() -> Self { Self::SATURDAY_EXCLUSIVE }
 
+    /// Standard types in canonical order (the order rules are evaluated in).
+    pub const STANDARD: [Self; 28] = [
+        Self::HAID, Self::NIFAS, Self::EID_AL_FITR, Self::EID_AL_ADHA, Self::TASHRIQ, Self::DAY_OF_DOUBT, Self::LATE_SHABAN, Self::PRE_RAMADHAN,
+        Self::RAMADHAN, Self::SAFAR, Self::AJZ, Self::HAML, Self::RADHA, Self::MARADH, Self::NADHAR, Self::ARAFAH, Self::ASHURA,
+        Self::TASUA, Self::AYYAMUL_BIDH, Self::NISF_SHABAN,
+        Self::MONDAY, Self::THURSDAY, Self::SHAWWAL, Self::DHUL_HIJJAH_TEN, Self::ARAFAH_PILGRIM, Self::DAUD,
+        Self::FRIDAY_EXCLUSIVE, Self::SATURDAY_EXCLUSIVE,
+    ];
+
+    /// Every built-in type, in [`FastingType::STANDARD`] order, for bindings,
+    /// exporters and localization tables that must cover each rule.
+    ///
+    /// ```rust
+    /// use shaum_types::FastingType;
+    ///
+    /// assert!(FastingType::builtin().contains(&FastingType::RAMADHAN));
+    /// assert!(FastingType::builtin().iter().all(|t| t.implied_status().is_some()));
+    /// ```
+    pub const fn builtin() -> &'static [FastingType] {
+        &Self::STANDARD
+    }
+
+    /// Standard type named `name` (e.g. `"SaturdayExclusive"`), or `None`.
+    pub fn standard(name: &str) -> Option<Self> {
+        Self::STANDARD.iter().find(|t| t.0 == name).cloned()
+    }
+
+    /// Position in [`FastingType::STANDARD`], or `None` for custom types.
+    pub fn canonical_index(&self) -> Option<usize> {
+        Self::STANDARD.iter().position(|t| t == self)
+    }
+
+    /// Status a standard type implies on its own, or `None` for custom types.
+    pub fn implied_status(&self) -> Option<FastingStatus> {
+        match self.0.as_ref() {
+            "EidAlFitr" | "EidAlAdha" | "Tashriq" | "Haid" | "Nifas" => Some(FastingStatus::Haram),
+            "Ramadhan" | "Nadhar" | "Safar" => Some(FastingStatus::Wajib),
+            "Arafah" | "Ashura" => Some(FastingStatus::SunnahMuakkadah),
+            "Tasua" | "AyyamulBidh" | "NisfShaban" | "Monday" | "Thursday" | "Shawwal" | "DhulHijjahTen" | "Daud" => Some(FastingStatus::Sunnah),
+            "DayOfDoubt" | "LateShaban" | "PreRamadhan" | "ArafahPilgrim" | "FridayExclusive" | "SaturdayExclusive" => Some(FastingStatus::Makruh),
+            "Maradh" | "Ajz" | "Haml" | "Radha" => Some(FastingStatus::Mubah),
+            _ => None,
+        }
+    }
+
+    /// Emphasis of a standard Sunnah type, or `None` for other and custom types.
+    pub fn emphasis(&self) -> Option<Emphasis> {
+        match self.0.as_ref() {
+            "Arafah" | "Ashura" => Some(Emphasis::Muakkadah),
+            "AyyamulBidh" | "Monday" | "Thursday" | "Shawwal" | "DhulHijjahTen" | "Daud" => Some(Emphasis::Mustahabb),
+            "Tasua" | "NisfShaban" => Some(Emphasis::GhairMuakkadah),
+            _ => None,
+        }
+    }
+
     pub fn is_haram_type(&self) -> bool {
-        matches!(self.0.as_ref(), "EidAlFitr" | "EidAlAdha" | "Tashriq")
+        matches!(self.0.as_ref(), "EidAlFitr" | "EidAlAdha" | "Tashriq" | "Haid" | "Nifas")
     }
     
     pub fn is_sunnah_type(&self) -> bool {
-        matches!(self.0.as_ref(), "Arafah" | "Tasua" | "Ashura" | "AyyamulBidh" | 
-                 "Monday" | "Thursday" | "Shawwal" | "Daud")
+        matches!(self.0.as_ref(), "Arafah" | "Tasua" | "Ashura" | "AyyamulBidh" | "NisfShaban" |
+                 "Monday" | "Thursday" | "Shawwal" | "DhulHijjahTen" | "Daud")
     }
 }
 
@@ -63,24 +165,123 @@ impl fmt::Display for FastingType {
 }
 
 /// Machine-readable trace codes for rules.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Serializes as the stable snake_case string from [`TraceCode::code`]
+/// (e.g. `"eid_al_fitr"`); deserialization also accepts the legacy variant names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TraceCode {
     EidAlFitr, EidAlAdha, Tashriq, FridaySingledOut, SaturdaySingledOut,
     Ramadhan, Arafah, Tasua, Ashura, AyyamulBidh,
     Monday, Thursday, Shawwal, Daud,
-    Custom, Debug,
+    Custom, Debug, MonthStart, DayOfDoubt, LateShaban, DhulHijjahTen, NisfShaban, PreRamadhan,
+    ArafahPilgrim, Haid, Nifas, Safar, Maradh, Haml, Radha, Ajz, Nadhar,
+}
+
+impl TraceCode {
+    /// Every code, for building exhaustive mappings in other languages.
+    pub const ALL: [TraceCode; 31] = [
+        Self::EidAlFitr, Self::EidAlAdha, Self::Tashriq, Self::FridaySingledOut, Self::SaturdaySingledOut,
+        Self::Ramadhan, Self::Arafah, Self::Tasua, Self::Ashura, Self::AyyamulBidh,
+        Self::Monday, Self::Thursday, Self::Shawwal, Self::Daud,
+        Self::Custom, Self::Debug, Self::MonthStart, Self::DayOfDoubt, Self::LateShaban, Self::DhulHijjahTen, Self::NisfShaban, Self::PreRamadhan,
+        Self::ArafahPilgrim, Self::Haid, Self::Nifas, Self::Safar, Self::Maradh,
+        Self::Haml, Self::Radha, Self::Ajz, Self::Nadhar,
+    ];
+
+    /// Every code, as a slice of [`TraceCode::ALL`].
+    pub const fn all() -> &'static [TraceCode] {
+        &Self::ALL
+    }
+
+    /// Stable snake_case identifier. Never changes for an existing variant.
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::EidAlFitr => "eid_al_fitr",
+            Self::EidAlAdha => "eid_al_adha",
+            Self::Tashriq => "tashriq",
+            Self::FridaySingledOut => "friday_singled_out",
+            Self::SaturdaySingledOut => "saturday_singled_out",
+            Self::Ramadhan => "ramadhan",
+            Self::Arafah => "arafah",
+            Self::Tasua => "tasua",
+            Self::Ashura => "ashura",
+            Self::AyyamulBidh => "ayyamul_bidh",
+            Self::Monday => "monday",
+            Self::Thursday => "thursday",
+            Self::Shawwal => "shawwal",
+            Self::Daud => "daud",
+            Self::Custom => "custom",
+            Self::Debug => "debug",
+            Self::MonthStart => "month_start",
+            Self::DayOfDoubt => "day_of_doubt",
+            Self::LateShaban => "late_shaban",
+            Self::DhulHijjahTen => "dhul_hijjah_ten",
+            Self::NisfShaban => "nisf_shaban",
+            Self::PreRamadhan => "pre_ramadhan",
+            Self::ArafahPilgrim => "arafah_pilgrim",
+            Self::Haid => "haid",
+            Self::Nifas => "nifas",
+            Self::Safar => "safar",
+            Self::Maradh => "maradh",
+            Self::Haml => "haml",
+            Self::Radha => "radha",
+            Self::Ajz => "ajz",
+            Self::Nadhar => "nadhar",
+        }
+    }
+
+    /// Parses a code produced by [`TraceCode::code`].
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.code() == code)
+    }
 }
 
 impl fmt::Display for TraceCode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{:?}", self) }
 }
 
+impl Serialize for TraceCode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for TraceCode {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Self::from_code(&s)
+            .or_else(|| Self::ALL.into_iter().find(|c| format!("{:?}", c) == s))
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown trace code: {}", s)))
+    }
+}
+
+/// Source that determined the start of a Hijri month.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MonthSource {
+    /// Astronomical prediction.
+    Calculation,
+    /// Official announcement (sighting authority).
+    Announcement,
+}
+
+impl fmt::Display for MonthSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{:?}", self) }
+}
+
 /// Payload for deferred trace formatting.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TracePayload {
     None,
     PostMaghribOffset,
+    /// Sunset was unavailable in this build; the day boundary is midnight.
+    MidnightBoundary,
+    /// The adjustment would have moved the date out of the supported range
+    /// and was reduced to reach its edge.
+    SaturatedAdjustment { requested: i64, applied: i64 },
     CustomReason(String),
+    MonthSource { source: MonthSource, adjustment: i64 },
+    /// The rule fired under this madhab's position, which differs between schools.
+    MadhabRuling(Madhab),
 }
 
 impl fmt::Display for TracePayload {
@@ -88,13 +289,21 @@ impl fmt::Display for TracePayload {
         match self {
             Self::None => Ok(()),
             Self::PostMaghribOffset => write!(f, "Post-Maghrib: Effective date +1"),
+            Self::MidnightBoundary => write!(f, "Sunset unavailable: day boundary at midnight"),
+            Self::SaturatedAdjustment { requested, applied } => {
+                write!(f, "Adjustment {:+} saturated to {:+} at the supported range edge", requested, applied)
+            }
             Self::CustomReason(s) => write!(f, "{}", s),
+            Self::MonthSource { source, adjustment } => {
+                write!(f, "Month start from {} (adjustment {:+})", source, adjustment)
+            }
+            Self::MadhabRuling(madhab) => write!(f, "{:?} ruling", madhab),
         }
     }
 }
 
 /// Rule trace event for explainability.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct RuleTrace {
     pub code: TraceCode,
     pub payload: TracePayload,
@@ -115,6 +324,23 @@ fn get_hijri_month_name(month: usize) -> &'static str {
     }
 }
 
+/// What is owed for an excused Ramadhan day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Compensation {
+    /// Make the day up later.
+    Qadha,
+    /// Make the day up and feed a poor person for it.
+    QadhaAndFidyah,
+    /// Feed a poor person for the day, without making it up.
+    Fidyah,
+}
+
+impl Compensation {
+    pub const fn requires_qadha(&self) -> bool { matches!(self, Self::Qadha | Self::QadhaAndFidyah) }
+    pub const fn requires_fidyah(&self) -> bool { matches!(self, Self::QadhaAndFidyah | Self::Fidyah) }
+}
+
 /// Fasting analysis result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FastingAnalysis {
@@ -123,8 +349,17 @@ pub struct FastingAnalysis {
     pub hijri_year: usize,
     pub hijri_month: usize,
     pub hijri_day: usize,
-    reasons: SmallVec<[FastingType; 2]>,
-    traces: SmallVec<[RuleTrace; 2]>,
+    pub(crate) reasons: SmallVec<[FastingType; 2]>,
+    pub(crate) traces: SmallVec<[RuleTrace; 2]>,
+    /// Per-reason emphasis, parallel to `reasons`; empty means derived from the types.
+    #[serde(default, skip_serializing_if = "SmallVec::is_empty")]
+    pub(crate) emphasis: SmallVec<[Option<Emphasis>; 2]>,
+    /// Per-reason status, parallel to `reasons`; empty means derived from the types.
+    #[serde(default, skip_serializing_if = "SmallVec::is_empty")]
+    pub(crate) statuses: SmallVec<[FastingStatus; 2]>,
+    /// Owed for an excused Ramadhan day; `None` when nothing is owed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) compensation: Option<Compensation>,
 }
 
 impl FastingAnalysis {
@@ -137,7 +372,7 @@ impl FastingAnalysis {
         Self {
             date, primary_status: status, reasons: types,
             hijri_year: hijri.0, hijri_month: hijri.1, hijri_day: hijri.2,
-            traces: SmallVec::new(),
+            traces: SmallVec::new(), emphasis: SmallVec::new(), statuses: SmallVec::new(), compensation: None,
         }
     }
 
@@ -151,19 +386,118 @@ impl FastingAnalysis {
         Self {
             date, primary_status: status, reasons: types,
             hijri_year: hijri.0, hijri_month: hijri.1, hijri_day: hijri.2,
-            traces,
+            traces, emphasis: SmallVec::new(), statuses: SmallVec::new(), compensation: None,
         }
     }
 
+    /// Sets the emphasis of each reason, in [`Self::reasons`] order. Needed for
+    /// custom reasons, whose emphasis cannot be derived from the type.
+    pub fn with_emphasis(mut self, emphasis: SmallVec<[Option<Emphasis>; 2]>) -> Self {
+        debug_assert_eq!(emphasis.len(), self.reasons.len());
+        self.emphasis = emphasis;
+        self
+    }
+
+    /// Sets the status each reason implies on its own, in [`Self::reasons`]
+    /// order. Needed for custom reasons and for standard reasons whose status
+    /// differs from [`FastingType::implied_status`].
+    pub fn with_reason_statuses(mut self, statuses: SmallVec<[FastingStatus; 2]>) -> Self {
+        debug_assert_eq!(statuses.len(), self.reasons.len());
+        self.statuses = statuses;
+        self
+    }
+
+    /// Reasons without duplicates, most severe first (Haram, Wajib, Sunnah
+    /// Muakkadah, Sunnah, Makruh, Mubah); ties follow [`FastingType::STANDARD`],
+    /// then custom rules in registration order. Analyses produced by the rules
+    /// engine always uphold this order.
     pub fn reasons(&self) -> impl Iterator<Item = &FastingType> { self.reasons.iter() }
     pub fn has_reason(&self, ftype: &FastingType) -> bool { self.reasons.contains(ftype) }
+
+    /// Sets what is owed for an excused Ramadhan day.
+    pub fn with_compensation(mut self, compensation: Compensation) -> Self {
+        self.compensation = Some(compensation);
+        self
+    }
+
+    /// What is owed for this day, if it is an excused Ramadhan day.
+    pub fn compensation(&self) -> Option<Compensation> { self.compensation }
+
+    /// Reasons paired with the status each implies on its own. They combine
+    /// to [`Self::primary_status`], except that a Makruh reason discouraging
+    /// an otherwise Sunnah fast (the pilgrim at Arafah, illness) prevails.
+    ///
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use smallvec::smallvec;
+    /// use shaum_types::{FastingAnalysis, FastingStatus, FastingType};
+    ///
+    /// let a = FastingAnalysis::new(
+    ///     Utc.with_ymd_and_hms(2024, 2, 12, 0, 0, 0).unwrap(),
+    ///     FastingStatus::Makruh,
+    ///     smallvec![FastingType::DAY_OF_DOUBT, FastingType::MONDAY],
+    ///     (1445, 8, 30),
+    /// );
+    /// let breakdown: Vec<_> = a.reason_statuses().collect();
+    /// assert_eq!(breakdown, [
+    ///     (FastingType::DAY_OF_DOUBT, FastingStatus::Makruh),
+    ///     (FastingType::MONDAY, FastingStatus::Sunnah),
+    /// ]);
+    /// ```
+    pub fn reason_statuses(&self) -> impl Iterator<Item = (FastingType, FastingStatus)> + '_ {
+        self.reasons.iter().enumerate().map(|(i, r)| {
+            let status = self.statuses.get(i).copied()
+                .unwrap_or_else(|| r.implied_status().unwrap_or(FastingStatus::Mubah));
+            (r.clone(), status)
+        })
+    }
+
+    /// Reasons paired with their Sunnah emphasis (`None` for non-Sunnah reasons).
+    pub fn reasons_with_emphasis(&self) -> impl Iterator<Item = (&FastingType, Option<Emphasis>)> {
+        self.reasons.iter().enumerate().map(|(i, r)| {
+            (r, self.emphasis.get(i).copied().unwrap_or_else(|| r.emphasis()))
+        })
+    }
+
+    /// The reason carrying the strongest emphasis (the first one on ties).
+    ///
+    /// On a Monday that is also Arafah this is Arafah with [`Emphasis::Muakkadah`].
+    pub fn emphasized_reason(&self) -> Option<(&FastingType, Emphasis)> {
+        self.reasons_with_emphasis()
+            .filter_map(|(r, e)| Some((r, e?)))
+            .fold(None, |best, (r, e)| match best {
+                Some((_, b)) if b >= e => best,
+                _ => Some((r, e)),
+            })
+    }
     pub fn reason_count(&self) -> usize { self.reasons.len() }
 
     pub fn is_ramadhan(&self) -> bool { self.has_reason(&FastingType::RAMADHAN) }
     pub fn is_white_day(&self) -> bool { self.has_reason(&FastingType::AYYAMUL_BIDH) }
+    pub fn is_nisf_shaban(&self) -> bool { self.has_reason(&FastingType::NISF_SHABAN) }
     pub fn is_eid(&self) -> bool { self.has_reason(&FastingType::EID_AL_FITR) || self.has_reason(&FastingType::EID_AL_ADHA) }
     pub fn is_tashriq(&self) -> bool { self.has_reason(&FastingType::TASHRIQ) }
+    /// Menstruation or postnatal bleeding made the day Haram.
+    pub fn is_personally_exempt(&self) -> bool {
+        self.has_reason(&FastingType::HAID) || self.has_reason(&FastingType::NIFAS)
+    }
+    /// A Ramadhan day missed through [`Self::is_personally_exempt`] or
+    /// otherwise excused, to be made up later.
+    pub fn requires_qadha(&self) -> bool {
+        self.compensation.is_some_and(|c| c.requires_qadha())
+            || (self.is_ramadhan() && (self.is_personally_exempt() || self.has_reason(&FastingType::MARADH)))
+    }
+    /// An excused Ramadhan day for which fidyah is owed.
+    pub fn requires_fidyah(&self) -> bool { self.compensation.is_some_and(|c| c.requires_fidyah()) }
+    /// A Ramadhan day on a journey: fasting is still Wajib, but the traveler
+    /// may break it, owing qadha for the day if they do.
+    pub fn has_rukhsah(&self) -> bool { self.is_ramadhan() && self.has_reason(&FastingType::SAFAR) }
+    pub fn is_day_of_doubt(&self) -> bool { self.has_reason(&FastingType::DAY_OF_DOUBT) }
+    pub fn is_late_shaban(&self) -> bool { self.has_reason(&FastingType::LATE_SHABAN) }
+    pub fn is_pre_ramadhan(&self) -> bool { self.has_reason(&FastingType::PRE_RAMADHAN) }
     pub fn is_arafah(&self) -> bool { self.has_reason(&FastingType::ARAFAH) }
+    pub fn is_arafah_pilgrim(&self) -> bool { self.has_reason(&FastingType::ARAFAH_PILGRIM) }
+    pub fn is_dhul_hijjah_ten(&self) -> bool { self.has_reason(&FastingType::DHUL_HIJJAH_TEN) }
     pub fn is_ashura(&self) -> bool { self.has_reason(&FastingType::ASHURA) }
 
     pub fn explain(&self) -> String {
@@ -182,6 +516,52 @@ impl FastingAnalysis {
 
     pub fn traces(&self) -> impl Iterator<Item = &RuleTrace> { self.traces.iter() }
 
+    /// Stable, locale-independent single line for snapshot tests.
+    ///
+    /// Format: `<date RFC 3339> <hijri yyyy-mm-dd> <status> reasons=[..] traces=[..]`,
+    /// with status and reasons by their serialized names and traces by
+    /// [`TraceCode::code`] plus payload. Changes to this format are breaking changes.
+    ///
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use smallvec::smallvec;
+    /// use shaum_types::{FastingAnalysis, FastingStatus, FastingType};
+    ///
+    /// let a = FastingAnalysis::new(
+    ///     Utc.with_ymd_and_hms(2024, 3, 11, 0, 0, 0).unwrap(),
+    ///     FastingStatus::Wajib,
+    ///     smallvec![FastingType::RAMADHAN, FastingType::MONDAY],
+    ///     (1445, 9, 1),
+    /// );
+    /// assert_eq!(a.to_canonical_string(), "2024-03-11T00:00:00Z 1445-09-01 Wajib reasons=[Ramadhan,Monday] traces=[]");
+    /// ```
+    pub fn to_canonical_string(&self) -> String {
+        let reasons: Vec<&str> = self.reasons.iter().map(|r| r.0.as_ref()).collect();
+        let traces: Vec<String> = self.traces.iter()
+            .map(|t| match &t.payload {
+                TracePayload::None => t.code.code().to_string(),
+                TracePayload::PostMaghribOffset => format!("{}:post_maghrib", t.code.code()),
+                TracePayload::MidnightBoundary => format!("{}:midnight_boundary", t.code.code()),
+                TracePayload::SaturatedAdjustment { requested, applied } => {
+                    format!("{}:saturated_adjustment{:+}{:+}", t.code.code(), requested, applied)
+                }
+                TracePayload::CustomReason(reason) => format!("{}:{}", t.code.code(), reason),
+                TracePayload::MonthSource { source, adjustment } => {
+                    format!("{}:{}{:+}", t.code.code(), source.to_string().to_lowercase(), adjustment)
+                }
+                TracePayload::MadhabRuling(madhab) => format!("{}:{:?}", t.code.code(), madhab).to_lowercase(),
+            })
+            .collect();
+        format!(
+            "{} {:04}-{:02}-{:02} {:?} reasons=[{}] traces=[{}]",
+            self.date.format("%Y-%m-%dT%H:%M:%SZ"),
+            self.hijri_year, self.hijri_month, self.hijri_day,
+            self.primary_status,
+            reasons.join(","),
+            traces.join(","),
+        )
+    }
+
     #[allow(dead_code)]
     pub(crate) fn add_trace(&mut self, trace: RuleTrace) { self.traces.push(trace); }
 
@@ -193,14 +573,7 @@ impl FastingAnalysis {
             self.hijri_year
         );
 
-        let status_str = match self.primary_status {
-            FastingStatus::Haram => "Haram",
-            FastingStatus::Wajib => "Wajib",
-            FastingStatus::SunnahMuakkadah => "Sunnah Muakkadah",
-            FastingStatus::Sunnah => "Sunnah",
-            FastingStatus::Makruh => "Makruh",
-            FastingStatus::Mubah => "Mubah",
-        };
+        let status_str = self.primary_status.label();
 
         if self.reasons.is_empty() {
             format!("{} - {}", hijri_str, status_str)
@@ -0,0 +1,111 @@
+//! Compact binary encoding of [`FastingAnalysis`] batches.
+//!
+//! For caches and offline bundles that store many analyses: the encoding is
+//! postcard (varint integers, no field names), typically a fraction of the
+//! JSON size and much faster to parse. Every buffer starts with a magic tag
+//! and [`FORMAT_VERSION`]; decoding a buffer from another version fails
+//! rather than misreading it, so a cache can simply be rebuilt.
+//!
+//! ```rust
+//! use chrono::{TimeZone, Utc};
+//! use smallvec::smallvec;
+//! use shaum_types::{binary, FastingAnalysis, FastingStatus, FastingType};
+//!
+//! let date = Utc.with_ymd_and_hms(2024, 3, 11, 12, 0, 0).unwrap();
+//! let analysis = FastingAnalysis::new(date, FastingStatus::Wajib, smallvec![FastingType::RAMADHAN], (1445, 9, 1));
+//! let bytes = binary::encode(&[analysis]).unwrap();
+//! let decoded = binary::decode(&bytes).unwrap();
+//! assert_eq!(decoded[0].primary_status, FastingStatus::Wajib);
+//! assert_eq!(decoded[0].date, date);
+//! ```
+
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+
+use crate::{Compensation, Emphasis, FastingAnalysis, FastingStatus, FastingType, RuleTrace, ShaumError};
+
+/// Version of the binary layout; bumped whenever it changes.
+pub const FORMAT_VERSION: u8 = 1;
+
+const MAGIC: &[u8; 4] = b"SHMB";
+
+/// [`FastingAnalysis`] with every field always present: postcard cannot
+/// skip fields, so the JSON-oriented `skip_serializing_if` must not apply.
+#[derive(Serialize, Deserialize)]
+struct WireAnalysis {
+    seconds: i64,
+    nanos: u32,
+    status: FastingStatus,
+    hijri: (usize, usize, usize),
+    reasons: SmallVec<[FastingType; 2]>,
+    traces: SmallVec<[RuleTrace; 2]>,
+    emphasis: SmallVec<[Option<Emphasis>; 2]>,
+    statuses: SmallVec<[FastingStatus; 2]>,
+    compensation: Option<Compensation>,
+}
+
+impl From<&FastingAnalysis> for WireAnalysis {
+    fn from(a: &FastingAnalysis) -> Self {
+        Self {
+            seconds: a.date.timestamp(),
+            nanos: a.date.timestamp_subsec_nanos(),
+            status: a.primary_status,
+            hijri: (a.hijri_year, a.hijri_month, a.hijri_day),
+            reasons: a.reasons.clone(),
+            traces: a.traces.clone(),
+            emphasis: a.emphasis.clone(),
+            statuses: a.statuses.clone(),
+            compensation: a.compensation,
+        }
+    }
+}
+
+impl TryFrom<WireAnalysis> for FastingAnalysis {
+    type Error = ShaumError;
+
+    fn try_from(w: WireAnalysis) -> Result<Self, ShaumError> {
+        let date = DateTime::from_timestamp(w.seconds, w.nanos)
+            .ok_or_else(|| ShaumError::SerializationError(format!("Invalid timestamp {}.{:09}", w.seconds, w.nanos)))?;
+        let mut analysis = FastingAnalysis::with_traces(date, w.status, w.reasons, w.hijri, w.traces);
+        analysis.emphasis = w.emphasis;
+        analysis.statuses = w.statuses;
+        analysis.compensation = w.compensation;
+        Ok(analysis)
+    }
+}
+
+/// Encodes `analyses` as one versioned buffer.
+///
+/// # Errors
+/// `SerializationError` if encoding fails.
+pub fn encode(analyses: &[FastingAnalysis]) -> Result<Vec<u8>, ShaumError> {
+    let wire: Vec<WireAnalysis> = analyses.iter().map(WireAnalysis::from).collect();
+    let mut bytes = Vec::with_capacity(MAGIC.len() + 1 + analyses.len() * 32);
+    bytes.extend_from_slice(MAGIC);
+    bytes.push(FORMAT_VERSION);
+    postcard::to_extend(&wire, bytes).map_err(|e| ShaumError::SerializationError(e.to_string()))
+}
+
+/// Decodes a buffer written by [`encode`].
+///
+/// # Errors
+/// `SerializationError` for a buffer that is not a shaum batch, was written
+/// with another [`FORMAT_VERSION`], or is truncated or corrupt.
+pub fn decode(bytes: &[u8]) -> Result<Vec<FastingAnalysis>, ShaumError> {
+    let body = bytes.strip_prefix(MAGIC)
+        .ok_or_else(|| ShaumError::SerializationError("Not a shaum binary batch".to_string()))?;
+    let (&version, body) = body.split_first()
+        .ok_or_else(|| ShaumError::SerializationError("Missing format version".to_string()))?;
+    if version != FORMAT_VERSION {
+        return Err(ShaumError::SerializationError(format!(
+            "Unsupported binary format version {} (expected {})", version, FORMAT_VERSION
+        )));
+    }
+    let (wire, rest): (Vec<WireAnalysis>, _) = postcard::take_from_bytes(body)
+        .map_err(|e| ShaumError::SerializationError(e.to_string()))?;
+    if !rest.is_empty() {
+        return Err(ShaumError::SerializationError(format!("{} trailing bytes", rest.len())));
+    }
+    wire.into_iter().map(FastingAnalysis::try_from).collect()
+}
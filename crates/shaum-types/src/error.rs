@@ -3,6 +3,7 @@
 use chrono::NaiveDate;
 use serde::{Serialize, Deserialize};
 use std::fmt;
+use crate::ProviderStatus;
 
 /// Minimum Gregorian year for Hijri conversion.
 pub const HIJRI_MIN_YEAR: i32 = 1938;
@@ -10,7 +11,10 @@ pub const HIJRI_MIN_YEAR: i32 = 1938;
 pub const HIJRI_MAX_YEAR: i32 = 2076;
 
 /// Errors from shaum operations.
+///
+/// Variants may be added in minor releases, so matches need a wildcard arm.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum ShaumError {
     /// Date outside supported range (1938-2076).
     DateOutOfRange {
@@ -18,6 +22,14 @@ pub enum ShaumError {
         min: NaiveDate,
         max: NaiveDate,
     },
+
+    /// `date` is in range, but the adjustment moves it outside (strict mode).
+    AdjustmentOutOfRange {
+        date: NaiveDate,
+        adjustment: i64,
+        min: NaiveDate,
+        max: NaiveDate,
+    },
     
     /// Invalid configuration.
     InvalidConfiguration { reason: String },
@@ -45,6 +57,23 @@ pub enum ShaumError {
 
     /// Network error (async/remote operations).
     NetworkError(String),
+
+    /// Provider failed and has no cached data to fall back on.
+    ProviderUnavailable {
+        reason: String,
+        status: ProviderStatus,
+    },
+
+    /// Encoding or decoding a serialized form failed (e.g. a corrupt or
+    /// newer-version binary cache).
+    SerializationError(String),
+
+    /// The operation needs a cargo feature this build was compiled without
+    /// (e.g. sunset times without `astronomy`).
+    CapabilityUnavailable {
+        capability: String,
+        feature: String,
+    },
 }
 
 impl fmt::Display for ShaumError {
@@ -53,6 +82,9 @@ impl fmt::Display for ShaumError {
             Self::DateOutOfRange { date, min, max } => {
                 write!(f, "Date {} is out of supported range ({} to {})", date, min, max)
             }
+            Self::AdjustmentOutOfRange { date, adjustment, min, max } => {
+                write!(f, "Adjustment {:+} moves {} out of supported range ({} to {})", adjustment, date, min, max)
+            }
             Self::InvalidConfiguration { reason } => write!(f, "Invalid configuration: {}", reason),
             Self::AnalysisError(s) => write!(f, "Analysis failed: {}", s),
             Self::HijriConversionError(s) => write!(f, "Hijri conversion failed: {}", s),
@@ -62,6 +94,13 @@ impl fmt::Display for ShaumError {
             Self::AstronomyError(s) => write!(f, "Astronomy error: {}", s),
             Self::DatabaseError(s) => write!(f, "Database error: {}", s),
             Self::NetworkError(s) => write!(f, "Network error: {}", s),
+            Self::SerializationError(s) => write!(f, "Serialization error: {}", s),
+            Self::ProviderUnavailable { reason, status } => {
+                write!(f, "Provider unavailable: {} ({})", reason, status)
+            }
+            Self::CapabilityUnavailable { capability, feature } => {
+                write!(f, "{} is unavailable: built without the `{}` feature", capability, feature)
+            }
         }
     }
 }
@@ -71,17 +110,51 @@ impl std::error::Error for ShaumError {}
 impl ShaumError {
     /// Creates a `DateOutOfRange` error with standard bounds.
     pub fn date_out_of_range(date: NaiveDate) -> Self {
-        Self::DateOutOfRange {
-            date,
-            min: NaiveDate::from_ymd_opt(HIJRI_MIN_YEAR, 1, 1)
-                .unwrap_or_else(|| NaiveDate::from_ymd_opt(1938, 1, 1).unwrap()),
-            max: NaiveDate::from_ymd_opt(HIJRI_MAX_YEAR, 12, 31)
-                .unwrap_or_else(|| NaiveDate::from_ymd_opt(2076, 12, 31).unwrap()),
-        }
+        let (min, max) = supported_range();
+        Self::DateOutOfRange { date, min, max }
+    }
+
+    /// Creates an `AdjustmentOutOfRange` error with standard bounds.
+    pub fn adjustment_out_of_range(date: NaiveDate, adjustment: i64) -> Self {
+        let (min, max) = supported_range();
+        Self::AdjustmentOutOfRange { date, adjustment, min, max }
     }
     
+    /// Creates a `CapabilityUnavailable` error.
+    pub fn capability_unavailable(capability: impl Into<String>, feature: impl Into<String>) -> Self {
+        Self::CapabilityUnavailable { capability: capability.into(), feature: feature.into() }
+    }
+
     /// Creates an `InvalidConfiguration` error.
     pub fn invalid_config(reason: impl Into<String>) -> Self {
         Self::InvalidConfiguration { reason: reason.into() }
     }
+
+    /// Stable machine-readable code, for bindings and logs.
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::DateOutOfRange { .. } => "date_out_of_range",
+            Self::AdjustmentOutOfRange { .. } => "adjustment_out_of_range",
+            Self::InvalidConfiguration { .. } => "invalid_configuration",
+            Self::AnalysisError(_) => "analysis",
+            Self::HijriConversionError(_) => "hijri_conversion",
+            Self::SunsetCalculationError(_) => "sunset_calculation",
+            Self::MoonProviderError(_) => "moon_provider",
+            Self::ValidationError(_) => "validation",
+            Self::AstronomyError(_) => "astronomy",
+            Self::DatabaseError(_) => "database",
+            Self::NetworkError(_) => "network",
+            Self::SerializationError(_) => "serialization",
+            Self::ProviderUnavailable { .. } => "provider_unavailable",
+            Self::CapabilityUnavailable { .. } => "capability_unavailable",
+        }
+    }
+}
+
+/// First and last supported Gregorian dates.
+fn supported_range() -> (NaiveDate, NaiveDate) {
+    (
+        NaiveDate::from_ymd_opt(HIJRI_MIN_YEAR, 1, 1).unwrap_or_else(|| NaiveDate::from_ymd_opt(1938, 1, 1).unwrap()),
+        NaiveDate::from_ymd_opt(HIJRI_MAX_YEAR, 12, 31).unwrap_or_else(|| NaiveDate::from_ymd_opt(2076, 12, 31).unwrap()),
+    )
 }
@@ -0,0 +1,124 @@
+//! Typed results for undefined or degenerate prayer times and directions.
+
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use std::fmt;
+
+use crate::GeoCoordinate;
+
+/// Altitude (meters) above which an observer is treated as being in orbit (Kármán line).
+pub const ORBIT_ALTITUDE_M: f64 = 100_000.0;
+
+/// The Kaaba, Makkah.
+pub const KAABA: GeoCoordinate = GeoCoordinate { lat: 21.422_487, lng: 39.826_206, altitude: 0.0 };
+
+/// Why a prayer time or direction cannot be computed for an observer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum UndefinedReason {
+    /// The sun stays above the horizon all day (midnight sun): no Maghrib.
+    SunNeverSets,
+    /// The sun stays below the horizon all day (polar night): no Maghrib.
+    SunNeverRises,
+    /// The sun never sinks to the twilight angle (white nights): no Fajr/Isha.
+    TwilightNeverEnds,
+    /// Observer above [`ORBIT_ALTITUDE_M`] (spacecraft); local sun events are meaningless.
+    Orbit,
+    /// Observer at a geographic pole; compass bearings are degenerate.
+    Pole,
+    /// Observer at the Kaaba; every direction faces it.
+    AtKaaba,
+    /// Observer at the Kaaba's antipode; every direction is equally short.
+    Antipode,
+}
+
+impl fmt::Display for UndefinedReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::SunNeverSets => "sun does not set",
+            Self::SunNeverRises => "sun does not rise",
+            Self::TwilightNeverEnds => "twilight does not end",
+            Self::Orbit => "observer is in orbit",
+            Self::Pole => "observer is at a pole",
+            Self::AtKaaba => "observer is at the Kaaba",
+            Self::Antipode => "observer is at the Kaaba's antipode",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// How to substitute a time that is undefined at the observer's location.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum ExtremePolicy {
+    /// Report the time as undefined.
+    #[default]
+    Strict,
+    /// Use the time at the same longitude on the nearest latitude (at most
+    /// `max_lat` degrees) where it is defined (aqrab al-bilad).
+    NearestLatitude { max_lat: f64 },
+    /// Fajr and Isha one seventh of the night before sunrise / after sunset.
+    /// Only resolves [`UndefinedReason::TwilightNeverEnds`].
+    SeventhOfNight,
+    /// Use the times of a fixed place, e.g. the launch site for astronauts or Makkah.
+    ReferenceLocation(GeoCoordinate),
+}
+
+impl ExtremePolicy {
+    /// Commonly recommended policy for `reason`.
+    pub fn suggested_for(reason: UndefinedReason) -> Self {
+        match reason {
+            UndefinedReason::SunNeverSets | UndefinedReason::SunNeverRises => Self::NearestLatitude { max_lat: 65.0 },
+            UndefinedReason::TwilightNeverEnds => Self::SeventhOfNight,
+            UndefinedReason::Orbit | UndefinedReason::Pole | UndefinedReason::AtKaaba | UndefinedReason::Antipode => {
+                Self::ReferenceLocation(KAABA)
+            }
+        }
+    }
+}
+
+/// A single prayer time that may be undefined at the observer's location.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PrayerTime {
+    /// Astronomically defined at the observer's location.
+    Defined(DateTime<Utc>),
+    /// Undefined at the location; substituted according to `policy`.
+    Adjusted { time: DateTime<Utc>, policy: ExtremePolicy },
+    /// Undefined and not substituted.
+    Undefined { reason: UndefinedReason, suggested_policy: ExtremePolicy },
+}
+
+impl PrayerTime {
+    /// Undefined time with the policy suggested for `reason`.
+    pub fn undefined(reason: UndefinedReason) -> Self {
+        Self::Undefined { reason, suggested_policy: ExtremePolicy::suggested_for(reason) }
+    }
+
+    /// The usable instant, if any (defined or adjusted).
+    pub fn time(&self) -> Option<DateTime<Utc>> {
+        match *self {
+            Self::Defined(t) | Self::Adjusted { time: t, .. } => Some(t),
+            Self::Undefined { .. } => None,
+        }
+    }
+
+    pub fn is_defined(&self) -> bool { matches!(self, Self::Defined(_)) }
+
+    /// Applies `f` to the instant, keeping the variant.
+    pub fn map(self, f: impl FnOnce(DateTime<Utc>) -> DateTime<Utc>) -> Self {
+        match self {
+            Self::Defined(t) => Self::Defined(f(t)),
+            Self::Adjusted { time, policy } => Self::Adjusted { time: f(time), policy },
+            undefined => undefined,
+        }
+    }
+}
+
+/// Direction of the Kaaba, which may be undefined for the observer.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Qibla {
+    /// Initial great-circle bearing in degrees clockwise from true north (0..360).
+    Bearing(f64),
+    /// No meaningful bearing; with [`UndefinedReason::AtKaaba`],
+    /// [`UndefinedReason::Antipode`] or [`UndefinedReason::Orbit`] any direction
+    /// the worshipper is able to face is accepted.
+    Undefined(UndefinedReason),
+}
@@ -1,6 +1,9 @@
 //! Geographic and visibility types.
 
 use serde::{Serialize, Deserialize};
+use std::str::FromStr;
+
+use crate::ShaumError;
 
 /// Geographic coordinates (Latitude, Longitude) with optional Altitude.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -16,17 +19,30 @@ impl GeoCoordinate {
     ///
     /// Returns `Err(ShaumError::ValidationError)` if coordinates are out of range.
     pub fn new(lat: f64, lng: f64) -> Result<Self, crate::ShaumError> {
-        if !(-90.0..=90.0).contains(&lat) {
+        let coords = Self { lat, lng, altitude: 0.0 };
+        coords.validate()?;
+        Ok(coords)
+    }
+
+    /// Checks the ranges enforced by [`Self::new`], plus a finite altitude.
+    /// Useful for values built with [`Self::new_unchecked`] or deserialized.
+    pub fn validate(&self) -> Result<(), crate::ShaumError> {
+        if !(-90.0..=90.0).contains(&self.lat) {
+            return Err(crate::ShaumError::ValidationError(
+                format!("Latitude {} out of range [-90, 90]", self.lat)
+            ));
+        }
+        if !(-180.0..=180.0).contains(&self.lng) {
             return Err(crate::ShaumError::ValidationError(
-                format!("Latitude {} out of range [-90, 90]", lat)
+                format!("Longitude {} out of range [-180, 180]", self.lng)
             ));
         }
-        if !(-180.0..=180.0).contains(&lng) {
+        if !self.altitude.is_finite() {
             return Err(crate::ShaumError::ValidationError(
-                format!("Longitude {} out of range [-180, 180]", lng)
+                format!("Altitude {} is not finite", self.altitude)
             ));
         }
-        Ok(Self { lat, lng, altitude: 0.0 })
+        Ok(())
     }
 
     /// Creates a coordinate without validation. Use with trusted inputs only.
@@ -42,35 +58,331 @@ impl GeoCoordinate {
     }
 }
 
+/// Parses `"lat,lng"` (or whitespace-separated) decimal degrees, degrees/minutes/
+/// seconds with hemisphere letters, or an RFC 5870 geo URI (optional altitude).
+/// The result is validated like [`GeoCoordinate::new`].
+///
+/// ```rust
+/// use shaum_types::GeoCoordinate;
+///
+/// let a: GeoCoordinate = "-6.2088, 106.8456".parse().unwrap();
+/// let b: GeoCoordinate = r#"6°12'31.7"S 106°50'44.2"E"#.parse().unwrap();
+/// let c: GeoCoordinate = "geo:-6.2088,106.8456,8;u=35".parse().unwrap();
+/// assert!((a.lat - b.lat).abs() < 1e-3 && (a.lng - b.lng).abs() < 1e-3);
+/// assert_eq!(c.altitude, 8.0);
+/// assert!("95,10".parse::<GeoCoordinate>().is_err());
+/// ```
+impl FromStr for GeoCoordinate {
+    type Err = ShaumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let input = s.trim();
+        let invalid = |why: &str| ShaumError::ValidationError(format!("Invalid coordinate '{}': {}", input, why));
+
+        let coords = if input.get(..4).is_some_and(|scheme| scheme.eq_ignore_ascii_case("geo:")) {
+            parse_geo_uri(&input[4..]).map_err(invalid)?
+        } else if input.chars().all(|c| c.is_ascii_digit() || "+-.,".contains(c) || c.is_whitespace()) {
+            let parts: Vec<&str> = input.split(|c: char| c == ',' || c.is_whitespace()).filter(|p| !p.is_empty()).collect();
+            match parts[..] {
+                [lat, lng] => Self::new_unchecked(parse_number(lat).map_err(invalid)?, parse_number(lng).map_err(invalid)?),
+                _ => return Err(invalid("expected two numbers")),
+            }
+        } else {
+            parse_dms(input).map_err(invalid)?
+        };
+        coords.validate()?;
+        Ok(coords)
+    }
+}
+
+fn parse_number(s: &str) -> Result<f64, &'static str> {
+    s.trim().replace('−', "-").parse::<f64>().ok().filter(|v| v.is_finite()).ok_or("not a number")
+}
+
+/// `lat,lng[,alt][;param=value...]`; only the WGS 84 reference system is accepted.
+fn parse_geo_uri(body: &str) -> Result<GeoCoordinate, &'static str> {
+    let mut sections = body.split(';');
+    let values: Vec<&str> = sections.next().unwrap_or_default().split(',').collect();
+    for param in sections {
+        if let Some((key, value)) = param.split_once('=')
+            && key.trim().eq_ignore_ascii_case("crs")
+            && !value.trim().eq_ignore_ascii_case("wgs84")
+        {
+            return Err("unsupported coordinate reference system");
+        }
+    }
+    match values[..] {
+        [lat, lng] => Ok(GeoCoordinate::new_unchecked(parse_number(lat)?, parse_number(lng)?)),
+        [lat, lng, alt] => Ok(GeoCoordinate::new_unchecked(parse_number(lat)?, parse_number(lng)?).with_altitude(parse_number(alt)?)),
+        _ => Err("expected lat,lng or lat,lng,alt"),
+    }
+}
+
+/// One axis of a DMS string: up to three numbers and an optional hemisphere letter.
+#[derive(Default)]
+struct DmsPart {
+    numbers: Vec<f64>,
+    hemisphere: Option<char>,
+}
+
+impl DmsPart {
+    fn degrees(&self) -> Result<f64, &'static str> {
+        let (d, m, s) = match self.numbers[..] {
+            [d] => (d, 0.0, 0.0),
+            [d, m] => (d, m, 0.0),
+            [d, m, s] => (d, m, s),
+            _ => return Err("expected degrees, minutes and seconds"),
+        };
+        if !(0.0..60.0).contains(&m) || !(0.0..60.0).contains(&s) {
+            return Err("minutes and seconds must be in [0, 60)");
+        }
+        let value = d.abs() + m / 60.0 + s / 3600.0;
+        let negative = d.is_sign_negative() ^ matches!(self.hemisphere, Some('S' | 'W'));
+        Ok(if negative { -value } else { value })
+    }
+}
+
+/// Degrees/minutes/seconds with `°`, `'`, `"` (or their Unicode primes) and
+/// N/S/E/W before or after each axis, e.g. `6°12'31.7"S 106°50'44.2"E`.
+fn parse_dms(input: &str) -> Result<GeoCoordinate, &'static str> {
+    let mut parts: Vec<DmsPart> = Vec::new();
+    let mut current = DmsPart::default();
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c.to_ascii_uppercase() {
+            '0'..='9' | '.' | '-' | '+' | '−' => {
+                let mut number = c.to_string();
+                while let Some(&n) = chars.peek().filter(|n| n.is_ascii_digit() || **n == '.') {
+                    number.push(n);
+                    chars.next();
+                }
+                if current.numbers.len() == 3 {
+                    parts.push(std::mem::take(&mut current));
+                }
+                current.numbers.push(parse_number(&number)?);
+            }
+            h @ ('N' | 'S' | 'E' | 'W') => {
+                if current.numbers.is_empty() {
+                    if current.hemisphere.replace(h).is_some() {
+                        return Err("two hemisphere letters in a row");
+                    }
+                } else if current.hemisphere.is_none() {
+                    current.hemisphere = Some(h);
+                    parts.push(std::mem::take(&mut current));
+                } else {
+                    parts.push(std::mem::take(&mut current));
+                    current.hemisphere = Some(h);
+                }
+            }
+            ',' | ';' => {
+                if !current.numbers.is_empty() {
+                    parts.push(std::mem::take(&mut current));
+                }
+            }
+            '°' | 'º' | '\'' | '′' | '’' | '"' | '″' | '”' => {}
+            w if w.is_whitespace() => {}
+            _ => return Err("unexpected character"),
+        }
+    }
+    if !current.numbers.is_empty() || current.hemisphere.is_some() {
+        parts.push(current);
+    }
+
+    let [first, second] = &parts[..] else { return Err("expected a latitude and a longitude") };
+    let is_lng = |p: &DmsPart| matches!(p.hemisphere, Some('E' | 'W'));
+    let is_lat = |p: &DmsPart| matches!(p.hemisphere, Some('N' | 'S'));
+    let (lat, lng) = if is_lng(first) || is_lat(second) { (second, first) } else { (first, second) };
+    if is_lng(lat) || is_lat(lng) {
+        return Err("both axes have the same hemisphere");
+    }
+    Ok(GeoCoordinate::new_unchecked(lat.degrees()?, lng.degrees()?))
+}
+
+/// Instant at which crescent visibility is evaluated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum EvaluationTime {
+    /// Local sunset.
+    #[default]
+    Sunset,
+    /// Local moonset.
+    Moonset,
+    /// Yallop/Odeh "best time": sunset + 4/9 of the sunset-to-moonset lag.
+    BestTime,
+}
+
+/// Whose crescent sighting starts a month (matla').
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum SightingZone {
+    /// Ittihad al-matali': a sighting anywhere on Earth starts the month everywhere.
+    #[default]
+    Global,
+    /// Ikhtilaf al-matali': only a sighting from this location counts, so month
+    /// starts can differ between regions.
+    Local(GeoCoordinate),
+}
+
+/// Numeric thresholds of a crescent visibility criterion.
+///
+/// All thresholds must be met at the evaluation instant. The optional ones
+/// are only checked when set. Named presets cover the common criteria;
+/// individual values can be tuned for sensitivity analyses:
+///
+/// ```rust
+/// use shaum_types::CriterionParams;
+///
+/// let params = CriterionParams::by_name("istanbul_1978").unwrap();
+/// assert_eq!((params.min_altitude, params.min_elongation), (5.0, 8.0));
+/// let stricter = CriterionParams::mabims().with_min_lag_minutes(40.0);
+/// assert!(!stricter.is_met(3.5, 7.0, 30.0, 0.3));
+/// assert!(stricter.is_met(3.5, 7.0, 45.0, 0.3));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CriterionParams {
+    /// Minimum moon altitude above the horizon (degrees).
+    pub min_altitude: f64,
+    /// Minimum Sun-Moon elongation (degrees).
+    pub min_elongation: f64,
+    /// Minimum minutes between sunset and moonset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_lag_minutes: Option<f64>,
+    /// Minimum topocentric crescent width (arc minutes).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_crescent_width: Option<f64>,
+}
+
+impl Default for CriterionParams {
+    fn default() -> Self { Self::mabims() }
+}
+
+impl CriterionParams {
+    /// Names accepted by [`Self::by_name`].
+    pub const PRESETS: [&'static str; 5] = ["mabims", "mabims_1992", "istanbul_1978", "danjon", "babylonian"];
+
+    /// Altitude and elongation thresholds only.
+    pub const fn new(min_altitude: f64, min_elongation: f64) -> Self {
+        Self { min_altitude, min_elongation, min_lag_minutes: None, min_crescent_width: None }
+    }
+
+    /// MABIMS 2021 (Neo-MABIMS): altitude 3°, elongation 6.4°.
+    pub const fn mabims() -> Self { Self::new(3.0, 6.4) }
+
+    /// MABIMS 1992: altitude 2°, elongation 3°.
+    pub const fn mabims_1992() -> Self { Self::new(2.0, 3.0) }
+
+    /// Istanbul 1978: altitude 5°, elongation 8°.
+    pub const fn istanbul_1978() -> Self { Self::new(5.0, 8.0) }
+
+    /// Danjon limit: elongation 7°, moon above the horizon.
+    pub const fn danjon() -> Self { Self::new(0.0, 7.0) }
+
+    /// Babylonian rule: moonset at least 48 minutes after sunset.
+    pub const fn babylonian() -> Self {
+        Self { min_lag_minutes: Some(48.0), ..Self::new(0.0, 0.0) }
+    }
+
+    /// Preset by name (see [`Self::PRESETS`]); case-insensitive, `-` and `_` interchangeable.
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "mabims" | "neo_mabims" | "mabims_2021" => Some(Self::mabims()),
+            "mabims_1992" => Some(Self::mabims_1992()),
+            "istanbul_1978" | "istanbul" => Some(Self::istanbul_1978()),
+            "danjon" => Some(Self::danjon()),
+            "babylonian" => Some(Self::babylonian()),
+            _ => None,
+        }
+    }
+
+    pub fn with_min_altitude(mut self, degrees: f64) -> Self {
+        self.min_altitude = degrees;
+        self
+    }
+
+    pub fn with_min_elongation(mut self, degrees: f64) -> Self {
+        self.min_elongation = degrees;
+        self
+    }
+
+    pub fn with_min_lag_minutes(mut self, minutes: f64) -> Self {
+        self.min_lag_minutes = Some(minutes);
+        self
+    }
+
+    pub fn with_min_crescent_width(mut self, arc_minutes: f64) -> Self {
+        self.min_crescent_width = Some(arc_minutes);
+        self
+    }
+
+    /// Whether measured values satisfy every configured threshold.
+    pub fn is_met(&self, altitude: f64, elongation: f64, lag_minutes: f64, crescent_width: f64) -> bool {
+        altitude >= self.min_altitude
+            && elongation >= self.min_elongation
+            && self.min_lag_minutes.is_none_or(|min| lag_minutes >= min)
+            && self.min_crescent_width.is_none_or(|min| crescent_width >= min)
+    }
+}
+
 /// Configurable moon visibility criteria for hilal observation.
 ///
 /// Controls the thresholds used when determining if the crescent moon
 /// is visible. Default values match MABIMS (Indonesia/Malaysia/Brunei/Singapore).
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct VisibilityCriteria {
-    /// Minimum moon altitude above horizon (degrees). Default: 3.0
-    pub min_altitude: f64,
-    /// Minimum elongation between sun and moon (degrees). Default: 6.4
-    pub min_elongation: f64,
+    /// Thresholds; serialized inline (`min_altitude`, `min_elongation`, ...).
+    #[serde(flatten)]
+    pub params: CriterionParams,
+    /// When the thresholds are evaluated. Default: sunset
+    #[serde(default)]
+    pub evaluation_time: EvaluationTime,
 }
 
 impl Default for VisibilityCriteria {
     fn default() -> Self {
-        Self { min_altitude: 3.0, min_elongation: 6.4 }
+        Self::from_params(CriterionParams::mabims())
     }
 }
 
+impl From<CriterionParams> for VisibilityCriteria {
+    fn from(params: CriterionParams) -> Self { Self::from_params(params) }
+}
+
 impl VisibilityCriteria {
-    /// Creates new visibility criteria with custom thresholds.
+    /// Creates new visibility criteria with custom thresholds, evaluated at sunset.
     pub fn new(min_altitude: f64, min_elongation: f64) -> Self {
-        Self { min_altitude, min_elongation }
+        Self::from_params(CriterionParams::new(min_altitude, min_elongation))
+    }
+
+    /// Criteria with the given thresholds, evaluated at sunset.
+    pub fn from_params(params: CriterionParams) -> Self {
+        Self { params, evaluation_time: EvaluationTime::Sunset }
+    }
+
+    /// Replaces the thresholds.
+    pub fn with_params(mut self, params: CriterionParams) -> Self {
+        self.params = params;
+        self
     }
 
-    /// MABIMS criteria (default for Southeast Asia).
+    /// Sets the evaluation instant.
+    pub fn with_evaluation_time(mut self, evaluation_time: EvaluationTime) -> Self {
+        self.evaluation_time = evaluation_time;
+        self
+    }
+
+    /// Minimum moon altitude (degrees), formerly a field.
+    #[deprecated(since = "0.9.0", note = "use `params.min_altitude`")]
+    pub fn min_altitude(&self) -> f64 { self.params.min_altitude }
+
+    /// Minimum Sun-Moon elongation (degrees), formerly a field.
+    #[deprecated(since = "0.9.0", note = "use `params.min_elongation`")]
+    pub fn min_elongation(&self) -> f64 { self.params.min_elongation }
+
+    /// MABIMS criteria (default for Southeast Asia). Altitude 3°, elongation 6.4°,
+    /// both measured at sunset.
     pub fn mabims() -> Self { Self::default() }
 
-    /// Istanbul 1978 criteria (more conservative).
+    /// Istanbul 1978 criteria (more conservative). Altitude 5°, elongation 8°,
+    /// measured at sunset.
     pub fn istanbul_1978() -> Self {
-        Self { min_altitude: 5.0, min_elongation: 8.0 }
+        Self::from_params(CriterionParams::istanbul_1978())
     }
 }
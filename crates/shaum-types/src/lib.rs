@@ -8,10 +8,16 @@ mod status;
 mod madhab;
 mod analysis;
 mod error;
+mod provider;
+mod extreme;
+#[cfg(feature = "binary")]
+pub mod binary;
 
-pub use geo::{GeoCoordinate, VisibilityCriteria};
-pub use prayer::PrayerParams;
-pub use status::FastingStatus;
+pub use geo::{GeoCoordinate, VisibilityCriteria, CriterionParams, EvaluationTime, SightingZone};
+pub use prayer::{PrayerParams, PrayerParamsBuilder, IshaRule, SeasonalPrayerParams, SuhoorPolicy};
+pub use status::{FastingStatus, Emphasis};
 pub use madhab::{Madhab, DaudStrategy};
-pub use analysis::{FastingType, FastingAnalysis, RuleTrace, TraceCode, TracePayload};
+pub use analysis::{FastingType, FastingAnalysis, RuleTrace, TraceCode, TracePayload, MonthSource, Compensation};
 pub use error::ShaumError;
+pub use provider::{ProviderHealth, ProviderStatus};
+pub use extreme::{UndefinedReason, ExtremePolicy, PrayerTime, Qibla, KAABA, ORBIT_ALTITUDE_M};
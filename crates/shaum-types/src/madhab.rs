@@ -3,27 +3,26 @@
 use serde::{Serialize, Deserialize};
 
 /// Sunni schools of jurisprudence.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum Madhab {
+    #[default]
     Shafi,
     Hanafi,
     Maliki,
     Hanbali,
 }
 
-impl Default for Madhab {
-    fn default() -> Self { Self::Shafi }
+impl Madhab {
+    /// Every madhab, in declaration order.
+    pub const ALL: [Madhab; 4] = [Self::Shafi, Self::Hanafi, Self::Maliki, Self::Hanbali];
 }
 
 /// Strategy for Daud fasting on Haram days.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum DaudStrategy {
     /// Skip turn, lose the fast.
+    #[default]
     Skip,
     /// Postpone to next permissible day.
     Postpone,
 }
-
-impl Default for DaudStrategy {
-    fn default() -> Self { Self::Skip }
-}
@@ -1,11 +1,15 @@
 //! Prayer time calculation parameters.
 
 use serde::{Serialize, Deserialize};
+use crate::ShaumError;
 
 /// Prayer time calculation parameters.
 ///
-/// Controls angles and buffers used for prayer time calculations.
+/// Controls angles and buffers used for prayer time calculations. Start from
+/// a preset such as [`PrayerParams::mabims`] or from [`PrayerParams::builder`];
+/// fields may be added in minor releases.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct PrayerParams {
     /// Sun altitude angle for Fajr (degrees below horizon). Default: -20.0 (MABIMS/Indonesia)
     pub fajr_angle: f64,
@@ -15,6 +19,71 @@ pub struct PrayerParams {
     pub ihtiyat_minutes: i64,
     /// Seconds to round prayer times to. Default: 60 (round to next minute)
     pub rounding_granularity_seconds: i64,
+    /// Isha definition. Default: -18.0° (MABIMS)
+    #[serde(default)]
+    pub isha: IshaRule,
+    /// Minutes added to sunset for Maghrib. Default: 0
+    #[serde(default)]
+    pub maghrib_offset_minutes: i64,
+    /// Recommended end of suhoor. Default: the Imsak buffer.
+    #[serde(default)]
+    pub suhoor: SuhoorPolicy,
+}
+
+/// How the recommended end of suhoor is placed before true dawn.
+///
+/// Fajr angles are uncertain by a degree or more, so besides the fixed
+/// Imsak buffer a margin can be taken on the angle itself (dawn at
+/// `fajr_angle - margin`, i.e. deeper twilight).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum SuhoorPolicy {
+    /// Fajr minus [`PrayerParams::imsak_buffer_minutes`] (the Imsak time).
+    #[default]
+    Buffer,
+    /// When the sun reaches `fajr_angle - margin` degrees.
+    AngleMargin(f64),
+    /// The earlier of [`Self::Buffer`] and [`Self::AngleMargin`].
+    Both(f64),
+}
+
+impl SuhoorPolicy {
+    /// Angle margin in degrees, if the policy uses one.
+    pub fn angle_margin(&self) -> Option<f64> {
+        match *self {
+            Self::Buffer => None,
+            Self::AngleMargin(margin) | Self::Both(margin) => Some(margin),
+        }
+    }
+
+    /// Whether the Imsak buffer bounds the recommended time.
+    pub fn uses_buffer(&self) -> bool {
+        matches!(self, Self::Buffer | Self::Both(_))
+    }
+}
+
+/// How Isha is determined.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum IshaRule {
+    /// Sun altitude angle (degrees below horizon).
+    Angle(f64),
+    /// Fixed minutes after Maghrib, optionally different during Ramadhan.
+    Interval { minutes: i64, ramadhan_minutes: Option<i64> },
+}
+
+impl Default for IshaRule {
+    fn default() -> Self { Self::Angle(-18.0) }
+}
+
+impl IshaRule {
+    /// Minutes after Maghrib for interval rules; `None` for angle rules.
+    pub fn interval_minutes(&self, in_ramadhan: bool) -> Option<i64> {
+        match *self {
+            Self::Angle(_) => None,
+            Self::Interval { minutes, ramadhan_minutes } => {
+                Some(if in_ramadhan { ramadhan_minutes.unwrap_or(minutes) } else { minutes })
+            }
+        }
+    }
 }
 
 impl Default for PrayerParams {
@@ -24,6 +93,9 @@ impl Default for PrayerParams {
             imsak_buffer_minutes: 10,
             ihtiyat_minutes: 2,
             rounding_granularity_seconds: 60,
+            isha: IshaRule::default(),
+            maghrib_offset_minutes: 0,
+            suhoor: SuhoorPolicy::default(),
         }
     }
 }
@@ -36,6 +108,9 @@ impl PrayerParams {
             imsak_buffer_minutes,
             ihtiyat_minutes: 2,
             rounding_granularity_seconds: 60,
+            isha: IshaRule::default(),
+            maghrib_offset_minutes: 0,
+            suhoor: SuhoorPolicy::default(),
         }
     }
     
@@ -51,26 +126,275 @@ impl PrayerParams {
         self
     }
 
-    /// MABIMS/Indonesia standard (-20°, 10 min, +2 min Ihtiyat).
+    /// Set the Isha definition.
+    pub fn with_isha(mut self, isha: IshaRule) -> Self {
+        self.isha = isha;
+        self
+    }
+
+    /// Set Maghrib as sunset plus a fixed number of minutes.
+    pub fn with_maghrib_offset(mut self, minutes: i64) -> Self {
+        self.maghrib_offset_minutes = minutes;
+        self
+    }
+
+    /// Set the suhoor policy.
+    pub fn with_suhoor(mut self, policy: SuhoorPolicy) -> Self {
+        self.suhoor = policy;
+        self
+    }
+
+    /// MABIMS/Indonesia standard (-20°/-18°, 10 min, +2 min Ihtiyat).
     pub fn mabims() -> Self { Self::default() }
 
-    /// Egyptian General Authority (-19.5°, 10 min).
+    /// Egyptian General Authority (-19.5°/-17.5°, 10 min).
     pub fn egyptian() -> Self {
-        Self { fajr_angle: -19.5, imsak_buffer_minutes: 10, ihtiyat_minutes: 2, rounding_granularity_seconds: 60 }
+        Self { fajr_angle: -19.5, isha: IshaRule::Angle(-17.5), ..Self::default() }
     }
 
-    /// Muslim World League (-18°, 10 min).
+    /// Muslim World League (-18°/-17°, 10 min).
     pub fn mwl() -> Self {
-        Self { fajr_angle: -18.0, imsak_buffer_minutes: 10, ihtiyat_minutes: 2, rounding_granularity_seconds: 60 }
+        Self { fajr_angle: -18.0, isha: IshaRule::Angle(-17.0), ..Self::default() }
     }
 
-    /// ISNA (North America) standard (-15°, 10 min).
+    /// ISNA (North America) standard (-15°/-15°, 10 min).
     pub fn isna() -> Self {
-        Self { fajr_angle: -15.0, imsak_buffer_minutes: 10, ihtiyat_minutes: 2, rounding_granularity_seconds: 60 }
+        Self { fajr_angle: -15.0, isha: IshaRule::Angle(-15.0), ..Self::default() }
     }
 
-    /// Umm Al-Qura (Saudi Arabia) standard (-18.5°, 10 min).
+    /// Umm Al-Qura (Saudi Arabia) standard (-18.5°, Isha Maghrib+90 min, +120 in Ramadhan).
     pub fn umm_al_qura() -> Self {
-        Self { fajr_angle: -18.5, imsak_buffer_minutes: 10, ihtiyat_minutes: 2, rounding_granularity_seconds: 60 }
+        Self { fajr_angle: -18.5, isha: IshaRule::Interval { minutes: 90, ramadhan_minutes: Some(120) }, ..Self::default() }
     }
+
+    /// Validating builder starting from the defaults (MABIMS).
+    pub fn builder() -> PrayerParamsBuilder { PrayerParamsBuilder::new() }
+
+    /// Checks for settings no calculation can use: a Fajr or Isha angle that
+    /// is not below the horizon, a non-positive Isha interval or rounding
+    /// granularity, a negative Imsak buffer or suhoor margin. Useful for
+    /// values built field by field or deserialized.
+    ///
+    /// # Errors
+    /// `InvalidConfiguration` naming every offending field.
+    pub fn validate(&self) -> Result<(), ShaumError> {
+        let mut problems = Vec::new();
+        if !(self.fajr_angle.is_finite() && self.fajr_angle < 0.0) {
+            problems.push(format!("fajr_angle must be negative (below the horizon), got {}", self.fajr_angle));
+        }
+        match self.isha {
+            IshaRule::Angle(angle) if !(angle.is_finite() && angle < 0.0) => {
+                problems.push(format!("isha_angle must be negative (below the horizon), got {}", angle));
+            }
+            IshaRule::Interval { minutes, ramadhan_minutes } => {
+                if minutes <= 0 {
+                    problems.push(format!("isha_interval must be positive, got {}", minutes));
+                }
+                if let Some(minutes) = ramadhan_minutes.filter(|m| *m <= 0) {
+                    problems.push(format!("isha_interval_ramadhan must be positive, got {}", minutes));
+                }
+            }
+            IshaRule::Angle(_) => {}
+        }
+        if self.rounding_granularity_seconds <= 0 {
+            problems.push(format!("rounding_granularity_seconds must be positive, got {}", self.rounding_granularity_seconds));
+        }
+        if self.imsak_buffer_minutes < 0 {
+            problems.push(format!("imsak_buffer_minutes must not be negative, got {}", self.imsak_buffer_minutes));
+        }
+        if let Some(margin) = self.suhoor.angle_margin().filter(|m| !(m.is_finite() && *m >= 0.0)) {
+            problems.push(format!("suhoor margin must not be negative, got {}", margin));
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ShaumError::invalid_config(format!("Invalid prayer parameters: {}", problems.join("; "))))
+        }
+    }
+
+    /// Preset by method name (case-insensitive): `mabims`, `egyptian`, `mwl`,
+    /// `isna` or `umm_al_qura`. Used by the language bindings.
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().replace('-', "_").as_str() {
+            "mabims" => Some(Self::mabims()),
+            "egyptian" => Some(Self::egyptian()),
+            "mwl" => Some(Self::mwl()),
+            "isna" => Some(Self::isna()),
+            "umm_al_qura" | "ummalqura" => Some(Self::umm_al_qura()),
+            _ => None,
+        }
+    }
+}
+
+impl PrayerParams {
+    /// Method commonly used in a country (ISO 3166-1 alpha-2, case-insensitive).
+    ///
+    /// MABIMS for ID/MY/SG/BN (their joint ministerial method), Umm Al-Qura
+    /// for SA, ISNA for US/CA/GB (the method of ISNA, the Islamic Society of
+    /// North America), Egyptian for EG. Everything else falls back to MWL.
+    pub fn for_country(code: &str) -> Self {
+        match code.to_ascii_uppercase().as_str() {
+            "ID" | "MY" | "SG" | "BN" => Self::mabims(),
+            "SA" => Self::umm_al_qura(),
+            "US" | "CA" | "GB" => Self::isna(),
+            "EG" => Self::egyptian(),
+            _ => Self::mwl(),
+        }
+    }
+
+    /// Like [`for_country`](Self::for_country), but `overrides` take precedence.
+    pub fn for_country_with(code: &str, overrides: &[(&str, PrayerParams)]) -> Self {
+        overrides
+            .iter()
+            .find(|(c, _)| c.eq_ignore_ascii_case(code))
+            .map(|(_, p)| *p)
+            .unwrap_or_else(|| Self::for_country(code))
+    }
+
+}
+
+/// Builder for [`PrayerParams`] that rejects impossible combinations when
+/// built, rather than producing times that silently make no sense.
+///
+/// ```rust
+/// use shaum_types::{IshaRule, PrayerParams};
+///
+/// let params = PrayerParams::builder()
+///     .fajr_angle(-18.5)
+///     .isha_interval(90, Some(120))
+///     .build()
+///     .unwrap();
+/// assert_eq!(params.isha, IshaRule::Interval { minutes: 90, ramadhan_minutes: Some(120) });
+///
+/// // Isha is either an angle or an interval, not both.
+/// let err = PrayerParams::builder().isha_angle(-17.0).isha_interval(90, None).build().unwrap_err();
+/// assert!(err.to_string().contains("isha_angle and isha_interval"));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrayerParamsBuilder {
+    params: PrayerParams,
+    isha_angle: Option<f64>,
+    isha_interval: Option<(i64, Option<i64>)>,
+}
+
+impl PrayerParamsBuilder {
+    /// Starts from the defaults (MABIMS).
+    pub fn new() -> Self { Self::default() }
+
+    /// Starts from a preset such as [`PrayerParams::mwl`]; setting either
+    /// Isha field replaces the preset's Isha rule.
+    pub fn from_preset(params: PrayerParams) -> Self {
+        Self { params, isha_angle: None, isha_interval: None }
+    }
+
+    /// Sun altitude for Fajr, in degrees (negative: below the horizon).
+    pub fn fajr_angle(mut self, degrees: f64) -> Self {
+        self.params.fajr_angle = degrees;
+        self
+    }
+
+    /// Isha when the sun reaches `degrees` (negative: below the horizon).
+    pub fn isha_angle(mut self, degrees: f64) -> Self {
+        self.isha_angle = Some(degrees);
+        self
+    }
+
+    /// Isha a fixed number of minutes after Maghrib, optionally different in Ramadhan.
+    pub fn isha_interval(mut self, minutes: i64, ramadhan_minutes: Option<i64>) -> Self {
+        self.isha_interval = Some((minutes, ramadhan_minutes));
+        self
+    }
+
+    /// Minutes before Fajr for Imsak.
+    pub fn imsak_buffer(mut self, minutes: i64) -> Self {
+        self.params.imsak_buffer_minutes = minutes;
+        self
+    }
+
+    /// Safety margin (Ihtiyat) in minutes.
+    pub fn ihtiyat(mut self, minutes: i64) -> Self {
+        self.params.ihtiyat_minutes = minutes;
+        self
+    }
+
+    /// Rounding granularity in seconds.
+    pub fn rounding(mut self, seconds: i64) -> Self {
+        self.params.rounding_granularity_seconds = seconds;
+        self
+    }
+
+    /// Maghrib as sunset plus a fixed number of minutes.
+    pub fn maghrib_offset(mut self, minutes: i64) -> Self {
+        self.params.maghrib_offset_minutes = minutes;
+        self
+    }
+
+    /// How the recommended end of suhoor is placed before Fajr.
+    pub fn suhoor(mut self, policy: SuhoorPolicy) -> Self {
+        self.params.suhoor = policy;
+        self
+    }
+
+    /// Validates and returns the parameters.
+    ///
+    /// # Errors
+    /// `InvalidConfiguration` naming the offending fields: both Isha fields
+    /// set, or anything [`PrayerParams::validate`] rejects.
+    pub fn build(self) -> Result<PrayerParams, ShaumError> {
+        let mut params = self.params;
+        params.isha = match (self.isha_angle, self.isha_interval) {
+            (Some(_), Some(_)) => {
+                return Err(ShaumError::invalid_config(
+                    "Invalid prayer parameters: isha_angle and isha_interval are mutually exclusive",
+                ));
+            }
+            (Some(angle), None) => IshaRule::Angle(angle),
+            (None, Some((minutes, ramadhan_minutes))) => IshaRule::Interval { minutes, ramadhan_minutes },
+            (None, None) => params.isha,
+        };
+        params.validate()?;
+        Ok(params)
+    }
+}
+
+/// Prayer parameters with a separate set used during Ramadhan.
+///
+/// ```rust
+/// use shaum_types::{PrayerParams, SeasonalPrayerParams};
+///
+/// let params = SeasonalPrayerParams::new(PrayerParams::mabims())
+///     .with_ramadhan(|p| p.with_ihtiyat(3));
+/// assert_eq!(params.select(true).ihtiyat_minutes, 3);
+/// assert_eq!(params.select(false).ihtiyat_minutes, 2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SeasonalPrayerParams {
+    pub regular: PrayerParams,
+    pub ramadhan: PrayerParams,
+}
+
+impl SeasonalPrayerParams {
+    /// Uses `regular` all year round until Ramadhan overrides are set.
+    pub fn new(regular: PrayerParams) -> Self {
+        Self { regular, ramadhan: regular }
+    }
+
+    /// Derives the Ramadhan set from the regular one.
+    pub fn with_ramadhan(mut self, f: impl FnOnce(PrayerParams) -> PrayerParams) -> Self {
+        self.ramadhan = f(self.regular);
+        self
+    }
+
+    /// Parameters in effect for the given season.
+    pub fn select(&self, in_ramadhan: bool) -> &PrayerParams {
+        if in_ramadhan { &self.ramadhan } else { &self.regular }
+    }
+}
+
+impl From<PrayerParams> for SeasonalPrayerParams {
+    fn from(params: PrayerParams) -> Self { Self::new(params) }
+}
+
+impl From<&PrayerParams> for SeasonalPrayerParams {
+    fn from(params: &PrayerParams) -> Self { Self::new(*params) }
 }
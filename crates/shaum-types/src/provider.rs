@@ -0,0 +1,58 @@
+//! Health and freshness reporting for network-backed providers.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Serialize, Deserialize};
+use std::fmt;
+
+/// Coarse health of a provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum ProviderHealth {
+    /// No fetch attempted yet.
+    #[default]
+    Unknown,
+    /// Last fetch succeeded.
+    Healthy,
+    /// Last fetch failed; serving cached data.
+    Degraded,
+    /// Last fetch failed and no cached data is available.
+    Offline,
+}
+
+impl fmt::Display for ProviderHealth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{:?}", self) }
+}
+
+/// Snapshot of a provider's state.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ProviderStatus {
+    pub health: ProviderHealth,
+    /// Time of the last successful fetch.
+    pub last_success: Option<DateTime<Utc>>,
+    /// Message of the most recent failure, cleared on success.
+    pub last_error: Option<String>,
+}
+
+impl ProviderStatus {
+    /// Age of the cached data relative to `now`, if anything was ever fetched.
+    pub fn cache_age(&self, now: DateTime<Utc>) -> Option<Duration> {
+        self.last_success.map(|t| now - t)
+    }
+
+    #[inline] pub fn is_healthy(&self) -> bool { self.health == ProviderHealth::Healthy }
+    #[inline] pub fn is_degraded(&self) -> bool { self.health == ProviderHealth::Degraded }
+    #[inline] pub fn is_offline(&self) -> bool { self.health == ProviderHealth::Offline }
+}
+
+impl fmt::Display for ProviderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.health)?;
+        match self.last_success {
+            Some(t) => write!(f, ", last success {}", t.to_rfc3339())?,
+            None => write!(f, ", never fetched")?,
+        }
+        if let Some(e) = &self.last_error {
+            write!(f, ", last error: {}", e)?;
+        }
+        Ok(())
+    }
+}
@@ -4,6 +4,10 @@ use serde::{Serialize, Deserialize};
 use std::fmt;
 
 /// Fasting status (Hukum). Ordered by priority: Haram > Wajib > SunnahMuakkadah > Sunnah > Makruh > Mubah.
+///
+/// The priority order is a lattice: combining two statuses yields the higher
+/// one, with `Mubah` as identity. Use [`FastingStatus::combine`] or `collect()`
+/// rather than comparing by hand.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum FastingStatus {
     Mubah,
@@ -20,6 +24,45 @@ impl FastingStatus {
     #[inline] pub fn is_sunnah(&self) -> bool { matches!(self, Self::Sunnah | Self::SunnahMuakkadah) }
     #[inline] pub fn is_makruh(&self) -> bool { matches!(self, Self::Makruh) }
     #[inline] pub fn is_mubah(&self) -> bool { matches!(self, Self::Mubah) }
+
+    /// Combines statuses from two sources: the higher priority wins.
+    ///
+    /// ```rust
+    /// use shaum_types::FastingStatus;
+    ///
+    /// assert_eq!(FastingStatus::combine(FastingStatus::Makruh, FastingStatus::Sunnah), FastingStatus::Sunnah);
+    /// let day: FastingStatus = [FastingStatus::Sunnah, FastingStatus::Haram].into_iter().collect();
+    /// assert_eq!(day, FastingStatus::Haram);
+    /// ```
+    #[inline]
+    pub const fn combine(self, other: Self) -> Self {
+        if (self as u8) >= (other as u8) { self } else { other }
+    }
+
+    /// Short name without the English gloss, e.g. "Sunnah Muakkadah".
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::Mubah => "Mubah",
+            Self::Makruh => "Makruh",
+            Self::Sunnah => "Sunnah",
+            Self::SunnahMuakkadah => "Sunnah Muakkadah",
+            Self::Wajib => "Wajib",
+            Self::Haram => "Haram",
+        }
+    }
+}
+
+/// Combines all statuses; an empty iterator yields `Mubah`.
+impl FromIterator<FastingStatus> for FastingStatus {
+    fn from_iter<I: IntoIterator<Item = FastingStatus>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::Mubah, Self::combine)
+    }
+}
+
+impl<'a> FromIterator<&'a FastingStatus> for FastingStatus {
+    fn from_iter<I: IntoIterator<Item = &'a FastingStatus>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
 }
 
 impl fmt::Display for FastingStatus {
@@ -35,3 +78,38 @@ impl fmt::Display for FastingStatus {
         write!(f, "{}", s)
     }
 }
+
+/// Degree of emphasis of a voluntary (Sunnah) fast, carried per reason.
+/// Ordered from weakest to strongest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Emphasis {
+    /// Ghair Muakkadah: recommended, not emphasized (e.g. Tasu'a alongside Ashura).
+    GhairMuakkadah,
+    /// Mustahabb: regularly practised and encouraged (e.g. Mondays, Ayyamul Bidh).
+    Mustahabb,
+    /// Muakkadah: strongly emphasized (Arafah, Ashura).
+    Muakkadah,
+}
+
+impl Emphasis {
+    /// Emphasis implied by a Sunnah status; `None` for any other status.
+    pub const fn from_status(status: FastingStatus) -> Option<Self> {
+        match status {
+            FastingStatus::SunnahMuakkadah => Some(Self::Muakkadah),
+            FastingStatus::Sunnah => Some(Self::Mustahabb),
+            _ => None,
+        }
+    }
+
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::GhairMuakkadah => "Ghair Muakkadah",
+            Self::Mustahabb => "Mustahabb",
+            Self::Muakkadah => "Muakkadah",
+        }
+    }
+}
+
+impl fmt::Display for Emphasis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{}", self.label()) }
+}
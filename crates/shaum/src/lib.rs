@@ -1,26 +1,189 @@
 //! # Shaum
 //!
-//! A production-grade Rust library for Islamic fasting (Shaum) jurisprudence 
+//! A production-grade Rust library for Islamic fasting (Shaum) jurisprudence
 //! with high-precision astronomical calculations for Hilal visibility.
 //!
-//! This crate is a facade that re-exports functionality from the `shaum` ecosystem.
-//!
-//! ## Modules
-//! 
-//! - `types`: Core types (FastingStatus, GeoCoordinate, etc.)
-//! - `calendar`: Hijri calendar conversion
-//! - `astronomy`: Astronomical calculations (Sun/Moon position, visibility)
-//! - `rules`: Jurisprudence rules engine (optional)
-//! - `network`: Network capabilities (optional)
+//! This crate is the published facade: it re-exports `shaum-core`, so
+//! `shaum::X` and `shaum_core::X` are the same item. Every fallible entry
+//! point returns `Result<_, ShaumError>`.
 //!
 //! ## Usage
 //!
 //! ```rust
 //! use shaum::prelude::*;
 //! use chrono::NaiveDate;
-//! 
+//!
+//! // 5 June 2025: 9 Dhu al-Hijjah 1446 (Arafah).
 //! let date = NaiveDate::from_ymd_opt(2025, 6, 5).unwrap();
-//! let analysis = shaum::analyze_date(date); // Result<FastingAnalysis, ShaumError>
+//! let analysis = shaum::analyze_date(date)?;
+//! assert_eq!(analysis.primary_status, FastingStatus::SunnahMuakkadah);
+//! # Ok::<(), ShaumError>(())
 //! ```
+//!
+//! ## Modules
+//!
+//! The top level re-exports the everyday items; each module below also
+//! exposes its whole sub-crate.
+//!
+//! - [`prelude`]: glob import for applications.
+//! - [`types`]: core types (`FastingStatus`, `GeoCoordinate`, ...).
+//! - [`calendar`]: Hijri conversion and calendar backends.
+//!
+//!   ```rust
+//!   use shaum::calendar::{to_hijri_with, HijriDuration, HijriYmd, UmmAlQura};
+//!
+//!   let hijri = to_hijri_with(chrono::NaiveDate::from_ymd_opt(2024, 3, 11).unwrap(), 0, &UmmAlQura)?;
+//!   assert_eq!((hijri.month, hijri.day), (9, 1));
+//!   let later = HijriYmd::new(1445, 9, 1).checked_add(HijriDuration::months(1), &UmmAlQura)?;
+//!   assert_eq!(later.month, 10);
+//!   # Ok::<(), shaum::ShaumError>(())
+//!   ```
+//! - [`astronomy`] (feature `astronomy`, on by default): sun and moon
+//!   positions, crescent visibility and prayer times.
+//!
+//!   ```rust
+//!   use shaum::astronomy::prayer::calculate_prayer_times;
+//!   use shaum::{GeoCoordinate, PrayerParams};
+//!
+//!   let date = chrono::NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+//!   let jakarta = GeoCoordinate::new(-6.2088, 106.8456)?;
+//!   let times = calculate_prayer_times(date, jakarta, &PrayerParams::default())?;
+//!   assert!(times.fajr < times.maghrib);
+//!   # Ok::<(), shaum::ShaumError>(())
+//!   ```
+//!
+//!   Without it, prayer APIs are absent and sunset-aware analysis falls back
+//!   to a midnight day boundary (`ShaumError::CapabilityUnavailable` in
+//!   strict mode).
+//! - [`rules`]: the rules engine and [`RuleContext`].
+//!
+//!   ```rust
+//!   use shaum::{check, Madhab, PersonalSchedule, RuleContext};
+//!
+//!   // Friday 5 January 2024, with the Thursday before it also fasted.
+//!   let friday = chrono::NaiveDate::from_ymd_opt(2024, 1, 5).unwrap();
+//!   let ctx = RuleContext::new().madhab(Madhab::Hanafi)
+//!       .with_schedule(PersonalSchedule::new().with_fast(friday.pred_opt().unwrap()));
+//!   assert!(!check(friday, &ctx)?.primary_status.is_makruh());
+//!   # Ok::<(), shaum::ShaumError>(())
+//!   ```
+//! - [`query`] and [`extension`]: date iteration and `NaiveDate` helpers.
+//!
+//!   ```rust
+//!   use shaum::prelude::*;
+//!   use chrono::NaiveDate;
+//!
+//!   let start = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+//!   let haram = FastingQuery::starting_from(start).until(start + chrono::Duration::days(60)).haram();
+//!   assert_eq!(haram.count(), 1); // Eid al-Fitr
+//!   assert!(NaiveDate::from_ymd_opt(2024, 3, 11).unwrap().fasting_analysis().is_ramadhan());
+//!   ```
+//! - [`intent`]: validating what a fast is intended as.
+//!
+//!   ```rust
+//!   use shaum::intent::{check_intention, Intention};
+//!   use shaum::RuleContext;
+//!
+//!   // 15 April 2024: 6 Shawwal 1445.
+//!   let date = chrono::NaiveDate::from_ymd_opt(2024, 4, 15).unwrap();
+//!   assert!(check_intention(date, &[Intention::Shawwal], &RuleContext::default())?.is_valid());
+//!   # Ok::<(), shaum::ShaumError>(())
+//!   ```
+//! - [`coverage`]: which built-in rules exist and which fired.
+//!
+//!   ```rust
+//!   use shaum::coverage::{rule_coverage, BUILTIN_RULES};
+//!
+//!   let analysis = shaum::analyze_date(chrono::NaiveDate::from_ymd_opt(2024, 3, 11).unwrap())?;
+//!   let report = rule_coverage(&analysis, shaum::Madhab::Shafi);
+//!   assert!(report.len() <= BUILTIN_RULES.len());
+//!   assert!(report.iter().any(|r| r.fired));
+//!   # Ok::<(), shaum::ShaumError>(())
+//!   ```
+//! - [`compat`]: comparing two configurations over a date range.
+//!
+//!   ```rust
+//!   use shaum::compat::compare;
+//!   use shaum::{Madhab, RuleContext};
+//!
+//!   let start = chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+//!   let end = chrono::NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+//!   let report = compare(start, end, &RuleContext::default(), &RuleContext::new().madhab(Madhab::Maliki))?;
+//!   assert_eq!(report.days_compared, 31);
+//!   # Ok::<(), shaum::ShaumError>(())
+//!   ```
+//! - [`recurrence`]: custom rules on Gregorian schedules.
+//!
+//!   ```rust
+//!   use shaum::recurrence::RecurringRule;
+//!   use shaum::{FastingStatus, FastingType};
+//!
+//!   let rule = RecurringRule::annual(8, 17, FastingStatus::Sunnah, FastingType::new("Independence"))?;
+//!   let mut ctx = shaum::RuleContext::new();
+//!   ctx.custom_rules.push(Box::new(rule));
+//!   # Ok::<(), shaum::ShaumError>(())
+//!   ```
+//! - [`occasions`]: commemorations and holidays (no fasting ruling).
+//!
+//!   ```rust
+//!   use shaum::occasions::{Occasion, OccasionSet};
+//!
+//!   // 7 July 2024: 1 Muharram 1446.
+//!   let date = chrono::NaiveDate::from_ymd_opt(2024, 7, 7).unwrap();
+//!   let set = OccasionSet::standard();
+//!   assert_eq!(set.on(date, &shaum::RuleContext::default())?, vec![&Occasion::ISLAMIC_NEW_YEAR]);
+//!   # Ok::<(), shaum::ShaumError>(())
+//!   ```
+//! - [`calibrate`]: fitting the adjustment to locally observed month starts.
+//!
+//!   ```rust
+//!   use shaum::calibrate::{calibrate, Observation};
+//!
+//!   // Ramadhan 1445 began locally on 12 March 2024, a day after Umm al-Qura.
+//!   let seen = Observation::month_start(chrono::NaiveDate::from_ymd_opt(2024, 3, 12).unwrap(), 1445, 9);
+//!   let fit = calibrate(shaum::RuleContext::default(), &[seen])?;
+//!   assert_eq!(fit.adjustment, -1);
+//!   # Ok::<(), shaum::ShaumError>(())
+//!   ```
+//! - [`qada`]: recording missed Ramadhan days and planning their makeup.
+//!
+//!   ```rust
+//!   use shaum::qada::QadaLedger;
+//!
+//!   let mut ledger = QadaLedger::new();
+//!   ledger.record_missed(chrono::NaiveDate::from_ymd_opt(2024, 3, 20).unwrap());
+//!   let plan = ledger.planner(chrono::NaiveDate::from_ymd_opt(2024, 4, 10).unwrap()).plan()?;
+//!   assert_eq!(plan.fasts.len(), 1);
+//!   # Ok::<(), shaum::ShaumError>(())
+//!   ```
+//! - [`vow`]: vowed fasts (nadhar), reported as Wajib on the days they name.
+//!
+//!   ```rust
+//!   use shaum::vow::{Vow, Vows};
+//!
+//!   let vows = Vows::new().with_vow(Vow::every(chrono::Weekday::Tue));
+//!   let ctx = shaum::RuleContext::new().with_vows(vows);
+//!   let tuesday = chrono::NaiveDate::from_ymd_opt(2024, 7, 2).unwrap();
+//!   assert!(shaum::check(tuesday, &ctx)?.primary_status.is_wajib());
+//!   # Ok::<(), shaum::ShaumError>(())
+//!   ```
+//! - [`registry`]: the named, prioritised rules behind [`analyze`], which can
+//!   be listed, reordered, removed or replaced.
+//!
+//!   ```rust
+//!   use shaum::registry::RuleRegistry;
+//!
+//!   let registry = RuleRegistry::builtin().without("saturday_exclusive")?;
+//!   let ctx = shaum::RuleContext::new().with_registry(registry);
+//!   let saturday = chrono::NaiveDate::from_ymd_opt(2024, 7, 6).unwrap();
+//!   assert!(shaum::check(saturday, &ctx)?.primary_status.is_mubah());
+//!   # Ok::<(), shaum::ShaumError>(())
+//!   ```
+//! - `network` (feature `network`, implied by `async`): IP geolocation and
+//!   reverse geocoding.
+//! - `bulk` (feature `async`): a year's calendar assembled from an
+//!   announcement provider, with concurrent, rate-limited requests per month.
+//! - `binary` (feature `binary`): compact, versioned binary encoding of
+//!   analysis batches for caches and offline bundles.
 
 pub use shaum_core::*;
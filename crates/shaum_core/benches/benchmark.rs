@@ -1,23 +1,37 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use chrono::NaiveDate;
-use shaum::{analyze, RuleContext};
+use shaum_core::{check, HijriCalendar, RuleContext, UmmAlQura};
+use std::hint::black_box;
+use std::time::Instant;
 
-fn bench_analyze(c: &mut Criterion) {
+fn bench<F: FnMut()>(name: &str, iterations: u32, mut f: F) {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    println!("{:<24} {:>12.3?} / iter", name, start.elapsed() / iterations);
+}
+
+fn main() {
     let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
     let ctx = RuleContext::default();
-    
-    c.bench_function("analyze_single_day", |b| b.iter(|| {
-        analyze(black_box(date), black_box(&ctx)).unwrap();
-    }));
 
-    c.bench_function("analyze_10_years", |b| b.iter(|| {
+    bench("analyze_single_day", 10_000, || {
+        check(black_box(date), black_box(&ctx)).unwrap();
+    });
+
+    bench("analyze_10_years", 10, || {
         let mut d = date;
         for _ in 0..3650 {
-            analyze(black_box(d), black_box(&ctx)).unwrap();
+            check(black_box(d), black_box(&ctx)).unwrap();
             d = d.succ_opt().unwrap();
         }
-    }));
-}
+    });
 
-criterion_group!(benches, bench_analyze);
-criterion_main!(benches);
+    bench("to_hijri_century", 3, || {
+        let mut d = NaiveDate::from_ymd_opt(1940, 1, 1).unwrap();
+        for _ in 0..36_525 {
+            UmmAlQura.to_hijri(black_box(d)).unwrap();
+            d = d.succ_opt().unwrap();
+        }
+    });
+}
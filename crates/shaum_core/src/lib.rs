@@ -17,17 +17,30 @@
 
 // Re-export specific items from sub-crates to maintain API
 pub use shaum_types::{
-    FastingStatus, FastingType, FastingAnalysis, Madhab, DaudStrategy,
-    GeoCoordinate, TraceCode, VisibilityCriteria, PrayerParams
+    FastingStatus, FastingType, FastingAnalysis, Madhab, DaudStrategy, Emphasis,
+    GeoCoordinate, TraceCode, VisibilityCriteria, CriterionParams, EvaluationTime, SightingZone, PrayerParams, PrayerParamsBuilder, MonthSource, Compensation,
+    ProviderHealth, ProviderStatus, IshaRule, SeasonalPrayerParams, SuhoorPolicy,
+    UndefinedReason, ExtremePolicy, PrayerTime, Qibla,
 };
 
-pub use shaum_calendar::{to_hijri, ShaumError};
+#[allow(deprecated)]
+pub use shaum_calendar::to_hijri;
+pub use shaum_calendar::{to_hijri_with, HijriCalendar, HijriYmd, HijriWeekday, HijriDuration, UmmAlQura, Tabular, HijriCache, CacheMetrics, ShaumError};
+pub use shaum_calendar::{is_hijri_leap_year, days_in_hijri_year, TableCalendar, CalendarMethod, calendar_by_name, compare_methods, hijri_month_from_name};
+pub use shaum_calendar::{to_julian_day, from_julian_day};
 
 pub use shaum_rules::{
-    analyze, check, RuleContext, MoonProvider, SunsetProvider, 
-    DefaultSunsetProvider, FixedAdjustment, NoAdjustment,
-    shaum_context, DaudIterator, generate_daud_schedule, DaudScheduleBuilder
+    analyze, check, is_fastable, RuleContext, MoonProvider, SunsetProvider, 
+    analyze_today, analyze_local, LocalDay, classify,
+    DefaultSunsetProvider, FixedAdjustment, NoAdjustment, HybridMoonProvider, MonthSightings,
+    adjustment_to_month_offset, month_offset_to_adjustment,
+    shaum_context, DaudIterator, generate_daud_schedule, DaudScheduleBuilder, HijriMonthIterator,
+    MonthView, MonthViewBuilder, MonthAnchor, CalendarDay, CalendarWeek, YearGrid, GridDay,
+    PersonalSchedule, PersonalContext, PersonalState, MaternalState, MaternalConcern, Vow, VowDay, Vows,
+    MonthWatcher, MonthEvent, Clock, SystemClock, ManualClock,
 };
+#[cfg(feature = "astronomy")]
+pub use shaum_rules::{prayer_times, niyyah_deadline, niyyah_deadline_for};
 
 // Re-export modules as if they were local (optional, but good for discovery)
 pub mod types {
@@ -36,8 +49,11 @@ pub mod types {
 
 pub mod calendar {
     pub use shaum_calendar::*;
+    #[cfg(feature = "astronomy")]
+    pub use shaum_astronomy::lunar_calendar::{predict_month_starts, MonthStartPrediction, CrescentVisibility};
 }
 
+#[cfg(feature = "astronomy")]
 pub mod astronomy {
     pub use shaum_astronomy::*;
 }
@@ -54,7 +70,53 @@ pub mod query {
     pub use shaum_rules::query::*;
 }
 
-#[cfg(feature = "shaum-network")]
+pub mod compat {
+    pub use shaum_rules::compat::*;
+}
+
+pub mod coverage {
+    pub use shaum_rules::coverage::*;
+}
+
+pub mod recurrence {
+    pub use shaum_rules::recurrence::*;
+}
+
+pub mod intent {
+    pub use shaum_rules::intent::*;
+}
+
+pub mod occasions {
+    pub use shaum_rules::occasions::*;
+}
+
+pub mod calibrate {
+    pub use shaum_rules::calibrate::*;
+}
+
+pub mod qada {
+    pub use shaum_rules::qada::*;
+}
+
+pub mod vow {
+    pub use shaum_rules::vow::*;
+}
+
+pub mod registry {
+    pub use shaum_rules::registry::*;
+}
+
+#[cfg(feature = "async")]
+pub mod bulk {
+    pub use shaum_rules::bulk::*;
+}
+
+#[cfg(feature = "binary")]
+pub mod binary {
+    pub use shaum_types::binary::*;
+}
+
+#[cfg(feature = "network")]
 pub mod network {
     pub use shaum_network::*;
 }
@@ -65,9 +127,12 @@ pub mod prelude {
         FastingStatus, FastingType, FastingAnalysis, Madhab, DaudStrategy,
         GeoCoordinate, TraceCode, VisibilityCriteria, PrayerParams
     };
-    pub use shaum_calendar::{to_hijri, ShaumError, HijriDate};
+    #[allow(deprecated)]
+    pub use shaum_calendar::to_hijri;
+    pub use shaum_calendar::{to_hijri_with, HijriCalendar, ShaumError, HijriDate};
     pub use shaum_rules::{
         analyze, check, RuleContext, MoonProvider, SunsetProvider,
+        analyze_today, LocalDay,
         // Extension traits are re-exported by rules prelude or directly?
         // Let's re-export items used in prelude previously.
         FastingQuery, QueryExt, ShaumDateExt,
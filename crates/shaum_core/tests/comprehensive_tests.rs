@@ -1,4 +1,4 @@
-use shaum_core::{to_hijri, check, RuleContext, Madhab, DaudStrategy, FastingStatus, generate_daud_schedule};
+use shaum_core::{to_hijri_with, UmmAlQura, check, RuleContext, Madhab, DaudStrategy, FastingStatus, generate_daud_schedule};
 #[cfg(feature = "async")]
 use shaum_core::rules::{RemoteMoonProvider, MoonProvider};
 use shaum_core::extension::ShaumDateExt;
@@ -9,7 +9,7 @@ fn test_to_hijri_out_of_range() {
     // Negative year - should error
     let bad_date = NaiveDate::from_ymd_opt(1800, 1, 1).unwrap();
     // In strict mode, this returns Error
-    let res = to_hijri(bad_date, 0);
+    let res = to_hijri_with(bad_date, 0, &UmmAlQura);
     assert!(res.is_err());
 }
 
@@ -31,23 +31,21 @@ fn test_arafah_friday_not_makruh() {
     let mut found = false;
     
     for _ in 0..5000 {
-        match to_hijri(d, 0) {
+        match to_hijri_with(d, 0, &UmmAlQura) {
             Ok(h) => {
                 // 9 Dhul Hijjah
-                if h.month() == 12 && h.day() == 9 {
-                    if d.weekday() == chrono::Weekday::Fri {
-                        let ctx = RuleContext::new().madhab(Madhab::Shafi);
-                        let analysis = check(d, &ctx).unwrap(); 
-                        
-                        // Should be Sunnah, NOT Makruh
-                        assert!(!analysis.primary_status.is_haram());
-                        assert_ne!(analysis.primary_status, FastingStatus::Makruh);
-                        // Depending on impl, might be SunnahMuakkadah
-                        println!("Date: {:?}, Status: {:?}", d, analysis.primary_status);
-                        
-                        found = true;
-                        break;
-                    }
+                if h.month() == 12 && h.day() == 9 && d.weekday() == chrono::Weekday::Fri {
+                    let ctx = RuleContext::new().madhab(Madhab::Shafi);
+                    let analysis = check(d, &ctx).unwrap(); 
+                    
+                    // Should be Sunnah, NOT Makruh
+                    assert!(!analysis.primary_status.is_haram());
+                    assert_ne!(analysis.primary_status, FastingStatus::Makruh);
+                    // Depending on impl, might be SunnahMuakkadah
+                    println!("Date: {:?}, Status: {:?}", d, analysis.primary_status);
+                    
+                    found = true;
+                    break;
                 }
             }
             Err(_) => {
@@ -64,9 +62,7 @@ fn test_daud_skip_strategy() {
     // Find Eid al-Fitr (1 Shawwal)
     let mut eid_date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
     loop {
-        if let Ok(h) = to_hijri(eid_date, 0) {
-            if h.month() == 10 && h.day() == 1 { break; }
-        }
+        if let Ok(h) = to_hijri_with(eid_date, 0, &UmmAlQura) && h.month() == 10 && h.day() == 1 { break; }
         eid_date = eid_date.succ_opt().unwrap();
     }
     
@@ -79,7 +75,7 @@ fn test_daud_skip_strategy() {
     // Next day (Eid+2): My turn is "Fast". Result: In list.
     
     let iter = generate_daud_schedule(eid_date, eid_date + chrono::Duration::days(5), &ctx);
-    let days: Vec<NaiveDate> = iter;
+    let days: Vec<NaiveDate> = iter.unwrap();
     
     assert!(!days.contains(&eid_date), "Should not fast on Eid");
     assert!(!days.contains(&(eid_date + chrono::Duration::days(1))), "Should eat on Eid+1 (Skip strategy)");
@@ -91,9 +87,7 @@ fn test_daud_postpone_strategy() {
     // Find Eid al-Fitr
     let mut eid_date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
     loop {
-        if let Ok(h) = to_hijri(eid_date, 0) {
-            if h.month() == 10 && h.day() == 1 { break; }
-        }
+        if let Ok(h) = to_hijri_with(eid_date, 0, &UmmAlQura) && h.month() == 10 && h.day() == 1 { break; }
         eid_date = eid_date.succ_opt().unwrap();
     }
     
@@ -106,7 +100,7 @@ fn test_daud_postpone_strategy() {
     // Next day (Eid+2): Toggle to "Eat". Result: Not in list.
     
     let iter = generate_daud_schedule(eid_date, eid_date + chrono::Duration::days(5), &ctx);
-    let days: Vec<NaiveDate> = iter;
+    let days: Vec<NaiveDate> = iter.unwrap();
     
     assert!(!days.contains(&eid_date), "Should not fast on Eid");
     assert!(days.contains(&(eid_date + chrono::Duration::days(1))), "Should fast on Eid+1 (Postpone strategy)");
@@ -141,3 +135,481 @@ async fn test_remote_moon_provider() {
     assert!(adj.is_ok());
     assert_eq!(adj.unwrap(), 1);
 }
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn test_remote_moon_provider_serves_cache_when_degraded() {
+    use std::sync::{Arc, Mutex};
+    use wiremock::{MockServer, Mock, ResponseTemplate};
+    use wiremock::matchers::{method, path};
+    use shaum_core::ProviderHealth;
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/adjustment"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "adjustment": 1 })))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/adjustment"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&mock_server)
+        .await;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = seen.clone();
+    let provider = RemoteMoonProvider::new(format!("{}/adjustment", mock_server.uri()))
+        .on_status(move |s| sink.lock().unwrap().push(s.health));
+    let date = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+
+    assert_eq!(provider.status().health, ProviderHealth::Unknown);
+    assert_eq!(provider.get_adjustment(date, None).await.unwrap(), 1);
+    assert!(provider.status().is_healthy());
+
+    // Server now failing: cached value is served, status degrades.
+    assert_eq!(provider.get_adjustment(date, None).await.unwrap(), 1);
+    let status = provider.status();
+    assert!(status.is_degraded());
+    assert!(status.last_success.is_some());
+    assert!(status.last_error.is_some());
+
+    // Nothing cached for the next month: the provider is offline there.
+    let shawwal = NaiveDate::from_ymd_opt(2024, 4, 15).unwrap();
+    let err = provider.get_adjustment(shawwal, None).await.unwrap_err();
+    assert!(matches!(err, shaum_core::ShaumError::ProviderUnavailable { .. }));
+    assert_eq!(provider.status().health, ProviderHealth::Offline);
+    assert_eq!(provider.month_offset(1445, 9), Some(-1));
+    assert_eq!(provider.month_offset(1445, 10), None);
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![ProviderHealth::Healthy, ProviderHealth::Degraded, ProviderHealth::Offline]
+    );
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn test_bulk_calendar_from_announcements() {
+    use std::time::{Duration, Instant};
+    use shaum_core::bulk::BulkCalendarBuilder;
+    use shaum_core::{HijriCalendar, MonthSightings, ShaumError};
+
+    // Ramadhan 1445 announced a day late; Shawwal "announced" beyond the
+    // two days a table accepts.
+    let provider = MonthSightings::new().with_month(1445, 9, 1).with_month(1445, 10, 3);
+    let started = Instant::now();
+    let bulk = BulkCalendarBuilder::new(&provider, 1445)
+        .with_months([10, 8, 9])
+        .with_concurrency(2)
+        .with_rate_limit(2, Duration::from_millis(40))
+        .build()
+        .await
+        .unwrap();
+    // Three requests at two per 40 ms: the third starts no earlier than 40 ms in.
+    assert!(started.elapsed() >= Duration::from_millis(40));
+
+    let months: Vec<usize> = bulk.months.iter().map(|m| m.month).collect();
+    assert_eq!(months, vec![8, 9, 10]);
+    assert_eq!(bulk.months[1].result.as_ref().unwrap(), &NaiveDate::from_ymd_opt(2024, 3, 12).unwrap());
+    assert!(!bulk.is_complete());
+    let failures: Vec<_> = bulk.failures().collect();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].month, 10);
+    assert!(matches!(failures[0].result, Err(ShaumError::ValidationError(_))));
+
+    assert_eq!(bulk.calendar.len(), 2);
+    let first = bulk.calendar.to_hijri(NaiveDate::from_ymd_opt(2024, 3, 12).unwrap()).unwrap();
+    assert_eq!((first.month, first.day), (9, 1));
+    let ctx = RuleContext::new().with_calendar(bulk.calendar);
+    assert_eq!(check(NaiveDate::from_ymd_opt(2024, 3, 11).unwrap(), &ctx).unwrap().hijri_month, 8);
+
+    assert!(BulkCalendarBuilder::new(&provider, 1445).with_months([13]).build().await.is_err());
+}
+
+#[test]
+fn test_analyze_local_uses_civil_date() {
+    use chrono::{FixedOffset, TimeZone, Utc};
+    use shaum_core::analyze_local;
+
+    // 2024-03-10 20:00 UTC is already 2024-03-11 03:00 in Jakarta (UTC+7).
+    let now = Utc.with_ymd_and_hms(2024, 3, 10, 20, 0, 0).unwrap();
+    let wib = FixedOffset::east_opt(7 * 3600).unwrap();
+    let ctx = RuleContext::default();
+
+    let local = analyze_local(now, wib, &ctx).unwrap();
+    let expected = check(NaiveDate::from_ymd_opt(2024, 3, 11).unwrap(), &ctx).unwrap();
+    assert_eq!(local.hijri_day, expected.hijri_day);
+    assert_eq!(local.hijri_month, expected.hijri_month);
+}
+
+#[cfg(feature = "astronomy")]
+#[test]
+fn test_analyze_local_maghrib_boundary() {
+    use chrono::{TimeZone, Utc};
+    use shaum_core::{analyze_local, GeoCoordinate};
+
+    // Jakarta, 2024-03-10 19:30 local (12:30 UTC) is after Maghrib: effective date is the 11th.
+    let jakarta = GeoCoordinate::new(-6.2088, 106.8456).unwrap();
+    let now = Utc.with_ymd_and_hms(2024, 3, 10, 12, 30, 0).unwrap();
+    let ctx = RuleContext::default();
+
+    let local = analyze_local(now, jakarta, &ctx).unwrap();
+    let expected = check(NaiveDate::from_ymd_opt(2024, 3, 11).unwrap(), &ctx).unwrap();
+    assert_eq!(local.hijri_day, expected.hijri_day);
+    assert!(local.explain().contains("Post-Maghrib"));
+}
+
+#[cfg(feature = "astronomy")]
+#[test]
+fn test_side_by_side_calendars() {
+    use shaum_core::{GeoCoordinate, SightingZone, VisibilityCriteria};
+    use shaum_core::astronomy::AstronomicalCalendar;
+
+    let local = |lat, lng| AstronomicalCalendar::new(VisibilityCriteria::mabims())
+        .with_zone(SightingZone::Local(GeoCoordinate::new(lat, lng).unwrap()));
+    let jakarta = RuleContext::new().with_calendar(local(-6.2088, 106.8456));
+    let new_york = RuleContext::new().with_calendar(local(40.7128, -74.0060));
+    let date = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+
+    assert!(!check(date, &jakarta).unwrap().primary_status.is_wajib());
+    assert!(check(date, &new_york).unwrap().primary_status.is_wajib());
+    // The default context is unaffected.
+    assert_eq!(RuleContext::new().hijri(date).unwrap(), to_hijri_with(date, 0, &UmmAlQura).unwrap());
+}
+
+#[test]
+fn test_tabular_calendar() {
+    use shaum_core::Tabular;
+
+    let tabular = RuleContext::new().with_calendar(Tabular::new());
+    // Tabular and Umm al-Qura agree to within a day or two in the table range.
+    let date = NaiveDate::from_ymd_opt(2024, 3, 12).unwrap();
+    let h = tabular.hijri(date).unwrap();
+    assert_eq!((h.year, h.month), (1445, 9));
+    assert!(check(date, &tabular).unwrap().primary_status.is_wajib());
+
+    // No range limit outside the Umm al-Qura tables.
+    let far = NaiveDate::from_ymd_opt(2150, 1, 1).unwrap();
+    assert!(check(far, &RuleContext::default()).is_err());
+    assert!(check(far, &tabular).is_ok());
+}
+
+#[test]
+fn test_kemenag_preset() {
+    // Ramadhan 1445: Umm al-Qura 11 March 2024, Indonesia 12 March.
+    let date = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+    assert!(check(date, &RuleContext::default()).unwrap().primary_status.is_wajib());
+    assert!(!check(date, &RuleContext::indonesia()).unwrap().primary_status.is_wajib());
+
+    // Eid al-Fitr 1444: Friday 21 April 2023 in Saudi Arabia, Saturday 22 April in Indonesia.
+    let eid = NaiveDate::from_ymd_opt(2023, 4, 22).unwrap();
+    assert!(check(eid, &RuleContext::indonesia()).unwrap().primary_status.is_haram());
+    let by_name = RuleContext { calendar: shaum_core::calendar_by_name("Kemenag").unwrap(), ..RuleContext::default() };
+    assert!(check(eid, &by_name).unwrap().primary_status.is_haram());
+    assert!(shaum_core::calendar_by_name("nope").is_none());
+    // Astronomical backends are not built into the calendar crate.
+    assert!(shaum_core::calendar_by_name("diyanet").is_none());
+    let err = "diyanet".parse::<shaum_core::CalendarMethod>().unwrap_err();
+    assert!(err.to_string().contains("AstronomicalCalendar"));
+}
+
+#[test]
+fn test_compare_calendar_methods() {
+    use shaum_core::{compare_methods, CalendarMethod};
+
+    let date = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+    let methods: Vec<_> = compare_methods(date).into_iter().map(|(m, _)| m).collect();
+    assert_eq!(methods, CalendarMethod::ALL);
+    for method in CalendarMethod::ALL {
+        assert_eq!(method.name().parse::<CalendarMethod>().unwrap(), method);
+    }
+
+    // Only the arithmetic calendars reach beyond the Umm al-Qura tables.
+    let far: Vec<_> = compare_methods(NaiveDate::from_ymd_opt(2100, 1, 1).unwrap()).into_iter().map(|(m, _)| m).collect();
+    assert_eq!(far, [CalendarMethod::Tabular, CalendarMethod::Fatimid]);
+}
+
+#[test]
+fn test_fatimid_calendar() {
+    let bohra = RuleContext::new().with_calendar(shaum_core::Tabular::fatimid());
+    // Bohra Ramadhan 1445: 10 March to 8 April 2024, Eid on 9 April.
+    assert!(check(NaiveDate::from_ymd_opt(2024, 3, 10).unwrap(), &bohra).unwrap().primary_status.is_wajib());
+    assert!(check(NaiveDate::from_ymd_opt(2024, 4, 9).unwrap(), &bohra).unwrap().primary_status.is_haram());
+    assert!(shaum_core::calendar_by_name("Misri").is_some());
+}
+
+#[cfg(feature = "astronomy")]
+#[test]
+fn test_diyanet_preset() {
+    let turkey = RuleContext::turkey();
+    assert_eq!(turkey.madhab, Madhab::Hanafi);
+    // Eid al-Fitr 1445 in Turkey: 10 April 2024.
+    assert!(check(NaiveDate::from_ymd_opt(2024, 4, 10).unwrap(), &turkey).unwrap().primary_status.is_haram());
+    assert!(check(NaiveDate::from_ymd_opt(2024, 4, 9).unwrap(), &turkey).unwrap().primary_status.is_wajib());
+}
+
+#[test]
+fn test_hijri_year_length_per_backend() {
+    use shaum_core::{days_in_hijri_year, is_hijri_leap_year, HijriCalendar, Tabular, UmmAlQura};
+
+    // Tabular: 11 leap years in every 30-year cycle.
+    let leaps = (1441..=1470).filter(|&y| is_hijri_leap_year(y, &Tabular::new()).unwrap()).count();
+    assert_eq!(leaps, 11);
+    assert_eq!(days_in_hijri_year(1445, &Tabular::new()).unwrap(), 355);
+
+    // Umm al-Qura year lengths add up to the Gregorian span.
+    let total: u32 = (1401..=1450).map(|y| days_in_hijri_year(y, &UmmAlQura).unwrap()).sum();
+    let span = UmmAlQura.year_start(1451).unwrap() - UmmAlQura.year_start(1401).unwrap();
+    assert_eq!(i64::from(total), span.num_days());
+}
+
+#[test]
+fn test_error_codes() {
+    let err = check(NaiveDate::from_ymd_opt(1900, 1, 1).unwrap(), &RuleContext::default()).unwrap_err();
+    assert_eq!(err.code(), "date_out_of_range");
+    assert_eq!(shaum_core::ShaumError::invalid_config("x").code(), "invalid_configuration");
+}
+
+#[test]
+fn test_geo_coordinate_parsing() {
+    use shaum_core::GeoCoordinate;
+
+    let parse = |s: &str| s.parse::<GeoCoordinate>();
+    let close = |c: GeoCoordinate, lat: f64, lng: f64| (c.lat - lat).abs() < 1e-4 && (c.lng - lng).abs() < 1e-4;
+
+    assert!(close(parse("-6.2088,106.8456").unwrap(), -6.2088, 106.8456));
+    assert!(close(parse("  21.4225 39.8262 ").unwrap(), 21.4225, 39.8262));
+    assert!(close(parse("21°25'21\"N, 39°49'34\"E").unwrap(), 21.4225, 39.826_111));
+    assert!(close(parse("S 6° 12.528' E 106° 50.736'").unwrap(), -6.2088, 106.8456));
+    assert!(close(parse("106.8456E 6.2088S").unwrap(), -6.2088, 106.8456));
+    assert!(close(parse("51°30′26″N 0°7′39″W").unwrap(), 51.507_222, -0.1275));
+    let uri = parse("GEO:48.2010,16.3695,183;crs=wgs84;u=40").unwrap();
+    assert!(close(uri, 48.201, 16.3695) && uri.altitude == 183.0);
+
+    for bad in ["", "1", "1,2,3", "91,0", "0,181", "geo:1", "geo:1,2;crs=epsg:4326", "6°61'N 106°E", "6N 7S", "abc", "a°°"] {
+        assert!(parse(bad).is_err(), "{:?} should not parse", bad);
+    }
+}
+
+#[test]
+fn test_hijri_string_parsing() {
+    use shaum_core::rules::i18n::{parse_hijri, EnglishLocalizer, Localizer};
+    use shaum_core::HijriYmd;
+
+    let ashura = "When is 10 Muharram 1447?".trim_start_matches("When is ");
+    let h: HijriYmd = ashura.parse().unwrap();
+    assert_eq!(h, HijriYmd::new(1447, 1, 10));
+    let ctx = RuleContext::default();
+    let date = ctx.gregorian(h).unwrap();
+    assert_eq!(date, NaiveDate::from_ymd_opt(2025, 7, 5).unwrap());
+    assert!(check(date, &ctx).unwrap().is_ashura());
+
+    assert_eq!("1445/9/1".parse::<HijriYmd>().unwrap(), HijriYmd::new(1445, 9, 1));
+    assert_eq!("1 Syawal 1445 H".parse::<HijriYmd>().unwrap(), HijriYmd::new(1445, 10, 1));
+    assert_eq!("9 dhul-hijjah 1445".parse::<HijriYmd>().unwrap(), HijriYmd::new(1445, 12, 9));
+    assert_eq!("9 Dhu'l-Hijja 1447".parse::<HijriYmd>().unwrap(), HijriYmd::new(1447, 12, 9));
+    assert_eq!("27 Rajjab 1446".parse::<HijriYmd>().unwrap(), HijriYmd::new(1446, 7, 27));
+    assert_eq!("1447-12-09".parse::<HijriYmd>().unwrap(), HijriYmd::new(1447, 12, 9));
+    for bad in ["1445-13-01", "1445-09-31", "0-1-1", "1 Smarch 1445", "Ramadhan 1445", "1445-09"] {
+        assert!(bad.parse::<HijriYmd>().is_err(), "{:?}", bad);
+    }
+
+    // Names come from the localizer.
+    struct Numbered;
+    impl Localizer for Numbered {
+        fn month_name(&self, month: usize) -> String { format!("Bulan {}", month) }
+        fn status_name(&self, status: FastingStatus) -> String { status.to_string() }
+        fn type_name(&self, f_type: shaum_core::FastingType) -> String { f_type.to_string() }
+        fn format_description(&self, _: &shaum_core::FastingAnalysis) -> String { String::new() }
+    }
+    assert_eq!(parse_hijri("2 Bulan 9 1445", &Numbered).unwrap(), HijriYmd::new(1445, 9, 2));
+    assert_eq!(parse_hijri("2 Ramadan 1445", &Numbered).unwrap(), HijriYmd::new(1445, 9, 2));
+    assert_eq!(parse_hijri("1 Dhu al-Qi'dah 1445", &EnglishLocalizer).unwrap(), HijriYmd::new(1445, 11, 1));
+
+    // 30 Sha'ban 1445 does not exist in Umm al-Qura (29 days).
+    assert!(ctx.gregorian(HijriYmd::new(1445, 8, 30)).is_err());
+}
+
+
+#[test]
+fn test_cached_localizer_formats_once() {
+    use shaum_core::rules::i18n::{CachedLocalizer, EnglishLocalizer, Localizer};
+    use std::cell::Cell;
+
+    #[derive(Default)]
+    struct Counting(Cell<usize>);
+    impl Localizer for Counting {
+        fn month_name(&self, month: usize) -> String { self.0.set(self.0.get() + 1); EnglishLocalizer.month_name(month) }
+        fn status_name(&self, status: FastingStatus) -> String { EnglishLocalizer.status_name(status) }
+        fn type_name(&self, f_type: shaum_core::FastingType) -> String { EnglishLocalizer.type_name(f_type) }
+        fn format_description(&self, analysis: &shaum_core::FastingAnalysis) -> String {
+            self.0.set(self.0.get() + 1);
+            EnglishLocalizer.format_description(analysis)
+        }
+    }
+
+    let ctx = RuleContext::default();
+    let days: Vec<_> = (0..30)
+        .map(|i| check(NaiveDate::from_ymd_opt(2024, 3, 11).unwrap() + chrono::Duration::days(i), &ctx).unwrap())
+        .collect();
+    let cached = CachedLocalizer::new(Counting::default());
+    for _ in 0..3 {
+        for day in &days {
+            assert_eq!(cached.format_description(day), EnglishLocalizer.format_description(day));
+            assert_eq!(cached.month_name(day.hijri_month), "Ramadhan");
+        }
+    }
+    // 30 descriptions and one month name, despite three passes.
+    assert_eq!(cached.inner().0.get(), 31);
+    cached.clear();
+    assert!(cached.is_empty());
+}
+
+#[test]
+fn test_cached_localizer_keys_on_rendered_inputs() {
+    use shaum_core::rules::i18n::{CachedLocalizer, Localizer};
+    use shaum_core::Madhab;
+    use std::cell::Cell;
+
+    #[derive(Default)]
+    struct Traces(Cell<usize>);
+    impl Localizer for Traces {
+        fn month_name(&self, month: usize) -> String { month.to_string() }
+        fn status_name(&self, status: FastingStatus) -> String { status.to_string() }
+        fn type_name(&self, f_type: shaum_core::FastingType) -> String { f_type.to_string() }
+        fn format_description(&self, analysis: &shaum_core::FastingAnalysis) -> String {
+            self.0.set(self.0.get() + 1);
+            analysis.traces().map(|t| t.payload.to_string()).collect::<Vec<_>>().join("; ")
+        }
+    }
+
+    // A lone Saturday: same reasons and status, payload names the madhab.
+    let saturday = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+    let shafi = check(saturday, &RuleContext::default().madhab(Madhab::Shafi)).unwrap();
+    let hanafi = check(saturday, &RuleContext::default().madhab(Madhab::Hanafi)).unwrap();
+    assert_eq!(shafi.primary_status, hanafi.primary_status);
+
+    let cached = CachedLocalizer::new(Traces::default());
+    assert!(cached.format_description(&shafi).contains("Shafi"));
+    assert!(cached.format_description(&hanafi).contains("Hanafi"));
+    assert_eq!(cached.inner().0.get(), 2);
+
+    // The Gregorian date is not part of the key.
+    let mut moved = shafi.clone();
+    moved.date += chrono::Duration::days(7);
+    cached.format_description(&moved);
+    assert_eq!(cached.inner().0.get(), 2);
+
+    // Each kind of string is bounded.
+    let small = CachedLocalizer::with_capacity(Traces::default(), 4);
+    let ctx = RuleContext::default();
+    for i in 0..30 {
+        small.format_description(&check(saturday + chrono::Duration::days(i), &ctx).unwrap());
+    }
+    assert!(small.len() <= 4);
+}
+
+
+#[cfg(feature = "astronomy")]
+#[test]
+fn test_julian_day_matches_astronomy() {
+    use chrono::{TimeZone, Utc};
+    use shaum_core::astronomy::visibility::datetime_to_jd;
+    use shaum_core::{from_julian_day, to_julian_day};
+
+    for (y, m, d) in [(1900, 3, 1), (2000, 1, 1), (2024, 2, 29), (2076, 11, 16)] {
+        let date = NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let midnight = Utc.with_ymd_and_hms(y, m, d, 0, 0, 0).unwrap();
+        assert_eq!(to_julian_day(date), datetime_to_jd(midnight));
+        let evening = Utc.with_ymd_and_hms(y, m, d, 18, 30, 0).unwrap();
+        assert_eq!(from_julian_day(datetime_to_jd(evening)).unwrap(), date);
+    }
+}
+
+#[test]
+fn test_adjustment_at_range_edges() {
+    use shaum_core::types::TracePayload;
+    use shaum_core::ShaumError;
+
+    let first = NaiveDate::from_ymd_opt(1938, 1, 1).unwrap();
+    let last = NaiveDate::from_ymd_opt(2076, 12, 31).unwrap();
+    let saturated = |a: &shaum_core::FastingAnalysis| a.traces().find_map(|t| match t.payload {
+        TracePayload::SaturatedAdjustment { requested, applied } => Some((requested, applied)),
+        _ => None,
+    });
+
+    for (edge, inward) in [(first, 1), (last, -1)] {
+        let edge_hijri = RuleContext::default().hijri(edge).unwrap();
+        for offset in 0..=30 {
+            let date = edge + chrono::Duration::days(offset * inward);
+            for adjustment in -30..=30 {
+                let lenient = RuleContext::new().adjustment(adjustment);
+                let strict = RuleContext::new().adjustment(adjustment).strict(true);
+                let crosses = adjustment * -inward > offset;
+
+                let analysis = check(date, &lenient).unwrap();
+                let hijri = lenient.hijri(date).unwrap();
+                if crosses {
+                    let applied = (edge - date).num_days();
+                    assert_eq!(saturated(&analysis), Some((adjustment, applied)), "{} {:+}", date, adjustment);
+                    assert_eq!(hijri, edge_hijri);
+                    let err = check(date, &strict).unwrap_err();
+                    assert_eq!(err.code(), "adjustment_out_of_range");
+                    assert!(matches!(err, ShaumError::AdjustmentOutOfRange { adjustment: a, .. } if a == adjustment));
+                    assert!(strict.hijri(date).is_err());
+                } else {
+                    assert_eq!(saturated(&analysis), None, "{} {:+}", date, adjustment);
+                    assert_eq!(hijri, RuleContext::default().hijri(date + chrono::Duration::days(adjustment)).unwrap());
+                    assert_eq!(check(date, &strict).unwrap().primary_status, analysis.primary_status);
+                }
+            }
+        }
+    }
+
+    // Dates already outside the range are shifted as requested, and strict
+    // mode rejects them as out of range.
+    let outside = NaiveDate::from_ymd_opt(2077, 1, 5).unwrap();
+    let inside = NaiveDate::from_ymd_opt(2076, 12, 26).unwrap();
+    assert_eq!(RuleContext::new().adjustment(-10).hijri(outside).unwrap(), RuleContext::default().hijri(inside).unwrap());
+    assert_eq!(check(outside, &RuleContext::new().adjustment(-10).strict(true)).unwrap_err().code(), "date_out_of_range");
+
+    // Shifting past the last representable date is an error, not a panic.
+    for cached in [true, false] {
+        let mut ctx = RuleContext::new().adjustment(1);
+        if !cached {
+            ctx.cache = None;
+        }
+        assert_eq!(ctx.hijri(NaiveDate::MAX).unwrap_err().code(), "adjustment_out_of_range");
+    }
+}
+
+#[cfg(feature = "binary")]
+#[test]
+fn test_binary_batch_roundtrip() {
+    use shaum_core::binary::{self, FORMAT_VERSION};
+    use shaum_core::PersonalContext;
+
+    // A Ramadhan with excused days carries statuses, compensation and traces.
+    let start = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+    let mut analyses: Vec<_> = start.iter_days().take(60).map(|d| check(d, &RuleContext::default()).unwrap()).collect();
+    let ill = RuleContext::new().with_personal(PersonalContext::ill(false));
+    analyses.push(check(NaiveDate::from_ymd_opt(2024, 3, 20).unwrap(), &ill).unwrap());
+
+    let bytes = binary::encode(&analyses).unwrap();
+    let json = serde_json::to_vec(&analyses).unwrap();
+    assert!(bytes.len() * 3 < json.len(), "{} vs {} bytes", bytes.len(), json.len());
+    assert_eq!(bytes[4], FORMAT_VERSION);
+
+    let decoded = binary::decode(&bytes).unwrap();
+    assert_eq!(serde_json::to_value(&decoded).unwrap(), serde_json::to_value(&analyses).unwrap());
+    assert!(decoded.last().unwrap().requires_qadha());
+
+    // Other versions, foreign data and truncation are rejected, not misread.
+    let mut newer = bytes.clone();
+    newer[4] = FORMAT_VERSION + 1;
+    for bad in [&newer[..], &json[..], &bytes[..bytes.len() - 1], &[]] {
+        assert_eq!(binary::decode(bad).unwrap_err().code(), "serialization");
+    }
+}
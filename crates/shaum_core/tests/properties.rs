@@ -29,7 +29,8 @@ proptest! {
         }
     }
     
-    /// Invariant: Ramadhan is always Wajib (unless Travel/Sick - not implemented yet, so Wajib).
+    /// Invariant: Ramadhan is always Wajib under the default context (illness,
+    /// set in `PersonalContext`, excuses it).
     #[test]
     fn ramadhan_is_wajib(days in 0i32..36500) {
         let base = NaiveDate::from_ymd_opt(1950, 1, 1).unwrap();
@@ -49,11 +50,70 @@ proptest! {
         let end = start.checked_add_signed(chrono::Duration::days(days as i64)).unwrap();
         
         let ctx = RuleContext::new().daud_strategy(DaudStrategy::Skip);
-        let daud_days = shaum_core::generate_daud_schedule(start, end, &ctx);
+        let daud_days = shaum_core::generate_daud_schedule(start, end, &ctx).unwrap();
         
         for date in daud_days {
             let analysis = shaum_core::analyze_date(date).unwrap();
             assert!(!analysis.primary_status.is_haram(), "Daud recommended Haram day: {:?}", date);
         }
     }
+
+    /// Invariant: the const classifier agrees with the full engine.
+    #[test]
+    fn classify_matches_analyze(days in 0i32..36500) {
+        use chrono::Datelike;
+        let base = NaiveDate::from_ymd_opt(1950, 1, 1).unwrap();
+        let date = base.checked_add_signed(chrono::Duration::days(days as i64)).unwrap();
+
+        let analysis = analyze_date(date).unwrap();
+        let status = shaum_core::classify(analysis.hijri_month, analysis.hijri_day, date.weekday());
+        prop_assert_eq!(status, analysis.primary_status);
+    }
+
+    /// Invariant: `combine` is a join (commutative, associative, idempotent, `Mubah` identity).
+    #[test]
+    fn combine_is_lattice_join(a in status(), b in status(), c in status()) {
+        prop_assert_eq!(a.combine(b), b.combine(a));
+        prop_assert_eq!(a.combine(b).combine(c), a.combine(b.combine(c)));
+        prop_assert_eq!(a.combine(a), a);
+        prop_assert_eq!(a.combine(FastingStatus::Mubah), a);
+        prop_assert_eq!([a, b, c].iter().collect::<FastingStatus>(), a.combine(b).combine(c));
+    }
+
+    /// Invariant: the primary status is the combination of the built-in reasons.
+    #[test]
+    fn status_is_combined_reasons(days in 0i32..36500) {
+        let base = NaiveDate::from_ymd_opt(1950, 1, 1).unwrap();
+        let date = base.checked_add_signed(chrono::Duration::days(days as i64)).unwrap();
+
+        let analysis = analyze_date(date).unwrap();
+        // Late Sha'ban, the day of doubt and the days before Ramadhan are not
+        // lifted by a Sunnah reason on the same day, so only the attributed
+        // statuses combine to the primary status there.
+        let prohibited = analysis.reasons()
+            .any(|r| [FastingType::LATE_SHABAN, FastingType::DAY_OF_DOUBT, FastingType::PRE_RAMADHAN].contains(r));
+        if !prohibited {
+            let combined: FastingStatus = analysis.reasons().filter_map(|r| r.implied_status()).collect();
+            prop_assert_eq!(combined, analysis.primary_status);
+        }
+        let attributed: FastingStatus = analysis.reason_statuses().map(|(_, s)| s).collect();
+        prop_assert_eq!(attributed, analysis.primary_status);
+    }
+
+    /// Invariant: the Haram-only check agrees with the full analysis.
+    #[test]
+    fn is_fastable_matches_analysis(days in 0i32..36500) {
+        let base = NaiveDate::from_ymd_opt(1950, 1, 1).unwrap();
+        let date = base.checked_add_signed(chrono::Duration::days(days as i64)).unwrap();
+
+        let fastable = shaum_core::is_fastable(date, &RuleContext::default());
+        prop_assert_eq!(fastable, !analyze_date(date).unwrap().primary_status.is_haram());
+    }
+}
+
+fn status() -> impl Strategy<Value = FastingStatus> {
+    prop::sample::select(vec![
+        FastingStatus::Mubah, FastingStatus::Makruh, FastingStatus::Sunnah,
+        FastingStatus::SunnahMuakkadah, FastingStatus::Wajib, FastingStatus::Haram,
+    ])
 }
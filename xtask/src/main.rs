@@ -6,6 +6,7 @@
 //! ```sh
 //! cargo xtask dist-web      # Build WASM + TypeScript + JSR
 //! cargo xtask dist-python   # Build Python wheel
+//! cargo xtask dist-cli      # Build static `shaum` CLI binaries
 //! cargo xtask dev-web       # Local WASM testing server
 //! cargo xtask publish-jsr   # Publish to JSR.io
 //! cargo xtask publish-npm   # Publish to NPM
@@ -26,10 +27,15 @@ fn main() -> Result<()> {
     }
 
     let dry_run = args.iter().any(|a| a == "--dry-run" || a == "-n");
+    let targets: Vec<String> = args.windows(2)
+        .filter(|pair| pair[0] == "--target")
+        .map(|pair| pair[1].clone())
+        .collect();
 
     match args[1].as_str() {
         "dist-web" => dist_web()?,
         "dist-python" => dist_python()?,
+        "dist-cli" => dist_cli(&targets)?,
         "dev-web" => dev_web()?,
         "build-all" => build_all()?,
         "sync-versions" => sync_versions()?,
@@ -63,6 +69,10 @@ BUILD COMMANDS:
     dist-python   Build Python wheel via maturin
                   Output: dist/python/
 
+    dist-cli      Build static `shaum` binaries for Linux, macOS, Windows
+                  with the locale bundles and a precomputed calendar table
+                  Output: dist/cli/
+
     dev-web       Create test HTML and serve locally
                   Output: dist/dev/
 
@@ -79,9 +89,11 @@ PUBLISH COMMANDS:
 
 OPTIONS:
     --dry-run, -n   Validate without actually publishing
+    --target <T>    dist-cli: build only target T (repeatable)
 
 EXAMPLES:
     cargo xtask dist-web
+    cargo xtask dist-cli --target x86_64-unknown-linux-musl
     cargo xtask publish-jsr --dry-run
     cargo xtask build-all
 "#);
@@ -148,6 +160,26 @@ fn run_cmd_in_dir(dir: &Path, cmd: &str, args: &[&str]) -> Result<()> {
     Ok(())
 }
 
+fn run_cmd_with_env(env: &[(&str, String)], cmd: &str, args: &[&str]) -> Result<()> {
+    let vars: Vec<String> = env.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+    println!("  → {} {} {}", vars.join(" "), cmd, args.join(" "));
+    
+    let status = Command::new(cmd)
+        .args(args)
+        .envs(env.iter().map(|(k, v)| (k, v)))
+        .current_dir(project_root()?)
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .with_context(|| format!("Failed to start: {} {}", cmd, args.join(" ")))?;
+
+    if !status.success() {
+        bail!("Command '{}' failed with exit code: {:?}", cmd, status.code());
+    }
+    Ok(())
+}
+
 fn command_exists(cmd: &str) -> bool {
     #[cfg(windows)]
     {
@@ -381,6 +413,118 @@ fn dist_python() -> Result<()> {
     Ok(())
 }
 
+// =============================================================================
+// Task: dist-cli
+// =============================================================================
+
+/// Targets `dist-cli` builds unless `--target` is given.
+const CLI_TARGETS: &[&str] = &[
+    "x86_64-unknown-linux-musl",
+    "x86_64-apple-darwin",
+    "aarch64-apple-darwin",
+    "x86_64-pc-windows-msvc",
+];
+
+/// Years of the calendar table embedded in the CLI.
+const CLI_TABLE_YEARS: &str = "2024..2030";
+
+fn dist_cli(targets: &[String]) -> Result<()> {
+    println!("\n🖥️  Building shaum CLI binaries...\n");
+    
+    let root = project_root()?;
+    let work_dir = root.join("target").join("dist-cli");
+    let dist_cli = root.join("dist").join("cli");
+    let version = read_cargo_version()?;
+    let targets: Vec<&str> = match targets {
+        [] => CLI_TARGETS.to_vec(),
+        targets => targets.iter().map(String::as_str).collect(),
+    };
+    ensure_dir(&work_dir)?;
+    ensure_dir(&dist_cli)?;
+    
+    // Precompute the Umm al-Qura table on the host; build.rs embeds it
+    println!("  📅 Precomputing calendar table for {}...", CLI_TABLE_YEARS);
+    let table = work_dir.join("calendar.bin");
+    let table_path = table.to_string_lossy().to_string();
+    run_cmd("cargo", &["run", "--release", "-p", "shaum-cli", "--", "precompute", CLI_TABLE_YEARS, &table_path])?;
+    
+    let mut archives = Vec::new();
+    for target in targets {
+        println!("\n  🏗️  Building {}...", target);
+        if command_exists("rustup") {
+            run_cmd("rustup", &["target", "add", target])?;
+        }
+        
+        // musl and the MSVC CRT link statically; macOS only ever links libSystem
+        let mut env = vec![("SHAUM_CALENDAR_TABLE", table_path.clone())];
+        if target.ends_with("-musl") || target.ends_with("-windows-msvc") {
+            env.push(("RUSTFLAGS", "-C target-feature=+crt-static".to_string()));
+        }
+        run_cmd_with_env(&env, "cargo", &[
+            "build", "--release",
+            "-p", "shaum-cli",
+            "--features", "tui,serve",
+            "--target", target,
+        ])?;
+        
+        // Package binary + docs → dist/cli/shaum-<version>-<target>
+        let windows = target.contains("windows");
+        let exe = if windows { "shaum.exe" } else { "shaum" };
+        let binary = root.join("target").join(target).join("release").join(exe);
+        if !binary.exists() {
+            bail!("Build produced no binary at {}", binary.display());
+        }
+        let name = format!("shaum-{}-{}", version, target);
+        let pkg_dir = dist_cli.join(&name);
+        copy_file(&binary, &pkg_dir.join(exe))?;
+        copy_file(&root.join("README.md"), &pkg_dir.join("README.md"))?;
+        copy_file(&root.join("LICENSE"), &pkg_dir.join("LICENSE"))?;
+        
+        let archive = if windows { format!("{}.zip", name) } else { format!("{}.tar.gz", name) };
+        if windows && command_exists("zip") {
+            run_cmd_in_dir(&dist_cli, "zip", &["-qr", &archive, &name])?;
+        } else if windows {
+            // bsdtar (Windows 10+, macOS) picks the format from the extension
+            run_cmd_in_dir(&dist_cli, "tar", &["-a", "-cf", &archive, &name])?;
+        } else {
+            run_cmd_in_dir(&dist_cli, "tar", &["-czf", &archive, &name])?;
+        }
+        println!("  ✅ Packaged {}", archive);
+        archives.push(archive);
+    }
+    
+    write_checksums(&dist_cli, &archives)?;
+    
+    println!("\n✅ CLI build complete!");
+    println!("   Output: dist/cli/");
+    
+    Ok(())
+}
+
+fn write_checksums(dir: &Path, files: &[String]) -> Result<()> {
+    let (cmd, mut args) = if command_exists("sha256sum") {
+        ("sha256sum", vec![])
+    } else if command_exists("shasum") {
+        ("shasum", vec!["-a", "256"])
+    } else {
+        println!("  ⚠️ sha256sum/shasum not found, skipping SHA256SUMS");
+        return Ok(());
+    };
+    args.extend(files.iter().map(String::as_str));
+    
+    let output = Command::new(cmd)
+        .args(&args)
+        .current_dir(dir)
+        .output()
+        .with_context(|| format!("Failed to start: {}", cmd))?;
+    if !output.status.success() {
+        bail!("Command '{}' failed with exit code: {:?}", cmd, output.status.code());
+    }
+    fs::write(dir.join("SHA256SUMS"), output.stdout)?;
+    println!("  ✅ Wrote SHA256SUMS");
+    Ok(())
+}
+
 // =============================================================================
 // Task: publish-jsr
 // =============================================================================
@@ -479,7 +623,7 @@ fn publish_pypi(dry_run: bool) -> Result<()> {
         
         // Check if it's runnable via python module
         let status = std::process::Command::new("python")
-             .args(&["-m", "maturin", "--version"])
+             .args(["-m", "maturin", "--version"])
              .stdout(std::process::Stdio::null())
              .stderr(std::process::Stdio::null())
              .status();